@@ -77,6 +77,7 @@ fn non_native_fee_payment_works() {
 			currency_1, // 500 KSM (500_000_033_400_002)
 			1_000 * BSX,
 			Price::from_float(0.5),
+			None,
 		));
 
 		let spot_price = XYKSpotPrice::<basilisk_runtime::Runtime>::spot_price(currency_0, currency_1);
@@ -91,6 +92,8 @@ fn non_native_fee_payment_works() {
 			66 * BSX,
 			1_000 * BSX,
 			false,
+			None,
+			None,
 		));
 
 		basilisk_run_to_block(3);
@@ -107,7 +110,7 @@ fn non_native_fee_payment_works() {
 		));
 
 		let dave_balance = basilisk_runtime::Tokens::free_balance(1, &AccountId::from(DAVE));
-		let expected_diff = 197_453_601_724;
+		let expected_diff = 196_923_155_927;
 		assert_eq!(dave_balance, bob_balance + expected_diff);
 
 		expect_basilisk_events(vec![
@@ -115,7 +118,7 @@ fn non_native_fee_payment_works() {
 				DAVE.into(),
 				1,
 				462_676_500_000,
-				265_222_898_276,
+				265_753_344_073,
 				FALLBACK.into(),
 			)
 			.into(),
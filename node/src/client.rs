@@ -28,6 +28,7 @@ pub trait RuntimeApiCollection:
 	+ sp_session::SessionKeys<Block>
 	+ pallet_xyk_rpc_runtime_api::XYKApi<Block, AccountId, AssetId, Balance>
 	+ pallet_lbp_rpc_runtime_api::LBPApi<Block, AccountId, AssetId>
+	+ pallet_exchange_rpc_runtime_api::ExchangeApi<Block, AccountId, AssetId, Balance, Hash>
 where
 	<Self as sp_api::ApiExt<Block>>::StateBackend: sp_api::StateBackend<BlakeTwo256>,
 {
@@ -44,7 +45,8 @@ where
 		+ sp_offchain::OffchainWorkerApi<Block>
 		+ sp_session::SessionKeys<Block>
 		+ pallet_xyk_rpc_runtime_api::XYKApi<Block, AccountId, AssetId, Balance>
-		+ pallet_lbp_rpc_runtime_api::LBPApi<Block, AccountId, AssetId>,
+		+ pallet_lbp_rpc_runtime_api::LBPApi<Block, AccountId, AssetId>
+		+ pallet_exchange_rpc_runtime_api::ExchangeApi<Block, AccountId, AssetId, Balance, Hash>,
 	<Self as sp_api::ApiExt<Block>>::StateBackend: sp_api::StateBackend<BlakeTwo256>,
 {
 }
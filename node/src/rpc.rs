@@ -7,7 +7,7 @@
 
 use std::sync::Arc;
 
-use basilisk_runtime::{opaque::Block, AccountId, AssetId, Balance, Index};
+use basilisk_runtime::{opaque::Block, AccountId, AssetId, Balance, Hash, Index};
 pub use sc_rpc_api::DenyUnsafe;
 use sc_transaction_pool_api::TransactionPool;
 use sp_api::ProvideRuntimeApi;
@@ -37,9 +37,11 @@ where
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
 	C::Api: pallet_xyk_rpc::XYKRuntimeApi<Block, AccountId, AssetId, Balance>,
 	C::Api: pallet_lbp_rpc::LBPRuntimeApi<Block, AccountId, AssetId>,
+	C::Api: pallet_exchange_rpc::ExchangeRuntimeApi<Block, AccountId, AssetId, Balance, Hash>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + Sync + Send + 'static,
 {
+	use pallet_exchange_rpc::{Exchange, ExchangeApi};
 	use pallet_lbp_rpc::{LBPApi, LBP};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
 	use pallet_xyk_rpc::{XYKApi, XYK};
@@ -68,7 +70,8 @@ where
 	// `io.extend_with(YourRpcTrait::to_delegate(YourRpcStruct::new(ReferenceToClient, ...)));`
 
 	io.extend_with(XYKApi::to_delegate(XYK::new(client.clone())));
-	io.extend_with(LBPApi::to_delegate(LBP::new(client)));
+	io.extend_with(LBPApi::to_delegate(LBP::new(client.clone())));
+	io.extend_with(ExchangeApi::to_delegate(Exchange::new(client)));
 
 	Ok(io)
 }
@@ -0,0 +1,233 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Circuit Breaker Pallet
+//!
+//! ## Overview
+//!
+//! Circuit breaker pallet stores operations allowed in pools - trading and adding or removing of liquidity.
+//!
+//! States of pools are set by governance. Trading in a pool is paused automatically when trades move reserves
+//! of the pool or its price within a single block by more than the configured percentage. Adding and removing
+//! of liquidity doesn't pause trading.
+//!
+//! AMM pallets read the states and report changes of reserves via `CircuitBreaker` trait implemented by this pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{
+	dispatch::DispatchResult,
+	sp_runtime::{helpers_128bit::multiply_by_rational, traits::Zero, FixedPointNumber, Permill},
+	traits::Get,
+};
+use primitives::{traits::CircuitBreaker, Balance, PoolState, Price};
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+mod benchmarking;
+
+pub mod weights;
+
+use weights::WeightInfo;
+
+// Re-export pallet items so that they can be accessed from the crate namespace.
+pub use pallet::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::OriginFor;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The origin which can set states of pools
+		type AuthorityOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Max change of a pool reserve within a block before trading in the pool is paused
+		#[pallet::constant]
+		type MaxReserveChange: Get<Permill>;
+
+		/// Max change of a pool price within a block before trading in the pool is paused
+		#[pallet::constant]
+		type MaxPriceChange: Get<Permill>;
+
+		/// Weight information for the extrinsics.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// State of a pool was set. [pool account id, state]
+		PoolStateChanged(T::AccountId, PoolState),
+
+		/// Trading in a pool was paused because its reserves or price moved too much within a block.
+		/// [pool account id, initial reserves, reserves]
+		CircuitBreakerTripped(T::AccountId, (Balance, Balance), (Balance, Balance)),
+	}
+
+	/// States of pools, pools without a state are active.
+	#[pallet::storage]
+	#[pallet::getter(fn pool_state)]
+	pub type PoolStates<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, PoolState, ValueQuery>;
+
+	/// Reserves of pools before their first change in the block.
+	#[pallet::storage]
+	#[pallet::getter(fn initial_reserves)]
+	pub type InitialReserves<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, (T::BlockNumber, (Balance, Balance)), OptionQuery>;
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set operations allowed in the pool identified by `pool_account`.
+		///
+		/// Setting a pool `Active` also resets its initial reserves in the current block
+		/// so that trading in the pool is not paused again by the same move.
+		///
+		/// The dispatch origin for this call must be `T::AuthorityOrigin`.
+		///
+		/// Emits `PoolStateChanged` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::set_pool_state())]
+		pub fn set_pool_state(origin: OriginFor<T>, pool_account: T::AccountId, state: PoolState) -> DispatchResult {
+			T::AuthorityOrigin::ensure_origin(origin)?;
+
+			if state == PoolState::Active {
+				<PoolStates<T>>::remove(&pool_account);
+				<InitialReserves<T>>::remove(&pool_account);
+			} else {
+				<PoolStates<T>>::insert(&pool_account, state);
+			}
+
+			Self::deposit_event(Event::PoolStateChanged(pool_account, state));
+
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Return true if `current` reserves or price of a pool differ from `initial` ones by more than allowed.
+	fn exceeds_max_change(initial: (Balance, Balance), current: (Balance, Balance)) -> bool {
+		let change = |initial: Balance, current: Balance| {
+			Permill::from_rational(
+				if current > initial {
+					current - initial
+				} else {
+					initial - current
+				},
+				initial,
+			)
+		};
+
+		let max_reserve_change = T::MaxReserveChange::get();
+		if change(initial.0, current.0) > max_reserve_change || change(initial.1, current.1) > max_reserve_change {
+			return true;
+		}
+
+		match (
+			Price::checked_from_rational(initial.1, initial.0),
+			Price::checked_from_rational(current.1, current.0),
+		) {
+			(Some(initial_price), Some(price)) => {
+				change(initial_price.into_inner(), price.into_inner()) > T::MaxPriceChange::get()
+			}
+			_ => false,
+		}
+	}
+}
+
+impl<T: Config> CircuitBreaker<T::AccountId> for Pallet<T> {
+	fn pool_state(pool_account: &T::AccountId) -> PoolState {
+		<PoolStates<T>>::get(pool_account)
+	}
+
+	fn on_reserves_change(
+		pool_account: &T::AccountId,
+		reserves_before: (Balance, Balance),
+		reserves_after: (Balance, Balance),
+	) {
+		let now = frame_system::Pallet::<T>::block_number();
+
+		let initial_reserves = match Self::initial_reserves(pool_account) {
+			Some((block, reserves)) if block == now => reserves,
+			_ => {
+				<InitialReserves<T>>::insert(pool_account, (now, reserves_before));
+				reserves_before
+			}
+		};
+
+		if <PoolStates<T>>::get(pool_account) != PoolState::Active {
+			return;
+		}
+
+		if Self::exceeds_max_change(initial_reserves, reserves_after) {
+			<PoolStates<T>>::insert(pool_account, PoolState::TradingPaused);
+
+			Self::deposit_event(Event::CircuitBreakerTripped(
+				pool_account.clone(),
+				initial_reserves,
+				reserves_after,
+			));
+		}
+	}
+
+	/// Changes of liquidity don't pause trading. Initial reserves of the pool in the current block are scaled
+	/// by the change of liquidity so that only trades are compared to them.
+	fn on_liquidity_change(
+		pool_account: &T::AccountId,
+		reserves_before: (Balance, Balance),
+		reserves_after: (Balance, Balance),
+	) {
+		let now = frame_system::Pallet::<T>::block_number();
+
+		<InitialReserves<T>>::mutate_exists(pool_account, |maybe_initial| {
+			if let Some((block, initial_reserves)) = *maybe_initial {
+				if block != now {
+					return;
+				}
+
+				let scale = |initial: Balance, before: Balance, after: Balance| {
+					multiply_by_rational(initial, after, before)
+						.ok()
+						.filter(|scaled| !scaled.is_zero())
+				};
+
+				*maybe_initial = match (
+					scale(initial_reserves.0, reserves_before.0, reserves_after.0),
+					scale(initial_reserves.1, reserves_before.1, reserves_after.1),
+				) {
+					(Some(initial_a), Some(initial_b)) => Some((now, (initial_a, initial_b))),
+					_ => None,
+				};
+			}
+		});
+	}
+}
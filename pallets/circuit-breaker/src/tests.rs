@@ -0,0 +1,237 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+pub use crate::mock::{
+	CircuitBreaker as CircuitBreakerPallet, Event as TestEvent, ExtBuilder, Origin, System, Test, ALICE, POOL,
+};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::DispatchError::BadOrigin;
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut ext = ExtBuilder::default().build();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+fn expect_events(e: Vec<TestEvent>) {
+	e.into_iter().for_each(frame_system::Pallet::<Test>::assert_has_event);
+}
+
+const INITIAL_RESERVES: (Balance, Balance) = (1_000_000, 1_000_000);
+
+#[test]
+fn set_pool_state_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(CircuitBreakerPallet::pool_state(POOL), PoolState::Active);
+
+		assert_ok!(CircuitBreakerPallet::set_pool_state(
+			Origin::root(),
+			POOL,
+			PoolState::Frozen
+		));
+
+		assert_eq!(CircuitBreakerPallet::pool_state(POOL), PoolState::Frozen);
+
+		expect_events(vec![Event::PoolStateChanged(POOL, PoolState::Frozen).into()]);
+
+		assert_ok!(CircuitBreakerPallet::set_pool_state(
+			Origin::root(),
+			POOL,
+			PoolState::Active
+		));
+
+		assert!(!<PoolStates<Test>>::contains_key(POOL));
+
+		expect_events(vec![Event::PoolStateChanged(POOL, PoolState::Active).into()]);
+	});
+}
+
+#[test]
+fn set_pool_state_should_not_work_when_origin_is_not_authority() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			CircuitBreakerPallet::set_pool_state(Origin::signed(ALICE), POOL, PoolState::Frozen),
+			BadOrigin
+		);
+	});
+}
+
+#[test]
+fn price_change_within_block_should_pause_trading() {
+	new_test_ext().execute_with(|| {
+		// price change 7.5%
+		<CircuitBreakerPallet as CircuitBreaker<_>>::on_reserves_change(&POOL, INITIAL_RESERVES, (1_040_000, 961_539));
+
+		assert_eq!(CircuitBreakerPallet::pool_state(POOL), PoolState::Active);
+
+		// price change 11% compared to the reserves at the beginning of the block
+		<CircuitBreakerPallet as CircuitBreaker<_>>::on_reserves_change(
+			&POOL,
+			(1_040_000, 961_539),
+			(1_060_000, 943_396),
+		);
+
+		assert_eq!(CircuitBreakerPallet::pool_state(POOL), PoolState::TradingPaused);
+
+		expect_events(vec![Event::CircuitBreakerTripped(
+			POOL,
+			INITIAL_RESERVES,
+			(1_060_000, 943_396),
+		)
+		.into()]);
+	});
+}
+
+#[test]
+fn reserve_change_within_block_should_pause_trading() {
+	new_test_ext().execute_with(|| {
+		// price doesn't change
+		<CircuitBreakerPallet as CircuitBreaker<_>>::on_reserves_change(
+			&POOL,
+			INITIAL_RESERVES,
+			(1_600_000, 1_600_000),
+		);
+
+		assert_eq!(CircuitBreakerPallet::pool_state(POOL), PoolState::TradingPaused);
+
+		expect_events(vec![Event::CircuitBreakerTripped(
+			POOL,
+			INITIAL_RESERVES,
+			(1_600_000, 1_600_000),
+		)
+		.into()]);
+	});
+}
+
+#[test]
+fn changes_in_different_blocks_should_not_pause_trading() {
+	new_test_ext().execute_with(|| {
+		<CircuitBreakerPallet as CircuitBreaker<_>>::on_reserves_change(&POOL, INITIAL_RESERVES, (1_040_000, 961_539));
+
+		System::set_block_number(2);
+
+		<CircuitBreakerPallet as CircuitBreaker<_>>::on_reserves_change(
+			&POOL,
+			(1_040_000, 961_539),
+			(1_081_600, 924_557),
+		);
+
+		assert_eq!(CircuitBreakerPallet::pool_state(POOL), PoolState::Active);
+		assert_eq!(
+			CircuitBreakerPallet::initial_reserves(POOL),
+			Some((2, (1_040_000, 961_539)))
+		);
+	});
+}
+
+#[test]
+fn tripped_circuit_breaker_should_not_change_state_set_by_governance() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CircuitBreakerPallet::set_pool_state(
+			Origin::root(),
+			POOL,
+			PoolState::LiquidityOnly
+		));
+
+		<CircuitBreakerPallet as CircuitBreaker<_>>::on_reserves_change(&POOL, INITIAL_RESERVES, (400_000, 400_000));
+
+		assert_eq!(CircuitBreakerPallet::pool_state(POOL), PoolState::LiquidityOnly);
+	});
+}
+
+#[test]
+fn activated_pool_should_not_be_paused_again_by_the_same_change() {
+	new_test_ext().execute_with(|| {
+		<CircuitBreakerPallet as CircuitBreaker<_>>::on_reserves_change(
+			&POOL,
+			INITIAL_RESERVES,
+			(1_600_000, 1_600_000),
+		);
+
+		assert_eq!(CircuitBreakerPallet::pool_state(POOL), PoolState::TradingPaused);
+
+		assert_ok!(CircuitBreakerPallet::set_pool_state(
+			Origin::root(),
+			POOL,
+			PoolState::Active
+		));
+
+		<CircuitBreakerPallet as CircuitBreaker<_>>::on_reserves_change(
+			&POOL,
+			(1_600_000, 1_600_000),
+			(1_650_000, 1_551_516),
+		);
+
+		assert_eq!(CircuitBreakerPallet::pool_state(POOL), PoolState::Active);
+	});
+}
+
+#[test]
+fn liquidity_change_should_not_pause_trading() {
+	new_test_ext().execute_with(|| {
+		<CircuitBreakerPallet as CircuitBreaker<_>>::on_liquidity_change(
+			&POOL,
+			INITIAL_RESERVES,
+			(3_000_000, 3_000_000),
+		);
+		<CircuitBreakerPallet as CircuitBreaker<_>>::on_liquidity_change(
+			&POOL,
+			(3_000_000, 3_000_000),
+			INITIAL_RESERVES,
+		);
+
+		assert_eq!(CircuitBreakerPallet::pool_state(POOL), PoolState::Active);
+		assert_eq!(CircuitBreakerPallet::initial_reserves(POOL), None);
+	});
+}
+
+#[test]
+fn trade_after_liquidity_change_should_be_compared_to_scaled_initial_reserves() {
+	new_test_ext().execute_with(|| {
+		// price change 7.5%
+		<CircuitBreakerPallet as CircuitBreaker<_>>::on_reserves_change(&POOL, INITIAL_RESERVES, (1_040_000, 961_539));
+
+		<CircuitBreakerPallet as CircuitBreaker<_>>::on_liquidity_change(
+			&POOL,
+			(1_040_000, 961_539),
+			(2_080_000, 1_923_078),
+		);
+
+		assert_eq!(CircuitBreakerPallet::pool_state(POOL), PoolState::Active);
+		assert_eq!(
+			CircuitBreakerPallet::initial_reserves(POOL),
+			Some((1, (2_000_000, 2_000_000)))
+		);
+
+		// price change 11% compared to the scaled reserves at the beginning of the block
+		<CircuitBreakerPallet as CircuitBreaker<_>>::on_reserves_change(
+			&POOL,
+			(2_080_000, 1_923_078),
+			(2_120_000, 1_886_793),
+		);
+
+		assert_eq!(CircuitBreakerPallet::pool_state(POOL), PoolState::TradingPaused);
+
+		expect_events(vec![Event::CircuitBreakerTripped(
+			POOL,
+			(2_000_000, 2_000_000),
+			(2_120_000, 1_886_793),
+		)
+		.into()]);
+	});
+}
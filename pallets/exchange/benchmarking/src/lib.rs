@@ -26,7 +26,7 @@ use sp_std::prelude::*;
 use pallet_exchange::Pallet as Exchange;
 
 use frame_benchmarking::{account, benchmarks};
-use frame_support::traits::OnFinalize;
+use frame_support::traits::{Get, OnFinalize, OnIdle};
 use frame_system::RawOrigin;
 use orml_traits::{MultiCurrency, MultiCurrencyExtended};
 use primitives::{AssetId, Balance, Price};
@@ -37,6 +37,7 @@ use sp_runtime::traits::{BlakeTwo256, Hash};
 use amounts::INTENTION_AMOUNTS;
 
 use frame_support::dispatch;
+use frame_support::weights::Weight;
 use pallet_xyk as xykpool;
 
 pub trait Config: pallet_exchange::Config + xykpool::Config {}
@@ -69,7 +70,7 @@ fn initialize_pool<T: Config>(
 	amount: Balance,
 	price: Price,
 ) -> dispatch::DispatchResult {
-	xykpool::Pallet::<T>::create_pool(RawOrigin::Signed(caller).into(), asset_a, asset_b, amount, price)?;
+	xykpool::Pallet::<T>::create_pool(RawOrigin::Signed(caller).into(), asset_a, asset_b, amount, price, None)?;
 
 	Ok(())
 }
@@ -94,6 +95,7 @@ fn feed_intentions<T: Config>(
 			amounts[idx as usize] as u128,
 			SELL_INTENTION_LIMIT,
 			false,
+			None,
 		)?;
 
 		let buyer = funded_account::<T>("user", idx + number + 1);
@@ -104,6 +106,31 @@ fn feed_intentions<T: Config>(
 			amounts[idx as usize] as u128,
 			amounts[idx as usize] as u128 * 2u128,
 			false,
+			None,
+		)?;
+	}
+
+	Ok(())
+}
+
+const LIMIT_ORDER_AMOUNT: Balance = 1_000_000_000;
+
+fn feed_limit_orders<T: Config>(
+	asset_a: AssetId,
+	asset_b: AssetId,
+	number: u32,
+	min_bought: Balance,
+	valid_until: T::BlockNumber,
+) -> dispatch::DispatchResult {
+	for idx in 0..number {
+		let user = funded_account::<T>("user", idx + 200);
+		pallet_exchange::Pallet::<T>::place_limit_order(
+			RawOrigin::Signed(user).into(),
+			asset_a,
+			asset_b,
+			LIMIT_ORDER_AMOUNT,
+			min_bought,
+			valid_until,
 		)?;
 	}
 
@@ -156,7 +183,7 @@ benchmarks! {
 
 		assert_eq!(pallet_exchange::Pallet::<T>::get_intentions_count((asset_a, asset_b)), nbr_intentions_appended);
 
-	}: {  Exchange::<T>::sell(RawOrigin::Signed(caller.clone()).into(), asset_a, asset_b, amount ,limit, false)? }
+	}: {  Exchange::<T>::sell(RawOrigin::Signed(caller.clone()).into(), asset_a, asset_b, amount, limit, false, None)? }
 	verify{
 		assert_eq!(pallet_exchange::Pallet::<T>::get_intentions_count((asset_a, asset_b)), nbr_intentions_appended + 1);
 	}
@@ -177,7 +204,7 @@ benchmarks! {
 
 		assert_eq!(pallet_exchange::Pallet::<T>::get_intentions_count((asset_a, asset_b)), nbr_intentions_appended);
 
-	}: {  Exchange::<T>::buy(RawOrigin::Signed(caller.clone()).into(), asset_a, asset_b, amount / 10 ,limit, false)? }
+	}: {  Exchange::<T>::buy(RawOrigin::Signed(caller.clone()).into(), asset_a, asset_b, amount / 10, limit, false, None)? }
 	verify{
 		assert_eq!(pallet_exchange::Pallet::<T>::get_intentions_count((asset_a, asset_b)), nbr_intentions_appended + 1);
 	}
@@ -226,6 +253,7 @@ benchmarks! {
 				BUY_INTENTION_AMOUNT,
 				BUY_INTENTION_LIMIT,
 				false,
+				None,
 			)?;
 		}
 
@@ -259,6 +287,7 @@ benchmarks! {
 				SELL_INTENTION_AMOUNT,
 				SELL_INTENTION_LIMIT,
 				false,
+				None,
 			)?;
 		}
 
@@ -285,7 +314,7 @@ benchmarks! {
 
 		initialize_pool::<T>(creator, asset_a, asset_b, amount, Price::from(1))?;
 
-	}: { xykpool::Pallet::<T>::sell(RawOrigin::Signed(seller.clone()).into(), asset_a, asset_b, 1_000_000_000, min_bought, false)?; }
+	}: { xykpool::Pallet::<T>::sell(RawOrigin::Signed(seller.clone()).into(), asset_a, asset_b, 1_000_000_000, min_bought, false, None, None)?; }
 	verify {
 		assert_eq!(<T as xykpool::Config>::Currency::free_balance(asset_a, &seller), 999_999_000_000_000);
 		assert_eq!(<T as xykpool::Config>::Currency::free_balance(asset_b, &seller), 1000000907272729);
@@ -309,6 +338,7 @@ benchmarks! {
 			SELL_INTENTION_AMOUNT,
 			SELL_INTENTION_LIMIT,
 			false,
+			None,
 		)?;
 
 		assert_eq!(pallet_exchange::Pallet::<T>::get_intentions_count((asset_a, asset_b)), 1);
@@ -332,7 +362,7 @@ benchmarks! {
 
 		initialize_pool::<T>(creator, asset_a, asset_b, amount, Price::from(1))?;
 
-	}: { xykpool::Pallet::<T>::buy(RawOrigin::Signed(buyer.clone()).into(), asset_a, asset_b, 1_000_000_000, max_sold, false)?; }
+	}: { xykpool::Pallet::<T>::buy(RawOrigin::Signed(buyer.clone()).into(), asset_a, asset_b, 1_000_000_000, max_sold, false, None, None)?; }
 	verify {
 		assert_eq!(<T as xykpool::Config>::Currency::free_balance(asset_a, &buyer), 1000001000000000);
 		assert_eq!(<T as xykpool::Config>::Currency::free_balance(asset_b, &buyer), 999998886666666);
@@ -359,6 +389,7 @@ benchmarks! {
 			1_000_000_000,
 			max_sold,
 			false,
+			None,
 		)?;
 
 		assert_eq!(pallet_exchange::Pallet::<T>::get_intentions_count((asset_a, asset_b)), 1);
@@ -369,6 +400,127 @@ benchmarks! {
 		assert_eq!(<T as xykpool::Config>::Currency::free_balance(asset_a, &buyer), 1000001000000000);
 		assert_eq!(<T as xykpool::Config>::Currency::free_balance(asset_b, &buyer), 999998886666666);
 	}
+
+	cancel_intention {
+		let caller = funded_account::<T>("caller", 1);
+
+		let nbr_intentions_appended: u32  = MAX_INTENTIONS_IN_BLOCK;
+
+		let asset_a: AssetId = 1;
+		let asset_b: AssetId = 2;
+		let amount : Balance =  DOLLARS;
+		let limit : Balance =  DOLLARS;
+
+		initialize_pool::<T>(caller.clone(), asset_a, asset_b, amount, Price::from(10))?;
+
+		feed_intentions::<T>(asset_a, asset_b, nbr_intentions_appended, &INTENTION_AMOUNTS)?;
+
+		Exchange::<T>::sell(RawOrigin::Signed(caller.clone()).into(), asset_a, asset_b, amount, limit, false, None)?;
+
+		let intention_id = pallet_exchange::Pallet::<T>::get_intentions((asset_a, asset_b)).last().unwrap().intention_id;
+
+		assert_eq!(pallet_exchange::Pallet::<T>::get_intentions_count((asset_a, asset_b)), nbr_intentions_appended + 1);
+
+	}: {  Exchange::<T>::cancel_intention(RawOrigin::Signed(caller.clone()).into(), intention_id)? }
+	verify{
+		assert_eq!(pallet_exchange::Pallet::<T>::get_intentions_count((asset_a, asset_b)), nbr_intentions_appended);
+	}
+
+	place_limit_order {
+		let creator = funded_account::<T>("creator", 100);
+		let caller = funded_account::<T>("caller", 1);
+
+		let asset_a: AssetId = 1;
+		let asset_b: AssetId = 2;
+		let amount : Balance = 100_000_000_000_000;
+		let max_orders = <T as pallet_exchange::Config>::MaxLimitOrdersPerPair::get();
+		let valid_until = frame_system::Pallet::<T>::block_number() + 1u32.into();
+
+		initialize_pool::<T>(creator, asset_a, asset_b, amount, Price::from(1))?;
+
+		// Full order book with higher limit prices than the new one - the new order is inserted at the beginning
+		// and the worst order is evicted
+		feed_limit_orders::<T>(asset_a, asset_b, max_orders, 2 * LIMIT_ORDER_AMOUNT, valid_until)?;
+
+	}: {  Exchange::<T>::place_limit_order(RawOrigin::Signed(caller.clone()).into(), asset_a, asset_b, LIMIT_ORDER_AMOUNT, LIMIT_ORDER_AMOUNT, valid_until)? }
+	verify {
+		assert_eq!(pallet_exchange::Pallet::<T>::get_limit_orders((asset_a, asset_b)).len() as u32, max_orders);
+		assert_eq!(<T as xykpool::Config>::Currency::free_balance(asset_a, &caller), INITIAL_ASSET_BALANCE - LIMIT_ORDER_AMOUNT);
+	}
+
+	cancel_limit_order {
+		let creator = funded_account::<T>("creator", 100);
+		let caller = funded_account::<T>("caller", 1);
+
+		let asset_a: AssetId = 1;
+		let asset_b: AssetId = 2;
+		let amount : Balance = 100_000_000_000_000;
+		let max_orders = <T as pallet_exchange::Config>::MaxLimitOrdersPerPair::get();
+		let valid_until = frame_system::Pallet::<T>::block_number() + 1u32.into();
+
+		initialize_pool::<T>(creator, asset_a, asset_b, amount, Price::from(1))?;
+
+		feed_limit_orders::<T>(asset_a, asset_b, max_orders - 1, 2 * LIMIT_ORDER_AMOUNT, valid_until)?;
+
+		// The order with the highest limit price is the last one in the book
+		Exchange::<T>::place_limit_order(RawOrigin::Signed(caller.clone()).into(), asset_a, asset_b, LIMIT_ORDER_AMOUNT, 3 * LIMIT_ORDER_AMOUNT, valid_until)?;
+
+		let order_id = pallet_exchange::Pallet::<T>::get_limit_orders((asset_a, asset_b))[max_orders as usize - 1].order_id;
+
+	}: {  Exchange::<T>::cancel_limit_order(RawOrigin::Signed(caller.clone()).into(), asset_a, asset_b, order_id)? }
+	verify {
+		assert_eq!(pallet_exchange::Pallet::<T>::get_limit_orders((asset_a, asset_b)).len() as u32, max_orders - 1);
+		assert_eq!(<T as xykpool::Config>::Currency::free_balance(asset_a, &caller), INITIAL_ASSET_BALANCE);
+	}
+
+	process_limit_orders {
+		let o in 1 .. <T as pallet_exchange::Config>::MaxLimitOrdersPerPair::get(); // Limit order component
+		let caller = funded_account::<T>("caller", 1);
+
+		let asset_a: AssetId = 1;
+		let asset_b: AssetId = 2;
+		let amount : Balance = 100_000_000_000_000;
+		let valid_until = frame_system::Pallet::<T>::block_number() + 1u32.into();
+
+		initialize_pool::<T>(caller, asset_a, asset_b, amount, Price::from(1))?;
+
+		// All orders are crossed and sold via AMM
+		feed_limit_orders::<T>(asset_a, asset_b, o, 1, valid_until)?;
+
+		assert_eq!(pallet_exchange::Pallet::<T>::get_limit_orders((asset_a, asset_b)).len() as u32, o);
+
+	}: {  Exchange::<T>::on_finalize(1u32.into()); }
+	verify {
+		assert!(pallet_exchange::Pallet::<T>::get_limit_orders((asset_a, asset_b)).is_empty());
+		for idx in 0..o  {
+			let user: T::AccountId = account("user", idx + 200, SEED);
+			assert_eq!(<T as xykpool::Config>::Currency::free_balance(asset_a, &user), INITIAL_ASSET_BALANCE - LIMIT_ORDER_AMOUNT);
+		}
+	}
+
+	prune_expired_limit_orders {
+		let o in 1 .. <T as pallet_exchange::Config>::MaxLimitOrdersPerPair::get(); // Limit order component
+		let caller = funded_account::<T>("caller", 1);
+
+		let asset_a: AssetId = 1;
+		let asset_b: AssetId = 2;
+		let amount : Balance = 100_000_000_000_000;
+		let now = frame_system::Pallet::<T>::block_number();
+
+		initialize_pool::<T>(caller, asset_a, asset_b, amount, Price::from(1))?;
+
+		feed_limit_orders::<T>(asset_a, asset_b, o, 2 * LIMIT_ORDER_AMOUNT, now)?;
+
+		frame_system::Pallet::<T>::set_block_number(now + 1u32.into());
+
+	}: {  Exchange::<T>::on_idle(now + 1u32.into(), Weight::MAX); }
+	verify {
+		assert!(pallet_exchange::Pallet::<T>::get_limit_orders((asset_a, asset_b)).is_empty());
+		for idx in 0..o  {
+			let user: T::AccountId = account("user", idx + 200, SEED);
+			assert_eq!(<T as xykpool::Config>::Currency::free_balance(asset_a, &user), INITIAL_ASSET_BALANCE);
+		}
+	}
 }
 
 #[cfg(test)]
@@ -390,6 +542,11 @@ mod tests {
 			assert_ok!(Pallet::<Test>::test_benchmark_on_finalize_for_one_sell_extrinsic());
 			assert_ok!(Pallet::<Test>::test_benchmark_buy_extrinsic());
 			assert_ok!(Pallet::<Test>::test_benchmark_on_finalize_for_one_buy_extrinsic());
+			assert_ok!(Pallet::<Test>::test_benchmark_cancel_intention());
+			assert_ok!(Pallet::<Test>::test_benchmark_place_limit_order());
+			assert_ok!(Pallet::<Test>::test_benchmark_cancel_limit_order());
+			assert_ok!(Pallet::<Test>::test_benchmark_process_limit_orders());
+			assert_ok!(Pallet::<Test>::test_benchmark_prune_expired_limit_orders());
 		});
 	}
 }
@@ -0,0 +1,57 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2021  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for exchange pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::{Codec, Decode, Encode};
+pub use primitives::{DirectTradeInfo, ExchangeIntention, IntentionsResolution};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_std::prelude::*;
+
+/// Intentions registered in current block for a pair of assets - kept until the next block is initialized.
+#[derive(PartialEq, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct PendingIntentions<AccountId, Balance, IntentionId> {
+	/// Intentions selling `asset_a`.
+	pub asset_a_intentions: Vec<ExchangeIntention<AccountId, Balance, IntentionId>>,
+	/// Intentions selling `asset_b`.
+	pub asset_b_intentions: Vec<ExchangeIntention<AccountId, Balance, IntentionId>>,
+}
+
+sp_api::decl_runtime_apis! {
+	pub trait ExchangeApi<AccountId, AssetId, Balance, IntentionId> where
+		AccountId: Codec,
+		AssetId: Codec,
+		Balance: Codec,
+		IntentionId: Codec,
+	{
+		/// Intentions registered in current block for given assets, per side. Resolved intentions of a finalized block
+		/// are returned too.
+		fn pending_intentions(
+			asset_a: AssetId,
+			asset_b: AssetId
+		) -> PendingIntentions<AccountId, Balance, IntentionId>;
+
+		/// Expected resolution of registered intentions of all pairs at the end of current block.
+		fn simulate_resolution() -> Vec<IntentionsResolution<Balance, IntentionId>>;
+	}
+}
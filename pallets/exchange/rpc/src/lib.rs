@@ -0,0 +1,116 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2021  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use pallet_exchange_rpc_runtime_api::{IntentionsResolution, PendingIntentions};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::sync::Arc;
+
+pub use pallet_exchange_rpc_runtime_api::ExchangeApi as ExchangeRuntimeApi;
+
+#[rpc]
+pub trait ExchangeApi<BlockHash, AssetId, PendingType, ResolutionType> {
+	#[rpc(name = "exchange_pendingIntentions")]
+	fn pending_intentions(&self, asset_a: AssetId, asset_b: AssetId, at: Option<BlockHash>) -> Result<PendingType>;
+
+	#[rpc(name = "exchange_simulateResolution")]
+	fn simulate_resolution(&self, at: Option<BlockHash>) -> Result<Vec<ResolutionType>>;
+}
+
+/// A struct that implements the [`ExchangeApi`].
+pub struct Exchange<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Exchange<C, B> {
+	/// Create new `Exchange` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Exchange {
+			client,
+			_marker: Default::default(),
+		}
+	}
+}
+
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+impl<C, Block, AccountId, AssetId, Balance, IntentionId>
+	ExchangeApi<
+		<Block as BlockT>::Hash,
+		AssetId,
+		PendingIntentions<AccountId, Balance, IntentionId>,
+		IntentionsResolution<Balance, IntentionId>,
+	> for Exchange<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: ExchangeRuntimeApi<Block, AccountId, AssetId, Balance, IntentionId>,
+	AccountId: Codec,
+	AssetId: Codec,
+	Balance: Codec,
+	IntentionId: Codec,
+{
+	fn pending_intentions(
+		&self,
+		asset_a: AssetId,
+		asset_b: AssetId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<PendingIntentions<AccountId, Balance, IntentionId>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.pending_intentions(&at, asset_a, asset_b).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to retrieve pending intentions.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn simulate_resolution(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<IntentionsResolution<Balance, IntentionId>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.simulate_resolution(&at).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to simulate resolution of intentions.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+}
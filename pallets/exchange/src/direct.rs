@@ -43,13 +43,13 @@ impl<'a, T: Config> DirectTradeData<'a, T> {
 	/// Prepare direct trade
 	/// 1. Validate balances
 	/// 2. Calculate fees
-	/// 3. Reserve amounts for each transfer ( including fee transfers )
+	/// 3. Reserve amounts for each transfer ( including fee transfers ) - crossed limit orders pay from their reserve
 	pub fn prepare(&mut self, pool_account: &'a T::AccountId) -> bool {
-		if T::Currency::free_balance(self.intention_a.assets.asset_in, &self.intention_a.who) < self.amount_from_a {
+		if !Pallet::<T>::can_pay(self.intention_a, self.amount_from_a) {
 			Self::send_insufficient_balance_event(self.intention_a, self.intention_a.assets.asset_in);
 			return false;
 		}
-		if T::Currency::free_balance(self.intention_a.assets.asset_out, &self.intention_b.who) < self.amount_from_b {
+		if !Pallet::<T>::can_pay(self.intention_b, self.amount_from_b) {
 			Self::send_insufficient_balance_event(self.intention_b, self.intention_a.assets.asset_out);
 			return false;
 		}
@@ -79,19 +79,11 @@ impl<'a, T: Config> DirectTradeData<'a, T> {
 		// Each one is handled slightly different, hence the complicated match statement.
 		match (&self.intention_a.sell_or_buy, &self.intention_b.sell_or_buy) {
 			(IntentionType::SELL, IntentionType::SELL) => {
-				if !Self::reserve_if_can(
-					self.intention_a.assets.asset_in,
-					&self.intention_a.who,
-					self.amount_from_a,
-				) {
+				if !Self::reserve_if_can(self.intention_a, self.amount_from_a) {
 					return false;
 				}
 
-				if !Self::reserve_if_can(
-					self.intention_a.assets.asset_out,
-					&self.intention_b.who,
-					self.amount_from_b,
-				) {
+				if !Self::reserve_if_can(self.intention_b, self.amount_from_b) {
 					return false;
 				}
 
@@ -132,19 +124,11 @@ impl<'a, T: Config> DirectTradeData<'a, T> {
 				self.transfers.push(transfer);
 			}
 			(IntentionType::BUY, IntentionType::BUY) => {
-				if !Self::reserve_if_can(
-					self.intention_a.assets.asset_in,
-					&self.intention_a.who,
-					self.amount_from_a + transfer_a_fee,
-				) {
+				if !Self::reserve_if_can(self.intention_a, self.amount_from_a + transfer_a_fee) {
 					return false;
 				}
 
-				if !Self::reserve_if_can(
-					self.intention_a.assets.asset_out,
-					&self.intention_b.who,
-					self.amount_from_b + transfer_b_fee,
-				) {
+				if !Self::reserve_if_can(self.intention_b, self.amount_from_b + transfer_b_fee) {
 					return false;
 				}
 
@@ -185,19 +169,11 @@ impl<'a, T: Config> DirectTradeData<'a, T> {
 				self.transfers.push(transfer);
 			}
 			(IntentionType::BUY, IntentionType::SELL) => {
-				if !Self::reserve_if_can(
-					self.intention_a.assets.asset_in,
-					&self.intention_a.who,
-					self.amount_from_a + transfer_a_fee,
-				) {
+				if !Self::reserve_if_can(self.intention_a, self.amount_from_a + transfer_a_fee) {
 					return false;
 				}
 
-				if !Self::reserve_if_can(
-					self.intention_a.assets.asset_out,
-					&self.intention_b.who,
-					self.amount_from_b,
-				) {
+				if !Self::reserve_if_can(self.intention_b, self.amount_from_b) {
 					return false;
 				}
 
@@ -229,19 +205,11 @@ impl<'a, T: Config> DirectTradeData<'a, T> {
 				self.transfers.push(transfer);
 			}
 			(IntentionType::SELL, IntentionType::BUY) => {
-				if !Self::reserve_if_can(
-					self.intention_a.assets.asset_in,
-					&self.intention_a.who,
-					self.amount_from_a,
-				) {
+				if !Self::reserve_if_can(self.intention_a, self.amount_from_a) {
 					return false;
 				}
 
-				if !Self::reserve_if_can(
-					self.intention_a.assets.asset_out,
-					&self.intention_b.who,
-					self.amount_from_b + transfer_b_fee,
-				) {
+				if !Self::reserve_if_can(self.intention_b, self.amount_from_b + transfer_b_fee) {
 					return false;
 				}
 
@@ -355,8 +323,8 @@ impl<'a, T: Config> DirectTradeData<'a, T> {
 		));
 	}
 
-	/// Reserve amount.
-	fn reserve_if_can(asset: AssetId, who: &T::AccountId, amount: Balance) -> bool {
-		T::Currency::reserve(asset, who, amount).is_ok()
+	/// Reserve amount paid by the intention. Amount paid by crossed limit order is reserved already.
+	fn reserve_if_can(intention: &Intention<T>, amount: Balance) -> bool {
+		intention.reserved >= amount || T::Currency::reserve(intention.assets.asset_in, &intention.who, amount).is_ok()
 	}
 }
@@ -22,7 +22,8 @@
 #![allow(clippy::unnecessary_wraps)]
 #![feature(drain_filter)]
 
-use frame_support::{dispatch, ensure};
+use frame_support::storage::{with_transaction, TransactionOutcome};
+use frame_support::{dispatch, ensure, transactional};
 use frame_system::{self as system, ensure_signed};
 
 use codec::Encode;
@@ -30,14 +31,18 @@ use sp_std::vec::Vec;
 
 use direct::{DirectTradeData, Transfer};
 use frame_support::weights::Weight;
-use hydradx_traits::{AMMTransfer, Resolver, AMM};
+use hydradx_traits::{AMMTransfer, AMM};
 use orml_traits::{MultiCurrency, MultiCurrencyExtended, MultiReservableCurrency};
 use primitives::{
-	asset::AssetPair, constants::chain::MIN_TRADING_LIMIT, Amount, AssetId, Balance, ExchangeIntention, IntentionType,
+	asset::AssetPair, constants::chain::MIN_TRADING_LIMIT, traits::Resolver, Amount, AssetId, Balance, DirectTradeInfo,
+	ExchangeIntention, IntentionType, IntentionsResolution,
 };
 
 use frame_support::sp_runtime::traits::BlockNumberProvider;
-use frame_support::sp_runtime::traits::Hash;
+use frame_support::sp_runtime::traits::{Hash, Saturating};
+use frame_support::sp_runtime::Permill;
+use primitive_types::U256;
+use sp_std::cmp::Ordering;
 
 #[cfg(test)]
 mod mock;
@@ -54,6 +59,50 @@ mod tests;
 type IntentionId<T> = <T as system::Config>::Hash;
 pub type Intention<T> = ExchangeIntention<<T as system::Config>::AccountId, Balance, IntentionId<T>>;
 
+/// Limit order alias
+pub type LimitOrderOf<T> =
+	LimitOrder<<T as system::Config>::AccountId, <T as system::Config>::BlockNumber, IntentionId<T>>;
+
+/// Maximum number of attempts to sell a crossed limit order via AMM. Each attempt halves the amount.
+const MAX_LIMIT_ORDER_AMM_ATTEMPTS: u32 = 4;
+
+/// Limit order kept in the order book across blocks until it is filled, cancelled or expired.
+/// Sold amount is reserved when the order is placed.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct LimitOrder<AccountId, BlockNumber, OrderId> {
+	pub who: AccountId,
+	pub assets: AssetPair,
+	/// Amount of `asset_in` left to be sold.
+	pub amount_in: Balance,
+	/// Minimum amount of `asset_out` (fee excluded) received for `amount_in` - the limit price of the order.
+	pub amount_out: Balance,
+	/// Last block in which the order can be filled.
+	pub valid_until: BlockNumber,
+	pub order_id: OrderId,
+}
+
+/// Intentions and crossed limit orders of a pair after direct trades were executed.
+struct MatchingOutcome<T: Config> {
+	main_intentions: Vec<Intention<T>>,
+	matched_intentions: Vec<Intention<T>>,
+	/// Intentions which cannot be matched, priced at the clearing price.
+	not_matched: Vec<Intention<T>>,
+	/// Ids of crossed limit orders taking part in matching.
+	order_ids: Vec<IntentionId<T>>,
+	direct_trades: Vec<DirectTradeInfo<Balance, IntentionId<T>>>,
+}
+
+impl<T: Config> MatchingOutcome<T> {
+	/// Intentions and orders with amount left to be traded via AMM.
+	fn left_intentions(&self) -> impl Iterator<Item = &Intention<T>> {
+		self.main_intentions
+			.iter()
+			.chain(self.matched_intentions.iter())
+			.filter(|x| Pallet::<T>::amount_left(x) > 0)
+			.chain(self.not_matched.iter())
+	}
+}
+
 // Re-export pallet items so that they can be accessed from the crate namespace.
 use frame_support::pallet_prelude::*;
 pub use pallet::*;
@@ -71,12 +120,11 @@ pub mod pallet {
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
 		/// Finalize and resolve all registered intentions.
 		/// Group/match intentions which can be directly traded.
+		/// Limit orders of each pair are matched too, so pairs with limit orders are processed even without intentions.
 		fn on_finalize(_n: T::BlockNumber) {
-			for ((asset_1, asset_2), count) in ExchangeAssetsIntentionCount::<T>::iter() {
-				// If no intention registered for asset1/2, move onto next one
-				if count == 0u32 {
-					continue;
-				}
+			let mut resolved_keys = Vec::<(AssetId, AssetId)>::new();
+
+			for (asset_1, asset_2) in Self::pairs_to_process() {
 				let pair = AssetPair {
 					asset_in: asset_1,
 					asset_out: asset_2,
@@ -87,17 +135,106 @@ pub mod pallet {
 				let mut asset_a_ins = <ExchangeAssetsIntentions<T>>::get((asset_2, asset_1));
 				let mut asset_b_ins = <ExchangeAssetsIntentions<T>>::get((asset_1, asset_2));
 
-				//TODO: we can short circuit here if nothing in asset_b_sells and just resolve asset_a sells.
+				let asset_a_orders = LimitOrders::<T>::get((asset_2, asset_1)).into_inner();
+				let asset_b_orders = LimitOrders::<T>::get((asset_1, asset_2)).into_inner();
+
+				let mut asset_a_orders_left = asset_a_orders.clone();
+				let mut asset_b_orders_left = asset_b_orders.clone();
 
-				Self::process_exchange_intentions(&pair_account, &mut asset_a_ins, &mut asset_b_ins);
+				Self::process_exchange_intentions(
+					&pair_account,
+					&mut asset_a_ins,
+					&mut asset_b_ins,
+					&mut asset_a_orders_left,
+					&mut asset_b_orders_left,
+				);
+
+				if asset_a_orders_left != asset_a_orders {
+					Self::put_limit_orders((asset_2, asset_1), asset_a_orders_left);
+				}
+				if asset_b_orders_left != asset_b_orders {
+					Self::put_limit_orders((asset_1, asset_2), asset_b_orders_left);
+				}
+
+				// Resolved intentions are kept until the next block is initialized, so they can be queried via RPC
+				for key in [(asset_2, asset_1), (asset_1, asset_2)] {
+					ExchangeAssetsIntentions::<T>::mutate_exists(key, |maybe_intentions| {
+						if let Some(intentions) = maybe_intentions {
+							intentions.iter_mut().for_each(|x| x.reserved = 0);
+							resolved_keys.push(key);
+						}
+					});
+				}
+			}
+
+			if !resolved_keys.is_empty() {
+				ResolvedIntentionsKeys::<T>::put(resolved_keys);
 			}
 
 			ExchangeAssetsIntentionCount::<T>::remove_all(None);
-			ExchangeAssetsIntentions::<T>::remove_all(None);
+			ExchangeAssetsCancelledIntentionCount::<T>::remove_all(None);
 		}
 
+		/// Remove intentions resolved in the previous block.
 		fn on_initialize(_n: T::BlockNumber) -> Weight {
+			let resolved_keys = ResolvedIntentionsKeys::<T>::take();
+			let mut removed_intentions: Weight = 0;
+
+			for key in resolved_keys.iter() {
+				for intention in ExchangeAssetsIntentions::<T>::take(key) {
+					IntentionAssetPairs::<T>::remove(intention.intention_id);
+					removed_intentions += 1;
+				}
+			}
+
+			let max_orders = T::MaxLimitOrdersPerPair::get();
+
 			T::WeightInfo::known_overhead_for_on_finalize()
+				.saturating_add(
+					T::WeightInfo::process_limit_orders(max_orders).saturating_mul(LimitOrders::<T>::count() as Weight),
+				)
+				.saturating_add(T::DbWeight::get().reads_writes(
+					resolved_keys.len() as Weight + 1,
+					resolved_keys.len() as Weight + removed_intentions + 1,
+				))
+		}
+
+		/// Prune expired limit orders and release their reserved amounts.
+		///
+		/// Pairs are pruned in round-robin order starting after the pair pruned last, so all pairs are pruned
+		/// eventually even if only a few of them fit into the remaining weight of a block.
+		fn on_idle(n: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			let cursor_weight = T::DbWeight::get().reads_writes(2, 1);
+			let prune_weight = T::WeightInfo::prune_expired_limit_orders(T::MaxLimitOrdersPerPair::get());
+
+			let max_pairs = remaining_weight
+				.saturating_sub(cursor_weight)
+				.checked_div(prune_weight)
+				.unwrap_or_default();
+			let max_pairs = usize::try_from(max_pairs)
+				.unwrap_or(usize::MAX)
+				.min(LimitOrders::<T>::count() as usize);
+
+			if max_pairs == 0 {
+				return T::DbWeight::get().reads(1);
+			}
+
+			// Pairs after the cursor first, then from the beginning - each pair is visited at most once
+			let pairs: Vec<(AssetId, AssetId)> = match LimitOrdersPruneCursor::<T>::get() {
+				Some(last) => LimitOrders::<T>::iter_keys_from(LimitOrders::<T>::hashed_key_for(last))
+					.chain(LimitOrders::<T>::iter_keys())
+					.take(max_pairs)
+					.collect(),
+				None => LimitOrders::<T>::iter_keys().take(max_pairs).collect(),
+			};
+
+			for pair in pairs.iter() {
+				Self::prune_expired_limit_orders(*pair, n);
+			}
+
+			LimitOrdersPruneCursor::<T>::set(pairs.last().copied());
+
+			cursor_weight.saturating_add(prune_weight.saturating_mul(pairs.len() as Weight))
 		}
 	}
 
@@ -109,7 +246,7 @@ pub mod pallet {
 		type AMMPool: AMM<Self::AccountId, AssetId, AssetPair, Balance>;
 
 		/// Intention resolver
-		type Resolver: Resolver<Self::AccountId, Intention<Self>, Error<Self>>;
+		type Resolver: Resolver<Intention<Self>>;
 
 		/// Currency for transfers
 		type Currency: MultiCurrencyExtended<Self::AccountId, CurrencyId = AssetId, Balance = Balance, Amount = Amount>
@@ -117,6 +254,14 @@ pub mod pallet {
 
 		/// Weight information for the extrinsics.
 		type WeightInfo: WeightInfo;
+
+		/// Maximum number of limit orders selling one asset of a pair.
+		#[pallet::constant]
+		type MaxLimitOrdersPerPair: Get<u32>;
+
+		/// Maximum number of blocks a limit order can be kept in the order book.
+		#[pallet::constant]
+		type MaxLimitOrderLifetime: Get<Self::BlockNumber>;
 	}
 
 	#[pallet::event]
@@ -171,6 +316,38 @@ pub mod pallet {
 			IntentionId<T>,
 			dispatch::DispatchError,
 		),
+
+		/// Limit order placed
+		/// [who, asset in, asset out, amount in, amount out, valid until, order id]
+		LimitOrderPlaced(
+			T::AccountId,
+			AssetId,
+			AssetId,
+			Balance,
+			Balance,
+			T::BlockNumber,
+			IntentionId<T>,
+		),
+
+		/// Limit order was filled - completely if amount left is 0
+		/// [who, order id, amount sold, amount left]
+		LimitOrderFilled(T::AccountId, IntentionId<T>, Balance, Balance),
+
+		/// Limit order cancelled
+		/// [who, order id, amount unreserved]
+		LimitOrderCancelled(T::AccountId, IntentionId<T>, Balance),
+
+		/// Limit order expired and was removed from the order book
+		/// [who, order id, amount unreserved]
+		LimitOrderExpired(T::AccountId, IntentionId<T>, Balance),
+
+		/// Intention cancelled before it was resolved
+		/// [who, intention id, amount unreserved]
+		IntentionCancelled(T::AccountId, IntentionId<T>, Balance),
+
+		/// Limit order with the worst limit price was evicted from full order book by a better order
+		/// [who, order id, amount unreserved]
+		LimitOrderEvicted(T::AccountId, IntentionId<T>, Balance),
 	}
 
 	#[pallet::error]
@@ -195,6 +372,30 @@ pub mod pallet {
 
 		/// Overflow
 		IntentionCountOverflow,
+
+		/// Price impact of AMM trade exceeds maximum price impact of the intention.
+		MaxPriceImpactExceeded,
+
+		/// Limit price of the order is zero.
+		InvalidLimitPrice,
+
+		/// Limit order expiry is in the past or too far in the future.
+		InvalidLimitOrderExpiry,
+
+		/// Order book of the pair is full and the limit price of the order is not better than the worst one.
+		LimitOrderBookFull,
+
+		/// Limit order does not exist.
+		LimitOrderNotFound,
+
+		/// Limit order belongs to another account.
+		NotLimitOrderOwner,
+
+		/// Intention does not exist.
+		IntentionNotFound,
+
+		/// Intention belongs to another account.
+		NotIntentionOwner,
 	}
 
 	/// Intention count for current block
@@ -205,11 +406,47 @@ pub mod pallet {
 
 	/// Registered intentions for current block
 	/// Stored as ( asset_a, asset_b ) combination where asset_a is meant to be exchanged for asset_b ( asset_a < asset_b)
+	/// Intentions are kept with released reserved amounts after they are resolved, until the next block is initialized.
 	#[pallet::storage]
 	#[pallet::getter(fn get_intentions)]
 	pub type ExchangeAssetsIntentions<T: Config> =
 		StorageMap<_, Blake2_128Concat, (AssetId, AssetId), Vec<Intention<T>>, ValueQuery>;
 
+	/// Number of intentions cancelled in current block
+	/// Used together with intention count to generate unique intention ids
+	#[pallet::storage]
+	pub type ExchangeAssetsCancelledIntentionCount<T: Config> =
+		StorageMap<_, Blake2_128Concat, (AssetId, AssetId), u32, ValueQuery>;
+
+	/// Key of each intention registered in current block, so the intention can be found by its id
+	#[pallet::storage]
+	pub type IntentionAssetPairs<T: Config> =
+		StorageMap<_, Blake2_128Concat, IntentionId<T>, (AssetId, AssetId), OptionQuery>;
+
+	/// Keys of intentions resolved in the previous block, removed when the next block is initialized
+	#[pallet::storage]
+	pub type ResolvedIntentionsKeys<T: Config> = StorageValue<_, Vec<(AssetId, AssetId)>, ValueQuery>;
+
+	/// Limit orders kept across blocks
+	/// Stored as ( asset_in, asset_out ) combination, sorted by limit price - the lowest price first.
+	#[pallet::storage]
+	#[pallet::getter(fn get_limit_orders)]
+	pub type LimitOrders<T: Config> = CountedStorageMap<
+		_,
+		Blake2_128Concat,
+		(AssetId, AssetId),
+		BoundedVec<LimitOrderOf<T>, T::MaxLimitOrdersPerPair>,
+		ValueQuery,
+	>;
+
+	/// Pair of assets whose limit orders were pruned last in `on_idle`
+	#[pallet::storage]
+	pub type LimitOrdersPruneCursor<T: Config> = StorageValue<_, (AssetId, AssetId), OptionQuery>;
+
+	/// Nonce used to generate unique limit order ids
+	#[pallet::storage]
+	pub type LimitOrderNonce<T: Config> = StorageValue<_, u64, ValueQuery>;
+
 	#[allow(dead_code)]
 	#[pallet::extra_constants]
 	impl<T: Config> Pallet<T> {
@@ -246,6 +483,10 @@ pub mod pallet {
 	impl<T: Config> Pallet<T> {
 		/// Create sell intention
 		/// Calculate current spot price, create an intention and store in ```ExchangeAssetsIntentions```
+		/// `amount_sell` is reserved until the intention is resolved.
+		///
+		/// `max_price_impact` - maximum deviation of the execution price (fee excluded) from the spot price of the pool
+		/// if the intention is resolved as AMM trade.
 		#[pallet::weight(< T as Config >::WeightInfo::sell_intention() + < T as Config >::WeightInfo::on_finalize_for_one_sell_extrinsic() - < T as Config >::WeightInfo::known_overhead_for_on_finalize())]
 		pub fn sell(
 			origin: OriginFor<T>,
@@ -254,6 +495,7 @@ pub mod pallet {
 			amount_sell: Balance,
 			min_bought: Balance,
 			discount: bool,
+			max_price_impact: Option<Permill>,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
@@ -269,11 +511,6 @@ pub mod pallet {
 
 			ensure!(T::AMMPool::exists(assets), Error::<T>::TokenPoolNotFound);
 
-			ensure!(
-				T::Currency::free_balance(asset_sell, &who) >= amount_sell,
-				Error::<T>::InsufficientAssetBalance
-			);
-
 			let amount_buy = T::AMMPool::get_spot_price_unchecked(asset_sell, asset_buy, amount_sell);
 
 			ensure!(amount_buy != 0, Error::<T>::ZeroSpotPrice);
@@ -285,6 +522,7 @@ pub mod pallet {
 				amount_sell,
 				amount_buy,
 				min_bought,
+				max_price_impact,
 				discount,
 			)?;
 
@@ -293,6 +531,11 @@ pub mod pallet {
 
 		/// Create buy intention
 		/// Calculate current spot price, create an intention and store in ```ExchangeAssetsIntentions```
+		/// `max_sold` is reserved until the intention is resolved, so the trade is covered at any price within limit.
+		/// The account must be able to pay the amount sold at current spot price including direct trade fee.
+		///
+		/// `max_price_impact` - maximum deviation of the execution price (fee excluded) from the spot price of the pool
+		/// if the intention is resolved as AMM trade.
 		#[pallet::weight(<T as Config>::WeightInfo::buy_intention() + <T as Config>::WeightInfo::on_finalize_for_one_buy_extrinsic() -  <T as Config>::WeightInfo::known_overhead_for_on_finalize())]
 		pub fn buy(
 			origin: OriginFor<T>,
@@ -301,6 +544,7 @@ pub mod pallet {
 			amount_buy: Balance,
 			max_sold: Balance,
 			discount: bool,
+			max_price_impact: Option<Permill>,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
@@ -320,11 +564,6 @@ pub mod pallet {
 
 			ensure!(amount_sell != 0, Error::<T>::ZeroSpotPrice);
 
-			ensure!(
-				T::Currency::free_balance(asset_sell, &who) >= amount_sell,
-				Error::<T>::InsufficientAssetBalance
-			);
-
 			Self::register_intention(
 				&who,
 				IntentionType::BUY,
@@ -332,17 +571,259 @@ pub mod pallet {
 				amount_sell,
 				amount_buy,
 				max_sold,
+				max_price_impact,
 				discount,
 			)?;
 
 			Ok(())
 		}
+
+		/// Cancel intention
+		/// Remove intention registered in current block before it is resolved and release reserved amount.
+		/// Only the account which registered the intention can cancel it.
+		#[pallet::weight(<T as Config>::WeightInfo::cancel_intention())]
+		pub fn cancel_intention(origin: OriginFor<T>, intention_id: IntentionId<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let key = IntentionAssetPairs::<T>::get(intention_id).ok_or(Error::<T>::IntentionNotFound)?;
+			let mut intentions = ExchangeAssetsIntentions::<T>::get(key);
+
+			let idx = intentions
+				.iter()
+				.position(|x| x.intention_id == intention_id)
+				.ok_or(Error::<T>::IntentionNotFound)?;
+
+			ensure!(intentions[idx].who == who, Error::<T>::NotIntentionOwner);
+
+			let intention = intentions.remove(idx);
+
+			T::Currency::unreserve(intention.assets.asset_in, &intention.who, intention.reserved);
+
+			IntentionAssetPairs::<T>::remove(intention_id);
+
+			if intentions.is_empty() {
+				ExchangeAssetsIntentions::<T>::remove(key);
+			} else {
+				ExchangeAssetsIntentions::<T>::insert(key, intentions);
+			}
+
+			let pair = intention.assets.ordered_pair();
+			ExchangeAssetsIntentionCount::<T>::mutate(pair, |count| *count = count.saturating_sub(1));
+			ExchangeAssetsCancelledIntentionCount::<T>::mutate(pair, |count| *count = count.saturating_add(1));
+
+			Self::deposit_event(Event::IntentionCancelled(who, intention_id, intention.reserved));
+
+			Ok(())
+		}
+
+		/// Place limit order
+		/// Reserve `amount_sell` and keep the order in the order book until it is filled, cancelled or expired.
+		///
+		/// The order is sold for at least `min_bought` (fee excluded), partially filled order keeps the same limit price.
+		/// In each block, the order is matched with intentions and sold via AMM when the spot price crosses its limit.
+		/// The order expires after `valid_until` block.
+		///
+		/// If the order book of the pair is full, the order with the worst limit price is evicted and its reserved
+		/// amount is released. The new order must have a better limit price than the evicted one.
+		#[pallet::weight(<T as Config>::WeightInfo::place_limit_order())]
+		#[transactional]
+		pub fn place_limit_order(
+			origin: OriginFor<T>,
+			asset_sell: AssetId,
+			asset_buy: AssetId,
+			amount_sell: Balance,
+			min_bought: Balance,
+			valid_until: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure! {
+				amount_sell >= MIN_TRADING_LIMIT,
+				Error::<T>::MinimumTradeLimitNotReached
+			};
+
+			ensure!(min_bought != 0, Error::<T>::InvalidLimitPrice);
+
+			let assets = AssetPair {
+				asset_in: asset_sell,
+				asset_out: asset_buy,
+			};
+
+			ensure!(T::AMMPool::exists(assets), Error::<T>::TokenPoolNotFound);
+
+			let now = <system::Pallet<T>>::block_number();
+			ensure!(
+				valid_until >= now && valid_until <= now.saturating_add(T::MaxLimitOrderLifetime::get()),
+				Error::<T>::InvalidLimitOrderExpiry
+			);
+
+			T::Currency::reserve(asset_sell, &who, amount_sell).map_err(|_| Error::<T>::InsufficientAssetBalance)?;
+
+			let order = LimitOrder {
+				who: who.clone(),
+				assets,
+				amount_in: amount_sell,
+				amount_out: min_bought,
+				valid_until,
+				order_id: Self::generate_limit_order_id(&who, &assets),
+			};
+
+			let mut orders = LimitOrders::<T>::get((asset_sell, asset_buy)).into_inner();
+
+			// Full order book only accepts an order with a better limit price than the worst order in the book
+			if orders.len() >= T::MaxLimitOrdersPerPair::get() as usize {
+				let worst = orders.pop().ok_or(Error::<T>::LimitOrderBookFull)?;
+
+				ensure!(
+					Self::compare_limit_prices(&order, &worst) == Ordering::Less,
+					Error::<T>::LimitOrderBookFull
+				);
+
+				T::Currency::unreserve(worst.assets.asset_in, &worst.who, worst.amount_in);
+
+				Self::deposit_event(Event::LimitOrderEvicted(worst.who, worst.order_id, worst.amount_in));
+			}
+
+			// Orders with the same limit price are filled in the order they were placed
+			let idx = orders.partition_point(|x| Self::compare_limit_prices(x, &order) != Ordering::Greater);
+			orders.insert(idx, order.clone());
+
+			Self::put_limit_orders((asset_sell, asset_buy), orders);
+
+			Self::deposit_event(Event::LimitOrderPlaced(
+				who,
+				asset_sell,
+				asset_buy,
+				amount_sell,
+				min_bought,
+				valid_until,
+				order.order_id,
+			));
+
+			Ok(())
+		}
+
+		/// Cancel limit order
+		/// Remove the order from the order book and release reserved amount. Only the owner of the order can cancel it.
+		#[pallet::weight(<T as Config>::WeightInfo::cancel_limit_order())]
+		pub fn cancel_limit_order(
+			origin: OriginFor<T>,
+			asset_sell: AssetId,
+			asset_buy: AssetId,
+			order_id: IntentionId<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut orders = LimitOrders::<T>::get((asset_sell, asset_buy)).into_inner();
+
+			let idx = orders
+				.iter()
+				.position(|x| x.order_id == order_id)
+				.ok_or(Error::<T>::LimitOrderNotFound)?;
+
+			ensure!(orders[idx].who == who, Error::<T>::NotLimitOrderOwner);
+
+			let order = orders.remove(idx);
+
+			T::Currency::unreserve(order.assets.asset_in, &order.who, order.amount_in);
+
+			Self::put_limit_orders((asset_sell, asset_buy), orders);
+
+			Self::deposit_event(Event::LimitOrderCancelled(who, order_id, order.amount_in));
+
+			Ok(())
+		}
 	}
 }
 
 // "Internal" functions, callable by code.
 impl<T: Config> Pallet<T> {
+	/// Expected resolution of registered intentions and crossed limit orders of all pairs at the end of current block.
+	///
+	/// Intentions are matched as in `on_finalize`, direct trades included, and all changes are rolled back.
+	/// AMM trades are not executed. Amounts left to be traded via AMM are netted at the spot price of the pool.
+	///
+	/// On the state of a finalized block, intentions of the block are matched again at the state after
+	/// they were resolved.
+	pub fn simulate_resolution() -> Vec<IntentionsResolution<Balance, IntentionId<T>>> {
+		with_transaction(|| {
+			let resolutions = Self::pairs_to_process()
+				.into_iter()
+				.map(|(asset_1, asset_2)| {
+					let pair_account = T::AMMPool::get_pair_id(AssetPair {
+						asset_in: asset_1,
+						asset_out: asset_2,
+					});
+
+					let outcome = Self::match_exchange_intentions(
+						&pair_account,
+						&mut <ExchangeAssetsIntentions<T>>::get((asset_2, asset_1)),
+						&mut <ExchangeAssetsIntentions<T>>::get((asset_1, asset_2)),
+						&LimitOrders::<T>::get((asset_2, asset_1)),
+						&LimitOrders::<T>::get((asset_1, asset_2)),
+					);
+
+					let amm_intentions: Vec<&Intention<T>> = outcome.left_intentions().collect();
+					let amount_sold = |asset: AssetId| -> Balance {
+						amm_intentions
+							.iter()
+							.filter(|x| x.assets.asset_in == asset)
+							.fold(0, |acc, x| acc.saturating_add(x.amount_in))
+					};
+					let amount_1 = amount_sold(asset_1);
+					let amount_2 = amount_sold(asset_2);
+
+					let amount_2_in_1 = T::AMMPool::get_spot_price_unchecked(asset_2, asset_1, amount_2);
+
+					let (assets, amm_amount_in) = if amount_1 >= amount_2_in_1 {
+						(
+							AssetPair {
+								asset_in: asset_1,
+								asset_out: asset_2,
+							},
+							amount_1 - amount_2_in_1,
+						)
+					} else {
+						(
+							AssetPair {
+								asset_in: asset_2,
+								asset_out: asset_1,
+							},
+							amount_2.saturating_sub(T::AMMPool::get_spot_price_unchecked(asset_1, asset_2, amount_1)),
+						)
+					};
+
+					let amm_intentions = amm_intentions.iter().map(|x| x.intention_id).collect();
+
+					IntentionsResolution {
+						assets,
+						direct_trades: outcome.direct_trades,
+						amm_intentions,
+						amm_amount_in,
+					}
+				})
+				.collect();
+
+			TransactionOutcome::Rollback(resolutions)
+		})
+	}
+
+	/// Ordered pairs with registered intentions or limit orders.
+	fn pairs_to_process() -> Vec<(AssetId, AssetId)> {
+		// Storage entry is removed when the last intention of the pair is cancelled
+		let mut pairs: Vec<(AssetId, AssetId)> = ExchangeAssetsIntentions::<T>::iter_keys()
+			.chain(LimitOrders::<T>::iter_keys())
+			.map(|(asset_in, asset_out)| AssetPair { asset_in, asset_out }.ordered_pair())
+			.collect();
+		pairs.sort_unstable();
+		pairs.dedup();
+		pairs
+	}
+
 	/// Register SELL or BUY intention
+	/// Reserve sold amount - maximum sold amount for BUY intention - so the intention can be resolved at finalization.
+	/// Reserved amount is released when the intention is resolved, unused part of it stays free.
+	#[transactional]
 	fn register_intention(
 		who: &T::AccountId,
 		intention_type: IntentionType,
@@ -350,11 +831,34 @@ impl<T: Config> Pallet<T> {
 		amount_in: Balance,
 		amount_out: Balance,
 		limit: Balance,
+		max_price_impact: Option<Permill>,
 		discount: bool,
 	) -> DispatchResult {
 		let intention_count = ExchangeAssetsIntentionCount::<T>::get(assets.ordered_pair());
 
-		let intention_id = Self::generate_intention_id(who, intention_count, &assets);
+		// Cancelled intentions are counted too, so ids of cancelled intentions are not reused
+		let intention_id = Self::generate_intention_id(
+			who,
+			intention_count.saturating_add(ExchangeAssetsCancelledIntentionCount::<T>::get(assets.ordered_pair())),
+			&assets,
+		);
+
+		let reserved = match intention_type {
+			IntentionType::SELL => amount_in,
+			IntentionType::BUY => {
+				let fee = Self::direct_trade_fee(&T::AMMPool::get_pair_id(assets), amount_in)
+					.ok_or(Error::<T>::InsufficientAssetBalance)?;
+				let amount_sold = amount_in.checked_add(fee).ok_or(Error::<T>::InsufficientAssetBalance)?;
+
+				let free_balance = T::Currency::free_balance(assets.asset_in, who);
+				ensure!(free_balance >= amount_sold, Error::<T>::InsufficientAssetBalance);
+
+				// Trade cannot cost more than the account has, so the limit is capped by its balance
+				limit.min(free_balance)
+			}
+		};
+
+		T::Currency::reserve(assets.asset_in, who, reserved).map_err(|_| Error::<T>::InsufficientAssetBalance)?;
 
 		let intention = Intention::<T> {
 			who: who.clone(),
@@ -365,6 +869,8 @@ impl<T: Config> Pallet<T> {
 			sell_or_buy: intention_type,
 			intention_id,
 			trade_limit: limit,
+			max_price_impact,
+			reserved,
 		};
 
 		ExchangeAssetsIntentionCount::<T>::try_mutate(assets.ordered_pair(), |total| -> DispatchResult {
@@ -374,6 +880,7 @@ impl<T: Config> Pallet<T> {
 
 		// Note: cannot use ordered tuple pair, as this must be stored as (in,out) pair
 		<ExchangeAssetsIntentions<T>>::append((assets.asset_in, assets.asset_out), intention);
+		IntentionAssetPairs::<T>::insert(intention_id, (assets.asset_in, assets.asset_out));
 
 		match intention_type {
 			IntentionType::SELL => {
@@ -402,54 +909,481 @@ impl<T: Config> Pallet<T> {
 	}
 
 	/// Process intentions and attempt to match them so they can be direct traded.
-	/// ```a_in_intentions``` are considered 'main' intentions.
 	///
-	/// This algorithm is quite simple at the moment and it tries to match as many intentions from ```b_in_intentions``` as possible while
-	/// satisfying  that sum( b_in_intentions.amount_sell ) <= a_in_intention.amount_sell
+	/// Intentions are matched at the clearing price given by the current spot price of the pool:
+	///
+	/// 1. Each intention is priced at the clearing price. Intentions which cannot satisfy their trade limit
+	/// at the clearing price (direct trade fee included) or whose account cannot cover the trade are not matched.
+	/// 2. The side with greater volume becomes the main side. Intentions of the other side are matched against
+	/// main intentions until one of the sides is exhausted, so the directly matched volume is the volume
+	/// of the smaller side.
+	/// 3. Each direct trade completely fills one of the two intentions. The other one is partially filled and its
+	/// amounts and trade limit are reduced, so an intention can be filled by several direct trades.
+	/// 4. Anything left unmatched is traded through AMM - partially filled main intention first.
+	///
+	/// Limit orders whose limit price is crossed by the clearing price take part in matching as sell intentions
+	/// with lower priority than intentions of the same side. What is left of them is sold via AMM as long as
+	/// their limit price is respected. Amounts of limit orders are updated accordingly.
 	///
-	/// Intention A must be valid - that means that it is verified first by validating if it was possible to do AMM trade.
+	/// Amounts reserved when intentions were registered are released first, so they can be traded.
+	/// Amounts of limit orders stay reserved and are traded from the reserve.
 	fn process_exchange_intentions(
 		pair_account: &T::AccountId,
 		a_in_intentions: &mut [Intention<T>],
 		b_in_intentions: &mut [Intention<T>],
+		a_in_orders: &mut [LimitOrderOf<T>],
+		b_in_orders: &mut [LimitOrderOf<T>],
 	) {
+		let outcome =
+			Self::match_exchange_intentions(pair_account, a_in_intentions, b_in_intentions, a_in_orders, b_in_orders);
+
+		let is_order = |intention: &Intention<T>| outcome.order_ids.contains(&intention.intention_id);
+
+		outcome
+			.left_intentions()
+			.filter(|x| !is_order(x))
+			.for_each(|x| T::Resolver::resolve_single_intention(x));
+
+		// Limit orders are resolved last so they can benefit from price changes caused by AMM trades of intentions
+		for intention in outcome
+			.main_intentions
+			.iter()
+			.chain(outcome.matched_intentions.iter())
+			.chain(outcome.not_matched.iter())
+			.filter(|x| is_order(x))
+		{
+			if let Some(order) = a_in_orders
+				.iter_mut()
+				.chain(b_in_orders.iter_mut())
+				.find(|x| x.order_id == intention.intention_id)
+			{
+				Self::fill_limit_order(order, intention);
+			}
+		}
+	}
+
+	/// Release reserved amounts of intentions, price intentions and crossed limit orders at the clearing price
+	/// and execute direct trades between the two sides. Crossed limit orders pay from their reserved amounts.
+	///
+	/// Intentions and orders are returned with amounts reduced by executed direct trades.
+	fn match_exchange_intentions(
+		pair_account: &T::AccountId,
+		a_in_intentions: &mut [Intention<T>],
+		b_in_intentions: &mut [Intention<T>],
+		a_in_orders: &[LimitOrderOf<T>],
+		b_in_orders: &[LimitOrderOf<T>],
+	) -> MatchingOutcome<T> {
+		for intention in a_in_intentions.iter_mut().chain(b_in_intentions.iter_mut()) {
+			T::Currency::unreserve(intention.assets.asset_in, &intention.who, intention.reserved);
+			intention.reserved = 0;
+		}
+
 		b_in_intentions.sort_by(|a, b| b.amount_in.cmp(&a.amount_in));
 		a_in_intentions.sort_by(|a, b| b.amount_in.cmp(&a.amount_in));
 
-		// indication of how many have been already matched
-		let mut to_skip: usize = 0;
+		let mut not_matched = Vec::<Intention<T>>::new();
+
+		let a_in_crossed_orders = Self::crossed_limit_orders(pair_account, a_in_orders);
+		let b_in_crossed_orders = Self::crossed_limit_orders(pair_account, b_in_orders);
+
+		let mut a_in_matchable = Self::matchable_intentions(pair_account, a_in_intentions, &mut not_matched);
+		a_in_matchable.extend(Self::matchable_intentions(
+			pair_account,
+			&a_in_crossed_orders,
+			&mut not_matched,
+		));
+		let mut b_in_matchable = Self::matchable_intentions(pair_account, b_in_intentions, &mut not_matched);
+		b_in_matchable.extend(Self::matchable_intentions(
+			pair_account,
+			&b_in_crossed_orders,
+			&mut not_matched,
+		));
+
+		// Volume of both sides in asset sold by a_in intentions
+		let a_in_volume = a_in_matchable.iter().fold(0, |acc, x| acc.saturating_add(x.amount_in));
+		let b_in_volume = b_in_matchable.iter().fold(0, |acc, x| acc.saturating_add(x.amount_out));
+
+		let (mut main_intentions, mut matched_intentions) = if a_in_volume >= b_in_volume {
+			(a_in_matchable, b_in_matchable)
+		} else {
+			(b_in_matchable, a_in_matchable)
+		};
+
+		let direct_trades = Self::match_intentions(pair_account, &mut main_intentions, &mut matched_intentions);
+
+		MatchingOutcome {
+			main_intentions,
+			matched_intentions,
+			not_matched,
+			order_ids: a_in_crossed_orders
+				.iter()
+				.chain(b_in_crossed_orders.iter())
+				.map(|x| x.intention_id)
+				.collect(),
+			direct_trades,
+		}
+	}
+
+	/// Price intentions at the clearing price and return those which can be matched.
+	///
+	/// Intentions which cannot be matched are moved to `not_matched`.
+	fn matchable_intentions(
+		pair_account: &T::AccountId,
+		intentions: &[Intention<T>],
+		not_matched: &mut Vec<Intention<T>>,
+	) -> Vec<Intention<T>> {
+		let mut matchable = Vec::<Intention<T>>::with_capacity(intentions.len());
+
+		for intention in intentions.iter() {
+			let mut intention = intention.clone();
+
+			match intention.sell_or_buy {
+				IntentionType::SELL => {
+					intention.amount_out = T::AMMPool::get_spot_price_unchecked(
+						intention.assets.asset_in,
+						intention.assets.asset_out,
+						intention.amount_in,
+					)
+				}
+				IntentionType::BUY => {
+					intention.amount_in = T::AMMPool::get_spot_price_unchecked(
+						intention.assets.asset_out,
+						intention.assets.asset_in,
+						intention.amount_out,
+					)
+				}
+			};
+
+			if Self::is_matchable(pair_account, &intention) {
+				matchable.push(intention);
+			} else {
+				not_matched.push(intention);
+			}
+		}
+
+		matchable
+	}
 
-		for intention in a_in_intentions {
-			if !Self::verify_intention(intention) {
+	/// Check if intention priced at the clearing price can be completely traded directly.
+	fn is_matchable(pair_account: &T::AccountId, intention: &Intention<T>) -> bool {
+		if intention.amount_in == 0 || intention.amount_out == 0 {
+			return false;
+		}
+
+		let (amount_paid, limit_respected) = match intention.sell_or_buy {
+			IntentionType::SELL => match Self::direct_trade_fee(pair_account, intention.amount_out) {
+				Some(fee) => (
+					intention.amount_in,
+					intention.amount_out.saturating_sub(fee) >= intention.trade_limit,
+				),
+				None => return false,
+			},
+			IntentionType::BUY => match Self::direct_trade_fee(pair_account, intention.amount_in) {
+				Some(fee) => {
+					let amount_paid = intention.amount_in.saturating_add(fee);
+					(amount_paid, amount_paid <= intention.trade_limit)
+				}
+				None => return false,
+			},
+		};
+
+		limit_respected && Self::can_pay(intention, amount_paid)
+	}
+
+	/// Check if the account of the intention can pay `amount` of `asset_in`.
+	///
+	/// Crossed limit orders pay from their reserved amount, intentions from free balance.
+	pub(crate) fn can_pay(intention: &Intention<T>, amount: Balance) -> bool {
+		if intention.reserved > 0 {
+			intention.reserved >= amount
+		} else {
+			T::Currency::free_balance(intention.assets.asset_in, &intention.who) >= amount
+		}
+	}
+
+	/// Match main intentions with intentions of the other side and resolve matched amounts as direct trades.
+	///
+	/// Both lists are processed in given order. If a direct trade cannot be prepared, the matched intention
+	/// is skipped and left to be traded through AMM. Executed direct trades are returned.
+	fn match_intentions(
+		pair_account: &T::AccountId,
+		main_intentions: &mut [Intention<T>],
+		matched_intentions: &mut [Intention<T>],
+	) -> Vec<DirectTradeInfo<Balance, IntentionId<T>>> {
+		let mut direct_trades = Vec::new();
+		let mut main_idx: usize = 0;
+		let mut matched_idx: usize = 0;
+
+		while main_idx < main_intentions.len() && matched_idx < matched_intentions.len() {
+			let main_intention = &main_intentions[main_idx];
+			let matched_intention = &matched_intentions[matched_idx];
+
+			// Direct trade completely fills the intention with smaller amount left
+			let (amount_from_main, amount_from_matched) = if matched_intention.amount_out <= main_intention.amount_in {
+				(matched_intention.amount_out, matched_intention.amount_in)
+			} else {
+				(main_intention.amount_in, main_intention.amount_out)
+			};
+
+			let mut dt = DirectTradeData::<T> {
+				intention_a: main_intention,
+				intention_b: matched_intention,
+				amount_from_a: amount_from_main,
+				amount_from_b: amount_from_matched,
+				transfers: Vec::<Transfer<T>>::new(),
+			};
+
+			if !dt.prepare(pair_account) {
+				dt.revert();
+				matched_idx += 1;
 				continue;
 			}
 
-			let mut total_left = intention.amount_in;
+			dt.execute();
 
-			let matched_intentions: Vec<&Intention<T>> = b_in_intentions
-				.iter()
-				.skip(to_skip)
-				.take_while(|x| {
-					if total_left > 0 {
-						total_left = total_left.saturating_sub(x.amount_out);
-						true
-					} else {
-						false
-					}
-				})
-				.collect();
+			direct_trades.push(DirectTradeInfo {
+				intention_a: main_intention.intention_id,
+				intention_b: matched_intention.intention_id,
+				amount_from_a: amount_from_main,
+				amount_from_b: amount_from_matched,
+			});
+
+			Self::fill_intention(
+				pair_account,
+				&mut main_intentions[main_idx],
+				amount_from_main,
+				amount_from_matched,
+			);
+			Self::fill_intention(
+				pair_account,
+				&mut matched_intentions[matched_idx],
+				amount_from_matched,
+				amount_from_main,
+			);
+
+			if Self::amount_left(&main_intentions[main_idx]) == 0 {
+				main_idx += 1;
+			}
+			if Self::amount_left(&matched_intentions[matched_idx]) == 0 {
+				matched_idx += 1;
+			}
+		}
+
+		direct_trades
+	}
+
+	/// Reduce amounts and trade limit of an intention by the amounts of executed direct trade.
+	fn fill_intention(
+		pair_account: &T::AccountId,
+		intention: &mut Intention<T>,
+		amount_in: Balance,
+		amount_out: Balance,
+	) {
+		intention.trade_limit = match intention.sell_or_buy {
+			IntentionType::SELL => {
+				let fee = Self::direct_trade_fee(pair_account, amount_out).unwrap_or_default();
+				intention.trade_limit.saturating_sub(amount_out.saturating_sub(fee))
+			}
+			IntentionType::BUY => {
+				let fee = Self::direct_trade_fee(pair_account, amount_in).unwrap_or_default();
+				intention.trade_limit.saturating_sub(amount_in.saturating_add(fee))
+			}
+		};
+
+		// Amount paid by crossed limit order was repatriated from its reserve
+		intention.reserved = intention.reserved.saturating_sub(amount_in);
+
+		if amount_in >= intention.amount_in || amount_out >= intention.amount_out {
+			// Intention is completely filled
+			intention.amount_in = 0;
+			intention.amount_out = 0;
+		} else {
+			intention.amount_in -= amount_in;
+			intention.amount_out -= amount_out;
+		}
+	}
+
+	/// Amount of the intention which is not resolved yet.
+	///
+	/// Amount sold for sell intentions and amount bought for buy intentions.
+	fn amount_left(intention: &Intention<T>) -> Balance {
+		match intention.sell_or_buy {
+			IntentionType::SELL => intention.amount_in,
+			IntentionType::BUY => intention.amount_out,
+		}
+	}
+
+	/// Fee paid by each account of a direct trade.
+	fn direct_trade_fee(pair_account: &T::AccountId, amount: Balance) -> Option<Balance> {
+		hydra_dx_math::fee::calculate_pool_trade_fee(amount, T::AMMPool::get_fee(pair_account))
+	}
+
+	/// Return limit orders whose limit price is crossed by the spot price of the pool as sell intentions.
+	///
+	/// Amounts of crossed orders stay reserved and direct trades are settled from the reserve.
+	fn crossed_limit_orders(pair_account: &T::AccountId, orders: &[LimitOrderOf<T>]) -> Vec<Intention<T>> {
+		let now = <system::Pallet<T>>::block_number();
+
+		orders
+			.iter()
+			.filter(|order| order.valid_until >= now)
+			.filter(|order| {
+				let amount_out = T::AMMPool::get_spot_price_unchecked(
+					order.assets.asset_in,
+					order.assets.asset_out,
+					order.amount_in,
+				);
+
+				Self::direct_trade_fee(pair_account, amount_out)
+					.map_or(false, |fee| amount_out.saturating_sub(fee) >= order.amount_out)
+			})
+			.map(|order| Intention::<T> {
+				who: order.who.clone(),
+				assets: order.assets,
+				amount_in: order.amount_in,
+				amount_out: order.amount_out,
+				trade_limit: order.amount_out,
+				max_price_impact: None,
+				reserved: order.amount_in,
+				discount: false,
+				sell_or_buy: IntentionType::SELL,
+				intention_id: order.order_id,
+			})
+			.collect()
+	}
+
+	/// Update limit order by the amount traded directly and sell the rest via AMM if its limit price allows it.
+	///
+	/// If the rest cannot be sold via AMM within the limit price, smaller amounts are tried.
+	/// Only the amount sold via AMM is released from the reserve, amount left in the order stays reserved.
+	fn fill_limit_order(order: &mut LimitOrderOf<T>, intention: &Intention<T>) {
+		let initial_amount = order.amount_in;
+
+		Self::reduce_limit_order(order, initial_amount.saturating_sub(intention.amount_in));
+
+		// Rounding leftover of an order completely filled by direct trades
+		T::Currency::unreserve(
+			order.assets.asset_in,
+			&order.who,
+			intention.reserved.saturating_sub(order.amount_in),
+		);
+
+		let mut amount = order.amount_in;
+		for _ in 0..MAX_LIMIT_ORDER_AMM_ATTEMPTS {
+			if amount < MIN_TRADING_LIMIT {
+				break;
+			}
+
+			let min_bought = Self::limit_order_amount_out(order, amount);
+
+			let sold = with_transaction(|| {
+				T::Currency::unreserve(order.assets.asset_in, &order.who, amount);
+
+				let sold = T::AMMPool::validate_sell(&order.who, order.assets, amount, min_bought, false)
+					.map_or(false, |transfer| {
+						Self::execute_amm_transfer(IntentionType::SELL, order.order_id, None, &transfer).is_ok()
+					});
+
+				if sold {
+					TransactionOutcome::Commit(true)
+				} else {
+					TransactionOutcome::Rollback(false)
+				}
+			});
+
+			if sold {
+				Self::reduce_limit_order(order, amount);
+				break;
+			}
+
+			amount /= 2;
+		}
+
+		let amount_sold = initial_amount.saturating_sub(order.amount_in);
+
+		if order.amount_in < MIN_TRADING_LIMIT {
+			// The rest cannot be traded anymore - the order is considered filled and the rest is released
+			T::Currency::unreserve(order.assets.asset_in, &order.who, order.amount_in);
+			order.amount_in = 0;
+		}
+
+		if amount_sold > 0 {
+			Self::deposit_event(Event::LimitOrderFilled(
+				order.who.clone(),
+				order.order_id,
+				amount_sold,
+				order.amount_in,
+			));
+		}
+	}
+
+	/// Reduce amounts of limit order after `amount_sold` was sold. The limit price of the order is kept.
+	fn reduce_limit_order(order: &mut LimitOrderOf<T>, amount_sold: Balance) {
+		order.amount_out = order
+			.amount_out
+			.saturating_sub(Self::limit_order_amount_out(order, amount_sold));
+		order.amount_in = order.amount_in.saturating_sub(amount_sold);
+	}
+
+	/// Minimum amount received for selling `amount` of limit order - rounded in favour of the order owner.
+	fn limit_order_amount_out(order: &LimitOrderOf<T>, amount: Balance) -> Balance {
+		if amount >= order.amount_in {
+			return order.amount_out;
+		}
+
+		let amount_in = U256::from(order.amount_in);
+		let amount_out = (U256::from(order.amount_out) * U256::from(amount) + amount_in - U256::one()) / amount_in;
+
+		amount_out.low_u128()
+	}
+
+	/// Compare limit prices of two orders of the same pair.
+	fn compare_limit_prices(order_a: &LimitOrderOf<T>, order_b: &LimitOrderOf<T>) -> Ordering {
+		let price_a = U256::from(order_a.amount_out).saturating_mul(U256::from(order_b.amount_in));
+		let price_b = U256::from(order_b.amount_out).saturating_mul(U256::from(order_a.amount_in));
 
-			// We need to remember how many we already resolved so for next A intention,
-			// we skip those
-			to_skip += matched_intentions.len();
+		price_a.cmp(&price_b)
+	}
 
-			T::Resolver::resolve_matched_intentions(pair_account, intention, &matched_intentions);
+	/// Store limit orders of the pair. Storage entry is removed when there are no orders left.
+	///
+	/// Orders which are completely filled are removed.
+	fn put_limit_orders(assets: (AssetId, AssetId), orders: Vec<LimitOrderOf<T>>) {
+		let orders: Vec<LimitOrderOf<T>> = orders.into_iter().filter(|x| x.amount_in > 0).collect();
+
+		match BoundedVec::<LimitOrderOf<T>, T::MaxLimitOrdersPerPair>::try_from(orders) {
+			Ok(orders) if !orders.is_empty() => LimitOrders::<T>::insert(assets, orders),
+			_ => LimitOrders::<T>::remove(assets),
 		}
+	}
+
+	/// Remove expired limit orders of the pair and release their reserved amounts.
+	fn prune_expired_limit_orders(assets: (AssetId, AssetId), now: T::BlockNumber) {
+		let (expired, valid): (Vec<LimitOrderOf<T>>, Vec<LimitOrderOf<T>>) = LimitOrders::<T>::get(assets)
+			.into_inner()
+			.into_iter()
+			.partition(|x| x.valid_until < now);
 
-		// If something left in b_in_intentions, just run it through AMM.
-		b_in_intentions.iter().skip(to_skip).for_each(|x| {
-			T::Resolver::resolve_single_intention(x);
+		if expired.is_empty() {
+			return;
+		}
+
+		for order in expired {
+			T::Currency::unreserve(order.assets.asset_in, &order.who, order.amount_in);
+
+			Self::deposit_event(Event::LimitOrderExpired(order.who, order.order_id, order.amount_in));
+		}
+
+		Self::put_limit_orders(assets, valid);
+	}
+
+	fn generate_limit_order_id(account: &T::AccountId, assets: &AssetPair) -> IntentionId<T> {
+		let nonce = LimitOrderNonce::<T>::mutate(|nonce| {
+			*nonce = nonce.wrapping_add(1);
+			*nonce
 		});
+
+		(b"limit_order", nonce, &account, assets.asset_in, assets.asset_out).using_encoded(T::Hashing::hash)
 	}
 
 	/// Execute AMM trade.
@@ -458,8 +1392,16 @@ impl<T: Config> Pallet<T> {
 	fn execute_amm_transfer(
 		amm_tranfer_type: IntentionType,
 		intention_id: IntentionId<T>,
+		max_price_impact: Option<Permill>,
 		transfer: &AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>,
 	) -> DispatchResult {
+		if let Some(max_price_impact) = max_price_impact {
+			ensure!(
+				Self::amm_price_impact(amm_tranfer_type, transfer) <= max_price_impact,
+				Error::<T>::MaxPriceImpactExceeded
+			);
+		}
+
 		match amm_tranfer_type {
 			IntentionType::SELL => {
 				T::AMMPool::execute_sell(transfer)?;
@@ -490,6 +1432,26 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Relative deviation of the execution price of AMM trade (fee excluded) from the spot price of the pool.
+	fn amm_price_impact(
+		amm_tranfer_type: IntentionType,
+		transfer: &AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>,
+	) -> Permill {
+		let (amount_in, amount_out) = match amm_tranfer_type {
+			IntentionType::SELL => (transfer.amount, transfer.amount_out.saturating_add(transfer.fee.1)),
+			IntentionType::BUY => (transfer.amount_out, transfer.amount),
+		};
+
+		let spot_amount_out =
+			T::AMMPool::get_spot_price_unchecked(transfer.assets.asset_in, transfer.assets.asset_out, amount_in);
+
+		if spot_amount_out == 0 {
+			return Permill::zero();
+		}
+
+		Permill::from_rational(spot_amount_out.saturating_sub(amount_out), spot_amount_out)
+	}
+
 	/// Send intention resolve error event.
 	///
 	/// Send event with error detail for intention that failed.
@@ -503,62 +1465,13 @@ impl<T: Config> Pallet<T> {
 		));
 	}
 
-	/// Verify sell or buy intention.
-	/// Perform AMM validate for given intention.
-	fn verify_intention(intention: &Intention<T>) -> bool {
-		match intention.sell_or_buy {
-			IntentionType::SELL => {
-				match T::AMMPool::validate_sell(
-					&intention.who,
-					intention.assets,
-					intention.amount_in,
-					intention.trade_limit,
-					intention.discount,
-				) {
-					Err(error) => {
-						Self::deposit_event(Event::IntentionResolveErrorEvent(
-							intention.who.clone(),
-							intention.assets,
-							intention.sell_or_buy,
-							intention.intention_id,
-							error,
-						));
-						false
-					}
-					_ => true,
-				}
-			}
-			IntentionType::BUY => {
-				match T::AMMPool::validate_buy(
-					&intention.who,
-					intention.assets,
-					intention.amount_out,
-					intention.trade_limit,
-					intention.discount,
-				) {
-					Err(error) => {
-						Self::deposit_event(Event::IntentionResolveErrorEvent(
-							intention.who.clone(),
-							intention.assets,
-							intention.sell_or_buy,
-							intention.intention_id,
-							error,
-						));
-						false
-					}
-					_ => true,
-				}
-			}
-		}
-	}
-
 	fn generate_intention_id(account: &T::AccountId, c: u32, assets: &AssetPair) -> IntentionId<T> {
 		let b = <system::Pallet<T>>::current_block_number();
 		(c, &account, b, assets.ordered_pair().0, assets.ordered_pair().1).using_encoded(T::Hashing::hash)
 	}
 }
 
-impl<T: Config> Resolver<T::AccountId, Intention<T>, Error<T>> for Pallet<T> {
+impl<T: Config> Resolver<Intention<T>> for Pallet<T> {
 	/// Resolve intention via AMM pool.
 	fn resolve_single_intention(intention: &Intention<T>) {
 		let amm_transfer = match intention.sell_or_buy {
@@ -579,7 +1492,12 @@ impl<T: Config> Resolver<T::AccountId, Intention<T>, Error<T>> for Pallet<T> {
 		};
 
 		match amm_transfer {
-			Ok(x) => match Self::execute_amm_transfer(intention.sell_or_buy, intention.intention_id, &x) {
+			Ok(x) => match Self::execute_amm_transfer(
+				intention.sell_or_buy,
+				intention.intention_id,
+				intention.max_price_impact,
+				&x,
+			) {
 				Ok(_) => {}
 				Err(error) => {
 					Self::send_intention_error_event(intention, error);
@@ -590,240 +1508,4 @@ impl<T: Config> Resolver<T::AccountId, Intention<T>, Error<T>> for Pallet<T> {
 			}
 		};
 	}
-
-	/// Resolve main intention and corresponding matched intentions
-	///
-	/// For each matched intention - work out how much can be traded directly and rest is AMM traded.
-	/// If there is anything left in the main intention - it is AMM traded.
-	fn resolve_matched_intentions(pair_account: &T::AccountId, intention: &Intention<T>, matched: &[&Intention<T>]) {
-		let mut intention_copy = intention.clone();
-
-		for matched_intention in matched.iter() {
-			let amount_a_in = intention_copy.amount_in;
-			let amount_a_out = intention_copy.amount_out;
-			let amount_b_in = matched_intention.amount_in;
-			let amount_b_out = matched_intention.amount_out;
-
-			// There are multiple scenarios to handle
-			// 1. Main intention amount left > matched intention amount
-			// 2. Main intention amount left < matched intention amount
-			// 3. Main intention amount left = matched intention amount
-
-			if amount_a_in > amount_b_out {
-				// Scenario 1: Matched intention can be completely direct traded
-				//
-				// 1. Prepare direct trade details - during preparation, direct amounts are reserved.
-				// 2. Execute if ok otherwise revert ( unreserve amounts if any ) .
-				// 3. Sets new amount (rest amount) and trade limit accordingly.
-				let mut dt = DirectTradeData::<T> {
-					intention_a: &intention_copy,
-					intention_b: matched_intention,
-					amount_from_a: amount_b_out,
-					amount_from_b: amount_b_in,
-					transfers: Vec::<Transfer<T>>::new(),
-				};
-
-				// As we direct trading the total matched intention amount - we need to check the trade limit for the matched intention
-				match matched_intention.sell_or_buy {
-					IntentionType::SELL => {
-						if dt.amount_from_a < matched_intention.trade_limit {
-							Self::send_intention_error_event(
-								matched_intention,
-								Error::<T>::TradeAmountNotReachedLimit.into(),
-							);
-							continue;
-						}
-					}
-					IntentionType::BUY => {
-						if dt.amount_from_b > matched_intention.trade_limit {
-							Self::send_intention_error_event(
-								matched_intention,
-								Error::<T>::TradeAmountExceededLimit.into(),
-							);
-							continue;
-						}
-					}
-				};
-
-				match dt.prepare(pair_account) {
-					true => {
-						dt.execute();
-
-						intention_copy.amount_in = amount_a_in.checked_sub(amount_b_out).unwrap(); // Conditionally checked
-						intention_copy.amount_out = if let Some(value) = amount_a_out.checked_sub(amount_b_in) {
-							value
-						} else {
-							// This cannot really happen. IF this happens, that would mean that in/out calculation are wrong.
-							// It is simply because if amount of one asset of intention A is < amount of the asset of intention B,
-							// that means - the second asset's amounts have to be in the same way ( intention A amount < Intention B Amount )
-
-							// however, we can send an error event just to be sure but we can actually panic here because the math is wrong!
-							panic!("In/out calculations are wrong! Intention B amount has to be less that Intention A amount!");
-						};
-
-						intention_copy.trade_limit = match intention_copy.sell_or_buy {
-							IntentionType::SELL => intention_copy.trade_limit.saturating_sub(amount_b_in),
-							IntentionType::BUY => intention_copy.trade_limit - amount_b_out,
-						};
-					}
-					false => {
-						dt.revert();
-						continue;
-					}
-				}
-			} else if amount_a_in < amount_b_out {
-				// Scenario 2: Matched intention CANNOT be completely directly traded
-				//
-				// 1. Work out rest amount and rest trade limits for direct trades.
-				// 2. Verify if AMM transfer can be successfully performed
-				// 3. Verify if direct trade can be successfully performed
-				// 4. If both ok - execute
-				// 5. Main intention is empty at this point - just set amount to 0.
-
-				let rest_out_amount = amount_b_out.checked_sub(amount_a_in).unwrap(); //Note: Conditionally checked
-
-				let rest_in_amount = if let Some(value) = amount_b_in.checked_sub(amount_a_out) {
-					value
-				} else {
-					// This cannot really happen. IF this happens, that would mean that in/out calculation are wrong.
-					// It is simply because if amount of one asset of intention A is < amount of the asset of intention B,
-					// that means - the second asset's amounts have to be in the same way ( intention A amount < Intention B Amount )
-
-					// however, we can send an error event just to be sure but we can actually panic here because the math is wrong!
-					panic!("In/out calculations are wrong! Intention B amount has to be less that Intention A amount!");
-				};
-
-				let mut dt = DirectTradeData::<T> {
-					intention_a: &intention_copy,
-					intention_b: matched_intention,
-					amount_from_a: amount_a_in,
-					amount_from_b: amount_a_out,
-					transfers: Vec::<Transfer<T>>::new(),
-				};
-
-				let amm_transfer_result = match matched_intention.sell_or_buy {
-					IntentionType::SELL => {
-						let rest_limit = matched_intention.trade_limit.saturating_sub(amount_a_in);
-
-						T::AMMPool::validate_sell(
-							&matched_intention.who,
-							matched_intention.assets,
-							rest_in_amount,
-							rest_limit,
-							matched_intention.discount,
-						)
-					}
-					IntentionType::BUY => {
-						let rest_limit = matched_intention.trade_limit.saturating_sub(amount_a_out);
-
-						T::AMMPool::validate_buy(
-							&matched_intention.who,
-							matched_intention.assets,
-							rest_out_amount,
-							rest_limit,
-							matched_intention.discount,
-						)
-					}
-				};
-
-				let amm_transfer = match amm_transfer_result {
-					Ok(x) => x,
-					Err(error) => {
-						Self::send_intention_error_event(matched_intention, error);
-						continue;
-					}
-				};
-
-				match dt.prepare(pair_account) {
-					true => {
-						match Self::execute_amm_transfer(
-							matched_intention.sell_or_buy,
-							matched_intention.intention_id,
-							&amm_transfer,
-						) {
-							Ok(_) => {
-								dt.execute();
-								intention_copy.amount_in = 0;
-							}
-							Err(error) => {
-								Self::send_intention_error_event(matched_intention, error);
-								dt.revert();
-								continue;
-							}
-						}
-					}
-					false => {
-						dt.revert();
-						continue;
-					}
-				}
-			} else {
-				// Scenario 3: Exact match
-				//
-				// 1. Prepare direct trade
-				// 2. Verify and execute
-				// 3. Main intention is emtpy at this point -set amount to 0.
-				let mut dt = DirectTradeData::<T> {
-					intention_a: &intention_copy,
-					intention_b: matched_intention,
-					amount_from_a: amount_a_in,
-					amount_from_b: amount_b_in,
-					transfers: Vec::<Transfer<T>>::new(),
-				};
-
-				// As we direct trading the total matched intention amount - we need to check the trade limit for the matched intention
-				match intention.sell_or_buy {
-					IntentionType::SELL => {
-						if dt.amount_from_b < intention.trade_limit {
-							Self::send_intention_error_event(intention, Error::<T>::TradeAmountNotReachedLimit.into());
-							continue;
-						}
-					}
-					IntentionType::BUY => {
-						if dt.amount_from_a > intention.trade_limit {
-							Self::send_intention_error_event(intention, Error::<T>::TradeAmountExceededLimit.into());
-							continue;
-						}
-					}
-				};
-
-				match matched_intention.sell_or_buy {
-					IntentionType::SELL => {
-						if dt.amount_from_a < matched_intention.trade_limit {
-							Self::send_intention_error_event(
-								matched_intention,
-								Error::<T>::TradeAmountNotReachedLimit.into(),
-							);
-							continue;
-						}
-					}
-					IntentionType::BUY => {
-						if dt.amount_from_b > matched_intention.trade_limit {
-							Self::send_intention_error_event(
-								matched_intention,
-								Error::<T>::TradeAmountExceededLimit.into(),
-							);
-							continue;
-						}
-					}
-				};
-
-				match dt.prepare(pair_account) {
-					true => {
-						dt.execute();
-						intention_copy.amount_in = 0;
-					}
-					false => {
-						dt.revert();
-						continue;
-					}
-				}
-			}
-		}
-
-		// If there is something left, just resolve as a single intention
-		if intention_copy.amount_in > 0 {
-			Self::resolve_single_intention(&intention_copy);
-		}
-	}
 }
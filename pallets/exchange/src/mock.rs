@@ -34,6 +34,7 @@ use frame_system::EnsureSigned;
 use hydradx_traits::AssetPairAccountIdFor;
 use primitives::{
 	constants::chain::{MAX_IN_RATIO, MAX_OUT_RATIO, MIN_POOL_LIQUIDITY, MIN_TRADING_LIMIT},
+	traits::ConstantTradingLimits,
 	AssetId, Balance,
 };
 use std::cell::RefCell;
@@ -154,22 +155,42 @@ parameter_types! {
 	pub const MinPoolLiquidity: Balance = MIN_POOL_LIQUIDITY;
 	pub const MaxInRatio: u128 = MAX_IN_RATIO;
 	pub const MaxOutRatio: u128 = MAX_OUT_RATIO;
+	pub const MaxFeeTiers: u32 = 5;
+	pub const MaxPriceObservations: u32 = 5;
+	pub const StatsWindow: u64 = 10;
+	pub const MaxBatchSize: u32 = 5;
+	pub const ProtocolFeeReceiver: AccountId = 99;
+	pub const PoolDeposit: Balance = 0;
+}
+
+parameter_types! {
+	pub const MaxLimitOrdersPerPair: u32 = 5;
+	pub const MaxLimitOrderLifetime: u64 = 10;
 }
 
 impl xyk::Config for Test {
 	type Event = Event;
 	type AssetRegistry = AssetRegistry;
+	type AssetMetadata = ();
 	type AssetPairAccountId = AssetPairAccountIdTest;
 	type Currency = Currency;
 	type NativeAssetId = HDXAssetId;
 	type WeightInfo = ();
 	type GetExchangeFee = ExchangeFeeRate;
-	type MinTradingLimit = MinTradingLimit;
-	type MinPoolLiquidity = MinPoolLiquidity;
-	type MaxInRatio = MaxInRatio;
-	type MaxOutRatio = MaxOutRatio;
+	type MaxFeeTiers = MaxFeeTiers;
+	type MaxPriceObservations = MaxPriceObservations;
+	type StatsWindow = StatsWindow;
+	type AuthorityOrigin = frame_system::EnsureRoot<AccountId>;
+	type ProtocolFeeReceiver = ProtocolFeeReceiver;
+	type TradingLimits = ConstantTradingLimits<MinTradingLimit, MinPoolLiquidity, MaxInRatio, MaxOutRatio>;
+	type CircuitBreaker = ();
 	type CanCreatePool = pallet_xyk::AllowAllPools;
+	type PoolDeposit = PoolDeposit;
 	type AMMHandler = ();
+	type LiquidityChangedHandler = ();
+	type PoolDestroyedHandler = ();
+	type FlashSwapHandler = ();
+	type MaxBatchSize = MaxBatchSize;
 }
 
 impl Config for Test {
@@ -178,6 +199,8 @@ impl Config for Test {
 	type Currency = Currency;
 	type Resolver = exchange::Pallet<Test>;
 	type WeightInfo = ();
+	type MaxLimitOrdersPerPair = MaxLimitOrdersPerPair;
+	type MaxLimitOrderLifetime = MaxLimitOrderLifetime;
 }
 
 impl pallet_asset_registry::Config for Test {
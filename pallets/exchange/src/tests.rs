@@ -17,15 +17,15 @@
 
 use super::*;
 pub use crate::mock::{
-	Currency, EndowedAmount, Event as TestEvent, Exchange, ExtBuilder, Origin, System, Test, ALICE, BOB, CHARLIE, DAVE,
-	DOT, ETH, FERDIE, GEORGE, HDX, XYK as XYKPallet,
+	Currency, EndowedAmount, Event as TestEvent, Exchange, ExtBuilder, MaxLimitOrdersPerPair, MinPoolLiquidity, Origin,
+	System, Test, ALICE, BOB, CHARLIE, DAVE, DOT, ETH, FERDIE, GEORGE, HDX, XYK as XYKPallet,
 };
 use frame_support::sp_runtime::traits::Hash;
 use frame_support::sp_runtime::FixedPointNumber;
 use frame_support::traits::Get;
-use frame_support::traits::OnFinalize;
+use frame_support::traits::{OnFinalize, OnIdle, OnInitialize};
 use frame_support::{assert_noop, assert_ok};
-use hydradx_traits::Resolver;
+use primitives::traits::Resolver;
 use primitives::Price;
 use sp_runtime::{DispatchError, ModuleError};
 
@@ -59,7 +59,8 @@ fn initialize_pool(asset_a: u32, asset_b: u32, user: u64, amount: u128, price: P
 		asset_a,
 		asset_b,
 		amount,
-		price
+		price,
+		None
 	));
 
 	let shares = if asset_a <= asset_b {
@@ -93,8 +94,11 @@ fn initialize_pool(asset_a: u32, asset_b: u32, user: u64, amount: u128, price: P
 	assert_eq!(Currency::free_balance(asset_a, &pair_account), amount);
 	assert_eq!(Currency::free_balance(asset_b, &pair_account), amount_b);
 
-	// Check pool shares
-	assert_eq!(Currency::free_balance(share_token, &user), shares);
+	// Check pool shares, minimum liquidity is locked by the pool
+	assert_eq!(
+		Currency::free_balance(share_token, &user),
+		shares - MinPoolLiquidity::get()
+	);
 
 	// Advance blockchain so that we kill old events
 	System::initialize(&1, &[0u8; 32].into(), &Default::default());
@@ -156,6 +160,7 @@ fn sell_test_pool_finalization_states() {
 			2_000_000_000_000,
 			20_000_000_000,
 			false,
+			None,
 		));
 
 		let user_2_sell_intention_id = generate_intention_id(&user_2, 0);
@@ -167,18 +172,21 @@ fn sell_test_pool_finalization_states() {
 			1_000_000_000_000,
 			4_000_000_000_000,
 			false,
+			None,
 		));
 
 		let user_3_sell_intention_id = generate_intention_id(&user_3, 1);
 
 		assert_eq!(Exchange::get_intentions_count((asset_b, asset_a)), 2);
 
-		// Balance should not change yet
-		assert_eq!(Currency::free_balance(asset_a, &user_2), EndowedAmount::get());
+		// Sold amounts are reserved until the block is finalized
+		assert_eq!(Currency::free_balance(asset_a, &user_2), 99_998_000_000_000_000);
+		assert_eq!(Currency::reserved_balance(asset_a, &user_2), 2_000_000_000_000);
 		assert_eq!(Currency::free_balance(asset_b, &user_2), EndowedAmount::get());
 
 		assert_eq!(Currency::free_balance(asset_a, &user_3), EndowedAmount::get());
-		assert_eq!(Currency::free_balance(asset_b, &user_3), EndowedAmount::get());
+		assert_eq!(Currency::free_balance(asset_b, &user_3), 99_996_000_000_000_000);
+		assert_eq!(Currency::reserved_balance(asset_b, &user_3), 4_000_000_000_000);
 
 		assert_eq!(Currency::free_balance(asset_a, &pair_account), 100_000_000_000_000);
 
@@ -242,6 +250,7 @@ fn sell_test_pool_finalization_states() {
 				asset_b,
 				3960554454,
 				pair_account,
+				Permill::from_parts(9_900),
 			)
 			.into(),
 			Event::IntentionResolvedAMMTrade(
@@ -295,6 +304,7 @@ fn sell_test_standard() {
 			2_000_000_000_000,
 			300_000_000_000,
 			false,
+			None,
 		));
 		let user_2_sell_intention_id = generate_intention_id(&user_2, 0);
 
@@ -305,6 +315,7 @@ fn sell_test_standard() {
 			1_000_000_000_000,
 			4_000_000_000_000,
 			false,
+			None,
 		));
 
 		let user_3_sell_intention_id = generate_intention_id(&user_3, 1);
@@ -400,6 +411,7 @@ fn sell_test_standard() {
 				2000,
 				3960554454,
 				pair_account,
+				Permill::from_parts(9_900),
 			)
 			.into(),
 			Event::IntentionResolvedAMMTrade(
@@ -440,6 +452,7 @@ fn sell_test_inverse_standard() {
 			1_000_000_000_000,
 			100_000_000_000,
 			false,
+			None,
 		));
 
 		let user_2_sell_intention_id = generate_intention_id(&user_2, 0);
@@ -451,6 +464,7 @@ fn sell_test_inverse_standard() {
 			4_000_000_000_000,
 			1_000_000_000_000,
 			false,
+			None,
 		));
 
 		let user_3_sell_intention_id = generate_intention_id(&user_3, 1);
@@ -468,13 +482,13 @@ fn sell_test_inverse_standard() {
 
 		let user_3_balance_a = Currency::free_balance(asset_a, &user_3);
 		let user_3_balance_b = Currency::free_balance(asset_b, &user_3);
-		assert_eq!(user_3_balance_a, 100_001_986_118_811_882);
+		assert_eq!(user_3_balance_a, 100_001_986_119_007_546);
 		assert_eq!(user_3_balance_b, 99_996_000_000_000_000);
 
 		// Check final pool balances  -> SEEMS LEGIT
 		let pool_balance_a = Currency::free_balance(asset_a, &pair_account);
 		let pool_balance_b = Currency::free_balance(asset_b, &pair_account);
-		assert_eq!(pool_balance_a, 99_013_881_188_118);
+		assert_eq!(pool_balance_a, 99_013_880_992_454);
 		assert_eq!(pool_balance_b, 202_004_000_000_000);
 
 		assert_eq!(
@@ -508,45 +522,33 @@ fn sell_test_inverse_standard() {
 				user_3_sell_intention_id,
 			)
 			.into(),
-			orml_tokens::Event::Reserved {
-				currency_id: asset_a,
-				who: user_2,
-				amount: 1000000000000,
-			}
-			.into(),
 			orml_tokens::Event::Reserved {
 				currency_id: asset_b,
 				who: user_3,
 				amount: 2000000000000,
 			}
 			.into(),
-			xyk::Event::SellExecuted(
-				3,
-				2000,
-				3000,
-				2000000000000,
-				988118811882,
-				3000,
-				1980198018,
-				pair_account,
-			)
+			orml_tokens::Event::Reserved {
+				currency_id: asset_a,
+				who: user_2,
+				amount: 1000000000000,
+			}
 			.into(),
-			Event::IntentionResolvedAMMTrade(
+			Event::IntentionResolvedDirectTrade(
 				user_3,
-				IntentionType::SELL,
+				user_2,
 				user_3_sell_intention_id,
+				user_2_sell_intention_id,
 				2000000000000,
-				990099009900,
-				pair_account,
+				1000000000000,
 			)
 			.into(),
-			Event::IntentionResolvedDirectTrade(
-				user_2,
+			Event::IntentionResolvedDirectTradeFees(
 				user_3,
-				user_2_sell_intention_id,
 				user_3_sell_intention_id,
-				1000000000000,
-				2000000000000,
+				pair_account,
+				asset_b,
+				4000000000,
 			)
 			.into(),
 			Event::IntentionResolvedDirectTradeFees(
@@ -557,12 +559,25 @@ fn sell_test_inverse_standard() {
 				2000000000,
 			)
 			.into(),
-			Event::IntentionResolvedDirectTradeFees(
+			xyk::Event::SellExecuted(
+				3,
+				2000,
+				3000,
+				2000000000000,
+				988119007546,
+				3000,
+				1980198410,
+				pair_account,
+				Permill::from_parts(9_900),
+			)
+			.into(),
+			Event::IntentionResolvedAMMTrade(
 				user_3,
+				IntentionType::SELL,
 				user_3_sell_intention_id,
+				2000000000000,
+				990099205956,
 				pair_account,
-				asset_b,
-				4000000000,
 			)
 			.into(),
 		]);
@@ -594,6 +609,7 @@ fn sell_test_exact_match() {
 			1_000_000_000_000,
 			1_500_000_000_000,
 			false,
+			None,
 		));
 
 		let user_2_sell_intention_id = generate_intention_id(&user_2, 0);
@@ -605,6 +621,7 @@ fn sell_test_exact_match() {
 			2_000_000_000_000,
 			200_000_000_000,
 			false,
+			None,
 		));
 
 		let user_3_sell_intention_id = generate_intention_id(&user_3, 1);
@@ -712,6 +729,7 @@ fn sell_test_single_eth_sells() {
 			1_000_000_000_000,
 			100_000_000_000,
 			false,
+			None,
 		));
 		let user_2_sell_intention_id = generate_intention_id(&user_2, 0);
 		assert_ok!(Exchange::sell(
@@ -721,6 +739,7 @@ fn sell_test_single_eth_sells() {
 			2_000_000_000_000,
 			200_000_000_000,
 			false,
+			None,
 		));
 
 		let user_3_sell_intention_id = generate_intention_id(&user_3, 1);
@@ -771,6 +790,7 @@ fn sell_test_single_eth_sells() {
 				asset_b,
 				7843137254,
 				pair_account,
+				Permill::from_parts(19_607),
 			)
 			.into(),
 			Event::IntentionResolvedAMMTrade(
@@ -791,6 +811,7 @@ fn sell_test_single_eth_sells() {
 				asset_b,
 				3807500474,
 				pair_account,
+				Permill::from_parts(9_708),
 			)
 			.into(),
 			Event::IntentionResolvedAMMTrade(
@@ -831,6 +852,7 @@ fn sell_test_single_dot_sells() {
 			1_000_000_000_000,
 			100_000_000_000,
 			false,
+			None,
 		));
 
 		let user_2_sell_intention_id = generate_intention_id(&user_2, 0);
@@ -841,6 +863,7 @@ fn sell_test_single_dot_sells() {
 			2_000_000_000_000,
 			200_000_000_000,
 			false,
+			None,
 		));
 
 		let user_3_sell_intention_id = generate_intention_id(&user_3, 1);
@@ -890,6 +913,7 @@ fn sell_test_single_dot_sells() {
 				asset_a,
 				1980198018,
 				pair_account,
+				Permill::from_parts(9_900),
 			)
 			.into(),
 			Event::IntentionResolvedAMMTrade(
@@ -910,6 +934,7 @@ fn sell_test_single_dot_sells() {
 				asset_a,
 				975486514,
 				pair_account,
+				Permill::from_parts(4_926),
 			)
 			.into(),
 			Event::IntentionResolvedAMMTrade(
@@ -946,6 +971,7 @@ fn sell_trade_limits_respected_for_matched_intention() {
 			1_000_000_000_000,
 			100_000_000_000,
 			false,
+			None,
 		));
 
 		assert_ok!(Exchange::sell(
@@ -953,8 +979,10 @@ fn sell_trade_limits_respected_for_matched_intention() {
 			asset_b,
 			asset_a,
 			1_000_000_000_000,
-			100_000_000_000_000_000, // Limit set to absurd amount which can't go through
+			100_000_000_000_000_000,
+			// Limit set to absurd amount which can't go through
 			false,
+			None,
 		));
 		let user_3_sell_intention_id = generate_intention_id(&user_3, 1);
 
@@ -996,8 +1024,8 @@ fn sell_trade_limits_respected_for_matched_intention() {
 				IntentionType::SELL,
 				user_3_sell_intention_id,
 				DispatchError::Module(ModuleError {
-					index: 1,
-					error: 3,
+					index: 2,
+					error: 9,
 					message: None,
 				}),
 			)
@@ -1011,6 +1039,7 @@ fn sell_trade_limits_respected_for_matched_intention() {
 				asset_b,
 				3960396038,
 				pair_account,
+				Permill::from_parts(9_900),
 			)
 			.into(),
 			Event::IntentionResolvedAMMTrade(
@@ -1047,6 +1076,7 @@ fn buy_trade_limits_respected_for_matched_intention() {
 			1_000_000_000_000,
 			10_000_000_000_000_000,
 			false,
+			None,
 		));
 
 		assert_ok!(Exchange::buy(
@@ -1056,6 +1086,7 @@ fn buy_trade_limits_respected_for_matched_intention() {
 			100_000_000_000,
 			1,
 			false,
+			None,
 		));
 		let user_3_buy_intention_id = generate_intention_id(&user_3, 1);
 
@@ -1097,8 +1128,8 @@ fn buy_trade_limits_respected_for_matched_intention() {
 				IntentionType::BUY,
 				user_3_buy_intention_id,
 				DispatchError::Module(ModuleError {
-					index: 1,
-					error: 2,
+					index: 2,
+					error: 8,
 					message: None,
 				}),
 			)
@@ -1112,6 +1143,7 @@ fn buy_trade_limits_respected_for_matched_intention() {
 				asset_a,
 				1005025124,
 				pair_account,
+				Permill::from_parts(5_000),
 			)
 			.into(),
 			Event::IntentionResolvedAMMTrade(
@@ -1156,6 +1188,7 @@ fn sell_test_single_multiple_sells() {
 			1_000_000_000_000,
 			100_000_000_000,
 			false,
+			None,
 		));
 		let user_2_sell_intention_id = generate_intention_id(&user_2, 0);
 		assert_ok!(Exchange::sell(
@@ -1165,6 +1198,7 @@ fn sell_test_single_multiple_sells() {
 			1_000_000_000_000,
 			100_000_000_000,
 			false,
+			None,
 		));
 		let user_3_sell_intention_id = generate_intention_id(&user_3, 1);
 		assert_ok!(Exchange::sell(
@@ -1174,6 +1208,7 @@ fn sell_test_single_multiple_sells() {
 			1_000_000_000_000,
 			100_000_000_000,
 			false,
+			None,
 		));
 		let user_4_sell_intention_id = generate_intention_id(&user_4, 2);
 		assert_ok!(Exchange::sell(
@@ -1183,6 +1218,7 @@ fn sell_test_single_multiple_sells() {
 			1_000_000_000_000,
 			100_000_000_000,
 			false,
+			None,
 		));
 		let user_5_sell_intention_id = generate_intention_id(&user_5, 3);
 		assert_ok!(Exchange::sell(
@@ -1192,6 +1228,7 @@ fn sell_test_single_multiple_sells() {
 			2_000_000_000_000,
 			200_000_000_000,
 			false,
+			None,
 		));
 		let user_6_sell_intention_id = generate_intention_id(&user_6, 4);
 
@@ -1407,6 +1444,7 @@ fn sell_test_group_sells() {
 			5_000_000_000_000,
 			200_000_000_000,
 			false,
+			None,
 		));
 		let user_2_sell_intention_id = generate_intention_id(&user_2, 0);
 		assert_ok!(Exchange::sell(
@@ -1416,6 +1454,7 @@ fn sell_test_group_sells() {
 			3_000_000_000_000,
 			200_000_000_000,
 			false,
+			None,
 		));
 		let user_3_sell_intention_id = generate_intention_id(&user_3, 1);
 		assert_ok!(Exchange::sell(
@@ -1425,6 +1464,7 @@ fn sell_test_group_sells() {
 			10_000_000_000_000,
 			200_000_000_000,
 			false,
+			None,
 		));
 		let user_4_sell_intention_id = generate_intention_id(&user_4, 2);
 
@@ -1560,6 +1600,7 @@ fn sell_test_group_sells() {
 				asset_b,
 				22641611952,
 				pair_account,
+				Permill::from_parts(56_599),
 			)
 			.into(),
 			Event::IntentionResolvedAMMTrade(
@@ -1579,12 +1620,12 @@ fn sell_test_group_sells() {
 fn trades_without_pool_should_not_work() {
 	new_test_ext().execute_with(|| {
 		assert_noop!(
-			Exchange::sell(Origin::signed(ALICE), HDX, ETH, 1000, 200, false),
+			Exchange::sell(Origin::signed(ALICE), HDX, ETH, 1000, 200, false, None),
 			Error::<Test>::TokenPoolNotFound
 		);
 
 		assert_noop!(
-			Exchange::buy(Origin::signed(ALICE), HDX, ETH, 1000, 200, false),
+			Exchange::buy(Origin::signed(ALICE), HDX, ETH, 1000, 200, false, None),
 			Error::<Test>::TokenPoolNotFound
 		);
 	});
@@ -1594,12 +1635,12 @@ fn trades_without_pool_should_not_work() {
 fn trade_min_limit() {
 	new_test_ext().execute_with(|| {
 		assert_noop!(
-			Exchange::sell(Origin::signed(ALICE), HDX, ETH, 10, 200, false),
+			Exchange::sell(Origin::signed(ALICE), HDX, ETH, 10, 200, false, None),
 			Error::<Test>::MinimumTradeLimitNotReached
 		);
 
 		assert_noop!(
-			Exchange::buy(Origin::signed(ALICE), HDX, ETH, 10, 200, false),
+			Exchange::buy(Origin::signed(ALICE), HDX, ETH, 10, 200, false, None),
 			Error::<Test>::MinimumTradeLimitNotReached
 		);
 	});
@@ -1613,18 +1654,35 @@ fn sell_more_than_owner_should_not_work() {
 			HDX,
 			ETH,
 			200_000,
-			Price::from(2)
+			Price::from(2),
+			None
 		));
 
 		// With SELL
 		assert_noop!(
-			Exchange::sell(Origin::signed(ALICE), HDX, ETH, 10 * EndowedAmount::get(), 1, false),
+			Exchange::sell(
+				Origin::signed(ALICE),
+				HDX,
+				ETH,
+				10 * EndowedAmount::get(),
+				1,
+				false,
+				None
+			),
 			Error::<Test>::InsufficientAssetBalance
 		);
 
 		// With BUY
 		assert_noop!(
-			Exchange::buy(Origin::signed(ALICE), ETH, HDX, 10 * EndowedAmount::get(), 1, false),
+			Exchange::buy(
+				Origin::signed(ALICE),
+				ETH,
+				HDX,
+				10 * EndowedAmount::get(),
+				1,
+				false,
+				None
+			),
 			Error::<Test>::InsufficientAssetBalance
 		);
 	});
@@ -1657,6 +1715,7 @@ fn sell_test_mixed_buy_sells() {
 			5_000_000_000_000,
 			20_000_000_000_000,
 			false,
+			None,
 		));
 		let user_2_sell_intention_id = generate_intention_id(&user_2, 0);
 		assert_ok!(Exchange::sell(
@@ -1666,6 +1725,7 @@ fn sell_test_mixed_buy_sells() {
 			3_000_000_000_000,
 			1_400_000_000_000,
 			false,
+			None,
 		));
 		let user_3_sell_intention_id = generate_intention_id(&user_3, 1);
 		assert_ok!(Exchange::sell(
@@ -1675,6 +1735,7 @@ fn sell_test_mixed_buy_sells() {
 			10_000_000_000_000,
 			2_000_000_000_000,
 			false,
+			None,
 		));
 		let user_4_sell_intention_id = generate_intention_id(&user_4, 2);
 
@@ -1772,6 +1833,7 @@ fn sell_test_mixed_buy_sells() {
 				asset_b,
 				31336479174,
 				pair_account,
+				Permill::from_parts(78_338),
 			)
 			.into(),
 			Event::IntentionResolvedAMMTrade(
@@ -1792,6 +1854,7 @@ fn sell_test_mixed_buy_sells() {
 				asset_a,
 				6049146808,
 				pair_account,
+				Permill::from_parts(27_119),
 			)
 			.into(),
 			Event::IntentionResolvedAMMTrade(
@@ -1834,6 +1897,7 @@ fn discount_tests_no_discount() {
 			5_000_000_000_000,
 			20_000_000_000_000,
 			false,
+			None,
 		));
 		let user_2_sell_intention_id = generate_intention_id(&user_2, 0);
 		assert_ok!(Exchange::sell(
@@ -1843,6 +1907,7 @@ fn discount_tests_no_discount() {
 			3_000_000_000_000,
 			1_400_000_000_000,
 			false,
+			None,
 		));
 		let user_3_sell_intention_id = generate_intention_id(&user_3, 1);
 		assert_ok!(Exchange::sell(
@@ -1852,6 +1917,7 @@ fn discount_tests_no_discount() {
 			10_000_000_000_000,
 			2_000_000_000_000,
 			false,
+			None,
 		));
 		let user_4_sell_intention_id = generate_intention_id(&user_4, 2);
 
@@ -1950,6 +2016,7 @@ fn discount_tests_no_discount() {
 				asset_b,
 				31336479174,
 				pair_account,
+				Permill::from_parts(78_338),
 			)
 			.into(),
 			Event::IntentionResolvedAMMTrade(
@@ -1970,6 +2037,7 @@ fn discount_tests_no_discount() {
 				asset_a,
 				6049146808,
 				pair_account,
+				Permill::from_parts(27_119),
 			)
 			.into(),
 			Event::IntentionResolvedAMMTrade(
@@ -2014,6 +2082,7 @@ fn discount_tests_with_discount() {
 			5_000_000_000_000,
 			20_000_000_000_000,
 			true,
+			None,
 		));
 		let user_2_sell_intention_id = generate_intention_id(&user_2, 0);
 		assert_ok!(Exchange::sell(
@@ -2023,6 +2092,7 @@ fn discount_tests_with_discount() {
 			3_000_000_000_000,
 			1_400_000_000_000,
 			true,
+			None,
 		));
 		let user_3_sell_intention_id = generate_intention_id(&user_3, 1);
 		assert_ok!(Exchange::sell(
@@ -2032,6 +2102,7 @@ fn discount_tests_with_discount() {
 			10_000_000_000_000,
 			2_000_000_000_000,
 			true,
+			None,
 		));
 		let user_4_sell_intention_id = generate_intention_id(&user_4, 2);
 
@@ -2134,6 +2205,7 @@ fn discount_tests_with_discount() {
 				asset_b,
 				10967767706,
 				pair_account,
+				Permill::from_parts(78_338),
 			)
 			.into(),
 			Event::IntentionResolvedAMMTrade(
@@ -2154,6 +2226,7 @@ fn discount_tests_with_discount() {
 				asset_a,
 				2117441830,
 				pair_account,
+				Permill::from_parts(27_122),
 			)
 			.into(),
 			Event::IntentionResolvedAMMTrade(
@@ -2194,6 +2267,7 @@ fn buy_test_exact_match() {
 			1_000_000_000_000,
 			4_000_000_000_000,
 			false,
+			None,
 		));
 		let user_2_sell_intention_id = generate_intention_id(&user_2, 0);
 		assert_ok!(Exchange::buy(
@@ -2203,6 +2277,7 @@ fn buy_test_exact_match() {
 			2_000_000_000_000,
 			4_000_000_000_000,
 			false,
+			None,
 		));
 		let user_3_sell_intention_id = generate_intention_id(&user_3, 1);
 
@@ -2311,6 +2386,7 @@ fn buy_test_group_buys() {
 			5_000_000_000_000,
 			20_000_000_000_000,
 			false,
+			None,
 		));
 		let user_2_sell_intention_id = generate_intention_id(&user_2, 0);
 		assert_ok!(Exchange::buy(
@@ -2320,6 +2396,7 @@ fn buy_test_group_buys() {
 			3_000_000_000_000,
 			20_000_000_000_000,
 			false,
+			None,
 		));
 		let user_3_sell_intention_id = generate_intention_id(&user_3, 1);
 		assert_ok!(Exchange::buy(
@@ -2329,6 +2406,7 @@ fn buy_test_group_buys() {
 			10_000_000_000_000,
 			22_000_000_000_000,
 			false,
+			None,
 		));
 		let user_4_sell_intention_id = generate_intention_id(&user_4, 2);
 
@@ -2341,15 +2419,15 @@ fn buy_test_group_buys() {
 		assert_eq!(Currency::free_balance(asset_a, &user_2), 99_997495000000000);
 		assert_eq!(Currency::free_balance(asset_b, &user_2), 100_005000000000000);
 
-		assert_eq!(Currency::free_balance(asset_a, &user_3), 99_998696090255838);
+		assert_eq!(Currency::free_balance(asset_a, &user_3), 99_998497000000000);
 		assert_eq!(Currency::free_balance(asset_b, &user_3), 100_003000000000000);
 
 		assert_eq!(Currency::free_balance(asset_a, &user_4), 100_010000000000000);
-		assert_eq!(Currency::free_balance(asset_b, &user_4), 99_978741351351351);
+		assert_eq!(Currency::free_balance(asset_b, &user_4), 99_979192575950983);
 
 		// Check final pool balances
-		assert_eq!(Currency::free_balance(asset_a, &pair_account), 93808909744162);
-		assert_eq!(Currency::free_balance(asset_b, &pair_account), 213258648648649);
+		assert_eq!(Currency::free_balance(asset_a, &pair_account), 94008000000000);
+		assert_eq!(Currency::free_balance(asset_b, &pair_account), 212807424049017);
 
 		assert_eq!(Exchange::get_intentions_count((asset_b, asset_a)), 0);
 
@@ -2381,45 +2459,33 @@ fn buy_test_group_buys() {
 				user_4_sell_intention_id,
 			)
 			.into(),
-			orml_tokens::Event::Reserved {
-				currency_id: asset_a,
-				who: 2,
-				amount: 2505000000000,
-			}
-			.into(),
 			orml_tokens::Event::Reserved {
 				currency_id: asset_b,
 				who: 4,
 				amount: 5010000000000,
 			}
 			.into(),
-			xyk::Event::BuyExecuted(
-				user_4,
-				asset_a,
-				asset_b,
-				7500000000000,
-				16216216216217,
-				asset_b,
-				32432432432,
-				pair_account,
-			)
+			orml_tokens::Event::Reserved {
+				currency_id: asset_a,
+				who: 2,
+				amount: 2505000000000,
+			}
 			.into(),
-			Event::IntentionResolvedAMMTrade(
+			Event::IntentionResolvedDirectTrade(
 				user_4,
-				IntentionType::BUY,
+				user_2,
 				user_4_sell_intention_id,
-				7500000000000,
-				16248648648649,
-				pair_account,
+				user_2_sell_intention_id,
+				5000000000000,
+				2500000000000,
 			)
 			.into(),
-			Event::IntentionResolvedDirectTrade(
-				user_2,
+			Event::IntentionResolvedDirectTradeFees(
 				user_4,
-				user_2_sell_intention_id,
 				user_4_sell_intention_id,
-				2500000000000,
-				5000000000000,
+				pair_account,
+				asset_b,
+				10000000000,
 			)
 			.into(),
 			Event::IntentionResolvedDirectTradeFees(
@@ -2430,31 +2496,61 @@ fn buy_test_group_buys() {
 				5000000000,
 			)
 			.into(),
+			orml_tokens::Event::Reserved {
+				currency_id: asset_b,
+				who: 4,
+				amount: 3006000000000,
+			}
+			.into(),
+			orml_tokens::Event::Reserved {
+				currency_id: asset_a,
+				who: 3,
+				amount: 1503000000000,
+			}
+			.into(),
+			Event::IntentionResolvedDirectTrade(
+				user_4,
+				user_3,
+				user_4_sell_intention_id,
+				user_3_sell_intention_id,
+				3000000000000,
+				1500000000000,
+			)
+			.into(),
 			Event::IntentionResolvedDirectTradeFees(
 				user_4,
 				user_4_sell_intention_id,
 				pair_account,
 				asset_b,
-				10000000000,
+				6000000000,
 			)
 			.into(),
-			xyk::Event::BuyExecuted(
+			Event::IntentionResolvedDirectTradeFees(
 				user_3,
-				asset_b,
+				user_3_sell_intention_id,
+				pair_account,
 				asset_a,
-				3000000000000,
-				1301307129904,
+				3000000000,
+			)
+			.into(),
+			xyk::Event::BuyExecuted(
+				user_4,
 				asset_a,
-				2602614258,
+				asset_b,
+				6000000000000,
+				12765892264489,
+				asset_b,
+				25531784528,
 				pair_account,
+				Permill::from_parts(59_995),
 			)
 			.into(),
 			Event::IntentionResolvedAMMTrade(
-				user_3,
+				user_4,
 				IntentionType::BUY,
-				user_3_sell_intention_id,
-				3000000000000,
-				1303909744162,
+				user_4_sell_intention_id,
+				6000000000000,
+				12791424049017,
 				pair_account,
 			)
 			.into(),
@@ -2489,6 +2585,7 @@ fn discount_tests_with_error() {
 			5_000_000_000_000,
 			20_000_000_000_000,
 			true,
+			None,
 		));
 		assert_ok!(Exchange::sell(
 			Origin::signed(user_3),
@@ -2497,6 +2594,7 @@ fn discount_tests_with_error() {
 			3_000_000_000_000,
 			20_000_000_000_000,
 			true,
+			None,
 		));
 		assert_ok!(Exchange::sell(
 			Origin::signed(user_4),
@@ -2505,6 +2603,7 @@ fn discount_tests_with_error() {
 			10_000_000_000_000,
 			20_000_000_000_000,
 			true,
+			None,
 		));
 		let user_2_sell_intention_id = generate_intention_id(&user_2, 0);
 		let user_3_sell_intention_id = generate_intention_id(&user_3, 1);
@@ -2637,6 +2736,7 @@ fn simple_sell_sell() {
 			2_000,
 			400,
 			false,
+			None,
 		));
 		assert_ok!(Exchange::sell(
 			Origin::signed(user_3),
@@ -2645,6 +2745,7 @@ fn simple_sell_sell() {
 			1_000,
 			400,
 			false,
+			None,
 		));
 		let user_2_sell_intention_id = generate_intention_id(&user_2, 0);
 		let user_3_sell_intention_id = generate_intention_id(&user_3, 1);
@@ -2705,7 +2806,18 @@ fn simple_sell_sell() {
 			.into(),
 			Event::IntentionResolvedDirectTradeFees(user_2, user_2_sell_intention_id, pair_account, asset_a, 0).into(),
 			Event::IntentionResolvedDirectTradeFees(user_3, user_3_sell_intention_id, pair_account, asset_b, 2).into(),
-			xyk::Event::SellExecuted(2, 3000, 2000, 1500, 2995, 2000, 4, pair_account).into(),
+			xyk::Event::SellExecuted(
+				2,
+				3000,
+				2000,
+				1500,
+				2995,
+				2000,
+				4,
+				pair_account,
+				Permill::from_parts(333),
+			)
+			.into(),
 			Event::IntentionResolvedAMMTrade(
 				user_2,
 				IntentionType::SELL,
@@ -2744,6 +2856,7 @@ fn simple_buy_buy() {
 			2_000,
 			5000,
 			false,
+			None,
 		));
 		assert_ok!(Exchange::buy(
 			Origin::signed(user_3),
@@ -2752,6 +2865,7 @@ fn simple_buy_buy() {
 			1_000,
 			5000,
 			false,
+			None,
 		));
 		let user_2_sell_intention_id = generate_intention_id(&user_2, 0);
 		let user_3_sell_intention_id = generate_intention_id(&user_3, 1);
@@ -2801,7 +2915,7 @@ fn simple_buy_buy() {
 				amount: 1002,
 			}
 			.into(),
-			xyk::Event::BuyExecuted(2, 3000, 2000, 1500, 3001, 2000, 6, pair_account).into(),
+			xyk::Event::BuyExecuted(2, 3000, 2000, 1500, 3001, 2000, 6, pair_account, Permill::zero()).into(),
 			Event::IntentionResolvedAMMTrade(
 				user_2,
 				IntentionType::BUY,
@@ -2812,12 +2926,12 @@ fn simple_buy_buy() {
 			)
 			.into(),
 			Event::IntentionResolvedDirectTrade(
-				user_3,
 				user_2,
-				user_3_sell_intention_id,
+				user_3,
 				user_2_sell_intention_id,
-				500,
+				user_3_sell_intention_id,
 				1000,
+				500,
 			)
 			.into(),
 			Event::IntentionResolvedDirectTradeFees(user_3, user_3_sell_intention_id, pair_account, asset_a, 0).into(),
@@ -2851,6 +2965,7 @@ fn simple_sell_buy() {
 			2_000,
 			400,
 			false,
+			None,
 		));
 		assert_ok!(Exchange::buy(
 			Origin::signed(user_3),
@@ -2859,6 +2974,7 @@ fn simple_sell_buy() {
 			1_000,
 			2_000,
 			false,
+			None,
 		));
 
 		let user_2_sell_intention_id = generate_intention_id(&user_2, 0);
@@ -2869,14 +2985,16 @@ fn simple_sell_buy() {
 
 		<Exchange as OnFinalize<u64>>::on_finalize(9);
 
+		// buy limit does not cover direct trade fee, so the buy is not matched and fails via AMM
 		assert_eq!(Currency::free_balance(asset_a, &user_2), 99_999999999998000);
 		assert_eq!(Currency::free_balance(asset_b, &user_2), 100_000000000003993);
 
-		assert_eq!(Currency::free_balance(asset_a, &user_3), 100_000000000001000);
-		assert_eq!(Currency::free_balance(asset_b, &user_3), 99_999999999997996);
+		assert_eq!(Currency::free_balance(asset_a, &user_3), 100_000000000000000);
+		assert_eq!(Currency::free_balance(asset_b, &user_3), 100_000000000000000);
+		assert_eq!(Currency::reserved_balance(asset_b, &user_3), 0);
 
-		assert_eq!(Currency::free_balance(asset_a, &pair_account), 100001000);
-		assert_eq!(Currency::free_balance(asset_b, &pair_account), 199998011);
+		assert_eq!(Currency::free_balance(asset_a, &pair_account), 100002000);
+		assert_eq!(Currency::free_balance(asset_b, &pair_account), 199996007);
 
 		expect_events(vec![
 			Event::IntentionRegistered(
@@ -2897,44 +3015,48 @@ fn simple_sell_buy() {
 				user_3_sell_intention_id,
 			)
 			.into(),
-			orml_tokens::Event::Reserved {
-				currency_id: asset_a,
-				who: 2,
-				amount: 1000,
-			}
-			.into(),
-			orml_tokens::Event::Reserved {
-				currency_id: asset_b,
-				who: 3,
-				amount: 2004,
-			}
-			.into(),
-			Event::IntentionResolvedDirectTrade(
-				user_2,
-				user_3,
-				user_2_sell_intention_id,
-				user_3_sell_intention_id,
-				1000,
+			xyk::Event::SellExecuted(
+				2,
+				3000,
+				2000,
 				2000,
+				3993,
+				2000,
+				6,
+				pair_account,
+				Permill::from_parts(250),
 			)
 			.into(),
-			Event::IntentionResolvedDirectTradeFees(user_3, user_3_sell_intention_id, pair_account, asset_b, 8).into(),
-			xyk::Event::SellExecuted(2, 3000, 2000, 1000, 1997, 2000, 2, pair_account).into(),
 			Event::IntentionResolvedAMMTrade(
 				user_2,
 				IntentionType::SELL,
 				user_2_sell_intention_id,
-				1000,
-				1999,
+				2000,
+				3999,
 				pair_account,
 			)
 			.into(),
+			Event::IntentionResolveErrorEvent(
+				user_3,
+				AssetPair {
+					asset_in: asset_b,
+					asset_out: asset_a,
+				},
+				IntentionType::BUY,
+				user_3_sell_intention_id,
+				DispatchError::Module(ModuleError {
+					index: 2,
+					error: 8,
+					message: None,
+				}),
+			)
+			.into(),
 		]);
 	});
 }
 
 #[test]
-fn simple_buy_sell() {
+fn simple_sell_buy_should_be_matched_when_buy_limit_covers_direct_trade_fee() {
 	new_test_ext().execute_with(|| {
 		let user_1 = ALICE;
 		let user_2 = BOB;
@@ -2951,21 +3073,23 @@ fn simple_buy_sell() {
 
 		initialize_pool(asset_a, asset_b, user_1, pool_amount, initial_price);
 
-		assert_ok!(Exchange::buy(
+		assert_ok!(Exchange::sell(
 			Origin::signed(user_2),
 			asset_a,
 			asset_b,
 			2_000,
-			5000,
+			400,
 			false,
+			None,
 		));
-		assert_ok!(Exchange::sell(
+		assert_ok!(Exchange::buy(
 			Origin::signed(user_3),
 			asset_a,
 			asset_b,
 			1_000,
-			1500,
+			2_004,
 			false,
+			None,
 		));
 
 		let user_2_sell_intention_id = generate_intention_id(&user_2, 0);
@@ -2976,14 +3100,14 @@ fn simple_buy_sell() {
 
 		<Exchange as OnFinalize<u64>>::on_finalize(9);
 
-		assert_eq!(Currency::free_balance(asset_a, &user_2), 100_000000000002000);
-		assert_eq!(Currency::free_balance(asset_b, &user_2), 99_999999999995991);
+		assert_eq!(Currency::free_balance(asset_a, &user_2), 99_999999999998000);
+		assert_eq!(Currency::free_balance(asset_b, &user_2), 100_000000000003993);
 
-		assert_eq!(Currency::free_balance(asset_a, &user_3), 99_999999999999000);
-		assert_eq!(Currency::free_balance(asset_b, &user_3), 100_000000000001996);
+		assert_eq!(Currency::free_balance(asset_a, &user_3), 100_000000000001000);
+		assert_eq!(Currency::free_balance(asset_b, &user_3), 99_999999999997996);
 
-		assert_eq!(Currency::free_balance(asset_a, &pair_account), 99999000);
-		assert_eq!(Currency::free_balance(asset_b, &pair_account), 200002013);
+		assert_eq!(Currency::free_balance(asset_a, &pair_account), 100001000);
+		assert_eq!(Currency::free_balance(asset_b, &pair_account), 199998011);
 
 		expect_events(vec![
 			Event::IntentionRegistered(
@@ -2991,7 +3115,7 @@ fn simple_buy_sell() {
 				asset_a,
 				asset_b,
 				2_000,
-				IntentionType::BUY,
+				IntentionType::SELL,
 				user_2_sell_intention_id,
 			)
 			.into(),
@@ -3000,43 +3124,163 @@ fn simple_buy_sell() {
 				asset_a,
 				asset_b,
 				1_000,
-				IntentionType::SELL,
+				IntentionType::BUY,
 				user_3_sell_intention_id,
 			)
 			.into(),
 			orml_tokens::Event::Reserved {
 				currency_id: asset_a,
-				who: 3,
+				who: 2,
 				amount: 1000,
 			}
 			.into(),
 			orml_tokens::Event::Reserved {
 				currency_id: asset_b,
-				who: 2,
+				who: 3,
 				amount: 2004,
 			}
 			.into(),
-			xyk::Event::BuyExecuted(user_2, 3000, 2000, 1000, 2001, 2000, 4, pair_account).into(),
-			Event::IntentionResolvedAMMTrade(
+			Event::IntentionResolvedDirectTrade(
 				user_2,
-				IntentionType::BUY,
+				user_3,
 				user_2_sell_intention_id,
+				user_3_sell_intention_id,
 				1000,
-				2005,
-				pair_account,
+				2000,
 			)
 			.into(),
-			Event::IntentionResolvedDirectTrade(
-				user_3,
-				user_2,
-				user_3_sell_intention_id,
-				user_2_sell_intention_id,
+			Event::IntentionResolvedDirectTradeFees(user_3, user_3_sell_intention_id, pair_account, asset_b, 8).into(),
+			xyk::Event::SellExecuted(
+				2,
+				3000,
+				2000,
 				1000,
+				1997,
 				2000,
+				2,
+				pair_account,
+				Permill::from_parts(500),
 			)
 			.into(),
-			Event::IntentionResolvedDirectTradeFees(user_2, user_2_sell_intention_id, pair_account, asset_b, 8).into(),
-		]);
+			Event::IntentionResolvedAMMTrade(
+				user_2,
+				IntentionType::SELL,
+				user_2_sell_intention_id,
+				1000,
+				1999,
+				pair_account,
+			)
+			.into(),
+		]);
+	});
+}
+
+#[test]
+fn simple_buy_sell() {
+	new_test_ext().execute_with(|| {
+		let user_1 = ALICE;
+		let user_2 = BOB;
+		let user_3 = CHARLIE;
+		let asset_a = ETH;
+		let asset_b = DOT;
+		let pool_amount = 100_000_000;
+		let initial_price = Price::from(2);
+
+		let pair_account = XYKPallet::get_pair_id(AssetPair {
+			asset_in: asset_a,
+			asset_out: asset_b,
+		});
+
+		initialize_pool(asset_a, asset_b, user_1, pool_amount, initial_price);
+
+		assert_ok!(Exchange::buy(
+			Origin::signed(user_2),
+			asset_a,
+			asset_b,
+			2_000,
+			5000,
+			false,
+			None,
+		));
+		assert_ok!(Exchange::sell(
+			Origin::signed(user_3),
+			asset_a,
+			asset_b,
+			1_000,
+			1500,
+			false,
+			None,
+		));
+
+		let user_2_sell_intention_id = generate_intention_id(&user_2, 0);
+		let user_3_sell_intention_id = generate_intention_id(&user_3, 1);
+
+		assert_eq!(Currency::free_balance(asset_a, &pair_account), 100000000);
+		assert_eq!(Currency::free_balance(asset_b, &pair_account), 200000000);
+
+		<Exchange as OnFinalize<u64>>::on_finalize(9);
+
+		assert_eq!(Currency::free_balance(asset_a, &user_2), 100_000000000002000);
+		assert_eq!(Currency::free_balance(asset_b, &user_2), 99_999999999995991);
+
+		assert_eq!(Currency::free_balance(asset_a, &user_3), 99_999999999999000);
+		assert_eq!(Currency::free_balance(asset_b, &user_3), 100_000000000001996);
+
+		assert_eq!(Currency::free_balance(asset_a, &pair_account), 99999000);
+		assert_eq!(Currency::free_balance(asset_b, &pair_account), 200002013);
+
+		expect_events(vec![
+			Event::IntentionRegistered(
+				user_2,
+				asset_a,
+				asset_b,
+				2_000,
+				IntentionType::BUY,
+				user_2_sell_intention_id,
+			)
+			.into(),
+			Event::IntentionRegistered(
+				user_3,
+				asset_a,
+				asset_b,
+				1_000,
+				IntentionType::SELL,
+				user_3_sell_intention_id,
+			)
+			.into(),
+			orml_tokens::Event::Reserved {
+				currency_id: asset_a,
+				who: 3,
+				amount: 1000,
+			}
+			.into(),
+			orml_tokens::Event::Reserved {
+				currency_id: asset_b,
+				who: 2,
+				amount: 2004,
+			}
+			.into(),
+			xyk::Event::BuyExecuted(user_2, 3000, 2000, 1000, 2001, 2000, 4, pair_account, Permill::zero()).into(),
+			Event::IntentionResolvedAMMTrade(
+				user_2,
+				IntentionType::BUY,
+				user_2_sell_intention_id,
+				1000,
+				2005,
+				pair_account,
+			)
+			.into(),
+			Event::IntentionResolvedDirectTrade(
+				user_2,
+				user_3,
+				user_2_sell_intention_id,
+				user_3_sell_intention_id,
+				2000,
+				1000,
+			)
+			.into(),
+			Event::IntentionResolvedDirectTradeFees(user_2, user_2_sell_intention_id, pair_account, asset_b, 8).into(),
+		]);
 	});
 }
 
@@ -3064,6 +3308,7 @@ fn single_sell_intention_test() {
 			2_000_000_000_000,
 			400_000_000_000,
 			false,
+			None,
 		));
 		let user_2_sell_intention_id = generate_intention_id(&user_2, 0);
 
@@ -3101,6 +3346,7 @@ fn single_sell_intention_test() {
 				2000,
 				7843137254,
 				pair_account,
+				Permill::from_parts(19_607),
 			)
 			.into(),
 			Event::IntentionResolvedAMMTrade(
@@ -3140,6 +3386,7 @@ fn single_buy_intention_test() {
 			2_000_000_000_000,
 			15_000_000_000_000,
 			false,
+			None,
 		));
 
 		let user_2_sell_intention_id = generate_intention_id(&user_2, 0);
@@ -3178,6 +3425,7 @@ fn single_buy_intention_test() {
 				2000,
 				8163265306,
 				pair_account,
+				Permill::from_parts(20_000),
 			)
 			.into(),
 			Event::IntentionResolvedAMMTrade(
@@ -3193,6 +3441,86 @@ fn single_buy_intention_test() {
 	});
 }
 
+#[test]
+fn amm_trade_should_respect_max_price_impact_of_intention() {
+	new_test_ext().execute_with(|| {
+		let user_1 = ALICE;
+		let user_2 = BOB;
+		let user_3 = CHARLIE;
+		let asset_a = ETH;
+		let asset_b = DOT;
+		let pool_amount = 100_000_000_000_000;
+		let initial_price = Price::from(2);
+
+		let pair_account = XYKPallet::get_pair_id(AssetPair {
+			asset_in: asset_a,
+			asset_out: asset_b,
+		});
+
+		initialize_pool(asset_a, asset_b, user_1, pool_amount, initial_price);
+
+		assert_ok!(Exchange::sell(
+			Origin::signed(user_2),
+			asset_a,
+			asset_b,
+			2_000_000_000_000,
+			400_000_000_000,
+			false,
+			Some(Permill::from_parts(19_606)),
+		));
+		let user_2_sell_intention_id = generate_intention_id(&user_2, 0);
+
+		<Exchange as OnFinalize<u64>>::on_finalize(9);
+
+		assert_eq!(Currency::free_balance(asset_a, &user_2), EndowedAmount::get());
+		assert_eq!(Currency::free_balance(asset_a, &pair_account), 100_000_000_000_000);
+
+		expect_event(Event::IntentionResolveErrorEvent(
+			user_2,
+			AssetPair {
+				asset_in: asset_a,
+				asset_out: asset_b,
+			},
+			IntentionType::SELL,
+			user_2_sell_intention_id,
+			DispatchError::Module(ModuleError {
+				index: 1,
+				error: 7,
+				message: None,
+			}),
+		));
+
+		<Exchange as OnInitialize<u64>>::on_initialize(10);
+
+		assert_ok!(Exchange::sell(
+			Origin::signed(user_3),
+			asset_a,
+			asset_b,
+			2_000_000_000_000,
+			400_000_000_000,
+			false,
+			Some(Permill::from_parts(19_607)),
+		));
+
+		<Exchange as OnFinalize<u64>>::on_finalize(10);
+
+		assert_eq!(Currency::free_balance(asset_a, &user_3), 99_998_000_000_000_000);
+		assert_eq!(Currency::free_balance(asset_a, &pair_account), 102_000_000_000_000);
+
+		expect_event(xyk::Event::SellExecuted(
+			user_3,
+			asset_a,
+			asset_b,
+			2_000_000_000_000,
+			3_913_725_490_196,
+			asset_b,
+			7_843_137_254,
+			pair_account,
+			Permill::from_parts(19_607),
+		));
+	});
+}
+
 #[test]
 fn simple_sell_sell_with_error_should_not_pass() {
 	new_test_ext().execute_with(|| {
@@ -3218,6 +3546,7 @@ fn simple_sell_sell_with_error_should_not_pass() {
 			2_000,
 			5_000,
 			false,
+			None,
 		));
 
 		let user_2_sell_intention_id = generate_intention_id(&user_2, 0);
@@ -3229,6 +3558,7 @@ fn simple_sell_sell_with_error_should_not_pass() {
 			1_000,
 			5_000,
 			false,
+			None,
 		));
 
 		let user_3_sell_intention_id = generate_intention_id(&user_3, 1);
@@ -3332,6 +3662,7 @@ fn matching_limits_buy_buy_should_work() {
 			100_000_000_000_000,
 			223333333333334,
 			false,
+			None,
 		));
 
 		let b = <system::Pallet<Test>>::current_block_number();
@@ -3344,6 +3675,7 @@ fn matching_limits_buy_buy_should_work() {
 			220 * one,
 			124213483146068,
 			false,
+			None,
 		));
 
 		let user_3_sell_intention_id = (1, &user_3, b, HDX, DOT).using_encoded(<Test as system::Config>::Hashing::hash);
@@ -3353,7 +3685,10 @@ fn matching_limits_buy_buy_should_work() {
 		assert_eq!(Currency::free_balance(asset_a, &user_2), 100_100 * one);
 		assert_eq!(Currency::free_balance(asset_b, &user_2), 99_799_600_000_000_000);
 
-		assert_eq!(Currency::free_balance(asset_a, &pair_account), 1010321212121213);
+		assert_eq!(Currency::free_balance(asset_a, &user_3), 99_889_678_808_321_551);
+		assert_eq!(Currency::free_balance(asset_b, &user_3), 100_220 * one);
+
+		assert_eq!(Currency::free_balance(asset_a, &pair_account), 1010321191678449);
 		assert_eq!(Currency::free_balance(asset_b, &pair_account), 1980400000000000);
 
 		expect_events(vec![
@@ -3392,10 +3727,11 @@ fn matching_limits_buy_buy_should_work() {
 				asset_b,
 				asset_a,
 				20_000_000_000_000,
-				10101010101011,
+				10100989699051,
 				asset_a,
-				20202020202,
+				20201979398,
 				pair_account,
+				Permill::from_parts(9_998),
 			)
 			.into(),
 			Event::IntentionResolvedAMMTrade(
@@ -3403,17 +3739,17 @@ fn matching_limits_buy_buy_should_work() {
 				IntentionType::BUY,
 				user_3_sell_intention_id,
 				20_000_000_000_000,
-				10121212121213,
+				10121191678449,
 				pair_account,
 			)
 			.into(),
 			Event::IntentionResolvedDirectTrade(
-				user_2,
 				user_3,
-				user_2_sell_intention_id,
+				user_2,
 				user_3_sell_intention_id,
-				200000000000000,
+				user_2_sell_intention_id,
 				100000000000000,
+				200000000000000,
 			)
 			.into(),
 			Event::IntentionResolvedDirectTradeFees(
@@ -3468,6 +3804,7 @@ fn matching_limits_sell_buy_should_work() {
 			30_000_000_000_000,
 			62164948453608,
 			false,
+			None,
 		));
 
 		let b = <system::Pallet<Test>>::current_block_number();
@@ -3480,6 +3817,7 @@ fn matching_limits_sell_buy_should_work() {
 			50 * one,
 			94761904761906,
 			false,
+			None,
 		));
 
 		let user_3_sell_intention_id = (1, &user_3, b, HDX, DOT).using_encoded(<Test as system::Config>::Hashing::hash);
@@ -3489,8 +3827,11 @@ fn matching_limits_sell_buy_should_work() {
 		assert_eq!(Currency::free_balance(asset_a, &user_2), 100_030 * one);
 		assert_eq!(Currency::free_balance(asset_b, &user_2), 99_939_880_000_000_000);
 
+		assert_eq!(Currency::free_balance(asset_a, &user_3), 99_950 * one);
+		assert_eq!(Currency::free_balance(asset_b, &user_3), 100_099_021_951_372_550);
+
 		assert_eq!(Currency::free_balance(asset_a, &pair_account), 1020000000000000);
-		assert_eq!(Currency::free_balance(asset_b, &pair_account), 1_961_102_745_098_039);
+		assert_eq!(Currency::free_balance(asset_b, &pair_account), 1_961_098_048_627_450);
 
 		expect_events(vec![
 			Event::IntentionRegistered(
@@ -3528,10 +3869,11 @@ fn matching_limits_sell_buy_should_work() {
 				asset_a,
 				asset_b,
 				20_000_000_000_000,
-				39137254901961,
+				39141951372550,
 				asset_b,
-				78431372548,
+				78440784312,
 				pair_account,
+				Permill::from_parts(19_607),
 			)
 			.into(),
 			Event::IntentionResolvedAMMTrade(
@@ -3539,17 +3881,17 @@ fn matching_limits_sell_buy_should_work() {
 				IntentionType::SELL,
 				user_3_sell_intention_id,
 				20_000_000_000_000,
-				39215686274509,
+				39220392156862,
 				pair_account,
 			)
 			.into(),
 			Event::IntentionResolvedDirectTrade(
-				user_2,
 				user_3,
-				user_2_sell_intention_id,
+				user_2,
 				user_3_sell_intention_id,
-				60000000000000,
+				user_2_sell_intention_id,
 				30000000000000,
+				60000000000000,
 			)
 			.into(),
 			Event::IntentionResolvedDirectTradeFees(
@@ -3596,6 +3938,7 @@ fn exact_match_limit_should_work() {
 			50_000_000_000_000,
 			106_315_789_473_684,
 			false,
+			None,
 		));
 
 		let b = <system::Pallet<Test>>::current_block_number();
@@ -3608,6 +3951,7 @@ fn exact_match_limit_should_work() {
 			100_000_000_000_000,
 			53_157_894_736_843,
 			false,
+			None,
 		));
 
 		let user_3_sell_intention_id = (1, &user_3, b, HDX, DOT).using_encoded(<Test as system::Config>::Hashing::hash);
@@ -3715,6 +4059,7 @@ fn matching_limit_scenario_2() {
 			100_000_000_000_000,
 			223_067_143_076_693,
 			false,
+			None,
 		));
 
 		let b = <system::Pallet<Test>>::current_block_number();
@@ -3727,6 +4072,7 @@ fn matching_limit_scenario_2() {
 			180_000_000_000_000,
 			220_242_387_444_707,
 			false,
+			None,
 		));
 
 		let user_3_sell_intention_id = (1, &user_3, b, HDX, DOT).using_encoded(<Test as system::Config>::Hashing::hash);
@@ -3807,6 +4153,7 @@ fn matching_limit_scenario_2() {
 				asset_b,
 				40403966954,
 				pair_account,
+				Permill::from_parts(9_998),
 			)
 			.into(),
 			Event::IntentionResolvedAMMTrade(
@@ -3854,6 +4201,7 @@ fn matching_limit_scenario_3() {
 			150_000_000_000_000,
 			356_315_789_473_684,
 			false,
+			None,
 		));
 
 		let b = <system::Pallet<Test>>::current_block_number();
@@ -3866,6 +4214,7 @@ fn matching_limit_scenario_3() {
 			200_000_000_000_000,
 			253_157_894_736_843,
 			false,
+			None,
 		));
 
 		let user_3_sell_intention_id = (1, &user_3, b, HDX, DOT).using_encoded(<Test as system::Config>::Hashing::hash);
@@ -3946,6 +4295,7 @@ fn matching_limit_scenario_3() {
 				asset_b,
 				210524100188,
 				pair_account,
+				Permill::from_parts(49_990),
 			)
 			.into(),
 			Event::IntentionResolvedAMMTrade(
@@ -3963,7 +4313,7 @@ fn matching_limit_scenario_3() {
 
 #[test]
 fn process_invalid_intention_should_work() {
-	// this test targets the `continue` statement in `process_exchange_intention`
+	// an intention which cannot be matched directly falls back to the AMM, where it fails
 	new_test_ext().execute_with(|| {
 		let one: Balance = 1_000_000_000_000;
 		let user = ALICE;
@@ -3988,6 +4338,8 @@ fn process_invalid_intention_should_work() {
 			amount_in: 2_000_000,
 			amount_out: 10 * pool_amount,
 			trade_limit: 10_000_000,
+			max_price_impact: None,
+			reserved: 0,
 			discount: false,
 			sell_or_buy: IntentionType::BUY,
 			intention_id: generate_intention_id(&user, 0),
@@ -3995,7 +4347,13 @@ fn process_invalid_intention_should_work() {
 
 		let mut intentions_a = vec![main_intention];
 
-		Exchange::process_exchange_intentions(&pair_account, &mut intentions_a, &mut Vec::<Intention<Test>>::new());
+		Exchange::process_exchange_intentions(
+			&pair_account,
+			&mut intentions_a,
+			&mut Vec::<Intention<Test>>::new(),
+			&mut [],
+			&mut [],
+		);
 
 		assert_eq!(Currency::free_balance(asset_a, &user), 99_000_000_000_000_000);
 		assert_eq!(Currency::free_balance(asset_b, &user), 98_000_000_000_000_000);
@@ -4007,7 +4365,7 @@ fn process_invalid_intention_should_work() {
 
 #[test]
 fn main_intention_greater_than_matched_should_work() {
-	// this test targets the `break` statement in `process_exchange_intentions`
+	// the larger side is matched first and its remainder is traded via the AMM
 	new_test_ext().execute_with(|| {
 		let user_1 = ALICE;
 		let user_2 = BOB;
@@ -4032,6 +4390,8 @@ fn main_intention_greater_than_matched_should_work() {
 			amount_in: 2_000_000,
 			amount_out: 1_000_000,
 			trade_limit: 10_000_000,
+			max_price_impact: None,
+			reserved: 0,
 			discount: false,
 			sell_or_buy: IntentionType::BUY,
 			intention_id: generate_intention_id(&user_1, 0),
@@ -4046,6 +4406,8 @@ fn main_intention_greater_than_matched_should_work() {
 			amount_in: 3_000_000,
 			amount_out: 6_000_000,
 			trade_limit: 10,
+			max_price_impact: None,
+			reserved: 0,
 			discount: false,
 			sell_or_buy: IntentionType::SELL,
 			intention_id: generate_intention_id(&user_2, 1),
@@ -4054,7 +4416,7 @@ fn main_intention_greater_than_matched_should_work() {
 		let mut intentions_a = vec![main_intention];
 		let mut intentions_b = vec![matched_intention];
 
-		Exchange::process_exchange_intentions(&pair_account, &mut intentions_a, &mut intentions_b);
+		Exchange::process_exchange_intentions(&pair_account, &mut intentions_a, &mut intentions_b, &mut [], &mut []);
 
 		assert_eq!(Currency::free_balance(asset_a, &pair_account), 1_000_000_002_000_000);
 		assert_eq!(Currency::free_balance(asset_b, &pair_account), 1_999_999_996_015_999);
@@ -4068,7 +4430,7 @@ fn main_intention_greater_than_matched_should_work() {
 }
 
 #[test]
-fn in_out_calculations_error_should_work() {
+fn intentions_should_be_repriced_at_spot_price() {
 	new_test_ext().execute_with(|| {
 		let user_1 = ALICE;
 		let user_2 = BOB;
@@ -4094,6 +4456,8 @@ fn in_out_calculations_error_should_work() {
 			amount_in: 3_000_000,
 			amount_out: 1_000_000,
 			trade_limit: 10_000_000,
+			max_price_impact: None,
+			reserved: 0,
 			discount: false,
 			sell_or_buy: IntentionType::BUY,
 			intention_id: generate_intention_id(&user_1, 0),
@@ -4108,18 +4472,27 @@ fn in_out_calculations_error_should_work() {
 			amount_in: 3_000_000,
 			amount_out: 2_000_000,
 			trade_limit: 10,
+			max_price_impact: None,
+			reserved: 0,
 			discount: false,
 			sell_or_buy: IntentionType::SELL,
 			intention_id: generate_intention_id(&user_2, 1),
 		};
 
-		let maybe_error = std::panic::catch_unwind(|| {
-			let mut intentions_a = vec![main_intention];
-			let mut intentions_b = vec![matched_intention];
+		let mut intentions_a = vec![main_intention];
+		let mut intentions_b = vec![matched_intention];
+
+		Exchange::process_exchange_intentions(&pair_account, &mut intentions_a, &mut intentions_b, &mut [], &mut []);
 
-			Exchange::process_exchange_intentions(&pair_account, &mut intentions_a, &mut intentions_b)
-		});
-		assert!(maybe_error.is_err());
+		// user_1 buys 1_000_000 HDX for 2_000_000 DOT from user_2, who sells the remaining 2_000_000 HDX to the pool
+		assert_eq!(Currency::free_balance(asset_a, &user_1), 99_000_000_001_000_000);
+		assert_eq!(Currency::free_balance(asset_b, &user_1), 97_999_999_997_996_000);
+
+		assert_eq!(Currency::free_balance(asset_a, &user_2), 99_999_999_997_000_000);
+		assert_eq!(Currency::free_balance(asset_b, &user_2), 100_000_000_005_988_001);
+
+		assert_eq!(Currency::free_balance(asset_a, &pair_account), 1_000_000_002_000_000);
+		assert_eq!(Currency::free_balance(asset_b, &pair_account), 1_999_999_996_015_999);
 
 		// amount_a_in < amount_b_out scenario
 		let main_intention = ExchangeIntention {
@@ -4131,6 +4504,8 @@ fn in_out_calculations_error_should_work() {
 			amount_in: 1_000_000,
 			amount_out: 2_000_000,
 			trade_limit: 10_000_000,
+			max_price_impact: None,
+			reserved: 0,
 			discount: false,
 			sell_or_buy: IntentionType::BUY,
 			intention_id: generate_intention_id(&user_1, 2),
@@ -4145,363 +4520,62 @@ fn in_out_calculations_error_should_work() {
 			amount_in: 1_000_000,
 			amount_out: 2_000_000,
 			trade_limit: 10,
+			max_price_impact: None,
+			reserved: 0,
 			discount: false,
 			sell_or_buy: IntentionType::SELL,
 			intention_id: generate_intention_id(&user_2, 3),
 		};
 
-		let maybe_error = std::panic::catch_unwind(|| {
-			let mut intentions_a = vec![main_intention];
-			let mut intentions_b = vec![matched_intention];
+		let mut intentions_a = vec![main_intention];
+		let mut intentions_b = vec![matched_intention];
+
+		Exchange::process_exchange_intentions(&pair_account, &mut intentions_a, &mut intentions_b, &mut [], &mut []);
 
-			Exchange::process_exchange_intentions(&pair_account, &mut intentions_a, &mut intentions_b)
-		});
-		assert!(maybe_error.is_err());
+		// user_2 sells 1_000_000 HDX to user_1, who buys the remaining 1_000_000 HDX from the pool
+		assert_eq!(Currency::free_balance(asset_a, &user_1), 99_000_000_003_000_000);
+		assert_eq!(Currency::free_balance(asset_b, &user_1), 97_999_999_993_988_003);
+
+		assert_eq!(Currency::free_balance(asset_a, &user_2), 99_999_999_996_000_000);
+		assert_eq!(Currency::free_balance(asset_b, &user_2), 100_000_000_007_984_002);
+
+		assert_eq!(Currency::free_balance(asset_a, &pair_account), 1_000_000_001_000_000);
+		assert_eq!(Currency::free_balance(asset_b, &pair_account), 1_999_999_998_027_995);
 	});
 }
 
 #[test]
-fn revert_invalid_direct_trades_should_work() {
+fn correct_matching() {
 	new_test_ext().execute_with(|| {
+		// Note: this test scenario came from dynamic testing where it led to panic
+		// This was due to incorrect matching of some intentions.
 		let user_1 = ALICE;
-		let user_2 = BOB;
 		let asset_a = HDX;
 		let asset_b = DOT;
-		let pool_amount = 1_000_000_000_000_000;
-		let initial_price = Price::from(2);
-
-		let pair_account = XYKPallet::get_pair_id(AssetPair {
-			asset_in: asset_a,
-			asset_out: asset_b,
-		});
+		let pool_amount = 139_637_976_727_557;
+		let initial_price = Price::from_float(0.072_057_594_037_927_94);
 
-		initialize_pool(asset_a, asset_b, user_1, pool_amount, initial_price);
+		initialize_pool(asset_b, asset_a, user_1, pool_amount, initial_price);
 
-		assert_ok!(Currency::transfer(Origin::signed(ALICE), BOB, asset_b, 98_000_000_000_000_000));
+		assert_ok!(Exchange::sell(
+			Origin::signed(3),
+			asset_b,
+			asset_a,
+			1048577,
+			0,
+			false,
+			None
+		));
 
-		// amount_a_in > amount_b_out scenario
-		let main_intention = ExchangeIntention {
-			who: user_1,
-			assets: AssetPair {
-				asset_in: asset_b,
-				asset_out: asset_a,
-			},
-			amount_in: 4_000_000,
-			amount_out: 2_000_000,
-			trade_limit: 10_000_000,
-			discount: false,
-			sell_or_buy: IntentionType::BUY,
-			intention_id: generate_intention_id(&user_1, 0),
-		};
-
-		let matched_intention = ExchangeIntention {
-			who: user_2,
-			assets: AssetPair {
-				asset_in: asset_a,
-				asset_out: asset_b,
-			},
-			amount_in: 1_000_000,
-			amount_out: 2_000_000,
-			trade_limit: 10,
-			discount: false,
-			sell_or_buy: IntentionType::SELL,
-			intention_id: generate_intention_id(&user_2, 1),
-		};
-
-		let intentions_b = vec![&matched_intention];
-
-		<Exchange as Resolver<<Test as system::Config>::AccountId, Intention<Test>, Error<Test>>>::resolve_matched_intentions(&pair_account, &main_intention, &intentions_b);
-
-		// amount_a_in < amount_b_out scenario
-		let main_intention = ExchangeIntention {
-			who: user_1,
-			assets: AssetPair {
-				asset_in: asset_b,
-				asset_out: asset_a,
-			},
-			amount_in: 2_000_000,
-			amount_out: 1_000_000,
-			trade_limit: 10_000_000,
-			discount: false,
-			sell_or_buy: IntentionType::BUY,
-			intention_id: generate_intention_id(&user_1, 0),
-		};
-
-		let matched_intention = ExchangeIntention {
-			who: user_2,
-			assets: AssetPair {
-				asset_in: asset_a,
-				asset_out: asset_b,
-			},
-			amount_in: 2_000_000,
-			amount_out: 4_000_000,
-			trade_limit: 10,
-			discount: false,
-			sell_or_buy: IntentionType::SELL,
-			intention_id: generate_intention_id(&user_2, 1),
-		};
-
-		let intentions_b = vec![&matched_intention];
-
-		<Exchange as Resolver<<Test as system::Config>::AccountId, Intention<Test>, Error<Test>>>::resolve_matched_intentions(&pair_account, &main_intention, &intentions_b);
-
-		// amount_a_in == amount_b_out scenario
-		let main_intention = ExchangeIntention {
-			who: user_1,
-			assets: AssetPair {
-				asset_in: asset_b,
-				asset_out: asset_a,
-			},
-			amount_in: 2_000_000,
-			amount_out: 1_000_000,
-			trade_limit: 10_000_000,
-			discount: false,
-			sell_or_buy: IntentionType::BUY,
-			intention_id: generate_intention_id(&user_1, 0),
-		};
-
-		let matched_intention = ExchangeIntention {
-			who: user_2,
-			assets: AssetPair {
-				asset_in: asset_a,
-				asset_out: asset_b,
-			},
-			amount_in: 1_000_000,
-			amount_out: 2_000_000,
-			trade_limit: 10,
-			discount: false,
-			sell_or_buy: IntentionType::SELL,
-			intention_id: generate_intention_id(&user_2, 1),
-		};
-
-		let intentions_b = vec![&matched_intention];
-
-		<Exchange as Resolver<<Test as system::Config>::AccountId, Intention<Test>, Error<Test>>>::resolve_matched_intentions(&pair_account, &main_intention, &intentions_b);
-
-		assert_eq!(Currency::free_balance(asset_a, &pair_account), 1_000_000_000_000_000);
-		assert_eq!(Currency::free_balance(asset_b, &pair_account), 2_000_000_000_000_000);
-
-		assert_eq!(Currency::free_balance(asset_a, &user_1), 99_000_000_000_000_000);
-		assert_eq!(Currency::free_balance(asset_b, &user_1), 0);
-
-		assert_eq!(Currency::free_balance(asset_a, &user_2), 100_000_000_000_000_000);
-		assert_eq!(Currency::free_balance(asset_b, &user_2), 198_000_000_000_000_000);
-	});
-}
-
-#[test]
-fn invalid_transfers_in_resolver_should_not_work() {
-	new_test_ext().execute_with(|| {
-		let user_1 = ALICE;
-		let user_2 = BOB;
-		let asset_a = HDX;
-		let asset_b = DOT;
-		let pool_amount = 1_000_000_000_000_000;
-		let initial_price = Price::from(2);
-
-		let pair_account = XYKPallet::get_pair_id(AssetPair {
-			asset_in: asset_a,
-			asset_out: asset_b,
-		});
-
-		initialize_pool(asset_a, asset_b, user_1, pool_amount, initial_price);
-
-		assert_ok!(Currency::transfer(Origin::signed(ALICE), BOB, asset_b, 98_000_000_000_000_000));
-
-		let main_intention = ExchangeIntention {
-			who: user_1,
-			assets: AssetPair {
-				asset_in: asset_b,
-				asset_out: asset_a,
-			},
-			amount_in: 2_000_000_000_000_000,
-			amount_out: 1_000_000_000_000_000,
-			trade_limit: 10_000_000,
-			discount: false,
-			sell_or_buy: IntentionType::BUY,
-			intention_id: generate_intention_id(&user_1, 0),
-		};
-
-		let matched_intention = ExchangeIntention {
-			who: user_2,
-			assets: AssetPair {
-				asset_in: asset_a,
-				asset_out: asset_b,
-			},
-			amount_in: 2_000_000_000_000_000,
-			amount_out: 4_000_000_000_000_000,
-			trade_limit: 10,
-			discount: false,
-			sell_or_buy: IntentionType::SELL,
-			intention_id: generate_intention_id(&user_2, 1),
-		};
-
-		let intentions_b = vec![&matched_intention];
-
-		<Exchange as Resolver<<Test as system::Config>::AccountId, Intention<Test>, Error<Test>>>::resolve_matched_intentions(&pair_account, &main_intention, &intentions_b);
-
-		assert_eq!(Currency::free_balance(asset_a, &pair_account), 1_000_000_000_000_000);
-		assert_eq!(Currency::free_balance(asset_b, &pair_account), 2_000_000_000_000_000);
-
-		assert_eq!(Currency::free_balance(asset_a, &user_1), 99_000_000_000_000_000);
-		assert_eq!(Currency::free_balance(asset_b, &user_1), 0);
-
-		assert_eq!(Currency::free_balance(asset_a, &user_2), 100_000_000_000_000_000);
-		assert_eq!(Currency::free_balance(asset_b, &user_2), 198_000_000_000_000_000);
-	});
-}
-
-#[test]
-fn trade_limits_in_exact_match_scenario_should_work() {
-	new_test_ext().execute_with(|| {
-		let user_1 = ALICE;
-		let user_2 = BOB;
-		let asset_a = HDX;
-		let asset_b = DOT;
-		let pool_amount = 1_000_000_000_000_000;
-		let initial_price = Price::from(2);
-
-		let pair_account = XYKPallet::get_pair_id(AssetPair {
-			asset_in: asset_a,
-			asset_out: asset_b,
-		});
-
-		initialize_pool(asset_a, asset_b, user_1, pool_amount, initial_price);
-
-		assert_ok!(Currency::transfer(Origin::signed(ALICE), BOB, asset_b, 98_000_000_000_000_000));
-
-		let main_intention_1 = ExchangeIntention {
-			who: user_1,
-			assets: AssetPair {
-				asset_in: asset_b,
-				asset_out: asset_a,
-			},
-			amount_in: 2_000_000,
-			amount_out: 1_000_000,
-			trade_limit: 10_000_000,
-			discount: false,
-			sell_or_buy: IntentionType::SELL,
-			intention_id: generate_intention_id(&user_1, 0),
-		};
-
-		let matched_intention_1 = ExchangeIntention {
-			who: user_2,
-			assets: AssetPair {
-				asset_in: asset_a,
-				asset_out: asset_b,
-			},
-			amount_in: 1_000_000,
-			amount_out: 2_000_000,
-			trade_limit: 10,
-			discount: false,
-			sell_or_buy: IntentionType::SELL,
-			intention_id: generate_intention_id(&user_2, 1),
-		};
-
-		let intentions_b = vec![&matched_intention_1];
-
-		<Exchange as Resolver<<Test as system::Config>::AccountId, Intention<Test>, Error<Test>>>::resolve_matched_intentions(&pair_account, &main_intention_1, &intentions_b);
-
-		let main_intention_2 = ExchangeIntention {
-			who: user_1,
-			assets: AssetPair {
-				asset_in: asset_b,
-				asset_out: asset_a,
-			},
-			amount_in: 2_000_000,
-			amount_out: 1_000_000,
-			trade_limit: 100_000,
-			discount: false,
-			sell_or_buy: IntentionType::BUY,
-			intention_id: generate_intention_id(&user_1, 0),
-		};
-
-		<Exchange as Resolver<<Test as system::Config>::AccountId, Intention<Test>, Error<Test>>>::resolve_matched_intentions(&pair_account, &main_intention_2, &intentions_b);
-
-		let main_intention_3 = ExchangeIntention {
-			who: user_1,
-			assets: AssetPair {
-				asset_in: asset_b,
-				asset_out: asset_a,
-			},
-			amount_in: 2_000_000,
-			amount_out: 1_000_000,
-			trade_limit: 1_000_000,
-			discount: false,
-			sell_or_buy: IntentionType::SELL,
-			intention_id: generate_intention_id(&user_1, 0),
-		};
-
-		let matched_intention_3 = ExchangeIntention {
-			who: user_2,
-			assets: AssetPair {
-				asset_in: asset_a,
-				asset_out: asset_b,
-			},
-			amount_in: 1_000_000,
-			amount_out: 2_000_000,
-			trade_limit: 10_000_000,
-			discount: false,
-			sell_or_buy: IntentionType::SELL,
-			intention_id: generate_intention_id(&user_2, 1),
-		};
-
-		let intentions_b = vec![&matched_intention_3];
-
-		<Exchange as Resolver<<Test as system::Config>::AccountId, Intention<Test>, Error<Test>>>::resolve_matched_intentions(&pair_account, &main_intention_3, &intentions_b);
-
-		let matched_intention_4 = ExchangeIntention {
-			who: user_2,
-			assets: AssetPair {
-				asset_in: asset_a,
-				asset_out: asset_b,
-			},
-			amount_in: 1_000_000,
-			amount_out: 2_000_000,
-			trade_limit: 100_000,
-			discount: false,
-			sell_or_buy: IntentionType::BUY,
-			intention_id: generate_intention_id(&user_2, 1),
-		};
-
-		let intentions_b = vec![&matched_intention_4];
-
-		<Exchange as Resolver<<Test as system::Config>::AccountId, Intention<Test>, Error<Test>>>::resolve_matched_intentions(&pair_account, &main_intention_3, &intentions_b);
-
-		assert_eq!(Currency::free_balance(asset_a, &pair_account), 1_000_000_000_000_000);
-		assert_eq!(Currency::free_balance(asset_b, &pair_account), 2_000_000_000_000_000);
-
-		assert_eq!(Currency::free_balance(asset_a, &user_1), 99_000_000_000_000_000);
-		assert_eq!(Currency::free_balance(asset_b, &user_1), 0);
-
-		assert_eq!(Currency::free_balance(asset_a, &user_2), 100_000_000_000_000_000);
-		assert_eq!(Currency::free_balance(asset_b, &user_2), 198_000_000_000_000_000);
-	});
-}
-
-#[test]
-fn correct_matching() {
-	new_test_ext().execute_with(|| {
-		// Note: this test scenario came from dynamic testing where it led to panic
-		// This was due to incorrect matching of some intentions.
-		let user_1 = ALICE;
-		let asset_a = HDX;
-		let asset_b = DOT;
-		let pool_amount = 139_637_976_727_557;
-		let initial_price = Price::from_float(0.072_057_594_037_927_94);
-
-		initialize_pool(asset_b, asset_a, user_1, pool_amount, initial_price);
-
-		assert_ok!(Exchange::sell(Origin::signed(3), asset_b, asset_a, 1048577, 0, false,));
-
-		assert_ok!(Exchange::buy(
-			Origin::signed(4),
-			asset_b,
-			asset_a,
-			7602433,
-			4722366482869645213696,
-			false,
-		));
+		assert_ok!(Exchange::buy(
+			Origin::signed(4),
+			asset_b,
+			asset_a,
+			7602433,
+			4722366482869645213696,
+			false,
+			None,
+		));
 
 		assert_ok!(Exchange::buy(
 			Origin::signed(6),
@@ -4510,6 +4584,7 @@ fn correct_matching() {
 			65536,
 			4722366482869645213696,
 			false,
+			None,
 		));
 
 		assert_eq!(Exchange::get_intentions_count((asset_a, asset_b)), 3);
@@ -4542,6 +4617,7 @@ fn trade_limit_test() {
 				281474976710656,
 				127547660566528,
 				false,
+				None,
 			));
 
 			assert_ok!(Exchange::sell(
@@ -4551,6 +4627,7 @@ fn trade_limit_test() {
 				190275657924608,
 				12075401216,
 				false,
+				None,
 			));
 
 			assert_eq!(Exchange::get_intentions_count((asset_a, asset_b)), 2);
@@ -4574,6 +4651,7 @@ fn register_intention_should_work() {
 			1,
 			1,
 			1,
+			None,
 			false
 		));
 		assert_ok!(Exchange::register_intention(
@@ -4586,6 +4664,7 @@ fn register_intention_should_work() {
 			1,
 			1,
 			1,
+			None,
 			false
 		));
 
@@ -4611,6 +4690,7 @@ fn register_intention_should_return_error_on_overflow() {
 				1,
 				1,
 				1,
+				None,
 				false
 			),
 			Error::<Test>::IntentionCountOverflow
@@ -4636,6 +4716,7 @@ fn execute_amm_transfer_should_work() {
 		assert_ok!(Exchange::execute_amm_transfer(
 			IntentionType::BUY,
 			alice_buy_intention_id,
+			None,
 			&AMMTransfer {
 				origin: ALICE,
 				assets: AssetPair {
@@ -4654,6 +4735,7 @@ fn execute_amm_transfer_should_work() {
 		assert_ok!(Exchange::execute_amm_transfer(
 			IntentionType::SELL,
 			alice_sell_intention_id,
+			None,
 			&AMMTransfer {
 				origin: ALICE,
 				assets: AssetPair {
@@ -4678,6 +4760,7 @@ fn execute_amm_transfer_should_work() {
 				HDX,
 				1000000,
 				pair_account,
+				Permill::zero(),
 			)
 			.into(),
 			Event::IntentionResolvedAMMTrade(
@@ -4698,6 +4781,7 @@ fn execute_amm_transfer_should_work() {
 				HDX,
 				1000000,
 				pair_account,
+				Permill::from_parts(999_927),
 			)
 			.into(),
 			Event::IntentionResolvedAMMTrade(
@@ -4732,6 +4816,8 @@ fn resolve_single_intention_should_work() {
 			amount_in: 150_000_000,
 			amount_out: 2_000_000_000,
 			trade_limit: 1_500_000_000_000,
+			max_price_impact: None,
+			reserved: 0,
 			discount: false,
 			sell_or_buy: IntentionType::BUY,
 			intention_id: alice_buy_intention_id,
@@ -4747,6 +4833,8 @@ fn resolve_single_intention_should_work() {
 			amount_in: 150_000_000,
 			amount_out: 2_000_000_000,
 			trade_limit: 101_000,
+			max_price_impact: None,
+			reserved: 0,
 			discount: false,
 			sell_or_buy: IntentionType::SELL,
 			intention_id: alice_sell_intention_id,
@@ -4767,6 +4855,7 @@ fn resolve_single_intention_should_work() {
 				DOT,
 				55_557_098,
 				pair_account,
+				Permill::from_parts(27),
 			)
 			.into(),
 			Event::IntentionResolvedAMMTrade(
@@ -4778,7 +4867,18 @@ fn resolve_single_intention_should_work() {
 				pair_account,
 			)
 			.into(),
-			xyk::Event::SellExecuted(ALICE, DOT, HDX, 150000000, 10777799, HDX, 21598, pair_account).into(),
+			xyk::Event::SellExecuted(
+				ALICE,
+				DOT,
+				HDX,
+				150000000,
+				10777799,
+				HDX,
+				21598,
+				pair_account,
+				Permill::zero(),
+			)
+			.into(),
 			Event::IntentionResolvedAMMTrade(
 				ALICE,
 				IntentionType::SELL,
@@ -4793,70 +4893,96 @@ fn resolve_single_intention_should_work() {
 }
 
 #[test]
-fn verify_intention_should_work() {
+fn is_matchable_should_work() {
 	new_test_ext().execute_with(|| {
 		let user = ALICE;
 		let asset_a = HDX;
 		let asset_b = DOT;
 		let pool_amount = 1_000_000_000_000_000;
 		let initial_price = Price::from_float(2.0);
+
+		let pair_account = XYKPallet::get_pair_id(AssetPair {
+			asset_in: asset_a,
+			asset_out: asset_b,
+		});
+
 		initialize_pool(asset_a, asset_b, user, pool_amount, initial_price);
 
-		assert!(Exchange::verify_intention(&Intention::<Test> {
-			who: user,
-			assets: AssetPair {
-				asset_in: asset_a,
-				asset_out: asset_b,
-			},
-			amount_in: 1_000_000_000,
-			amount_out: 2_000_000_000,
-			trade_limit: 3_000_000_000,
-			discount: false,
-			sell_or_buy: IntentionType::BUY,
-			intention_id: generate_intention_id(&user, 0),
-		}));
+		assert!(Exchange::is_matchable(
+			&pair_account,
+			&Intention::<Test> {
+				who: user,
+				assets: AssetPair {
+					asset_in: asset_a,
+					asset_out: asset_b,
+				},
+				amount_in: 1_000_000_000,
+				amount_out: 2_000_000_000,
+				trade_limit: 3_000_000_000,
+				max_price_impact: None,
+				reserved: 0,
+				discount: false,
+				sell_or_buy: IntentionType::BUY,
+				intention_id: generate_intention_id(&user, 0),
+			}
+		));
 
-		assert!(Exchange::verify_intention(&Intention::<Test> {
-			who: user,
-			assets: AssetPair {
-				asset_in: asset_a,
-				asset_out: asset_b,
-			},
-			amount_in: 1_000_000_000,
-			amount_out: 2_000_000_000,
-			trade_limit: 100_000_000,
-			discount: false,
-			sell_or_buy: IntentionType::SELL,
-			intention_id: generate_intention_id(&user, 0),
-		}));
+		assert!(Exchange::is_matchable(
+			&pair_account,
+			&Intention::<Test> {
+				who: user,
+				assets: AssetPair {
+					asset_in: asset_a,
+					asset_out: asset_b,
+				},
+				amount_in: 1_000_000_000,
+				amount_out: 2_000_000_000,
+				trade_limit: 100_000_000,
+				max_price_impact: None,
+				reserved: 0,
+				discount: false,
+				sell_or_buy: IntentionType::SELL,
+				intention_id: generate_intention_id(&user, 0),
+			}
+		));
 
-		assert!(!Exchange::verify_intention(&Intention::<Test> {
-			who: user,
-			assets: AssetPair {
-				asset_in: asset_a,
-				asset_out: asset_b,
-			},
-			amount_in: 1_000_000_000,
-			amount_out: 2_000_000_000,
-			trade_limit: 100_000_000,
-			discount: false,
-			sell_or_buy: IntentionType::BUY,
-			intention_id: generate_intention_id(&user, 0),
-		}));
+		assert!(!Exchange::is_matchable(
+			&pair_account,
+			&Intention::<Test> {
+				who: user,
+				assets: AssetPair {
+					asset_in: asset_a,
+					asset_out: asset_b,
+				},
+				amount_in: 1_000_000_000,
+				amount_out: 2_000_000_000,
+				trade_limit: 100_000_000,
+				max_price_impact: None,
+				reserved: 0,
+				discount: false,
+				sell_or_buy: IntentionType::BUY,
+				intention_id: generate_intention_id(&user, 0),
+			}
+		));
 
-		assert!(!Exchange::verify_intention(&Intention::<Test> {
-			who: user,
-			assets: AssetPair {
-				asset_in: asset_a,
-				asset_out: asset_b,
-			},
-			amount_in: 1_000_000_000,
-			amount_out: 2_000_000_000,
-			trade_limit: 10_000_000_000,
-			discount: false,
-			sell_or_buy: IntentionType::SELL,
-			intention_id: generate_intention_id(&user, 0),
-		}));
+		assert!(!Exchange::is_matchable(
+			&pair_account,
+			&Intention::<Test> {
+				who: user,
+				assets: AssetPair {
+					asset_in: asset_a,
+					asset_out: asset_b,
+				},
+				amount_in: 1_000_000_000,
+				amount_out: 2_000_000_000,
+				trade_limit: 10_000_000_000,
+				max_price_impact: None,
+				reserved: 0,
+				discount: false,
+				sell_or_buy: IntentionType::SELL,
+				intention_id: generate_intention_id(&user, 0),
+			}
+		));
 	});
 }
 
@@ -4900,6 +5026,7 @@ fn direct_sell_sell_transfers_without_other_asset_should_work() {
 			1_000_000,
 			400,
 			false,
+			None,
 		));
 		assert_ok!(Exchange::sell(
 			Origin::signed(user_3),
@@ -4908,6 +5035,7 @@ fn direct_sell_sell_transfers_without_other_asset_should_work() {
 			2_000_000,
 			400,
 			false,
+			None,
 		));
 		let user_2_sell_intention_id = generate_intention_id(&user_2, 0);
 		let user_3_sell_intention_id = generate_intention_id(&user_3, 1);
@@ -5028,6 +5156,7 @@ fn direct_buy_buy_transfers_without_other_asset_should_work() {
 			1_000_000,
 			40_000_000,
 			false,
+			None,
 		));
 		assert_ok!(Exchange::buy(
 			Origin::signed(user_3),
@@ -5036,6 +5165,7 @@ fn direct_buy_buy_transfers_without_other_asset_should_work() {
 			2_000_000,
 			40_000_000,
 			false,
+			None,
 		));
 		let user_2_sell_intention_id = generate_intention_id(&user_2, 0);
 		let user_3_sell_intention_id = generate_intention_id(&user_3, 1);
@@ -5156,6 +5286,7 @@ fn direct_sell_buy_transfers_without_other_asset_should_work() {
 			1_000_000,
 			40_000,
 			false,
+			None,
 		));
 		assert_ok!(Exchange::buy(
 			Origin::signed(user_3),
@@ -5164,6 +5295,7 @@ fn direct_sell_buy_transfers_without_other_asset_should_work() {
 			1_000_000,
 			40_000_000,
 			false,
+			None,
 		));
 		let user_2_sell_intention_id = generate_intention_id(&user_2, 0);
 		let user_3_sell_intention_id = generate_intention_id(&user_3, 1);
@@ -5282,6 +5414,7 @@ fn direct_buy_sell_transfers_without_other_asset_should_work() {
 			1_000_000,
 			40_000_000,
 			false,
+			None,
 		));
 		assert_ok!(Exchange::sell(
 			Origin::signed(user_3),
@@ -5290,6 +5423,7 @@ fn direct_buy_sell_transfers_without_other_asset_should_work() {
 			1_000_000,
 			40_000,
 			false,
+			None,
 		));
 		let user_2_sell_intention_id = generate_intention_id(&user_2, 0);
 		let user_3_sell_intention_id = generate_intention_id(&user_3, 1);
@@ -5367,3 +5501,1214 @@ fn direct_buy_sell_transfers_without_other_asset_should_work() {
 		]);
 	});
 }
+
+/// Deterministic pseudo-random number generator for randomized matching tests
+struct XorShift(u64);
+
+impl XorShift {
+	fn next(&mut self) -> u64 {
+		self.0 ^= self.0 << 13;
+		self.0 ^= self.0 >> 7;
+		self.0 ^= self.0 << 17;
+		self.0
+	}
+
+	fn range(&mut self, from: u128, to: u128) -> u128 {
+		from + self.next() as u128 % (to - from)
+	}
+}
+
+/// Trade of an intention resolved purely via AMM
+/// [who, asset sold, asset bought, amount sold (fee included), amount bought (fee excluded)]
+type AMMQuote = (u64, u32, u32, Balance, Balance);
+
+/// HELPER FOR REGISTERING RANDOM INTENTIONS
+/// Returns quote of each intention as if it was traded alone via AMM.
+fn register_random_intentions(rng: &mut XorShift, asset_a: u32, asset_b: u32, pool_amount: Balance) -> Vec<AMMQuote> {
+	let mut quotes = Vec::new();
+
+	for who in [BOB, CHARLIE, DAVE, FERDIE, GEORGE] {
+		let (asset_in, asset_out) = if rng.next() % 2 == 0 {
+			(asset_a, asset_b)
+		} else {
+			(asset_b, asset_a)
+		};
+		let assets = AssetPair { asset_in, asset_out };
+		let amount = rng.range(10_000_000_000, pool_amount / 100);
+
+		if rng.next() % 2 == 0 {
+			let transfer = XYKPallet::validate_sell(&who, assets, amount, 0, false).unwrap();
+			quotes.push((who, asset_in, asset_out, amount, transfer.amount_out));
+
+			assert_ok!(Exchange::sell(
+				Origin::signed(who),
+				asset_in,
+				asset_out,
+				amount,
+				0,
+				false,
+				None,
+			));
+		} else {
+			let transfer = XYKPallet::validate_buy(&who, assets, amount, Balance::MAX, false).unwrap();
+			quotes.push((who, asset_in, asset_out, transfer.amount_out + transfer.fee.1, amount));
+
+			assert_ok!(Exchange::buy(
+				Origin::signed(who),
+				asset_out,
+				asset_in,
+				amount,
+				Balance::MAX,
+				false,
+				None,
+			));
+		}
+	}
+
+	quotes
+}
+
+/// HELPER FOR COLLECTING AMM TRADES
+/// Returns amount sold (fee included) and amount bought (fee excluded) via AMM by given account.
+fn amm_trades_of(who: u64) -> (Balance, Balance) {
+	System::events()
+		.into_iter()
+		.fold((0, 0), |(sold, bought), record| match record.event {
+			TestEvent::XYK(xyk::Event::SellExecuted(account, _, _, amount, sale_price, _, _, _, _))
+				if account == who =>
+			{
+				(sold + amount, bought + sale_price)
+			}
+			TestEvent::XYK(xyk::Event::BuyExecuted(account, _, _, amount, buy_price, _, fee, _, _))
+				if account == who =>
+			{
+				(sold + buy_price + fee, bought + amount)
+			}
+			_ => (sold, bought),
+		})
+}
+
+#[test]
+fn matched_intentions_should_not_get_worse_price_than_amm() {
+	let mut rng = XorShift(0x2545_f491_4f6c_dd1d);
+
+	for _ in 0..100 {
+		new_test_ext().execute_with(|| {
+			let asset_a = ETH;
+			let asset_b = DOT;
+			let pool_amount = rng.range(100_000_000_000_000, 1_000_000_000_000_000);
+			let initial_price = Price::from(rng.range(1, 5));
+
+			initialize_pool(asset_a, asset_b, ALICE, pool_amount, initial_price);
+
+			let quotes = register_random_intentions(&mut rng, asset_a, asset_b, pool_amount);
+
+			<Exchange as OnFinalize<u64>>::on_finalize(9);
+
+			for (who, asset_in, asset_out, quote_sold, quote_bought) in quotes {
+				let (amm_sold, amm_bought) = amm_trades_of(who);
+
+				let direct_sold = EndowedAmount::get() - Currency::free_balance(asset_in, &who) - amm_sold;
+				let direct_bought = Currency::free_balance(asset_out, &who) - EndowedAmount::get() - amm_bought;
+
+				// direct_bought / direct_sold >= quote_bought / quote_sold
+				assert!(
+					direct_bought * quote_sold >= quote_bought * direct_sold,
+					"account {} traded directly {} for {}, AMM quote is {} for {}",
+					who,
+					direct_sold,
+					direct_bought,
+					quote_sold,
+					quote_bought
+				);
+			}
+		});
+	}
+}
+
+#[test]
+fn only_one_side_should_be_traded_via_amm() {
+	let mut rng = XorShift(0x9e37_79b9_7f4a_7c15);
+
+	for _ in 0..100 {
+		new_test_ext().execute_with(|| {
+			let asset_a = ETH;
+			let asset_b = DOT;
+			let pool_amount = rng.range(100_000_000_000_000, 1_000_000_000_000_000);
+			let initial_price = Price::from(rng.range(1, 5));
+
+			initialize_pool(asset_a, asset_b, ALICE, pool_amount, initial_price);
+
+			register_random_intentions(&mut rng, asset_a, asset_b, pool_amount);
+
+			<Exchange as OnFinalize<u64>>::on_finalize(9);
+
+			let amm_assets_in: Vec<u32> = System::events()
+				.into_iter()
+				.filter_map(|record| match record.event {
+					TestEvent::XYK(xyk::Event::SellExecuted(_, asset_in, _, _, _, _, _, _, _)) => Some(asset_in),
+					TestEvent::XYK(xyk::Event::BuyExecuted(_, _, asset_in, _, _, _, _, _, _)) => Some(asset_in),
+					_ => None,
+				})
+				.collect();
+
+			assert!(amm_assets_in.windows(2).all(|w| w[0] == w[1]));
+			assert_eq!(Exchange::get_intentions_count((asset_b, asset_a)), 0);
+		});
+	}
+}
+
+fn generate_limit_order_id(
+	account: &<Test as system::Config>::AccountId,
+	nonce: u64,
+	asset_in: AssetId,
+	asset_out: AssetId,
+) -> crate::IntentionId<Test> {
+	(b"limit_order", nonce, &account, asset_in, asset_out).using_encoded(<Test as system::Config>::Hashing::hash)
+}
+
+#[test]
+fn place_limit_order_should_work() {
+	new_test_ext().execute_with(|| {
+		let asset_a = ETH;
+		let asset_b = DOT;
+
+		initialize_pool(asset_a, asset_b, ALICE, 100_000_000_000_000, Price::from(2));
+
+		assert_ok!(Exchange::place_limit_order(
+			Origin::signed(BOB),
+			asset_a,
+			asset_b,
+			1_000_000_000_000,
+			2_100_000_000_000,
+			5,
+		));
+
+		let bob_order_id = generate_limit_order_id(&BOB, 1, asset_a, asset_b);
+
+		// Lower limit price - should be placed first
+		assert_ok!(Exchange::place_limit_order(
+			Origin::signed(CHARLIE),
+			asset_a,
+			asset_b,
+			2_000_000_000_000,
+			4_000_000_000_000,
+			10,
+		));
+
+		let charlie_order_id = generate_limit_order_id(&CHARLIE, 2, asset_a, asset_b);
+
+		assert_eq!(Currency::free_balance(asset_a, &BOB), 99_999_000_000_000_000);
+		assert_eq!(Currency::reserved_balance(asset_a, &BOB), 1_000_000_000_000);
+		assert_eq!(Currency::free_balance(asset_a, &CHARLIE), 99_998_000_000_000_000);
+		assert_eq!(Currency::reserved_balance(asset_a, &CHARLIE), 2_000_000_000_000);
+
+		assert_eq!(
+			Exchange::get_limit_orders((asset_a, asset_b)).into_inner(),
+			vec![
+				LimitOrder {
+					who: CHARLIE,
+					assets: AssetPair {
+						asset_in: asset_a,
+						asset_out: asset_b
+					},
+					amount_in: 2_000_000_000_000,
+					amount_out: 4_000_000_000_000,
+					valid_until: 10,
+					order_id: charlie_order_id,
+				},
+				LimitOrder {
+					who: BOB,
+					assets: AssetPair {
+						asset_in: asset_a,
+						asset_out: asset_b
+					},
+					amount_in: 1_000_000_000_000,
+					amount_out: 2_100_000_000_000,
+					valid_until: 5,
+					order_id: bob_order_id,
+				},
+			]
+		);
+		assert_eq!(LimitOrders::<Test>::count(), 1);
+
+		expect_events(vec![
+			Event::LimitOrderPlaced(
+				BOB,
+				asset_a,
+				asset_b,
+				1_000_000_000_000,
+				2_100_000_000_000,
+				5,
+				bob_order_id,
+			)
+			.into(),
+			Event::LimitOrderPlaced(
+				CHARLIE,
+				asset_a,
+				asset_b,
+				2_000_000_000_000,
+				4_000_000_000_000,
+				10,
+				charlie_order_id,
+			)
+			.into(),
+		]);
+	});
+}
+
+#[test]
+fn place_limit_order_with_invalid_params_should_not_work() {
+	new_test_ext().execute_with(|| {
+		let asset_a = ETH;
+		let asset_b = DOT;
+
+		initialize_pool(asset_a, asset_b, ALICE, 100_000_000_000_000, Price::from(2));
+
+		assert_noop!(
+			Exchange::place_limit_order(Origin::signed(BOB), asset_a, asset_b, 100, 200, 5),
+			Error::<Test>::MinimumTradeLimitNotReached
+		);
+
+		assert_noop!(
+			Exchange::place_limit_order(Origin::signed(BOB), asset_a, asset_b, 1_000_000_000_000, 0, 5),
+			Error::<Test>::InvalidLimitPrice
+		);
+
+		assert_noop!(
+			Exchange::place_limit_order(
+				Origin::signed(BOB),
+				HDX,
+				asset_b,
+				1_000_000_000_000,
+				2_000_000_000_000,
+				5
+			),
+			Error::<Test>::TokenPoolNotFound
+		);
+
+		assert_noop!(
+			Exchange::place_limit_order(
+				Origin::signed(BOB),
+				asset_a,
+				asset_b,
+				1_000_000_000_000,
+				2_000_000_000_000,
+				0
+			),
+			Error::<Test>::InvalidLimitOrderExpiry
+		);
+
+		assert_noop!(
+			Exchange::place_limit_order(
+				Origin::signed(BOB),
+				asset_a,
+				asset_b,
+				1_000_000_000_000,
+				2_000_000_000_000,
+				12
+			),
+			Error::<Test>::InvalidLimitOrderExpiry
+		);
+
+		assert_noop!(
+			Exchange::place_limit_order(
+				Origin::signed(BOB),
+				asset_a,
+				asset_b,
+				200_000_000_000_000_000,
+				400_000_000_000_000_000,
+				5
+			),
+			Error::<Test>::InsufficientAssetBalance
+		);
+
+		for _ in 0..MaxLimitOrdersPerPair::get() {
+			assert_ok!(Exchange::place_limit_order(
+				Origin::signed(BOB),
+				asset_a,
+				asset_b,
+				1_000_000_000_000,
+				2_000_000_000_000,
+				5
+			));
+		}
+
+		assert_noop!(
+			Exchange::place_limit_order(
+				Origin::signed(BOB),
+				asset_a,
+				asset_b,
+				1_000_000_000_000,
+				2_000_000_000_000,
+				5
+			),
+			Error::<Test>::LimitOrderBookFull
+		);
+	});
+}
+
+#[test]
+fn better_limit_order_should_evict_worst_order_from_full_order_book() {
+	new_test_ext().execute_with(|| {
+		let asset_a = ETH;
+		let asset_b = DOT;
+
+		initialize_pool(asset_a, asset_b, ALICE, 100_000_000_000_000, Price::from(2));
+
+		// The last order has the highest limit price
+		for idx in 1..=MaxLimitOrdersPerPair::get() {
+			assert_ok!(Exchange::place_limit_order(
+				Origin::signed(BOB),
+				asset_a,
+				asset_b,
+				1_000_000_000_000,
+				2_000_000_000_000 + idx as u128 * 100_000_000_000,
+				5
+			));
+		}
+
+		let worst_order_id = generate_limit_order_id(&BOB, MaxLimitOrdersPerPair::get() as u64, asset_a, asset_b);
+
+		// Same limit price as the worst order is not better
+		assert_noop!(
+			Exchange::place_limit_order(
+				Origin::signed(CHARLIE),
+				asset_a,
+				asset_b,
+				1_000_000_000_000,
+				2_500_000_000_000,
+				5
+			),
+			Error::<Test>::LimitOrderBookFull
+		);
+
+		assert_ok!(Exchange::place_limit_order(
+			Origin::signed(CHARLIE),
+			asset_a,
+			asset_b,
+			2_000_000_000_000,
+			4_000_000_000_000,
+			5
+		));
+
+		expect_event(Event::LimitOrderEvicted(BOB, worst_order_id, 1_000_000_000_000));
+
+		let orders = Exchange::get_limit_orders((asset_a, asset_b));
+		assert_eq!(orders.len() as u32, MaxLimitOrdersPerPair::get());
+		assert_eq!(orders[0].who, CHARLIE);
+		assert!(orders.iter().all(|x| x.order_id != worst_order_id));
+
+		assert_eq!(
+			Currency::reserved_balance(asset_a, &BOB),
+			(MaxLimitOrdersPerPair::get() as u128 - 1) * 1_000_000_000_000
+		);
+		assert_eq!(Currency::reserved_balance(asset_a, &CHARLIE), 2_000_000_000_000);
+	});
+}
+
+#[test]
+fn cancel_limit_order_should_work() {
+	new_test_ext().execute_with(|| {
+		let asset_a = ETH;
+		let asset_b = DOT;
+
+		initialize_pool(asset_a, asset_b, ALICE, 100_000_000_000_000, Price::from(2));
+
+		assert_ok!(Exchange::place_limit_order(
+			Origin::signed(BOB),
+			asset_a,
+			asset_b,
+			1_000_000_000_000,
+			2_100_000_000_000,
+			5,
+		));
+
+		let order_id = generate_limit_order_id(&BOB, 1, asset_a, asset_b);
+
+		assert_noop!(
+			Exchange::cancel_limit_order(Origin::signed(CHARLIE), asset_a, asset_b, order_id),
+			Error::<Test>::NotLimitOrderOwner
+		);
+
+		assert_noop!(
+			Exchange::cancel_limit_order(Origin::signed(BOB), asset_b, asset_a, order_id),
+			Error::<Test>::LimitOrderNotFound
+		);
+
+		assert_ok!(Exchange::cancel_limit_order(
+			Origin::signed(BOB),
+			asset_a,
+			asset_b,
+			order_id
+		));
+
+		assert_eq!(Currency::free_balance(asset_a, &BOB), EndowedAmount::get());
+		assert_eq!(Currency::reserved_balance(asset_a, &BOB), 0);
+		assert!(Exchange::get_limit_orders((asset_a, asset_b)).is_empty());
+		assert_eq!(LimitOrders::<Test>::count(), 0);
+
+		expect_event(Event::LimitOrderCancelled(BOB, order_id, 1_000_000_000_000));
+
+		assert_noop!(
+			Exchange::cancel_limit_order(Origin::signed(BOB), asset_a, asset_b, order_id),
+			Error::<Test>::LimitOrderNotFound
+		);
+	});
+}
+
+#[test]
+fn limit_order_should_be_sold_via_amm_when_price_crosses_limit() {
+	new_test_ext().execute_with(|| {
+		let asset_a = ETH;
+		let asset_b = DOT;
+
+		initialize_pool(asset_a, asset_b, ALICE, 100_000_000_000_000, Price::from(2));
+
+		let pair_account = XYKPallet::get_pair_id(AssetPair {
+			asset_in: asset_a,
+			asset_out: asset_b,
+		});
+
+		assert_ok!(Exchange::place_limit_order(
+			Origin::signed(BOB),
+			asset_a,
+			asset_b,
+			1_000_000_000_000,
+			2_100_000_000_000,
+			5,
+		));
+
+		let order_id = generate_limit_order_id(&BOB, 1, asset_a, asset_b);
+		let order = Exchange::get_limit_orders((asset_a, asset_b));
+
+		// Limit price is above the spot price - the order stays in the order book
+		<Exchange as OnFinalize<u64>>::on_finalize(1);
+
+		assert_eq!(Exchange::get_limit_orders((asset_a, asset_b)), order);
+		assert_eq!(Currency::reserved_balance(asset_a, &BOB), 1_000_000_000_000);
+		assert_eq!(Currency::free_balance(asset_b, &BOB), EndowedAmount::get());
+
+		System::set_block_number(2);
+
+		// Move the price of asset_a above the limit price
+		assert_ok!(XYKPallet::sell(
+			Origin::signed(CHARLIE),
+			asset_b,
+			asset_a,
+			10_000_000_000_000,
+			0,
+			false,
+			None,
+			None,
+		));
+
+		<Exchange as OnFinalize<u64>>::on_finalize(2);
+
+		expect_events(vec![
+			xyk::Event::SellExecuted(
+				BOB,
+				asset_a,
+				asset_b,
+				1_000_000_000_000,
+				2_177_508_410_845,
+				asset_b,
+				4_363_744_310,
+				pair_account,
+				Permill::from_parts(10_389),
+			)
+			.into(),
+			Event::IntentionResolvedAMMTrade(
+				BOB,
+				IntentionType::SELL,
+				order_id,
+				1_000_000_000_000,
+				2_181_872_155_155,
+				pair_account,
+			)
+			.into(),
+			Event::LimitOrderFilled(BOB, order_id, 1_000_000_000_000, 0).into(),
+		]);
+
+		assert_eq!(Currency::free_balance(asset_a, &BOB), 99_999_000_000_000_000);
+		assert_eq!(Currency::reserved_balance(asset_a, &BOB), 0);
+		assert_eq!(Currency::free_balance(asset_b, &BOB), 100_002_177_508_410_845);
+
+		assert!(Exchange::get_limit_orders((asset_a, asset_b)).is_empty());
+		assert_eq!(LimitOrders::<Test>::count(), 0);
+	});
+}
+
+#[test]
+fn partially_filled_limit_order_should_keep_limit_price() {
+	new_test_ext().execute_with(|| {
+		let asset_a = ETH;
+		let asset_b = DOT;
+
+		initialize_pool(asset_a, asset_b, ALICE, 100_000_000_000_000, Price::from(2));
+
+		assert_ok!(Exchange::place_limit_order(
+			Origin::signed(BOB),
+			asset_a,
+			asset_b,
+			5_000_000_000_000,
+			10_500_000_000_000,
+			5,
+		));
+
+		let order_id = generate_limit_order_id(&BOB, 1, asset_a, asset_b);
+
+		assert_ok!(XYKPallet::sell(
+			Origin::signed(CHARLIE),
+			asset_b,
+			asset_a,
+			10_000_000_000_000,
+			0,
+			false,
+			None,
+			None,
+		));
+
+		System::set_block_number(2);
+
+		// Whole order would be sold below its limit price, half of it can be sold
+		<Exchange as OnFinalize<u64>>::on_finalize(2);
+
+		expect_event(Event::LimitOrderFilled(
+			BOB,
+			order_id,
+			2_500_000_000_000,
+			2_500_000_000_000,
+		));
+
+		assert_eq!(Currency::free_balance(asset_a, &BOB), 99_995_000_000_000_000);
+		assert_eq!(Currency::reserved_balance(asset_a, &BOB), 2_500_000_000_000);
+		assert_eq!(Currency::free_balance(asset_b, &BOB), 100_005_360_232_864_034);
+
+		assert_eq!(
+			Exchange::get_limit_orders((asset_a, asset_b)).into_inner(),
+			vec![LimitOrder {
+				who: BOB,
+				assets: AssetPair {
+					asset_in: asset_a,
+					asset_out: asset_b
+				},
+				amount_in: 2_500_000_000_000,
+				amount_out: 5_250_000_000_000,
+				valid_until: 5,
+				order_id,
+			}]
+		);
+	});
+}
+
+#[test]
+fn limit_order_should_be_matched_with_intention() {
+	new_test_ext().execute_with(|| {
+		let asset_a = ETH;
+		let asset_b = DOT;
+
+		initialize_pool(asset_a, asset_b, ALICE, 100_000_000_000_000, Price::from(2));
+
+		let pair_account = XYKPallet::get_pair_id(AssetPair {
+			asset_in: asset_a,
+			asset_out: asset_b,
+		});
+
+		assert_ok!(Exchange::place_limit_order(
+			Origin::signed(BOB),
+			asset_a,
+			asset_b,
+			1_000_000_000_000,
+			1_900_000_000_000,
+			5,
+		));
+
+		let order_id = generate_limit_order_id(&BOB, 1, asset_a, asset_b);
+
+		assert_ok!(Exchange::sell(
+			Origin::signed(CHARLIE),
+			asset_b,
+			asset_a,
+			3_000_000_000_000,
+			1_400_000_000_000,
+			false,
+			None,
+		));
+
+		let charlie_intention_id = generate_intention_id(&CHARLIE, 0);
+
+		<Exchange as OnFinalize<u64>>::on_finalize(1);
+
+		expect_events(vec![
+			Event::IntentionResolvedDirectTrade(
+				CHARLIE,
+				BOB,
+				charlie_intention_id,
+				order_id,
+				2_000_000_000_000,
+				1_000_000_000_000,
+			)
+			.into(),
+			Event::IntentionResolvedDirectTradeFees(
+				CHARLIE,
+				charlie_intention_id,
+				pair_account,
+				asset_b,
+				4_000_000_000,
+			)
+			.into(),
+			Event::IntentionResolvedDirectTradeFees(BOB, order_id, pair_account, asset_a, 2_000_000_000).into(),
+			xyk::Event::SellExecuted(
+				CHARLIE,
+				asset_b,
+				asset_a,
+				1_000_000_000_000,
+				496_517_462_339,
+				asset_a,
+				995_024_974,
+				pair_account,
+				Permill::from_parts(4_975),
+			)
+			.into(),
+			Event::LimitOrderFilled(BOB, order_id, 1_000_000_000_000, 0).into(),
+		]);
+
+		assert_eq!(Currency::free_balance(asset_a, &BOB), 99_999_000_000_000_000);
+		assert_eq!(Currency::reserved_balance(asset_a, &BOB), 0);
+		assert_eq!(Currency::free_balance(asset_b, &BOB), 100_001_996_000_000_000);
+
+		assert_eq!(Currency::free_balance(asset_a, &CHARLIE), 100_001_494_517_462_339);
+		assert_eq!(Currency::free_balance(asset_b, &CHARLIE), 99_997_000_000_000_000);
+
+		assert!(Exchange::get_limit_orders((asset_a, asset_b)).is_empty());
+	});
+}
+
+#[test]
+fn expired_limit_orders_should_be_pruned() {
+	new_test_ext().execute_with(|| {
+		let asset_a = ETH;
+		let asset_b = DOT;
+
+		initialize_pool(asset_a, asset_b, ALICE, 100_000_000_000_000, Price::from(2));
+
+		assert_ok!(Exchange::place_limit_order(
+			Origin::signed(BOB),
+			asset_a,
+			asset_b,
+			1_000_000_000_000,
+			2_100_000_000_000,
+			3,
+		));
+
+		assert_ok!(Exchange::place_limit_order(
+			Origin::signed(CHARLIE),
+			asset_a,
+			asset_b,
+			1_000_000_000_000,
+			2_200_000_000_000,
+			5,
+		));
+
+		let bob_order_id = generate_limit_order_id(&BOB, 1, asset_a, asset_b);
+
+		System::set_block_number(3);
+		<Exchange as OnIdle<u64>>::on_idle(3, Weight::MAX);
+
+		assert_eq!(Exchange::get_limit_orders((asset_a, asset_b)).len(), 2);
+
+		System::set_block_number(4);
+		<Exchange as OnIdle<u64>>::on_idle(4, Weight::MAX);
+
+		expect_event(Event::LimitOrderExpired(BOB, bob_order_id, 1_000_000_000_000));
+
+		assert_eq!(Currency::free_balance(asset_a, &BOB), EndowedAmount::get());
+		assert_eq!(Currency::reserved_balance(asset_a, &BOB), 0);
+		assert_eq!(Currency::reserved_balance(asset_a, &CHARLIE), 1_000_000_000_000);
+
+		let orders = Exchange::get_limit_orders((asset_a, asset_b));
+		assert_eq!(orders.len(), 1);
+		assert_eq!(orders[0].who, CHARLIE);
+	});
+}
+
+#[test]
+fn expired_limit_orders_of_all_pairs_should_be_pruned_in_turns() {
+	new_test_ext().execute_with(|| {
+		let asset_a = ETH;
+		let asset_b = DOT;
+
+		initialize_pool(asset_a, asset_b, ALICE, 100_000_000_000_000, Price::from(2));
+
+		for (asset_sell, asset_buy, min_bought) in [
+			(asset_a, asset_b, 2_100_000_000_000),
+			(asset_b, asset_a, 600_000_000_000),
+		] {
+			assert_ok!(Exchange::place_limit_order(
+				Origin::signed(BOB),
+				asset_sell,
+				asset_buy,
+				1_000_000_000_000,
+				min_bought,
+				2,
+			));
+			assert_ok!(Exchange::place_limit_order(
+				Origin::signed(CHARLIE),
+				asset_sell,
+				asset_buy,
+				1_000_000_000_000,
+				min_bought,
+				5,
+			));
+		}
+
+		// Weight of pruning one pair
+		let weight = <Test as Config>::WeightInfo::prune_expired_limit_orders(MaxLimitOrdersPerPair::get())
+			+ <Test as system::Config>::DbWeight::get().reads_writes(2, 1);
+
+		System::set_block_number(3);
+		<Exchange as OnIdle<u64>>::on_idle(3, weight);
+		<Exchange as OnIdle<u64>>::on_idle(3, weight);
+
+		for (asset_sell, asset_buy) in [(asset_a, asset_b), (asset_b, asset_a)] {
+			let orders = Exchange::get_limit_orders((asset_sell, asset_buy));
+			assert_eq!(orders.len(), 1);
+			assert_eq!(orders[0].who, CHARLIE);
+		}
+
+		assert_eq!(Currency::reserved_balance(asset_a, &BOB), 0);
+		assert_eq!(Currency::reserved_balance(asset_b, &BOB), 0);
+	});
+}
+
+#[test]
+fn limit_order_should_not_be_spent_by_intention_of_same_account() {
+	new_test_ext().execute_with(|| {
+		let asset_a = ETH;
+		let asset_b = DOT;
+
+		initialize_pool(asset_a, asset_b, ALICE, 100_000_000_000_000, Price::from(2));
+
+		// Keep 9.5 of asset_a free after the order is placed
+		assert_ok!(Currency::transfer(
+			Origin::signed(BOB),
+			ALICE,
+			asset_a,
+			EndowedAmount::get() - 14_500_000_000_000
+		));
+
+		assert_ok!(Exchange::place_limit_order(
+			Origin::signed(BOB),
+			asset_a,
+			asset_b,
+			5_000_000_000_000,
+			10_500_000_000_000,
+			5,
+		));
+
+		let order_id = generate_limit_order_id(&BOB, 1, asset_a, asset_b);
+
+		assert_ok!(XYKPallet::sell(
+			Origin::signed(CHARLIE),
+			asset_b,
+			asset_a,
+			10_000_000_000_000,
+			0,
+			false,
+			None,
+			None,
+		));
+
+		System::set_block_number(2);
+
+		// AMM trade of the intention costs more than the free balance
+		assert_ok!(Exchange::buy(
+			Origin::signed(BOB),
+			asset_b,
+			asset_a,
+			20_000_000_000_000,
+			11_000_000_000_000,
+			false,
+			None,
+		));
+
+		<Exchange as OnFinalize<u64>>::on_finalize(2);
+
+		// Only the amount sold via AMM was taken from the reserve of the order
+		expect_event(Event::LimitOrderFilled(
+			BOB,
+			order_id,
+			2_500_000_000_000,
+			2_500_000_000_000,
+		));
+
+		assert_eq!(Currency::free_balance(asset_a, &BOB), 9_500_000_000_000);
+		assert_eq!(Currency::reserved_balance(asset_a, &BOB), 2_500_000_000_000);
+		assert_eq!(Currency::free_balance(asset_b, &BOB), 100_005_360_232_864_034);
+
+		let orders = Exchange::get_limit_orders((asset_a, asset_b));
+		assert_eq!(orders.len(), 1);
+		assert_eq!(orders[0].amount_in, 2_500_000_000_000);
+	});
+}
+
+#[test]
+fn sold_amount_should_be_reserved_until_intention_is_resolved() {
+	new_test_ext().execute_with(|| {
+		let asset_a = ETH;
+		let asset_b = DOT;
+
+		initialize_pool(asset_a, asset_b, ALICE, 100_000_000_000_000, Price::from(2));
+
+		assert_ok!(Exchange::sell(
+			Origin::signed(BOB),
+			asset_a,
+			asset_b,
+			1_000_000_000_000,
+			1_900_000_000_000,
+			false,
+			None,
+		));
+
+		assert_eq!(Currency::reserved_balance(asset_a, &BOB), 1_000_000_000_000);
+
+		// Reserved amount cannot be spent by following transactions in the block
+		assert_noop!(
+			Currency::transfer(Origin::signed(BOB), CHARLIE, asset_a, EndowedAmount::get()),
+			orml_tokens::Error::<Test>::BalanceTooLow
+		);
+		assert_noop!(
+			Exchange::sell(
+				Origin::signed(BOB),
+				asset_a,
+				asset_b,
+				EndowedAmount::get(),
+				1,
+				false,
+				None
+			),
+			Error::<Test>::InsufficientAssetBalance
+		);
+
+		assert_ok!(Exchange::buy(
+			Origin::signed(CHARLIE),
+			asset_b,
+			asset_a,
+			1_000_000_000_000,
+			600_000_000_000,
+			false,
+			None,
+		));
+
+		// Maximum sold amount
+		assert_eq!(Currency::reserved_balance(asset_a, &CHARLIE), 600_000_000_000);
+
+		<Exchange as OnFinalize<u64>>::on_finalize(1);
+
+		assert_eq!(Currency::reserved_balance(asset_a, &BOB), 0);
+		assert_eq!(Currency::reserved_balance(asset_a, &CHARLIE), 0);
+
+		assert_eq!(Currency::free_balance(asset_a, &BOB), 99_999_000_000_000_000);
+		assert_eq!(Currency::free_balance(asset_b, &CHARLIE), 100_001_000_000_000_000);
+	});
+}
+
+#[test]
+fn max_sold_amount_should_be_reserved_for_buy_intention() {
+	new_test_ext().execute_with(|| {
+		let asset_a = ETH;
+		let asset_b = DOT;
+
+		initialize_pool(asset_a, asset_b, ALICE, 100_000_000_000_000, Price::from(2));
+
+		assert_ok!(Currency::transfer(
+			Origin::signed(BOB),
+			ALICE,
+			asset_a,
+			EndowedAmount::get() - 6_000_000_000_000
+		));
+
+		// Amount sold at spot price including direct trade fee is 5.01, AMM trade costs more
+		assert_ok!(Exchange::buy(
+			Origin::signed(BOB),
+			asset_b,
+			asset_a,
+			10_000_000_000_000,
+			6_000_000_000_000,
+			false,
+			None,
+		));
+
+		assert_eq!(Currency::reserved_balance(asset_a, &BOB), 6_000_000_000_000);
+		assert_noop!(
+			Currency::transfer(Origin::signed(BOB), CHARLIE, asset_a, 1),
+			orml_tokens::Error::<Test>::BalanceTooLow
+		);
+
+		<Exchange as OnFinalize<u64>>::on_finalize(1);
+
+		// Unused part of the reserved amount is released
+		assert_eq!(Currency::reserved_balance(asset_a, &BOB), 0);
+		assert_eq!(
+			Currency::free_balance(asset_b, &BOB),
+			EndowedAmount::get() + 10_000_000_000_000
+		);
+		assert!(Currency::free_balance(asset_a, &BOB) < 990_000_000_000);
+		assert!(Currency::free_balance(asset_a, &BOB) > 0);
+	});
+}
+
+#[test]
+fn cancel_intention_should_work() {
+	new_test_ext().execute_with(|| {
+		let asset_a = ETH;
+		let asset_b = DOT;
+
+		initialize_pool(asset_a, asset_b, ALICE, 100_000_000_000_000, Price::from(2));
+
+		assert_ok!(Exchange::sell(
+			Origin::signed(BOB),
+			asset_a,
+			asset_b,
+			1_000_000_000_000,
+			1_900_000_000_000,
+			false,
+			None,
+		));
+		let bob_intention_id = generate_intention_id(&BOB, 0);
+
+		assert_ok!(Exchange::sell(
+			Origin::signed(CHARLIE),
+			asset_a,
+			asset_b,
+			2_000_000_000_000,
+			3_800_000_000_000,
+			false,
+			None,
+		));
+		let charlie_intention_id = generate_intention_id(&CHARLIE, 1);
+
+		assert_ok!(Exchange::cancel_intention(Origin::signed(BOB), bob_intention_id));
+
+		expect_event(Event::IntentionCancelled(BOB, bob_intention_id, 1_000_000_000_000));
+		assert_eq!(IntentionAssetPairs::<Test>::get(bob_intention_id), None);
+
+		assert_eq!(Currency::free_balance(asset_a, &BOB), EndowedAmount::get());
+		assert_eq!(Currency::reserved_balance(asset_a, &BOB), 0);
+
+		assert_eq!(Exchange::get_intentions_count((asset_b, asset_a)), 1);
+		let intentions = Exchange::get_intentions((asset_a, asset_b));
+		assert_eq!(intentions.len(), 1);
+		assert_eq!(intentions[0].intention_id, charlie_intention_id);
+
+		// Id of cancelled intention is not reused
+		assert_ok!(Exchange::sell(
+			Origin::signed(CHARLIE),
+			asset_a,
+			asset_b,
+			1_000_000_000_000,
+			1_800_000_000_000,
+			false,
+			None,
+		));
+
+		expect_event(Event::IntentionRegistered(
+			CHARLIE,
+			asset_a,
+			asset_b,
+			1_000_000_000_000,
+			IntentionType::SELL,
+			generate_intention_id(&CHARLIE, 2),
+		));
+
+		assert_eq!(Exchange::get_intentions_count((asset_b, asset_a)), 2);
+
+		<Exchange as OnFinalize<u64>>::on_finalize(1);
+
+		assert_eq!(Currency::free_balance(asset_a, &BOB), EndowedAmount::get());
+		assert_eq!(Currency::free_balance(asset_b, &BOB), EndowedAmount::get());
+		assert_eq!(Currency::free_balance(asset_a, &CHARLIE), 99_997_000_000_000_000);
+	});
+}
+
+#[test]
+fn cancel_intention_with_invalid_params_should_not_work() {
+	new_test_ext().execute_with(|| {
+		let asset_a = ETH;
+		let asset_b = DOT;
+
+		initialize_pool(asset_a, asset_b, ALICE, 100_000_000_000_000, Price::from(2));
+
+		assert_ok!(Exchange::buy(
+			Origin::signed(BOB),
+			asset_a,
+			asset_b,
+			1_000_000_000_000,
+			2_100_000_000_000,
+			false,
+			None,
+		));
+		let bob_intention_id = generate_intention_id(&BOB, 0);
+
+		assert_noop!(
+			Exchange::cancel_intention(Origin::signed(CHARLIE), bob_intention_id),
+			Error::<Test>::NotIntentionOwner
+		);
+
+		assert_noop!(
+			Exchange::cancel_intention(Origin::signed(BOB), generate_intention_id(&BOB, 1)),
+			Error::<Test>::IntentionNotFound
+		);
+
+		assert_ok!(Exchange::cancel_intention(Origin::signed(BOB), bob_intention_id));
+
+		// Maximum sold amount is released
+		expect_event(Event::IntentionCancelled(BOB, bob_intention_id, 2_100_000_000_000));
+		assert_eq!(Currency::free_balance(asset_b, &BOB), EndowedAmount::get());
+
+		assert_eq!(Exchange::get_intentions_count((asset_b, asset_a)), 0);
+		assert!(Exchange::get_intentions((asset_b, asset_a)).is_empty());
+
+		assert_noop!(
+			Exchange::cancel_intention(Origin::signed(BOB), bob_intention_id),
+			Error::<Test>::IntentionNotFound
+		);
+	});
+}
+
+#[test]
+fn intentions_should_be_queryable_until_next_block_is_initialized() {
+	new_test_ext().execute_with(|| {
+		let asset_a = ETH;
+		let asset_b = DOT;
+
+		initialize_pool(asset_a, asset_b, ALICE, 100_000_000_000_000, Price::from(2));
+
+		assert_ok!(Exchange::sell(
+			Origin::signed(BOB),
+			asset_a,
+			asset_b,
+			2_000_000_000_000,
+			20_000_000_000,
+			false,
+			None,
+		));
+		let bob_intention_id = generate_intention_id(&BOB, 0);
+
+		assert_ok!(Exchange::buy(
+			Origin::signed(CHARLIE),
+			asset_a,
+			asset_b,
+			1_000_000_000_000,
+			4_000_000_000_000,
+			false,
+			None,
+		));
+		let charlie_intention_id = generate_intention_id(&CHARLIE, 1);
+
+		<Exchange as OnFinalize<u64>>::on_finalize(1);
+
+		// Resolved intentions are kept with released reserved amounts
+		let asset_a_intentions = Exchange::get_intentions((asset_a, asset_b));
+		assert_eq!(asset_a_intentions.len(), 1);
+		assert_eq!(asset_a_intentions[0].intention_id, bob_intention_id);
+		assert_eq!(asset_a_intentions[0].reserved, 0);
+
+		let asset_b_intentions = Exchange::get_intentions((asset_b, asset_a));
+		assert_eq!(asset_b_intentions.len(), 1);
+		assert_eq!(asset_b_intentions[0].intention_id, charlie_intention_id);
+		assert_eq!(asset_b_intentions[0].reserved, 0);
+
+		assert_eq!(Currency::reserved_balance(asset_a, &BOB), 0);
+		assert_eq!(Currency::reserved_balance(asset_b, &CHARLIE), 0);
+
+		assert_eq!(
+			IntentionAssetPairs::<Test>::get(bob_intention_id),
+			Some((asset_a, asset_b))
+		);
+
+		let mut resolved_keys = ResolvedIntentionsKeys::<Test>::get();
+		resolved_keys.sort_unstable();
+		assert_eq!(resolved_keys, vec![(asset_b, asset_a), (asset_a, asset_b)]);
+
+		let resolutions = Exchange::simulate_resolution();
+		assert_eq!(resolutions.len(), 1);
+		assert_eq!(
+			resolutions[0]
+				.direct_trades
+				.first()
+				.map(|x| (x.intention_a, x.intention_b)),
+			Some((bob_intention_id, charlie_intention_id))
+		);
+
+		System::set_block_number(2);
+		<Exchange as OnInitialize<u64>>::on_initialize(2);
+
+		assert!(Exchange::get_intentions((asset_a, asset_b)).is_empty());
+		assert!(Exchange::get_intentions((asset_b, asset_a)).is_empty());
+		assert!(Exchange::simulate_resolution().is_empty());
+		assert!(ResolvedIntentionsKeys::<Test>::get().is_empty());
+		assert_eq!(IntentionAssetPairs::<Test>::get(bob_intention_id), None);
+		assert_eq!(IntentionAssetPairs::<Test>::get(charlie_intention_id), None);
+	});
+}
+
+#[test]
+fn simulate_resolution_should_not_change_state() {
+	new_test_ext().execute_with(|| {
+		let asset_a = ETH;
+		let asset_b = DOT;
+
+		initialize_pool(asset_a, asset_b, ALICE, 100_000_000_000_000, Price::from(2));
+
+		assert_ok!(Exchange::sell(
+			Origin::signed(BOB),
+			asset_a,
+			asset_b,
+			2_000_000_000_000,
+			20_000_000_000,
+			false,
+			None,
+		));
+		let bob_intention_id = generate_intention_id(&BOB, 0);
+
+		assert_ok!(Exchange::buy(
+			Origin::signed(CHARLIE),
+			asset_a,
+			asset_b,
+			1_000_000_000_000,
+			4_000_000_000_000,
+			false,
+			None,
+		));
+		let charlie_intention_id = generate_intention_id(&CHARLIE, 1);
+
+		let events_count = System::events().len();
+
+		// Half of BOB's intention is traded directly with CHARLIE, the rest is left for AMM
+		assert_eq!(
+			Exchange::simulate_resolution(),
+			vec![IntentionsResolution {
+				assets: AssetPair {
+					asset_in: asset_a,
+					asset_out: asset_b,
+				},
+				direct_trades: vec![DirectTradeInfo {
+					intention_a: bob_intention_id,
+					intention_b: charlie_intention_id,
+					amount_from_a: 1_000_000_000_000,
+					amount_from_b: 2_000_000_000_000,
+				}],
+				amm_intentions: vec![bob_intention_id],
+				amm_amount_in: 1_000_000_000_000,
+			}]
+		);
+
+		assert_eq!(System::events().len(), events_count);
+		assert_eq!(Exchange::get_intentions_count((asset_b, asset_a)), 2);
+		assert_eq!(Currency::reserved_balance(asset_a, &BOB), 2_000_000_000_000);
+		assert_eq!(Currency::reserved_balance(asset_b, &CHARLIE), 4_000_000_000_000);
+		assert_eq!(Currency::free_balance(asset_a, &CHARLIE), EndowedAmount::get());
+	});
+}
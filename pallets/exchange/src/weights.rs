@@ -57,6 +57,11 @@ pub trait WeightInfo {
 	fn on_finalize_for_one_sell_extrinsic() -> Weight;
 	fn buy_extrinsic() -> Weight;
 	fn on_finalize_for_one_buy_extrinsic() -> Weight;
+	fn place_limit_order() -> Weight;
+	fn cancel_limit_order() -> Weight;
+	fn process_limit_orders(o: u32) -> Weight;
+	fn prune_expired_limit_orders(o: u32) -> Weight;
+	fn cancel_intention() -> Weight;
 }
 
 /// Weights for exchange using the hydraDX node and recommended hardware.
@@ -68,13 +73,13 @@ impl<T: frame_system::Config> WeightInfo for HydraWeight<T> {
 	}
 	fn sell_intention() -> Weight {
 		(109_767_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(6 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
 	}
 	fn buy_intention() -> Weight {
 		(112_747_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(6 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
 	}
 	fn on_finalize(t: u32) -> Weight {
 		(0 as Weight)
@@ -123,6 +128,39 @@ impl<T: frame_system::Config> WeightInfo for HydraWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(9 as Weight))
 			.saturating_add(T::DbWeight::get().writes(6 as Weight))
 	}
+	fn place_limit_order() -> Weight {
+		(68_412_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn cancel_limit_order() -> Weight {
+		(52_183_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn process_limit_orders(o: u32) -> Weight {
+		(21_530_000 as Weight)
+			// Standard Error: 61_000
+			.saturating_add((141_219_000 as Weight).saturating_mul(o as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(o as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((4 as Weight).saturating_mul(o as Weight)))
+	}
+	fn prune_expired_limit_orders(o: u32) -> Weight {
+		(9_874_000 as Weight)
+			// Standard Error: 14_000
+			.saturating_add((31_642_000 as Weight).saturating_mul(o as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(o as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(o as Weight)))
+	}
+	fn cancel_intention() -> Weight {
+		(42_317_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
 }
 
 // For backwards compatibility and tests
@@ -132,13 +170,13 @@ impl WeightInfo for () {
 	}
 	fn sell_intention() -> Weight {
 		(109_767_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
 	}
 	fn buy_intention() -> Weight {
 		(112_747_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
 	}
 	fn on_finalize(t: u32) -> Weight {
 		(0 as Weight)
@@ -187,4 +225,37 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(9 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
 	}
+	fn place_limit_order() -> Weight {
+		(68_412_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn cancel_limit_order() -> Weight {
+		(52_183_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn process_limit_orders(o: u32) -> Weight {
+		(21_530_000 as Weight)
+			// Standard Error: 61_000
+			.saturating_add((141_219_000 as Weight).saturating_mul(o as Weight))
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(o as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((4 as Weight).saturating_mul(o as Weight)))
+	}
+	fn prune_expired_limit_orders(o: u32) -> Weight {
+		(9_874_000 as Weight)
+			// Standard Error: 14_000
+			.saturating_add((31_642_000 as Weight).saturating_mul(o as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(o as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(o as Weight)))
+	}
+	fn cancel_intention() -> Weight {
+		(42_317_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
 }
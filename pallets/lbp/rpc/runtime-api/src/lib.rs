@@ -20,8 +20,11 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::Codec;
+pub use primitives::PoolStats;
+use sp_runtime::traits::NumberFor;
 
 sp_api::decl_runtime_apis! {
+	#[api_version(2)]
 	pub trait LBPApi<AccountId, AssetId> where
 		AccountId: Codec,
 		AssetId: Codec,
@@ -30,5 +33,11 @@ sp_api::decl_runtime_apis! {
 			asset_a: AssetId,
 			asset_b: AssetId
 		) -> AccountId;
+
+		/// Trade statistics of the pool of given assets as of current block.
+		fn get_pool_stats(
+			asset_a: AssetId,
+			asset_b: AssetId
+		) -> Option<PoolStats<NumberFor<Block>>>;
 	}
 }
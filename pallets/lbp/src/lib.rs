@@ -38,8 +38,8 @@ use hydradx_traits::{AMMTransfer, AssetPairAccountIdFor, CanCreatePool, LockedBa
 use orml_traits::{MultiCurrency, MultiCurrencyExtended, MultiLockableCurrency};
 use primitives::{
 	asset::AssetPair,
-	constants::chain::{MAX_IN_RATIO, MAX_OUT_RATIO},
-	Amount, AssetId, Balance,
+	traits::{CircuitBreaker, TradingLimitsProvider},
+	Amount, AssetId, Balance, PoolStats,
 };
 
 use scale_info::TypeInfo;
@@ -205,24 +205,18 @@ pub mod pallet {
 		/// Weight information for the extrinsics
 		type WeightInfo: WeightInfo;
 
-		/// Minimum trading limit, sole purpose of this is to keep the math working
-		#[pallet::constant]
-		type MinTradingLimit: Get<Balance>;
+		/// Trading limits of pools, minimum trading limit and minimum pool liquidity keep the math working
+		type TradingLimits: TradingLimitsProvider<Self::AccountId>;
 
-		/// Minimum pool liquidity, sole purpose of this is to keep the math working
-		#[pallet::constant]
-		type MinPoolLiquidity: Get<Balance>;
-
-		/// Max fraction of pool to sell in single transaction
-		#[pallet::constant]
-		type MaxInRatio: Get<u128>;
-
-		/// Max fraction of pool to buy in single transaction
-		#[pallet::constant]
-		type MaxOutRatio: Get<u128>;
+		/// Circuit breaker which can halt trading and changes of liquidity in pools
+		type CircuitBreaker: CircuitBreaker<Self::AccountId>;
 
 		/// The block number provider
 		type BlockNumberProvider: BlockNumberProvider<BlockNumber = Self::BlockNumber>;
+
+		/// Number of blocks of a window of pool trade statistics
+		#[pallet::constant]
+		type StatsWindow: Get<Self::BlockNumber>;
 	}
 
 	#[pallet::hooks]
@@ -298,6 +292,12 @@ pub mod pallet {
 
 		/// Not more than one fee collector per asset id
 		FeeCollectorWithAssetAlreadyUsed,
+
+		/// Trading is not allowed in current state of the pool
+		TradingNotAllowed,
+
+		/// Change of liquidity is not allowed in current state of the pool
+		LiquidityChangeNotAllowed,
 	}
 
 	#[pallet::event]
@@ -350,6 +350,12 @@ pub mod pallet {
 	pub type FeeCollectorWithAsset<T: Config> =
 		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, AssetId, bool, ValueQuery>;
 
+	/// Statistics of sells and buys executed in a pool.
+	#[pallet::storage]
+	#[pallet::getter(fn pool_trade_stats)]
+	pub type PoolTradeStats<T: Config> =
+		StorageMap<_, Blake2_128Concat, PoolId<T>, PoolStats<T::BlockNumber>, ValueQuery>;
+
 	#[pallet::extra_constants]
 	impl<T: Config> Pallet<T> {
 		pub fn repay_fee() -> (u32, u32) {
@@ -410,18 +416,20 @@ pub mod pallet {
 		) -> DispatchResult {
 			T::CreatePoolOrigin::ensure_origin(origin)?;
 
+			let asset_pair = AssetPair {
+				asset_in: asset_a,
+				asset_out: asset_b,
+			};
+
+			let min_pool_liquidity = T::TradingLimits::pool_limits(&Self::get_pair_id(asset_pair)).min_pool_liquidity;
+
 			ensure!(
-				asset_a_amount >= T::MinPoolLiquidity::get() && asset_b_amount >= T::MinPoolLiquidity::get(),
+				asset_a_amount >= min_pool_liquidity && asset_b_amount >= min_pool_liquidity,
 				Error::<T>::InsufficientLiquidity
 			);
 
 			ensure!(asset_a != asset_b, Error::<T>::CannotCreatePoolWithSameAssets);
 
-			let asset_pair = AssetPair {
-				asset_in: asset_a,
-				asset_out: asset_b,
-			};
-
 			ensure!(!Self::exists(asset_pair), Error::<T>::PoolAlreadyExists);
 
 			ensure!(
@@ -592,6 +600,11 @@ pub mod pallet {
 
 			ensure!(who == pool_data.owner, Error::<T>::NotOwner);
 
+			ensure!(
+				T::CircuitBreaker::pool_state(&pool_id).is_adding_liquidity_allowed(),
+				Error::<T>::LiquidityChangeNotAllowed
+			);
+
 			ensure!(
 				!amount_a.is_zero() || !amount_b.is_zero(),
 				Error::<T>::CannotAddZeroLiquidity
@@ -611,9 +624,17 @@ pub mod pallet {
 				);
 			}
 
+			let reserves_before = Self::pool_reserves(&pool_id, &pool_data);
+
 			T::MultiCurrency::transfer(asset_a, &who, &pool_id, amount_a)?;
 			T::MultiCurrency::transfer(asset_b, &who, &pool_id, amount_b)?;
 
+			T::CircuitBreaker::on_liquidity_change(
+				&pool_id,
+				reserves_before,
+				Self::pool_reserves(&pool_id, &pool_data),
+			);
+
 			Self::deposit_event(Event::LiquidityAdded(pool_id, asset_a, asset_b, amount_a, amount_b));
 
 			Ok(())
@@ -641,6 +662,11 @@ pub mod pallet {
 
 			ensure!(!Self::is_pool_running(&pool_data), Error::<T>::SaleNotEnded);
 
+			ensure!(
+				T::CircuitBreaker::pool_state(&pool_id).is_removing_liquidity_allowed(),
+				Error::<T>::LiquidityChangeNotAllowed
+			);
+
 			let (asset_a, asset_b) = pool_data.assets;
 
 			let amount_a = T::MultiCurrency::free_balance(asset_a, &pool_id);
@@ -655,6 +681,7 @@ pub mod pallet {
 
 			<FeeCollectorWithAsset<T>>::remove(pool_data.fee_collector, pool_data.assets.0);
 			<PoolData<T>>::remove(&pool_id);
+			<PoolTradeStats<T>>::remove(&pool_id);
 
 			Self::deposit_event(Event::LiquidityRemoved(pool_id, asset_a, asset_b, amount_a, amount_b));
 
@@ -833,6 +860,8 @@ impl<T: Config> Pallet<T> {
 		let pool_account = Self::get_pair_id(transfer.assets);
 		let pool = <PoolData<T>>::try_get(&pool_account).map_err(|_| Error::<T>::PoolNotFound)?;
 
+		let reserves_before = Self::pool_reserves(&pool_account, &pool);
+
 		// Transfer assets between pool and user
 		T::MultiCurrency::transfer(
 			transfer.assets.asset_in,
@@ -861,9 +890,69 @@ impl<T: Config> Pallet<T> {
 		let collected_fee_total = Self::collected_fees(&pool) + fee_amount;
 		T::MultiCurrency::set_lock(COLLECTOR_LOCK_ID, fee_asset, &pool.fee_collector, collected_fee_total)?;
 
+		T::CircuitBreaker::on_reserves_change(
+			&pool_account,
+			reserves_before,
+			Self::pool_reserves(&pool_account, &pool),
+		);
+
+		Self::update_pool_stats(&pool_account, &pool, transfer);
+
 		Ok(())
 	}
 
+	/// Add a trade to statistics of the pool.
+	///
+	/// Volume is counted as amounts paid (fee included) and received (fee deducted) by the trader.
+	fn update_pool_stats(
+		pool_id: &PoolId<T>,
+		pool: &Pool<T::AccountId, T::BlockNumber>,
+		transfer: &AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>,
+	) {
+		let (fee_asset, fee_amount) = transfer.fee;
+		let (amount_in, fee_in) = if fee_asset == transfer.assets.asset_in {
+			(transfer.amount.saturating_add(fee_amount), fee_amount)
+		} else {
+			(transfer.amount, 0)
+		};
+		let fee_out = fee_amount - fee_in;
+
+		let (volume, fees) = if pool.assets.0 == transfer.assets.asset_in {
+			((amount_in, transfer.amount_out), (fee_in, fee_out))
+		} else {
+			((transfer.amount_out, amount_in), (fee_out, fee_in))
+		};
+
+		<PoolTradeStats<T>>::mutate(pool_id, |stats| {
+			stats.add_trade(
+				T::BlockNumberProvider::current_block_number(),
+				T::StatsWindow::get(),
+				volume,
+				fees,
+			)
+		});
+	}
+
+	/// Return trade statistics of a pool as of current block, or `None` if the pool does not exist.
+	pub fn get_pool_stats(pool_id: PoolId<T>) -> Option<PoolStats<T::BlockNumber>> {
+		if !<PoolData<T>>::contains_key(&pool_id) {
+			return None;
+		}
+
+		let mut stats = Self::pool_trade_stats(&pool_id);
+		stats.roll_window(T::BlockNumberProvider::current_block_number(), T::StatsWindow::get());
+
+		Some(stats)
+	}
+
+	/// Reserves of the pool in the order of its assets.
+	fn pool_reserves(pool_id: &PoolId<T>, pool: &Pool<T::AccountId, T::BlockNumber>) -> (Balance, Balance) {
+		(
+			T::MultiCurrency::free_balance(pool.assets.0, pool_id),
+			T::MultiCurrency::free_balance(pool.assets.1, pool_id),
+		)
+	}
+
 	/// determines fee rate and applies it to the amount
 	fn calculate_fees(
 		pool: &Pool<T::AccountId, T::BlockNumber>,
@@ -953,16 +1042,25 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, BalanceOf<T>> for Pallet<T
 
 		let pool_id = Self::get_pair_id(assets);
 		let pool_data = <PoolData<T>>::try_get(&pool_id).map_err(|_| Error::<T>::PoolNotFound)?;
+		let limits = T::TradingLimits::pool_limits(&pool_id);
 
 		ensure!(Self::is_pool_running(&pool_data), Error::<T>::SaleIsNotRunning);
 
+		ensure!(
+			T::CircuitBreaker::pool_state(&pool_id).is_trading_allowed(),
+			Error::<T>::TradingNotAllowed
+		);
+
 		let now = T::BlockNumberProvider::current_block_number();
 		let (weight_in, weight_out) = Self::get_sorted_weight(assets.asset_in, now, &pool_data)?;
 		let asset_in_reserve = T::MultiCurrency::free_balance(assets.asset_in, &pool_id);
 		let asset_out_reserve = T::MultiCurrency::free_balance(assets.asset_out, &pool_id);
 
 		ensure!(
-			amount <= asset_in_reserve.checked_div(MAX_IN_RATIO).ok_or(Error::<T>::Overflow)?,
+			amount
+				<= asset_in_reserve
+					.checked_div(limits.max_in_ratio)
+					.ok_or(Error::<T>::Overflow)?,
 			Error::<T>::MaxInRatioExceeded
 		);
 
@@ -989,7 +1087,7 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, BalanceOf<T>> for Pallet<T
 			ensure!(
 				amount_out
 					<= asset_out_reserve
-						.checked_div(MAX_OUT_RATIO)
+						.checked_div(limits.max_out_ratio)
 						.ok_or(Error::<T>::Overflow)?,
 				Error::<T>::MaxOutRatioExceeded
 			);
@@ -1029,7 +1127,7 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, BalanceOf<T>> for Pallet<T
 			ensure!(
 				calculated_out
 					<= asset_out_reserve
-						.checked_div(MAX_OUT_RATIO)
+						.checked_div(limits.max_out_ratio)
 						.ok_or(Error::<T>::Overflow)?,
 				Error::<T>::MaxOutRatioExceeded
 			);
@@ -1079,9 +1177,15 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, BalanceOf<T>> for Pallet<T
 
 		let pool_id = Self::get_pair_id(assets);
 		let pool_data = <PoolData<T>>::try_get(&pool_id).map_err(|_| Error::<T>::PoolNotFound)?;
+		let limits = T::TradingLimits::pool_limits(&pool_id);
 
 		ensure!(Self::is_pool_running(&pool_data), Error::<T>::SaleIsNotRunning);
 
+		ensure!(
+			T::CircuitBreaker::pool_state(&pool_id).is_trading_allowed(),
+			Error::<T>::TradingNotAllowed
+		);
+
 		let now = T::BlockNumberProvider::current_block_number();
 		let (weight_in, weight_out) = Self::get_sorted_weight(assets.asset_in, now, &pool_data)?;
 		let asset_in_reserve = T::MultiCurrency::free_balance(assets.asset_in, &pool_id);
@@ -1090,7 +1194,7 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, BalanceOf<T>> for Pallet<T
 		ensure!(
 			amount
 				<= asset_out_reserve
-					.checked_div(MAX_OUT_RATIO)
+					.checked_div(limits.max_out_ratio)
 					.ok_or(Error::<T>::Overflow)?,
 			Error::<T>::MaxOutRatioExceeded
 		);
@@ -1117,7 +1221,10 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, BalanceOf<T>> for Pallet<T
 			.map_err(|_| Error::<T>::Overflow)?;
 
 			ensure!(
-				calculated_in <= asset_in_reserve.checked_div(MAX_IN_RATIO).ok_or(Error::<T>::Overflow)?,
+				calculated_in
+					<= asset_in_reserve
+						.checked_div(limits.max_in_ratio)
+						.ok_or(Error::<T>::Overflow)?,
 				Error::<T>::MaxInRatioExceeded
 			);
 
@@ -1152,7 +1259,10 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, BalanceOf<T>> for Pallet<T
 			let calculated_in_without_fee = calculated_in.checked_sub(fee).ok_or(Error::<T>::Overflow)?;
 
 			ensure!(
-				calculated_in <= asset_in_reserve.checked_div(MAX_IN_RATIO).ok_or(Error::<T>::Overflow)?,
+				calculated_in
+					<= asset_in_reserve
+						.checked_div(limits.max_in_ratio)
+						.ok_or(Error::<T>::Overflow)?,
 				Error::<T>::MaxInRatioExceeded
 			);
 
@@ -1186,19 +1296,19 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, BalanceOf<T>> for Pallet<T
 	}
 
 	fn get_min_trading_limit() -> Balance {
-		T::MinTradingLimit::get()
+		T::TradingLimits::default_limits().min_trading_limit
 	}
 
 	fn get_min_pool_liquidity() -> Balance {
-		T::MinPoolLiquidity::get()
+		T::TradingLimits::default_limits().min_pool_liquidity
 	}
 
 	fn get_max_in_ratio() -> u128 {
-		T::MaxInRatio::get()
+		T::TradingLimits::default_limits().max_in_ratio
 	}
 
 	fn get_max_out_ratio() -> u128 {
-		T::MaxOutRatio::get()
+		T::TradingLimits::default_limits().max_out_ratio
 	}
 
 	fn get_fee(pool_account_id: &T::AccountId) -> (u32, u32) {
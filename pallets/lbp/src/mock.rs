@@ -10,11 +10,14 @@ use orml_traits::parameter_type_with_key;
 use primitives::constants::chain::{
 	AssetId, Balance, CORE_ASSET_ID, MAX_IN_RATIO, MAX_OUT_RATIO, MIN_POOL_LIQUIDITY, MIN_TRADING_LIMIT,
 };
+use primitives::traits::{CircuitBreaker, ConstantTradingLimits};
+use primitives::PoolState;
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
 };
+use std::cell::RefCell;
 use std::collections::BTreeMap;
 
 pub type Amount = i128;
@@ -155,6 +158,36 @@ parameter_types! {
 	pub const MinPoolLiquidity: Balance = MIN_POOL_LIQUIDITY;
 	pub const MaxInRatio: u128 = MAX_IN_RATIO;
 	pub const MaxOutRatio: u128 = MAX_OUT_RATIO;
+	pub const StatsWindow: BlockNumber = 10;
+}
+
+thread_local! {
+	static POOL_STATES: RefCell<Vec<(AccountId, PoolState)>> = RefCell::new(vec![]);
+}
+
+/// Circuit breaker with states of pools set by `set_pool_state`.
+pub struct MockCircuitBreaker;
+
+impl CircuitBreaker<AccountId> for MockCircuitBreaker {
+	fn pool_state(pool_account: &AccountId) -> PoolState {
+		POOL_STATES
+			.with(|v| {
+				v.borrow()
+					.iter()
+					.rev()
+					.find(|(account, _)| account == pool_account)
+					.map(|(_, state)| *state)
+			})
+			.unwrap_or_default()
+	}
+
+	fn on_reserves_change(_: &AccountId, _: (Balance, Balance), _: (Balance, Balance)) {}
+
+	fn on_liquidity_change(_: &AccountId, _: (Balance, Balance), _: (Balance, Balance)) {}
+}
+
+pub fn set_pool_state(pool_account: AccountId, state: PoolState) {
+	POOL_STATES.with(|v| v.borrow_mut().push((pool_account, state)));
 }
 
 pub struct MultiLockedBalance();
@@ -189,11 +222,10 @@ impl Config for Test {
 	type LBPWeightFunction = lbp::LBPWeightFunction;
 	type AssetPairAccountId = AssetPairAccountIdTest;
 	type WeightInfo = ();
-	type MinTradingLimit = MinTradingLimit;
-	type MinPoolLiquidity = MinPoolLiquidity;
-	type MaxInRatio = MaxInRatio;
-	type MaxOutRatio = MaxOutRatio;
+	type TradingLimits = ConstantTradingLimits<MinTradingLimit, MinPoolLiquidity, MaxInRatio, MaxOutRatio>;
+	type CircuitBreaker = MockCircuitBreaker;
 	type BlockNumberProvider = System;
+	type StatsWindow = StatsWindow;
 }
 
 pub struct ExtBuilder {
@@ -22,9 +22,10 @@ use crate::mock::{
 	INITIAL_BALANCE, KUSD_BSX_POOL_ID, SALE_END, SALE_START, SAMPLE_AMM_TRANSFER, SAMPLE_POOL_DATA,
 };
 pub use crate::mock::{
-	set_block_number, Currency, Event as TestEvent, ExtBuilder, LBPPallet, Origin, Test, ALICE, BOB, BSX, CHARLIE, ETH,
-	HDX, KUSD,
+	set_block_number, set_pool_state, Currency, Event as TestEvent, ExtBuilder, LBPPallet, MaxInRatio, MaxOutRatio,
+	MinPoolLiquidity, MinTradingLimit, Origin, Test, ALICE, BOB, BSX, CHARLIE, ETH, HDX, KUSD,
 };
+use frame_support::traits::Get;
 use frame_support::{assert_err, assert_noop, assert_ok};
 use hydradx_traits::{AMMTransfer, LockedBalance};
 use sp_runtime::traits::BadOrigin;
@@ -33,6 +34,7 @@ use sp_std::convert::TryInto;
 use primitives::{
 	asset::AssetPair,
 	constants::chain::{MAX_IN_RATIO, MAX_OUT_RATIO},
+	PoolState, PoolStats, TradeStats,
 };
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
@@ -2270,6 +2272,50 @@ fn update_pool_data_after_sale_should_not_work() {
 	});
 }
 
+#[test]
+fn operations_should_respect_pool_state() {
+	predefined_test_ext().execute_with(|| {
+		//start sale
+		set_block_number::<Test>(11);
+
+		set_pool_state(KUSD_BSX_POOL_ID, PoolState::TradingPaused);
+
+		assert_noop!(
+			LBPPallet::sell(Origin::signed(BOB), KUSD, BSX, 10_000_000_u128, 2_000_u128),
+			Error::<Test>::TradingNotAllowed
+		);
+		assert_noop!(
+			LBPPallet::buy(Origin::signed(BOB), BSX, KUSD, 10_000_000_u128, 2_000_000_000_u128),
+			Error::<Test>::TradingNotAllowed
+		);
+		assert_ok!(LBPPallet::add_liquidity(
+			Origin::signed(ALICE),
+			(KUSD, 10_000_000),
+			(BSX, 20_000_000),
+		));
+
+		set_pool_state(KUSD_BSX_POOL_ID, PoolState::LiquidityOnly);
+
+		assert_noop!(
+			LBPPallet::add_liquidity(Origin::signed(ALICE), (KUSD, 10_000_000), (BSX, 20_000_000)),
+			Error::<Test>::LiquidityChangeNotAllowed
+		);
+
+		set_pool_state(KUSD_BSX_POOL_ID, PoolState::Frozen);
+
+		run_to_sale_end();
+
+		assert_noop!(
+			LBPPallet::remove_liquidity(Origin::signed(ALICE), KUSD_BSX_POOL_ID),
+			Error::<Test>::LiquidityChangeNotAllowed
+		);
+
+		set_pool_state(KUSD_BSX_POOL_ID, PoolState::LiquidityOnly);
+
+		assert_ok!(LBPPallet::remove_liquidity(Origin::signed(ALICE), KUSD_BSX_POOL_ID));
+	});
+}
+
 #[test]
 fn sell_should_work() {
 	predefined_test_ext().execute_with(|| {
@@ -2380,6 +2426,66 @@ fn sell_should_work() {
 	});
 }
 
+#[test]
+fn pool_stats_should_be_updated_by_trades() {
+	predefined_test_ext().execute_with(|| {
+		set_block_number::<Test>(11);
+
+		assert_ok!(LBPPallet::sell(
+			Origin::signed(BOB),
+			KUSD,
+			BSX,
+			10_000_000_u128,
+			2_000_u128
+		));
+
+		let trade = TradeStats {
+			volume: (10_000_000, 5_605_137),
+			fees: (20_000, 0),
+			trade_count: 1,
+		};
+
+		assert_eq!(
+			LBPPallet::get_pool_stats(KUSD_BSX_POOL_ID),
+			Some(PoolStats {
+				total: trade,
+				window_start: 10,
+				window: trade,
+				previous_window: TradeStats::default(),
+			})
+		);
+
+		set_block_number::<Test>(25);
+
+		assert_eq!(
+			LBPPallet::get_pool_stats(KUSD_BSX_POOL_ID),
+			Some(PoolStats {
+				total: trade,
+				window_start: 20,
+				window: TradeStats::default(),
+				previous_window: trade,
+			})
+		);
+
+		set_block_number::<Test>(41);
+
+		assert_eq!(
+			LBPPallet::get_pool_stats(KUSD_BSX_POOL_ID),
+			Some(PoolStats {
+				total: trade,
+				window_start: 40,
+				window: TradeStats::default(),
+				previous_window: TradeStats::default(),
+			})
+		);
+
+		assert_ok!(LBPPallet::remove_liquidity(Origin::signed(ALICE), KUSD_BSX_POOL_ID));
+
+		assert!(!<PoolTradeStats<Test>>::contains_key(KUSD_BSX_POOL_ID));
+		assert_eq!(LBPPallet::get_pool_stats(KUSD_BSX_POOL_ID), None);
+	});
+}
+
 #[test]
 fn zero_fee_should_work() {
 	new_test_ext().execute_with(|| {
@@ -2511,16 +2617,10 @@ fn amm_trait_should_work() {
 			t_buy
 		);
 
-		assert_eq!(
-			LBPPallet::get_min_trading_limit(),
-			<Test as Config>::MinTradingLimit::get()
-		);
-		assert_eq!(
-			LBPPallet::get_min_pool_liquidity(),
-			<Test as Config>::MinPoolLiquidity::get()
-		);
-		assert_eq!(LBPPallet::get_max_in_ratio(), <Test as Config>::MaxInRatio::get());
-		assert_eq!(LBPPallet::get_max_out_ratio(), <Test as Config>::MaxOutRatio::get());
+		assert_eq!(LBPPallet::get_min_trading_limit(), MinTradingLimit::get());
+		assert_eq!(LBPPallet::get_min_pool_liquidity(), MinPoolLiquidity::get());
+		assert_eq!(LBPPallet::get_max_in_ratio(), MaxInRatio::get());
+		assert_eq!(LBPPallet::get_max_out_ratio(), MaxOutRatio::get());
 
 		assert_ok!(LBPPallet::create_pool(
 			Origin::root(),
@@ -74,7 +74,7 @@ fn initialize_pool<T: Config>(
 	amount: Balance,
 	price: Price,
 ) -> dispatch::DispatchResult {
-	xykpool::Pallet::<T>::create_pool(RawOrigin::Signed(caller).into(), asset_a, asset_b, amount, price)?;
+	xykpool::Pallet::<T>::create_pool(RawOrigin::Signed(caller).into(), asset_a, asset_b, amount, price, None)?;
 
 	Ok(())
 }
@@ -110,6 +110,8 @@ fn xyk_add_liquidity<T: Config>(
 		assets.asset_out,
 		amount_a,
 		amount_b_max,
+		0,
+		None,
 	)?;
 
 	Ok(())
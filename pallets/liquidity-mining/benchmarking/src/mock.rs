@@ -32,6 +32,7 @@ use primitives::{
 		currency::NATIVE_EXISTENTIAL_DEPOSIT,
 	},
 	nft::{ClassType, NftPermissions},
+	traits::ConstantTradingLimits,
 	Amount, AssetId, Balance, ReserveIdentifier,
 };
 use sp_core::H256;
@@ -127,6 +128,7 @@ parameter_types! {
 	pub const MinPlannedYieldingPeriods: BlockNumber = 100;
 	pub const MinTotalFarmRewards: Balance = 1_000_000;
 	pub const NftClass: primitives::ClassId = LIQ_MINING_NFT_CLASS;
+	pub const MaxFarmsPerAmmPool: u32 = 2;
 }
 
 impl pallet_liquidity_mining::Config for Test {
@@ -140,6 +142,7 @@ impl pallet_liquidity_mining::Config for Test {
 	type MinTotalFarmRewards = MinTotalFarmRewards;
 	type BlockNumberProvider = MockBlockNumberProvider;
 	type NftClass = NftClass;
+	type MaxFarmsPerAmmPool = MaxFarmsPerAmmPool;
 	type AMM = XYK;
 }
 
@@ -255,22 +258,37 @@ parameter_types! {
 	pub const MinPoolLiquidity: Balance = MIN_POOL_LIQUIDITY;
 	pub const MaxInRatio: u128 = MAX_IN_RATIO;
 	pub const MaxOutRatio: u128 = MAX_OUT_RATIO;
+	pub const MaxFeeTiers: u32 = 5;
+	pub const MaxPriceObservations: u32 = 5;
+	pub const StatsWindow: u64 = 10;
+	pub const MaxBatchSize: u32 = 5;
+	pub const ProtocolFeeReceiver: AccountId = 99;
+	pub const PoolDeposit: Balance = 0;
 }
 
 impl pallet_xyk::Config for Test {
 	type Event = Event;
 	type AssetRegistry = AssetRegistry;
+	type AssetMetadata = ();
 	type AssetPairAccountId = AssetPairAccountIdTest;
 	type Currency = Currency;
 	type NativeAssetId = BSXAssetId;
 	type WeightInfo = ();
 	type GetExchangeFee = ExchangeFeeRate;
-	type MinTradingLimit = MinTradingLimit;
-	type MinPoolLiquidity = MinPoolLiquidity;
-	type MaxInRatio = MaxInRatio;
-	type MaxOutRatio = MaxOutRatio;
+	type MaxFeeTiers = MaxFeeTiers;
+	type MaxPriceObservations = MaxPriceObservations;
+	type StatsWindow = StatsWindow;
+	type AuthorityOrigin = frame_system::EnsureRoot<AccountId>;
+	type ProtocolFeeReceiver = ProtocolFeeReceiver;
+	type TradingLimits = ConstantTradingLimits<MinTradingLimit, MinPoolLiquidity, MaxInRatio, MaxOutRatio>;
+	type CircuitBreaker = ();
 	type CanCreatePool = pallet_xyk::AllowAllPools;
+	type PoolDeposit = PoolDeposit;
 	type AMMHandler = ();
+	type LiquidityChangedHandler = ();
+	type PoolDestroyedHandler = ();
+	type FlashSwapHandler = ();
+	type MaxBatchSize = MaxBatchSize;
 }
 
 impl Default for ExtBuilder {
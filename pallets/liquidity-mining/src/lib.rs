@@ -61,6 +61,7 @@ pub mod weights;
 pub use pallet::*;
 
 use codec::{Decode, Encode};
+use frame_support::storage::{with_transaction, TransactionOutcome};
 use frame_support::{
 	ensure,
 	sp_runtime::traits::{BlockNumberProvider, One, Zero},
@@ -74,13 +75,14 @@ use frame_system::ensure_signed;
 use hydra_dx_math::liquidity_mining as math;
 use hydradx_traits::AMM;
 use orml_traits::MultiCurrency;
-use primitives::{asset::AssetPair, nft::ClassType, Balance};
+use primitives::{asset::AssetPair, nft::ClassType, traits::OnPoolDestroyedHandler, AssetId, Balance};
 use scale_info::TypeInfo;
 use sp_arithmetic::{
 	traits::{CheckedDiv, CheckedSub},
 	FixedU128, Permill,
 };
 use sp_std::convert::{From, Into, TryInto};
+use weights::WeightInfo;
 
 //This value is result of: u128::from_le_bytes([255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 0, 0, 0, 0])
 //This is necessary because first 4 bytes of NftInstanceIdOf (u128) is reserved to encode liq_pool_id (u32) into NftInstanceIdOf.
@@ -241,7 +243,7 @@ pub mod pallet {
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
 		fn on_runtime_upgrade() -> frame_support::weights::Weight {
-			migration::init_nft_class::<T>()
+			migration::init_nft_class::<T>().saturating_add(migration::index_amm_pool_farms::<T>())
 		}
 
 		fn integrity_test() {
@@ -291,6 +293,9 @@ pub mod pallet {
 		/// NFT class id for liq. mining deposit nfts. Has to be within the range of reserved NFT class IDs.
 		type NftClass: Get<primitives::ClassId>;
 
+		/// Max number of farms in which liq. pool of the same AMM pool can exist.
+		type MaxFarmsPerAmmPool: Get<u32>;
+
 		/// Weight information for extrinsic in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -384,6 +389,9 @@ pub mod pallet {
 
 		/// Farm's `incentivized_asset` is missing in provided asset pair.
 		MissingIncentivizedAsset,
+
+		/// Max number of farms with liq. pool of the AMM pool was reached.
+		MaxFarmsPerAmmPoolReached,
 	}
 
 	#[pallet::event]
@@ -451,6 +459,13 @@ pub mod pallet {
 			asset_pair: AssetPair,
 		},
 
+		/// Liquidity mining for asset pair was canceled because its AMM pool was destroyed.
+		LiquidityMiningCanceledForDestroyedAmm {
+			farm_id: GlobalPoolId,
+			liq_pool_farm_id: PoolId,
+			asset_pair: AssetPair,
+		},
+
 		/// Liquidity mining for asset pair was resumed.
 		LiquidityMiningResumed {
 			farm_id: GlobalPoolId,
@@ -518,7 +533,7 @@ pub mod pallet {
 	/// Liquidity pool yield farm details.
 	#[pallet::storage]
 	#[pallet::getter(fn liquidity_pool)]
-	type LiquidityPoolData<T: Config> = StorageDoubleMap<
+	pub(crate) type LiquidityPoolData<T: Config> = StorageDoubleMap<
 		_,
 		Twox64Concat,
 		GlobalPoolId,
@@ -539,6 +554,12 @@ pub mod pallet {
 	type LiquidityPoolMetadata<T: Config> =
 		StorageMap<_, Twox64Concat, PoolId, (AssetPair, u64, GlobalPoolId), OptionQuery>;
 
+	/// Farms with liq. pool of an AMM pool, used to cancel liq. mining when the AMM pool is destroyed.
+	#[pallet::storage]
+	#[pallet::getter(fn amm_pool_farms)]
+	pub(crate) type AmmPoolFarms<T: Config> =
+		StorageMap<_, Twox64Concat, AccountIdOf<T>, BoundedVec<GlobalPoolId, T::MaxFarmsPerAmmPool>, ValueQuery>;
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Create new liquidity mining program with proved parameters.
@@ -765,6 +786,9 @@ pub mod pallet {
 					Error::<T>::LiquidityPoolAlreadyExists
 				);
 
+				<AmmPoolFarms<T>>::try_mutate(&amm_pool_id, |farms| farms.try_push(global_pool.id))
+					.map_err(|_| Error::<T>::MaxFarmsPerAmmPoolReached)?;
+
 				// update  global pool accumulated RPZ
 				let now_period = Self::get_now_period(global_pool.blocks_per_period)?;
 				if !global_pool.total_shares_z.is_zero() && global_pool.updated_at != now_period {
@@ -896,17 +920,7 @@ pub mod pallet {
 
 					ensure!(global_pool.owner == who, Error::<T>::Forbidden);
 
-					let now_period = Self::get_now_period(global_pool.blocks_per_period)?;
-					Self::maybe_update_pools(global_pool, liq_pool, now_period)?;
-
-					global_pool.total_shares_z = global_pool
-						.total_shares_z
-						.checked_sub(liq_pool.stake_in_global_pool)
-						.ok_or(Error::<T>::Overflow)?;
-
-					liq_pool.canceled = true;
-					liq_pool.stake_in_global_pool = 0;
-					liq_pool.multiplier = 0.into();
+					Self::do_cancel_liquidity_pool(global_pool, liq_pool)?;
 
 					Self::deposit_event(Event::LiquidityMiningCanceled {
 						farm_id,
@@ -1062,6 +1076,15 @@ pub mod pallet {
 							}
 						};
 
+						<AmmPoolFarms<T>>::mutate_exists(&amm_account, |maybe_farms| {
+							if let Some(farms) = maybe_farms {
+								farms.retain(|id| *id != farm_id);
+								if farms.is_empty() {
+									*maybe_farms = None;
+								}
+							}
+						});
+
 						Ok(().into())
 					})?;
 
@@ -1773,4 +1796,89 @@ impl<T: Config> Pallet<T> {
 		}
 		Ok(())
 	}
+	/// Stop incentivization of `liq_pool` from `global_pool`.
+	///
+	/// Pools are updated before the cancellation so rewards accumulated until now are kept.
+	fn do_cancel_liquidity_pool(
+		global_pool: &mut GlobalPool<T>,
+		liq_pool: &mut LiquidityPoolYieldFarm<T>,
+	) -> DispatchResult {
+		let now_period = Self::get_now_period(global_pool.blocks_per_period)?;
+		Self::maybe_update_pools(global_pool, liq_pool, now_period)?;
+
+		global_pool.total_shares_z = global_pool
+			.total_shares_z
+			.checked_sub(liq_pool.stake_in_global_pool)
+			.ok_or(Error::<T>::Overflow)?;
+
+		liq_pool.canceled = true;
+		liq_pool.stake_in_global_pool = 0;
+		liq_pool.multiplier = 0.into();
+
+		Ok(())
+	}
+
+	/// Cancel liq. mining of liq. pool of destroyed AMM pool `amm_account` in farm `farm_id`.
+	///
+	/// Liq. pool which is already canceled is left untouched.
+	fn cancel_destroyed_amm_liquidity_pool(
+		farm_id: GlobalPoolId,
+		amm_account: &AccountIdOf<T>,
+		asset_pair: AssetPair,
+	) -> DispatchResult {
+		<LiquidityPoolData<T>>::try_mutate(farm_id, amm_account, |maybe_liq_pool| -> DispatchResult {
+			let liq_pool = maybe_liq_pool.as_mut().ok_or(Error::<T>::LiquidityPoolNotFound)?;
+
+			if liq_pool.canceled {
+				return Ok(());
+			}
+
+			<GlobalPoolData<T>>::try_mutate(farm_id, |maybe_global_pool| -> DispatchResult {
+				let global_pool = maybe_global_pool.as_mut().ok_or(Error::<T>::FarmNotFound)?;
+
+				Self::do_cancel_liquidity_pool(global_pool, liq_pool)?;
+
+				Self::deposit_event(Event::LiquidityMiningCanceledForDestroyedAmm {
+					farm_id,
+					liq_pool_farm_id: liq_pool.id,
+					asset_pair,
+				});
+
+				Ok(())
+			})
+		})
+	}
+}
+
+/// Liq. mining of destroyed AMM pool is canceled in all farms, so the farms stop rewarding it
+/// and users can only withdraw their shares.
+impl<T: Config> OnPoolDestroyedHandler<AccountIdOf<T>, AssetId> for Pallet<T> {
+	fn on_pool_destroyed(amm_account: &AccountIdOf<T>, asset_a: AssetId, asset_b: AssetId) -> Weight {
+		let asset_pair = AssetPair {
+			asset_in: asset_a,
+			asset_out: asset_b,
+		};
+
+		let farms = Self::amm_pool_farms(amm_account);
+
+		for farm_id in farms.iter() {
+			// Failure to cancel one liq. pool must not prevent cancellation of the others.
+			let _ = with_transaction(|| {
+				match Self::cancel_destroyed_amm_liquidity_pool(*farm_id, amm_account, asset_pair) {
+					Ok(()) => TransactionOutcome::Commit(Ok(())),
+					Err(e) => TransactionOutcome::Rollback(Err(e)),
+				}
+			});
+		}
+
+		<T as Config>::WeightInfo::cancel_liquidity_pool()
+			.saturating_mul(farms.len() as Weight)
+			.saturating_add(T::DbWeight::get().reads(1))
+	}
+
+	fn on_pool_destroyed_weight() -> Weight {
+		<T as Config>::WeightInfo::cancel_liquidity_pool()
+			.saturating_mul(T::MaxFarmsPerAmmPool::get() as Weight)
+			.saturating_add(T::DbWeight::get().reads(1))
+	}
 }
@@ -42,11 +42,35 @@ pub fn init_nft_class<T: Config>() -> frame_support::weights::Weight {
 	}
 }
 
+/// Index farms of liq. pools by their AMM pools.
+pub fn index_amm_pool_farms<T: Config>() -> frame_support::weights::Weight {
+	let version = StorageVersion::get::<Pallet<T>>();
+
+	if version == 1 {
+		let mut reads: u64 = 1;
+		let mut writes: u64 = 1;
+
+		for (farm_id, amm_account, _) in <LiquidityPoolData<T>>::iter() {
+			// AMM pools in more than `MaxFarmsPerAmmPool` farms keep the extra farms unindexed.
+			let _ = <AmmPoolFarms<T>>::try_mutate(&amm_account, |farms| farms.try_push(farm_id));
+			reads += 2;
+			writes += 1;
+		}
+
+		StorageVersion::new(2).put::<Pallet<T>>();
+
+		T::DbWeight::get().reads_writes(reads, writes)
+	} else {
+		0
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::mock::Test;
+	use crate::mock::{ExtBuilder, LiquidityMining, Test, BSX_FARM, BSX_TKN1_AMM, BSX_TKN2_AMM, GC_FARM, KSM_FARM};
 	use frame_support::assert_noop;
+	use sp_arithmetic::FixedU128;
 
 	#[test]
 	fn init_nft_class_migration_should_work() {
@@ -87,4 +111,31 @@ mod tests {
 			init_nft_class::<Test>();
 		});
 	}
+
+	#[test]
+	fn index_amm_pool_farms_migration_should_work() {
+		ExtBuilder::default().build().execute_with(|| {
+			let liq_pool = |id| LiquidityPoolYieldFarm::<Test>::new(id, 0, None, FixedU128::from(1));
+
+			<LiquidityPoolData<Test>>::insert(BSX_FARM, BSX_TKN1_AMM, liq_pool(5));
+			<LiquidityPoolData<Test>>::insert(GC_FARM, BSX_TKN1_AMM, liq_pool(6));
+			<LiquidityPoolData<Test>>::insert(KSM_FARM, BSX_TKN2_AMM, liq_pool(7));
+
+			StorageVersion::new(1).put::<Pallet<Test>>();
+
+			index_amm_pool_farms::<Test>();
+
+			let mut bsx_tkn1_farms = LiquidityMining::amm_pool_farms(BSX_TKN1_AMM).into_inner();
+			bsx_tkn1_farms.sort_unstable();
+
+			assert_eq!(bsx_tkn1_farms, vec![BSX_FARM, GC_FARM]);
+			assert_eq!(
+				LiquidityMining::amm_pool_farms(BSX_TKN2_AMM).into_inner(),
+				vec![KSM_FARM]
+			);
+			assert_eq!(StorageVersion::get::<Pallet<Test>>(), 2);
+
+			assert_eq!(index_amm_pool_farms::<Test>(), 0);
+		});
+	}
 }
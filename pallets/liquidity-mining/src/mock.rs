@@ -253,6 +253,7 @@ parameter_types! {
 	pub const MinPlannedYieldingPeriods: BlockNumber = 100;
 	pub const MinTotalFarmRewards: Balance = 1_000_000;
 	pub const NftClass: primitives::ClassId = LIQ_MINING_NFT_CLASS;
+	pub const MaxFarmsPerAmmPool: u32 = 2;
 }
 
 impl Config for Test {
@@ -266,6 +267,7 @@ impl Config for Test {
 	type MinTotalFarmRewards = MinTotalFarmRewards;
 	type BlockNumberProvider = MockBlockNumberProvider;
 	type NftClass = NftClass;
+	type MaxFarmsPerAmmPool = MaxFarmsPerAmmPool;
 	type AMM = Amm;
 }
 
@@ -197,6 +197,7 @@ pub mod claim_rewards;
 pub mod create_farm;
 pub mod deposit_shares;
 pub mod destroy_farm;
+pub mod pool_destroyed;
 pub mod remove_liquidity_pool;
 pub mod resume_liquidity_pool;
 pub mod test_ext;
@@ -0,0 +1,154 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2021  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use test_ext::*;
+
+const BSX_TKN1_ASSETS: AssetPair = AssetPair {
+	asset_in: BSX,
+	asset_out: TKN1,
+};
+
+#[test]
+fn on_pool_destroyed_should_cancel_liquidity_mining_in_all_farms() {
+	predefined_test_ext_with_deposits().execute_with(|| {
+		assert_ok!(LiquidityMining::add_liquidity_pool(
+			Origin::signed(ALICE),
+			ALICE_FARM,
+			BSX_TKN1_ASSETS,
+			FixedU128::from(5_u128),
+			None
+		));
+
+		assert_eq!(
+			LiquidityMining::amm_pool_farms(BSX_TKN1_AMM).into_inner(),
+			vec![GC_FARM, ALICE_FARM]
+		);
+
+		let liq_pool = LiquidityMining::liquidity_pool(GC_FARM, BSX_TKN1_AMM).unwrap();
+		let global_pool = LiquidityMining::global_pool(GC_FARM).unwrap();
+		let bsx_tkn2_liq_pool = LiquidityMining::liquidity_pool(GC_FARM, BSX_TKN2_AMM).unwrap();
+
+		LiquidityMining::on_pool_destroyed(&BSX_TKN1_AMM, BSX, TKN1);
+
+		assert_eq!(
+			LiquidityMining::liquidity_pool(GC_FARM, BSX_TKN1_AMM).unwrap(),
+			LiquidityPoolYieldFarm {
+				stake_in_global_pool: 0,
+				canceled: true,
+				multiplier: 0.into(),
+				..liq_pool
+			}
+		);
+		assert!(
+			LiquidityMining::liquidity_pool(ALICE_FARM, BSX_TKN1_AMM)
+				.unwrap()
+				.canceled
+		);
+
+		assert_eq!(
+			LiquidityMining::global_pool(GC_FARM).unwrap().total_shares_z,
+			global_pool.total_shares_z - liq_pool.stake_in_global_pool
+		);
+		assert_eq!(
+			LiquidityMining::liquidity_pool(GC_FARM, BSX_TKN2_AMM).unwrap(),
+			bsx_tkn2_liq_pool
+		);
+
+		frame_system::Pallet::<Test>::assert_has_event(mock::Event::LiquidityMining(
+			Event::LiquidityMiningCanceledForDestroyedAmm {
+				farm_id: GC_FARM,
+				liq_pool_farm_id: BSX_TKN1_LIQ_POOL_ID,
+				asset_pair: BSX_TKN1_ASSETS,
+			},
+		));
+	});
+}
+
+#[test]
+fn on_pool_destroyed_should_skip_canceled_liquidity_pool() {
+	predefined_test_ext_with_deposits().execute_with(|| {
+		assert_ok!(LiquidityMining::cancel_liquidity_pool(
+			Origin::signed(GC),
+			GC_FARM,
+			BSX_TKN1_ASSETS
+		));
+
+		let liq_pool = LiquidityMining::liquidity_pool(GC_FARM, BSX_TKN1_AMM).unwrap();
+		let global_pool = LiquidityMining::global_pool(GC_FARM).unwrap();
+
+		LiquidityMining::on_pool_destroyed(&BSX_TKN1_AMM, BSX, TKN1);
+
+		assert_eq!(
+			LiquidityMining::liquidity_pool(GC_FARM, BSX_TKN1_AMM).unwrap(),
+			liq_pool
+		);
+		assert_eq!(LiquidityMining::global_pool(GC_FARM).unwrap(), global_pool);
+	});
+}
+
+#[test]
+fn add_liquidity_pool_should_not_work_when_max_farms_per_amm_pool_is_reached() {
+	predefined_test_ext_with_deposits().execute_with(|| {
+		assert_ok!(LiquidityMining::add_liquidity_pool(
+			Origin::signed(ALICE),
+			ALICE_FARM,
+			BSX_TKN1_ASSETS,
+			FixedU128::from(5_u128),
+			None
+		));
+
+		assert_noop!(
+			LiquidityMining::add_liquidity_pool(
+				Origin::signed(BOB),
+				BOB_FARM,
+				BSX_TKN1_ASSETS,
+				FixedU128::from(5_u128),
+				None
+			),
+			Error::<Test>::MaxFarmsPerAmmPoolReached
+		);
+	});
+}
+
+#[test]
+fn remove_liquidity_pool_should_remove_farm_from_amm_pool_farms() {
+	predefined_test_ext_with_deposits().execute_with(|| {
+		let bsx_tkn2_assets = AssetPair {
+			asset_in: BSX,
+			asset_out: TKN2,
+		};
+
+		assert_ok!(LiquidityMining::cancel_liquidity_pool(
+			Origin::signed(GC),
+			GC_FARM,
+			bsx_tkn2_assets
+		));
+		assert_ok!(LiquidityMining::remove_liquidity_pool(
+			Origin::signed(GC),
+			GC_FARM,
+			bsx_tkn2_assets
+		));
+
+		assert!(LiquidityMining::amm_pool_farms(BSX_TKN2_AMM).is_empty());
+		assert!(!<AmmPoolFarms<Test>>::contains_key(BSX_TKN2_AMM));
+		assert_eq!(
+			LiquidityMining::amm_pool_farms(BSX_TKN1_AMM).into_inner(),
+			vec![GC_FARM]
+		);
+	});
+}
@@ -79,8 +79,8 @@ impl<T: frame_system::Config> WeightInfo for BasiliskWeight<T> {
 	}
 	fn add_liquidity_pool() -> Weight {
 		(36_999_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(4 as Weight))
-			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
 	}
 	fn update_liquidity_pool() -> Weight {
 		(27_472_000 as Weight)
@@ -94,8 +94,8 @@ impl<T: frame_system::Config> WeightInfo for BasiliskWeight<T> {
 	}
 	fn remove_liquidity_pool() -> Weight {
 		(37_571_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(4 as Weight))
-			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
 	}
 	fn deposit_shares() -> Weight {
 		(130_025_000 as Weight)
@@ -138,8 +138,8 @@ impl WeightInfo for () {
 	}
 	fn add_liquidity_pool() -> Weight {
 		(36_999_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
 	}
 	fn update_liquidity_pool() -> Weight {
 		(27_472_000 as Weight)
@@ -153,8 +153,8 @@ impl WeightInfo for () {
 	}
 	fn remove_liquidity_pool() -> Weight {
 		(37_571_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
 	}
 	fn deposit_shares() -> Weight {
 		(130_025_000 as Weight)
@@ -0,0 +1,214 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Route Executor Pallet
+//!
+//! ## Overview
+//!
+//! Route executor pallet provides functionality for executing a series of trades through AMM pools
+//! in a single extrinsic.
+//!
+//! Route is a list of assets `[asset_0, asset_1, ..., asset_n]` where each consecutive pair of assets is traded
+//! in the pool of given AMM implementation. All trades of a route are executed atomically - if any of the trades
+//! fails or the trade limit of the whole route is not met, none of the trades is executed.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+#![allow(clippy::upper_case_acronyms)]
+
+use frame_support::sp_runtime::traits::Zero;
+use frame_support::{dispatch::DispatchResult, ensure, traits::Get, transactional};
+use frame_system::ensure_signed;
+use hydradx_traits::AMM;
+use primitives::{asset::AssetPair, traits::AMMTradeCalculation, AssetId, Balance};
+use sp_std::vec::Vec;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+pub mod weights;
+
+use weights::WeightInfo;
+
+// Re-export pallet items so that they can be accessed from the crate namespace.
+pub use pallet::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::OriginFor;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// AMM implementation the trades of a route are executed in
+		type AMM: AMM<Self::AccountId, AssetId, AssetPair, Balance> + AMMTradeCalculation<AssetPair, Balance>;
+
+		/// Max number of trades in a single route
+		#[pallet::constant]
+		type MaxNumberOfTrades: Get<u32>;
+
+		/// Weight information for the extrinsics.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Route has to contain at least two assets.
+		InvalidRoute,
+		/// Route contains more trades than allowed.
+		MaxNumberOfTradesExceeded,
+		/// Trading limit of the route has been reached.
+		TradingLimitReached,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Route was executed. [who, asset in, asset out, amount in, amount out]
+		RouteExecuted(T::AccountId, AssetId, AssetId, Balance, Balance),
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Sell `amount_in` of the first asset of the route for the last asset of the route.
+		///
+		/// Trades are executed one after another, amount received in a trade is sold in the next one.
+		///
+		/// `min_amount_out` - minimum amount of the last asset of the route to be received.
+		///
+		/// Emits `RouteExecuted` when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::sell(route.len().saturating_sub(1) as u32))]
+		#[transactional]
+		pub fn sell(
+			origin: OriginFor<T>,
+			route: Vec<AssetId>,
+			amount_in: Balance,
+			min_amount_out: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::ensure_route_is_valid(&route)?;
+
+			let mut amount = amount_in;
+
+			for assets in route.windows(2) {
+				let transfer = T::AMM::validate_sell(
+					&who,
+					AssetPair::new(assets[0], assets[1]),
+					amount,
+					Balance::zero(),
+					false,
+				)?;
+
+				T::AMM::execute_sell(&transfer)?;
+
+				amount = transfer.amount_out;
+			}
+
+			ensure!(amount >= min_amount_out, Error::<T>::TradingLimitReached);
+
+			Self::deposit_event(Event::RouteExecuted(
+				who,
+				route[0],
+				route[route.len() - 1],
+				amount_in,
+				amount,
+			));
+
+			Ok(())
+		}
+
+		/// Buy `amount_out` of the last asset of the route for the first asset of the route.
+		///
+		/// Amounts of all trades are calculated from the last trade to the first one before any trade is executed.
+		///
+		/// `max_amount_in` - maximum amount of the first asset of the route to be sold.
+		///
+		/// Emits `RouteExecuted` when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::buy(route.len().saturating_sub(1) as u32))]
+		#[transactional]
+		pub fn buy(
+			origin: OriginFor<T>,
+			route: Vec<AssetId>,
+			amount_out: Balance,
+			max_amount_in: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::ensure_route_is_valid(&route)?;
+
+			// (amount out, amount in) of each trade, in reverse order
+			let mut amounts = Vec::<(Balance, Balance)>::with_capacity(route.len() - 1);
+			let mut amount = amount_out;
+
+			for assets in route.windows(2).rev() {
+				let amount_in = T::AMM::calculate_buy(AssetPair::new(assets[0], assets[1]), amount)?;
+				amounts.push((amount, amount_in));
+				amount = amount_in;
+			}
+
+			ensure!(amount <= max_amount_in, Error::<T>::TradingLimitReached);
+
+			for (assets, (trade_amount_out, trade_amount_in)) in route.windows(2).zip(amounts.into_iter().rev()) {
+				let transfer = T::AMM::validate_buy(
+					&who,
+					AssetPair::new(assets[0], assets[1]),
+					trade_amount_out,
+					trade_amount_in,
+					false,
+				)?;
+
+				T::AMM::execute_buy(&transfer)?;
+			}
+
+			Self::deposit_event(Event::RouteExecuted(
+				who,
+				route[0],
+				route[route.len() - 1],
+				amount,
+				amount_out,
+			));
+
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	fn ensure_route_is_valid(route: &[AssetId]) -> DispatchResult {
+		ensure!(route.len() >= 2, Error::<T>::InvalidRoute);
+
+		ensure!(
+			route.len() - 1 <= T::MaxNumberOfTrades::get() as usize,
+			Error::<T>::MaxNumberOfTradesExceeded
+		);
+
+		Ok(())
+	}
+}
@@ -0,0 +1,157 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+pub use crate::mock::{
+	Currency, Event as TestEvent, ExtBuilder, Origin, RouteExecutor, System, Test, ACA, ALICE, BOB, DOT, HDX, KSM, XYK,
+};
+use frame_support::{assert_noop, assert_ok};
+use orml_traits::MultiCurrency;
+use primitives::Price;
+
+const POOL_LIQUIDITY: Balance = 100_000_000_000_000;
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut ext = ExtBuilder::default().build();
+	ext.execute_with(|| {
+		System::set_block_number(1);
+
+		for (asset_a, asset_b) in [(HDX, DOT), (DOT, ACA), (ACA, KSM)] {
+			assert_ok!(XYK::create_pool(
+				Origin::signed(ALICE),
+				asset_a,
+				asset_b,
+				POOL_LIQUIDITY,
+				Price::from(1),
+				None
+			));
+		}
+	});
+	ext
+}
+
+fn expect_events(e: Vec<TestEvent>) {
+	e.into_iter().for_each(frame_system::Pallet::<Test>::assert_has_event);
+}
+
+#[test]
+fn sell_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(RouteExecutor::sell(
+			Origin::signed(BOB),
+			vec![HDX, DOT, ACA],
+			500_000_000_000,
+			493_076_169_072
+		));
+
+		assert_eq!(Currency::free_balance(HDX, &BOB), 500_000_000_000);
+		assert_eq!(Currency::free_balance(DOT, &BOB), 0);
+		assert_eq!(Currency::free_balance(ACA, &BOB), 493_076_169_072);
+
+		expect_events(vec![Event::RouteExecuted(
+			BOB,
+			HDX,
+			ACA,
+			500_000_000_000,
+			493_076_169_072,
+		)
+		.into()]);
+	});
+}
+
+#[test]
+fn sell_should_not_work_when_min_amount_out_is_not_reached() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			RouteExecutor::sell(
+				Origin::signed(BOB),
+				vec![HDX, DOT, ACA],
+				500_000_000_000,
+				493_076_169_073
+			),
+			Error::<Test>::TradingLimitReached
+		);
+	});
+}
+
+#[test]
+fn sell_should_not_work_when_any_trade_of_route_fails() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			RouteExecutor::sell(Origin::signed(BOB), vec![HDX, DOT, KSM], 500_000_000_000, 0),
+			pallet_xyk::Error::<Test>::TokenPoolNotFound
+		);
+	});
+}
+
+#[test]
+fn buy_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(RouteExecutor::buy(
+			Origin::signed(BOB),
+			vec![HDX, DOT, ACA],
+			100_000_000_000,
+			100_601_804_813
+		));
+
+		assert_eq!(Currency::free_balance(HDX, &BOB), 1_000_000_000_000 - 100_601_804_813);
+		assert_eq!(Currency::free_balance(DOT, &BOB), 0);
+		assert_eq!(Currency::free_balance(ACA, &BOB), 100_000_000_000);
+
+		expect_events(vec![Event::RouteExecuted(
+			BOB,
+			HDX,
+			ACA,
+			100_601_804_813,
+			100_000_000_000,
+		)
+		.into()]);
+	});
+}
+
+#[test]
+fn buy_should_not_work_when_max_amount_in_is_exceeded() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			RouteExecutor::buy(
+				Origin::signed(BOB),
+				vec![HDX, DOT, ACA],
+				100_000_000_000,
+				100_601_804_812
+			),
+			Error::<Test>::TradingLimitReached
+		);
+	});
+}
+
+#[test]
+fn trade_should_not_work_with_invalid_route() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			RouteExecutor::sell(Origin::signed(BOB), vec![HDX], 500_000_000_000, 0),
+			Error::<Test>::InvalidRoute
+		);
+		assert_noop!(
+			RouteExecutor::buy(Origin::signed(BOB), vec![], 100_000_000_000, 1_000_000_000_000),
+			Error::<Test>::InvalidRoute
+		);
+		assert_noop!(
+			RouteExecutor::sell(Origin::signed(BOB), vec![HDX, DOT, ACA, KSM, HDX], 500_000_000_000, 0),
+			Error::<Test>::MaxNumberOfTradesExceeded
+		);
+	});
+}
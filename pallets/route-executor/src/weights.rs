@@ -0,0 +1,86 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Autogenerated weights for pallet_route_executor
+//!
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
+//! DATE: 2022-03-21, STEPS: 5, REPEAT: 20, LOW RANGE: [], HIGH RANGE: []
+//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 128
+
+// Executed Command:
+// target/release/basilisk
+// benchmark
+// --chain=dev
+// --steps=5
+// --repeat=20
+// --execution=wasm
+// --wasm-execution=compiled
+// --heap-pages=4096
+// --template=.maintain/pallet-weight-template.hbs
+// --pallet=pallet_route_executor
+// --output=weights.rs
+// --extrinsic=*
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(clippy::unnecessary_cast)]
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_route_executor.
+pub trait WeightInfo {
+	fn sell(n: u32) -> Weight;
+	fn buy(n: u32) -> Weight;
+}
+
+/// Weights for pallet_route_executor using the hydraDX node and recommended hardware.
+pub struct HydraWeight<T>(PhantomData<T>);
+
+impl<T: frame_system::Config> WeightInfo for HydraWeight<T> {
+	fn sell(n: u32) -> Weight {
+		(3_542_000 as Weight)
+			.saturating_add((125_816_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads((6 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes((5 as Weight).saturating_mul(n as Weight)))
+	}
+	fn buy(n: u32) -> Weight {
+		(3_771_000 as Weight)
+			.saturating_add((139_254_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads((8 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes((5 as Weight).saturating_mul(n as Weight)))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn sell(n: u32) -> Weight {
+		(3_542_000 as Weight)
+			.saturating_add((125_816_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads((6 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((5 as Weight).saturating_mul(n as Weight)))
+	}
+	fn buy(n: u32) -> Weight {
+		(3_771_000 as Weight)
+			.saturating_add((139_254_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads((8 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((5 as Weight).saturating_mul(n as Weight)))
+	}
+}
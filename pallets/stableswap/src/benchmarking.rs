@@ -0,0 +1,158 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_benchmarking::{account, benchmarks};
+use frame_system::RawOrigin;
+use sp_std::prelude::*;
+
+use crate::Pallet as Stableswap;
+
+const SEED: u32 = 1;
+
+const AMPLIFICATION: u32 = 100;
+
+// Pools of max number of assets are benchmarked.
+fn pool_assets<T: Config>() -> Vec<AssetId> {
+	(0..T::MaxAssetsInPool::get()).map(|i| 1_000 + i).collect()
+}
+
+fn funded_account<T: Config>(name: &'static str, index: u32, assets: &[AssetId]) -> T::AccountId {
+	let caller: T::AccountId = account(name, index, SEED);
+	for asset in assets {
+		T::Currency::update_balance(*asset, &caller, 1_000_000_000_000_000).unwrap();
+	}
+	caller
+}
+
+fn create_pool_with_liquidity<T: Config>(assets: &[AssetId]) -> Result<AssetId, DispatchError> {
+	let maker = funded_account::<T>("maker", 0, assets);
+
+	Stableswap::<T>::create_pool(
+		RawOrigin::Root.into(),
+		assets.to_vec(),
+		AMPLIFICATION,
+		Permill::from_parts(3_000),
+	)?;
+
+	let pool_id = Stableswap::<T>::asset_pair_pool((assets[0], assets[1])).ok_or(Error::<T>::PoolNotFound)?;
+
+	Stableswap::<T>::add_liquidity(
+		RawOrigin::Signed(maker).into(),
+		pool_id,
+		assets.iter().map(|asset| (*asset, 1_000_000_000_000_000)).collect(),
+		0,
+	)?;
+
+	Ok(pool_id)
+}
+
+benchmarks! {
+	create_pool {
+		let assets = pool_assets::<T>();
+
+	}: _(RawOrigin::Root, assets.clone(), AMPLIFICATION, Permill::from_parts(3_000))
+	verify {
+		assert!(Stableswap::<T>::asset_pair_pool((assets[0], assets[1])).is_some());
+	}
+
+	add_liquidity {
+		let assets = pool_assets::<T>();
+		let pool_id = create_pool_with_liquidity::<T>(&assets)?;
+
+		let caller = funded_account::<T>("caller", 0, &assets);
+
+		// liquidity is added in a single asset, so the imbalance fee is calculated
+		let liquidity = vec![(assets[0], 10_000_000_000_000)];
+
+	}: _(RawOrigin::Signed(caller.clone()), pool_id, liquidity, 0)
+	verify {
+		assert!(T::Currency::free_balance(pool_id, &caller) > 0);
+	}
+
+	remove_liquidity {
+		let assets = pool_assets::<T>();
+		let pool_id = create_pool_with_liquidity::<T>(&assets)?;
+
+		let caller = funded_account::<T>("caller", 0, &assets);
+		let liquidity = vec![(assets[0], 10_000_000_000_000)];
+		Stableswap::<T>::add_liquidity(RawOrigin::Signed(caller.clone()).into(), pool_id, liquidity, 0)?;
+
+		let shares = T::Currency::free_balance(pool_id, &caller);
+
+		let min_amounts = assets.iter().map(|asset| (*asset, 0)).collect::<Vec<_>>();
+
+	}: _(RawOrigin::Signed(caller.clone()), pool_id, shares, min_amounts)
+	verify {
+		assert_eq!(T::Currency::free_balance(pool_id, &caller), 0);
+	}
+
+	sell {
+		let assets = pool_assets::<T>();
+		create_pool_with_liquidity::<T>(&assets)?;
+
+		let caller = funded_account::<T>("caller", 0, &assets[..1]);
+
+	}: _(RawOrigin::Signed(caller.clone()), assets[0], assets[1], 10_000_000_000_000, 0)
+	verify {
+		assert!(T::Currency::free_balance(assets[1], &caller) > 0);
+	}
+
+	buy {
+		let assets = pool_assets::<T>();
+		create_pool_with_liquidity::<T>(&assets)?;
+
+		let caller = funded_account::<T>("caller", 0, &assets[..1]);
+
+	}: _(RawOrigin::Signed(caller.clone()), assets[1], assets[0], 10_000_000_000_000, 20_000_000_000_000)
+	verify {
+		assert_eq!(T::Currency::free_balance(assets[1], &caller), 10_000_000_000_000);
+	}
+
+	ramp_amplification {
+		let assets = pool_assets::<T>();
+		let pool_id = create_pool_with_liquidity::<T>(&assets)?;
+
+		let final_block = frame_system::Pallet::<T>::block_number() + T::MinRampDuration::get();
+
+	}: _(RawOrigin::Root, pool_id, 2 * AMPLIFICATION, final_block)
+	verify {
+		assert_eq!(Stableswap::<T>::pools(pool_id).unwrap().final_amplification, 2 * AMPLIFICATION);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::tests::{new_test_ext, Test};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_benchmarks() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Pallet::<Test>::test_benchmark_create_pool());
+			assert_ok!(Pallet::<Test>::test_benchmark_add_liquidity());
+			assert_ok!(Pallet::<Test>::test_benchmark_remove_liquidity());
+			assert_ok!(Pallet::<Test>::test_benchmark_sell());
+			assert_ok!(Pallet::<Test>::test_benchmark_buy());
+			assert_ok!(Pallet::<Test>::test_benchmark_ramp_amplification());
+		});
+	}
+}
@@ -0,0 +1,1063 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Stableswap Pallet
+//!
+//! ## Overview
+//!
+//! Stableswap pallet provides functionality for managing Curve-style liquidity pools of 2 or more assets
+//! of similar value and executing trades in them.
+//!
+//! The pool curve is controlled by the amplification coefficient - the higher the amplification,
+//! the closer the curve is to constant sum and the lower is the price impact of trades around
+//! the balanced state of the pool. Amplification can be changed by governance linearly over a number of blocks.
+//!
+//! Pools are created by governance. Each pair of assets can be traded in at most one pool,
+//! so the pool of a trade is determined by the traded assets.
+//!
+//! This pallet implements AMM Api trait therefore it is possible to plug this pool implementation
+//! into the exchange pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+#![allow(clippy::upper_case_acronyms)]
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::sp_runtime::{
+	helpers_128bit::multiply_by_rational,
+	traits::{AccountIdConversion, SaturatedConversion, Zero},
+	DispatchError, Permill, RuntimeDebug,
+};
+use frame_support::{dispatch::DispatchResult, ensure, traits::Get, transactional, BoundedVec, PalletId};
+use frame_system::{ensure_signed, pallet_prelude::BlockNumberFor};
+use hydradx_traits::{AMMTransfer, AMM};
+use orml_traits::{MultiCurrency, MultiCurrencyExtended};
+use primitive_types::U256;
+use primitives::{
+	asset::AssetPair,
+	traits::{AMMTradeCalculation, TradingLimitsProvider},
+	Amount, AssetId, Balance, TradingLimits,
+};
+use scale_info::TypeInfo;
+use sp_std::{vec, vec::Vec};
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+mod benchmarking;
+
+mod impls;
+pub mod math;
+pub mod weights;
+
+pub use impls::StableswapSpotPrice;
+
+use weights::WeightInfo;
+
+// Re-export pallet items so that they can be accessed from the crate namespace.
+pub use pallet::*;
+
+/// Information about a pool.
+///
+/// Amplification changes linearly from `initial_amplification` at `initial_block`
+/// to `final_amplification` at `final_block` and stays constant afterwards.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub struct PoolInfo<Assets, BlockNumber> {
+	/// Assets of the pool, sorted by id.
+	pub assets: Assets,
+	pub initial_amplification: u32,
+	pub final_amplification: u32,
+	pub initial_block: BlockNumber,
+	pub final_block: BlockNumber,
+	/// Trade fee - in `asset_out` for sell, in `asset_in` for buy.
+	pub fee: Permill,
+}
+
+pub type PoolInfoOf<T> = PoolInfo<BoundedVec<AssetId, <T as Config>::MaxAssetsInPool>, BlockNumberFor<T>>;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::{BlockNumberFor, OriginFor};
+	use hydradx_traits::ShareTokenRegistry;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Registry support
+		type AssetRegistry: ShareTokenRegistry<AssetId, Vec<u8>, Balance, DispatchError>;
+
+		/// Multi currency for transfer of currencies
+		type Currency: MultiCurrencyExtended<Self::AccountId, CurrencyId = AssetId, Balance = Balance, Amount = Amount>;
+
+		/// Pallet id used to derive pool accounts
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// The origin which can create pools and change their amplification
+		type AuthorityOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Max number of assets in a single pool
+		#[pallet::constant]
+		type MaxAssetsInPool: Get<u32>;
+
+		/// Max amplification of a pool
+		#[pallet::constant]
+		type MaxAmplification: Get<u32>;
+
+		/// Max factor by which amplification can be increased or decreased by a single ramp
+		#[pallet::constant]
+		type MaxAmplificationChange: Get<u32>;
+
+		/// Min number of blocks over which amplification is ramped
+		#[pallet::constant]
+		type MinRampDuration: Get<Self::BlockNumber>;
+
+		/// Trading limits of pools
+		type TradingLimits: TradingLimitsProvider<Self::AccountId>;
+
+		/// Weight information for the extrinsics.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Pool has to consist of at least two distinct assets.
+		IncorrectAssets,
+
+		/// Max number of assets in a pool has been exceeded.
+		MaxAssetsExceeded,
+
+		/// Amplification has to be between 1 and `MaxAmplification`.
+		InvalidAmplification,
+
+		/// Final block of amplification change has to be at least `MinRampDuration` blocks in the future.
+		InvalidRampBlock,
+
+		/// Pool of given assets already exists.
+		PoolAlreadyExists,
+
+		/// Pool does not exist.
+		PoolNotFound,
+
+		/// Asset is not in the pool.
+		AssetNotInPool,
+
+		/// Liquidity has not reached the required minimum.
+		InsufficientLiquidity,
+
+		/// Remaining liquidity has to be zero or at least the required minimum.
+		InsufficientLiquidityRemaining,
+
+		/// Initial liquidity has to be provided in all assets of the pool.
+		InitialLiquidityMissing,
+
+		/// Amount is less than min trading limit.
+		InsufficientTradingAmount,
+
+		/// Asset balance is not sufficient.
+		InsufficientAssetBalance,
+
+		/// Not enough asset liquidity in the pool.
+		InsufficientPoolAssetBalance,
+
+		/// Share balance is not sufficient.
+		InsufficientShares,
+
+		/// Amount of shares is zero.
+		ZeroShares,
+
+		/// Asset amount has exceeded given limit.
+		AssetAmountExceededLimit,
+
+		/// Asset amount has not reached given limit.
+		AssetAmountNotReachedLimit,
+
+		/// Amount of shares has not reached given limit.
+		SharesAmountNotReachedLimit,
+
+		/// Max fraction of pool to buy in single transaction has been exceeded.
+		MaxOutRatioExceeded,
+
+		/// Max fraction of pool to sell in single transaction has been exceeded.
+		MaxInRatioExceeded,
+
+		/// Trade fee discount is not supported by stableswap pools.
+		DiscountNotSupported,
+
+		/// Invariant calculation has not converged or overflowed.
+		MathError,
+
+		/// Overflow
+		Overflow,
+
+		/// Amplification can be increased or decreased at most `MaxAmplificationChange` times by a single ramp.
+		AmplificationChangeTooLarge,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Pool was created. [pool id, assets, amplification, fee, pool account id]
+		PoolCreated(AssetId, Vec<AssetId>, u32, Permill, T::AccountId),
+
+		/// Liquidity was added to the pool. [who, pool id, assets and amounts, shares]
+		LiquidityAdded(T::AccountId, AssetId, Vec<(AssetId, Balance)>, Balance),
+
+		/// Liquidity was removed from the pool. [who, pool id, shares, assets and amounts]
+		LiquidityRemoved(T::AccountId, AssetId, Balance, Vec<(AssetId, Balance)>),
+
+		/// Asset sale executed. [who, asset in, asset out, amount, sale price, fee asset, fee amount, pool id]
+		SellExecuted(
+			T::AccountId,
+			AssetId,
+			AssetId,
+			Balance,
+			Balance,
+			AssetId,
+			Balance,
+			AssetId,
+		),
+
+		/// Asset purchase executed. [who, asset out, asset in, amount, buy price, fee asset, fee amount, pool id]
+		BuyExecuted(
+			T::AccountId,
+			AssetId,
+			AssetId,
+			Balance,
+			Balance,
+			AssetId,
+			Balance,
+			AssetId,
+		),
+
+		/// Amplification change was scheduled. [pool id, from amplification, to amplification, from block, to block]
+		AmplificationRampStarted(AssetId, u32, u32, T::BlockNumber, T::BlockNumber),
+	}
+
+	/// Pools by pool id, which is the asset id of the pool's share token
+	#[pallet::storage]
+	#[pallet::getter(fn pools)]
+	pub type Pools<T: Config> = StorageMap<_, Blake2_128Concat, AssetId, PoolInfoOf<T>, OptionQuery>;
+
+	/// Pool id of each ordered pair of assets which can be traded in a pool
+	#[pallet::storage]
+	#[pallet::getter(fn asset_pair_pool)]
+	pub type AssetPairPools<T: Config> = StorageMap<_, Blake2_128Concat, (AssetId, AssetId), AssetId, OptionQuery>;
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Create a pool of `assets` without liquidity.
+		///
+		/// Share token of the pool is registered in asset registry, its id is also the id of the pool.
+		/// None of the pairs of `assets` can be part of another pool.
+		///
+		/// The dispatch origin for this call must be `T::AuthorityOrigin`.
+		///
+		/// Parameters:
+		/// - `assets`: assets of the pool, at least 2 and at most `MaxAssetsInPool`
+		/// - `amplification`: amplification coefficient, between 1 and `MaxAmplification`
+		/// - `fee`: trade fee
+		///
+		/// Emits `PoolCreated` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::create_pool())]
+		#[transactional]
+		pub fn create_pool(
+			origin: OriginFor<T>,
+			assets: Vec<AssetId>,
+			amplification: u32,
+			fee: Permill,
+		) -> DispatchResult {
+			T::AuthorityOrigin::ensure_origin(origin)?;
+
+			ensure!(
+				assets.len() <= T::MaxAssetsInPool::get() as usize,
+				Error::<T>::MaxAssetsExceeded
+			);
+
+			let assets_count = assets.len();
+			let mut assets = assets;
+			assets.sort_unstable();
+			assets.dedup();
+
+			ensure!(
+				assets.len() == assets_count && assets_count >= 2,
+				Error::<T>::IncorrectAssets
+			);
+
+			Self::ensure_amplification_is_valid(amplification)?;
+
+			let pairs = Self::asset_pairs(&assets);
+
+			ensure!(
+				pairs.iter().all(|pair| !<AssetPairPools<T>>::contains_key(pair)),
+				Error::<T>::PoolAlreadyExists
+			);
+
+			let pool_id = T::AssetRegistry::get_or_create_shared_asset(
+				Self::share_token_name(&assets),
+				assets.clone(),
+				T::TradingLimits::default_limits().min_pool_liquidity,
+			)?;
+
+			ensure!(!<Pools<T>>::contains_key(pool_id), Error::<T>::PoolAlreadyExists);
+
+			let pool_assets: BoundedVec<AssetId, T::MaxAssetsInPool> =
+				assets.clone().try_into().map_err(|_| Error::<T>::MaxAssetsExceeded)?;
+
+			let now = <frame_system::Pallet<T>>::block_number();
+
+			<Pools<T>>::insert(
+				pool_id,
+				PoolInfo {
+					assets: pool_assets,
+					initial_amplification: amplification,
+					final_amplification: amplification,
+					initial_block: now,
+					final_block: now,
+					fee,
+				},
+			);
+
+			for pair in pairs {
+				<AssetPairPools<T>>::insert(pair, pool_id);
+			}
+
+			Self::deposit_event(Event::PoolCreated(
+				pool_id,
+				assets,
+				amplification,
+				fee,
+				Self::pool_account(pool_id),
+			));
+
+			Ok(())
+		}
+
+		/// Add liquidity to a pool in any subset of its assets.
+		///
+		/// Initial liquidity has to be provided in all assets of the pool and the amount of shares is
+		/// the invariant of the pool. Afterwards, shares are issued in proportion to the increase of the invariant.
+		/// Part of the liquidity which does not match the current ratio of reserves is charged a fee
+		/// of `fee * n / (4 * (n - 1))` which stays in the pool.
+		///
+		/// `min_shares` - minimum amount of shares to be received.
+		///
+		/// Emits `LiquidityAdded` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::add_liquidity())]
+		#[transactional]
+		pub fn add_liquidity(
+			origin: OriginFor<T>,
+			pool_id: AssetId,
+			assets: Vec<(AssetId, Balance)>,
+			min_shares: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let pool = Self::pools(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+			let limits = Self::trading_limits(pool_id);
+
+			ensure!(!assets.is_empty(), Error::<T>::IncorrectAssets);
+
+			let mut amounts = vec![Balance::zero(); pool.assets.len()];
+			for (asset, amount) in assets.iter() {
+				let index = pool
+					.assets
+					.iter()
+					.position(|a| a == asset)
+					.ok_or(Error::<T>::AssetNotInPool)?;
+
+				ensure!(amounts[index].is_zero(), Error::<T>::IncorrectAssets);
+				ensure!(
+					*amount >= limits.min_trading_limit,
+					Error::<T>::InsufficientTradingAmount
+				);
+				ensure!(
+					T::Currency::free_balance(*asset, &who) >= *amount,
+					Error::<T>::InsufficientAssetBalance
+				);
+
+				amounts[index] = *amount;
+			}
+
+			let pool_account = Self::pool_account(pool_id);
+			let amplification = Self::amplification(&pool);
+			let reserves = Self::pool_reserves(&pool_account, &pool.assets);
+			let share_issuance = T::Currency::total_issuance(pool_id);
+
+			let new_reserves = reserves
+				.iter()
+				.zip(amounts.iter())
+				.map(|(reserve, amount)| reserve.checked_add(*amount))
+				.collect::<Option<Vec<Balance>>>()
+				.ok_or(Error::<T>::Overflow)?;
+
+			let shares = if share_issuance.is_zero() {
+				ensure!(
+					amounts.iter().all(|amount| !amount.is_zero()),
+					Error::<T>::InitialLiquidityMissing
+				);
+
+				let shares = math::calculate_d(&new_reserves, amplification).ok_or(Error::<T>::MathError)?;
+
+				ensure!(shares >= limits.min_pool_liquidity, Error::<T>::InsufficientLiquidity);
+
+				shares
+			} else {
+				Self::calculate_shares(&reserves, &new_reserves, share_issuance, amplification, pool.fee)?
+			};
+
+			ensure!(!shares.is_zero(), Error::<T>::ZeroShares);
+			ensure!(shares >= min_shares, Error::<T>::SharesAmountNotReachedLimit);
+
+			for (asset, amount) in pool.assets.iter().zip(amounts.iter()) {
+				if !amount.is_zero() {
+					T::Currency::transfer(*asset, &who, &pool_account, *amount)?;
+				}
+			}
+
+			T::Currency::deposit(pool_id, &who, shares)?;
+
+			Self::deposit_event(Event::LiquidityAdded(who, pool_id, assets, shares));
+
+			Ok(())
+		}
+
+		/// Remove liquidity from a pool in all its assets, in proportion to their reserves.
+		///
+		/// Remaining shares of the pool have to be zero or at least minimum pool liquidity of its trading limits.
+		///
+		/// `min_amounts` - minimum amounts to be received of any subset of the pool assets.
+		///
+		/// Emits `LiquidityRemoved` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::remove_liquidity())]
+		#[transactional]
+		pub fn remove_liquidity(
+			origin: OriginFor<T>,
+			pool_id: AssetId,
+			shares: Balance,
+			min_amounts: Vec<(AssetId, Balance)>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let pool = Self::pools(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+			let limits = Self::trading_limits(pool_id);
+
+			ensure!(!shares.is_zero(), Error::<T>::ZeroShares);
+			ensure!(min_amounts.len() <= pool.assets.len(), Error::<T>::IncorrectAssets);
+
+			ensure!(
+				T::Currency::free_balance(pool_id, &who) >= shares,
+				Error::<T>::InsufficientShares
+			);
+
+			let share_issuance = T::Currency::total_issuance(pool_id);
+			let remaining_shares = share_issuance.checked_sub(shares).ok_or(Error::<T>::Overflow)?;
+
+			ensure!(
+				remaining_shares.is_zero() || remaining_shares >= limits.min_pool_liquidity,
+				Error::<T>::InsufficientLiquidityRemaining
+			);
+
+			let pool_account = Self::pool_account(pool_id);
+			let reserves = Self::pool_reserves(&pool_account, &pool.assets);
+
+			let mut removed = Vec::with_capacity(pool.assets.len());
+			for (asset, reserve) in pool.assets.iter().zip(reserves.into_iter()) {
+				let amount = multiply_by_rational(reserve, shares, share_issuance).map_err(|_| Error::<T>::Overflow)?;
+
+				removed.push((*asset, amount));
+			}
+
+			for (asset, min_amount) in min_amounts.iter() {
+				let (_, amount) = removed
+					.iter()
+					.find(|(removed_asset, _)| removed_asset == asset)
+					.ok_or(Error::<T>::AssetNotInPool)?;
+
+				ensure!(amount >= min_amount, Error::<T>::AssetAmountNotReachedLimit);
+			}
+
+			for (asset, amount) in removed.iter() {
+				T::Currency::transfer(*asset, &pool_account, &who, *amount)?;
+			}
+
+			T::Currency::withdraw(pool_id, &who, shares)?;
+
+			Self::deposit_event(Event::LiquidityRemoved(who, pool_id, shares, removed));
+
+			Ok(())
+		}
+
+		/// Trade asset in for asset out.
+		///
+		/// Executes a swap of `asset_in` for `asset_out`. Price is determined by the pool and is
+		/// affected by the amount and proportion of the pool assets and by the amplification.
+		///
+		/// `max_limit` - minimum amount of `asset_out` to be obtained from the pool in exchange for `asset_in`.
+		///
+		/// Emits `SellExecuted` when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::sell())]
+		pub fn sell(
+			origin: OriginFor<T>,
+			asset_in: AssetId,
+			asset_out: AssetId,
+			amount: Balance,
+			max_limit: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			<Self as AMM<_, _, _, _>>::sell(&who, AssetPair { asset_in, asset_out }, amount, max_limit, false)?;
+
+			Ok(())
+		}
+
+		/// Trade asset in for asset out.
+		///
+		/// Executes a swap of `asset_in` for `asset_out`. Price is determined by the pool and is
+		/// affected by the amount and proportion of the pool assets and by the amplification.
+		///
+		/// `max_limit` - maximum amount of `asset_in` to be sold in exchange for `asset_out`.
+		///
+		/// Emits `BuyExecuted` when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::buy())]
+		pub fn buy(
+			origin: OriginFor<T>,
+			asset_out: AssetId,
+			asset_in: AssetId,
+			amount: Balance,
+			max_limit: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			<Self as AMM<_, _, _, _>>::buy(&who, AssetPair { asset_in, asset_out }, amount, max_limit, false)?;
+
+			Ok(())
+		}
+
+		/// Change amplification of a pool linearly from its current value to `final_amplification`
+		/// reached at `final_block`.
+		///
+		/// Amplification change already in progress is replaced, starting from the current amplification.
+		/// Amplification can change at most `MaxAmplificationChange` times over at least `MinRampDuration` blocks.
+		///
+		/// The dispatch origin for this call must be `T::AuthorityOrigin`.
+		///
+		/// Emits `AmplificationRampStarted` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::ramp_amplification())]
+		pub fn ramp_amplification(
+			origin: OriginFor<T>,
+			pool_id: AssetId,
+			final_amplification: u32,
+			final_block: BlockNumberFor<T>,
+		) -> DispatchResult {
+			T::AuthorityOrigin::ensure_origin(origin)?;
+
+			Self::ensure_amplification_is_valid(final_amplification)?;
+
+			let now = <frame_system::Pallet<T>>::block_number();
+
+			ensure!(
+				final_block > now && final_block - now >= T::MinRampDuration::get(),
+				Error::<T>::InvalidRampBlock
+			);
+
+			<Pools<T>>::try_mutate(pool_id, |maybe_pool| -> DispatchResult {
+				let pool = maybe_pool.as_mut().ok_or(Error::<T>::PoolNotFound)?;
+
+				let current_amplification = Self::amplification(pool).saturated_into::<u32>();
+
+				let max_change = T::MaxAmplificationChange::get();
+				ensure!(
+					final_amplification <= current_amplification.saturating_mul(max_change)
+						&& final_amplification.saturating_mul(max_change) >= current_amplification,
+					Error::<T>::AmplificationChangeTooLarge
+				);
+
+				pool.initial_amplification = current_amplification;
+				pool.final_amplification = final_amplification;
+				pool.initial_block = now;
+				pool.final_block = final_block;
+
+				Self::deposit_event(Event::AmplificationRampStarted(
+					pool_id,
+					current_amplification,
+					final_amplification,
+					now,
+					final_block,
+				));
+
+				Ok(())
+			})
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Account holding reserves of the pool.
+	pub fn pool_account(pool_id: AssetId) -> T::AccountId {
+		T::PalletId::get().into_sub_account(pool_id)
+	}
+
+	/// Trading limits of the pool, limits set for the pool take precedence over the default ones.
+	fn trading_limits(pool_id: AssetId) -> TradingLimits {
+		T::TradingLimits::pool_limits(&Self::pool_account(pool_id))
+	}
+
+	/// Id of the pool of given pool account.
+	fn pool_id_from_account(pool_account: &T::AccountId) -> Option<AssetId> {
+		match PalletId::try_from_sub_account::<AssetId>(pool_account) {
+			Some((pallet_id, pool_id)) if pallet_id == T::PalletId::get() => Some(pool_id),
+			_ => None,
+		}
+	}
+
+	/// Id of the pool in which `assets` can be traded.
+	fn pool_id(assets: AssetPair) -> Option<AssetId> {
+		Self::asset_pair_pool(assets.ordered_pair())
+	}
+
+	/// Amplification of the pool at the current block.
+	pub fn amplification(pool: &PoolInfoOf<T>) -> u128 {
+		math::calculate_amplification(
+			pool.initial_amplification.into(),
+			pool.final_amplification.into(),
+			pool.initial_block.saturated_into(),
+			pool.final_block.saturated_into(),
+			<frame_system::Pallet<T>>::block_number().saturated_into(),
+		)
+	}
+
+	/// Reserves of `assets` in the pool, in the same order.
+	pub fn pool_reserves(pool_account: &T::AccountId, assets: &[AssetId]) -> Vec<Balance> {
+		assets
+			.iter()
+			.map(|asset| T::Currency::free_balance(*asset, pool_account))
+			.collect()
+	}
+
+	fn ensure_amplification_is_valid(amplification: u32) -> DispatchResult {
+		ensure!(
+			amplification > 0 && amplification <= T::MaxAmplification::get(),
+			Error::<T>::InvalidAmplification
+		);
+
+		Ok(())
+	}
+
+	/// All ordered pairs of sorted `assets`.
+	fn asset_pairs(assets: &[AssetId]) -> Vec<(AssetId, AssetId)> {
+		let mut pairs = Vec::new();
+		for (i, asset_a) in assets.iter().enumerate() {
+			for asset_b in assets.iter().skip(i + 1) {
+				pairs.push((*asset_a, *asset_b));
+			}
+		}
+		pairs
+	}
+
+	fn share_token_name(assets: &[AssetId]) -> Vec<u8> {
+		let mut buf: Vec<u8> = Vec::new();
+
+		buf.extend_from_slice(b"SSP");
+		for asset in assets {
+			buf.extend_from_slice(&asset.to_le_bytes());
+		}
+
+		buf
+	}
+
+	/// Calculate shares issued for liquidity which changes pool reserves from `reserves` to `new_reserves`.
+	///
+	/// Imbalance fee is deducted from each reserve by the difference from its ideal value, which keeps
+	/// the ratio of reserves, before the invariant of new reserves is calculated.
+	fn calculate_shares(
+		reserves: &[Balance],
+		new_reserves: &[Balance],
+		share_issuance: Balance,
+		amplification: u128,
+		fee: Permill,
+	) -> Result<Balance, DispatchError> {
+		let n = reserves.len() as u128;
+
+		let initial_d = math::calculate_d(reserves, amplification).ok_or(Error::<T>::MathError)?;
+		let updated_d = math::calculate_d(new_reserves, amplification).ok_or(Error::<T>::MathError)?;
+
+		ensure!(updated_d > initial_d, Error::<T>::ZeroShares);
+
+		let mut adjusted_reserves = Vec::with_capacity(reserves.len());
+		for (reserve, new_reserve) in reserves.iter().zip(new_reserves.iter()) {
+			let ideal_reserve =
+				multiply_by_rational(updated_d, *reserve, initial_d).map_err(|_| Error::<T>::Overflow)?;
+
+			let difference = if ideal_reserve > *new_reserve {
+				ideal_reserve - new_reserve
+			} else {
+				new_reserve - ideal_reserve
+			};
+
+			let imbalance_fee = multiply_by_rational(
+				difference,
+				(fee.deconstruct() as u128).saturating_mul(n),
+				4 * (n - 1) * 1_000_000,
+			)
+			.map_err(|_| Error::<T>::Overflow)?;
+
+			adjusted_reserves.push(new_reserve.checked_sub(imbalance_fee).ok_or(Error::<T>::Overflow)?);
+		}
+
+		let adjusted_d = math::calculate_d(&adjusted_reserves, amplification).ok_or(Error::<T>::MathError)?;
+
+		let shares = U256::from(share_issuance)
+			.checked_mul(U256::from(adjusted_d.saturating_sub(initial_d)))
+			.and_then(|v| v.checked_div(U256::from(initial_d)))
+			.ok_or(Error::<T>::Overflow)?;
+
+		Balance::try_from(shares).map_err(|_| Error::<T>::Overflow.into())
+	}
+
+	/// Reserves of the pool of `assets` with indices of `asset_in` and `asset_out` in the reserves.
+	fn trade_state(assets: AssetPair) -> Result<(AssetId, PoolInfoOf<T>, Vec<Balance>, usize, usize), DispatchError> {
+		let pool_id = Self::pool_id(assets).ok_or(Error::<T>::PoolNotFound)?;
+		let pool = Self::pools(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+
+		let index_in = pool
+			.assets
+			.iter()
+			.position(|asset| *asset == assets.asset_in)
+			.ok_or(Error::<T>::AssetNotInPool)?;
+		let index_out = pool
+			.assets
+			.iter()
+			.position(|asset| *asset == assets.asset_out)
+			.ok_or(Error::<T>::AssetNotInPool)?;
+
+		let reserves = Self::pool_reserves(&Self::pool_account(pool_id), &pool.assets);
+
+		ensure!(
+			reserves.iter().all(|reserve| !reserve.is_zero()),
+			Error::<T>::InsufficientPoolAssetBalance
+		);
+
+		Ok((pool_id, pool, reserves, index_in, index_out))
+	}
+
+	/// Perform all checks and calculations of a sell. Checks of trader's balances are skipped if `who` is `None`.
+	///
+	/// Return amount of `asset_out` to be received (fee deducted) and trade fee.
+	fn calculate_sell_trade(
+		who: Option<&T::AccountId>,
+		assets: AssetPair,
+		amount: Balance,
+		min_bought: Balance,
+	) -> Result<(Balance, Balance), DispatchError> {
+		let limits = T::TradingLimits::pool_limits(&Self::get_pair_id(assets));
+
+		ensure!(
+			amount >= limits.min_trading_limit,
+			Error::<T>::InsufficientTradingAmount
+		);
+
+		let (_, pool, reserves, index_in, index_out) = Self::trade_state(assets)?;
+
+		if let Some(who) = who {
+			ensure!(
+				T::Currency::free_balance(assets.asset_in, who) >= amount,
+				Error::<T>::InsufficientAssetBalance
+			);
+		}
+
+		ensure!(
+			amount
+				<= reserves[index_in]
+					.checked_div(limits.max_in_ratio)
+					.ok_or(Error::<T>::Overflow)?,
+			Error::<T>::MaxInRatioExceeded
+		);
+
+		let amount_out =
+			math::calculate_out_given_in(&reserves, index_in, index_out, amount, Self::amplification(&pool))
+				.ok_or(Error::<T>::MathError)?;
+
+		let transfer_fee = pool.fee.mul_ceil(amount_out);
+
+		let amount_out_without_fee = amount_out.checked_sub(transfer_fee).ok_or(Error::<T>::Overflow)?;
+
+		ensure!(
+			reserves[index_out] > amount_out,
+			Error::<T>::InsufficientPoolAssetBalance
+		);
+
+		ensure!(
+			min_bought <= amount_out_without_fee,
+			Error::<T>::AssetAmountNotReachedLimit
+		);
+
+		Ok((amount_out_without_fee, transfer_fee))
+	}
+
+	/// Perform all checks and calculations of a buy. Checks of trader's balances are skipped if `who` is `None`.
+	///
+	/// Return amount of `asset_in` to be paid (fee excluded) and trade fee.
+	fn calculate_buy_trade(
+		who: Option<&T::AccountId>,
+		assets: AssetPair,
+		amount: Balance,
+		max_limit: Balance,
+	) -> Result<(Balance, Balance), DispatchError> {
+		let limits = T::TradingLimits::pool_limits(&Self::get_pair_id(assets));
+
+		ensure!(
+			amount >= limits.min_trading_limit,
+			Error::<T>::InsufficientTradingAmount
+		);
+
+		let (_, pool, reserves, index_in, index_out) = Self::trade_state(assets)?;
+
+		ensure!(
+			amount
+				<= reserves[index_out]
+					.checked_div(limits.max_out_ratio)
+					.ok_or(Error::<T>::Overflow)?,
+			Error::<T>::MaxOutRatioExceeded
+		);
+
+		let amount_in =
+			math::calculate_in_given_out(&reserves, index_in, index_out, amount, Self::amplification(&pool))
+				.ok_or(Error::<T>::MathError)?;
+
+		let transfer_fee = pool.fee.mul_ceil(amount_in);
+
+		let amount_in_with_fee = amount_in.checked_add(transfer_fee).ok_or(Error::<T>::Overflow)?;
+
+		ensure!(max_limit >= amount_in_with_fee, Error::<T>::AssetAmountExceededLimit);
+
+		if let Some(who) = who {
+			ensure!(
+				T::Currency::free_balance(assets.asset_in, who) >= amount_in_with_fee,
+				Error::<T>::InsufficientAssetBalance
+			);
+		}
+
+		Ok((amount_in, transfer_fee))
+	}
+}
+
+// Implementation of AMM API which makes possible to plug the AMM pool into the exchange pallet.
+impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
+	fn exists(assets: AssetPair) -> bool {
+		Self::pool_id(assets).is_some()
+	}
+
+	/// Return account of the pool of `assets`, or account of the pallet if the pool does not exist.
+	fn get_pair_id(assets: AssetPair) -> T::AccountId {
+		Self::pool_id(assets)
+			.map(Self::pool_account)
+			.unwrap_or_else(|| T::PalletId::get().into_account())
+	}
+
+	fn get_share_token(assets: AssetPair) -> AssetId {
+		Self::pool_id(assets).unwrap_or_default()
+	}
+
+	fn get_pool_assets(pool_account_id: &T::AccountId) -> Option<Vec<AssetId>> {
+		let pool_id = Self::pool_id_from_account(pool_account_id)?;
+		Self::pools(pool_id).map(|pool| pool.assets.into_inner())
+	}
+
+	fn get_spot_price_unchecked(asset_a: AssetId, asset_b: AssetId, amount: Balance) -> Balance {
+		Self::trade_state(AssetPair::new(asset_a, asset_b))
+			.ok()
+			.and_then(|(_, pool, reserves, index_a, index_b)| {
+				math::calculate_spot_price(&reserves, index_a, index_b, amount, Self::amplification(&pool))
+			})
+			.unwrap_or_else(Balance::zero)
+	}
+
+	/// Validate a sell. Perform all necessary checks and calculations.
+	/// No storage changes are performed yet.
+	///
+	/// Return `AMMTransfer` with all info needed to execute the transaction.
+	fn validate_sell(
+		who: &T::AccountId,
+		assets: AssetPair,
+		amount: Balance,
+		min_bought: Balance,
+		discount: bool,
+	) -> Result<AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>, DispatchError> {
+		ensure!(!discount, Error::<T>::DiscountNotSupported);
+
+		let (amount_out, transfer_fee) = Self::calculate_sell_trade(Some(who), assets, amount, min_bought)?;
+
+		Ok(AMMTransfer {
+			origin: who.clone(),
+			assets,
+			amount,
+			amount_out,
+			discount,
+			discount_amount: Balance::zero(),
+			fee: (assets.asset_out, transfer_fee),
+		})
+	}
+
+	/// Execute sell. validate_sell must be called first.
+	/// Perform necessary storage/state changes.
+	/// Note : the execution should not return error as everything was previously verified and validated.
+	#[transactional]
+	fn execute_sell(transfer: &AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>) -> DispatchResult {
+		let pool_id = Self::pool_id(transfer.assets).ok_or(Error::<T>::PoolNotFound)?;
+		let pool_account = Self::pool_account(pool_id);
+
+		T::Currency::transfer(
+			transfer.assets.asset_in,
+			&transfer.origin,
+			&pool_account,
+			transfer.amount,
+		)?;
+		T::Currency::transfer(
+			transfer.assets.asset_out,
+			&pool_account,
+			&transfer.origin,
+			transfer.amount_out,
+		)?;
+
+		Self::deposit_event(Event::<T>::SellExecuted(
+			transfer.origin.clone(),
+			transfer.assets.asset_in,
+			transfer.assets.asset_out,
+			transfer.amount,
+			transfer.amount_out,
+			transfer.fee.0,
+			transfer.fee.1,
+			pool_id,
+		));
+
+		Ok(())
+	}
+
+	/// Validate a buy. Perform all necessary checks and calculations.
+	/// No storage changes are performed yet.
+	///
+	/// Return `AMMTransfer` with all info needed to execute the transaction.
+	fn validate_buy(
+		who: &T::AccountId,
+		assets: AssetPair,
+		amount: Balance,
+		max_limit: Balance,
+		discount: bool,
+	) -> Result<AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>, DispatchError> {
+		ensure!(!discount, Error::<T>::DiscountNotSupported);
+
+		let (amount_in, transfer_fee) = Self::calculate_buy_trade(Some(who), assets, amount, max_limit)?;
+
+		Ok(AMMTransfer {
+			origin: who.clone(),
+			assets,
+			amount,
+			amount_out: amount_in,
+			discount,
+			discount_amount: Balance::zero(),
+			fee: (assets.asset_in, transfer_fee),
+		})
+	}
+
+	/// Execute buy. validate_buy must be called first.
+	/// Perform necessary storage/state changes.
+	/// Note : the execution should not return error as everything was previously verified and validated.
+	#[transactional]
+	fn execute_buy(transfer: &AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>) -> DispatchResult {
+		let pool_id = Self::pool_id(transfer.assets).ok_or(Error::<T>::PoolNotFound)?;
+		let pool_account = Self::pool_account(pool_id);
+
+		T::Currency::transfer(
+			transfer.assets.asset_out,
+			&pool_account,
+			&transfer.origin,
+			transfer.amount,
+		)?;
+		T::Currency::transfer(
+			transfer.assets.asset_in,
+			&transfer.origin,
+			&pool_account,
+			transfer.amount_out + transfer.fee.1,
+		)?;
+
+		Self::deposit_event(Event::<T>::BuyExecuted(
+			transfer.origin.clone(),
+			transfer.assets.asset_out,
+			transfer.assets.asset_in,
+			transfer.amount,
+			transfer.amount_out,
+			transfer.fee.0,
+			transfer.fee.1,
+			pool_id,
+		));
+
+		Ok(())
+	}
+
+	fn get_min_trading_limit() -> Balance {
+		T::TradingLimits::default_limits().min_trading_limit
+	}
+
+	fn get_min_pool_liquidity() -> Balance {
+		T::TradingLimits::default_limits().min_pool_liquidity
+	}
+
+	fn get_max_in_ratio() -> u128 {
+		T::TradingLimits::default_limits().max_in_ratio
+	}
+
+	fn get_max_out_ratio() -> u128 {
+		T::TradingLimits::default_limits().max_out_ratio
+	}
+
+	fn get_fee(pool_account_id: &T::AccountId) -> (u32, u32) {
+		let fee = Self::pool_id_from_account(pool_account_id)
+			.and_then(Self::pools)
+			.map(|pool| pool.fee)
+			.unwrap_or_default();
+
+		(fee.deconstruct(), Permill::one().deconstruct())
+	}
+}
+
+// Implementation of trade calculation which makes possible to price trades of a route before executing them.
+impl<T: Config> AMMTradeCalculation<AssetPair, Balance> for Pallet<T> {
+	fn calculate_sell(assets: AssetPair, amount: Balance) -> Result<Balance, DispatchError> {
+		let (amount_out, _) = Self::calculate_sell_trade(None, assets, amount, Balance::zero())?;
+
+		Ok(amount_out)
+	}
+
+	fn calculate_buy(assets: AssetPair, amount: Balance) -> Result<Balance, DispatchError> {
+		let (amount_in, transfer_fee) = Self::calculate_buy_trade(None, assets, amount, Balance::MAX)?;
+
+		Ok(amount_in.saturating_add(transfer_fee))
+	}
+}
@@ -0,0 +1,230 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! StableSwap invariant calculations.
+//!
+//! Pool of `n` assets with reserves `x_i` and amplification `A` holds the invariant `D`:
+//!
+//! `A * n^n * sum(x_i) + D = A * n^n * D + D^(n+1) / (n^n * prod(x_i))`
+//!
+//! Both `D` and the reserve of an asset for given `D` are calculated by Newton's method.
+//! All results are rounded in favour of the pool.
+
+use primitive_types::U256;
+use primitives::Balance;
+use sp_std::vec::Vec;
+
+/// Max number of iterations of Newton's method.
+pub const MAX_ITERATIONS: u8 = 255;
+
+/// Linear interpolation of amplification between `initial_block` and `final_block`.
+pub fn calculate_amplification(
+	initial_amplification: u128,
+	final_amplification: u128,
+	initial_block: u128,
+	final_block: u128,
+	current_block: u128,
+) -> u128 {
+	if current_block >= final_block || final_block <= initial_block {
+		return final_amplification;
+	}
+
+	let elapsed = current_block.saturating_sub(initial_block);
+	let duration = final_block - initial_block;
+
+	if final_amplification >= initial_amplification {
+		let change = (final_amplification - initial_amplification).saturating_mul(elapsed) / duration;
+		initial_amplification.saturating_add(change)
+	} else {
+		let change = (initial_amplification - final_amplification).saturating_mul(elapsed) / duration;
+		initial_amplification.saturating_sub(change)
+	}
+}
+
+/// Calculate invariant `D` of `reserves`. Zero is returned for empty pool.
+pub fn calculate_d(reserves: &[Balance], amplification: u128) -> Option<Balance> {
+	let reserves: Vec<U256> = reserves.iter().map(|&reserve| U256::from(reserve)).collect();
+
+	let sum = reserves
+		.iter()
+		.try_fold(U256::zero(), |acc, &reserve| acc.checked_add(reserve))?;
+	if sum.is_zero() {
+		return Some(0);
+	}
+	if reserves.iter().any(|reserve| reserve.is_zero()) {
+		return None;
+	}
+
+	let n = U256::from(reserves.len());
+	let ann = calculate_ann(reserves.len(), amplification)?;
+
+	let mut d = sum;
+	for _ in 0..MAX_ITERATIONS {
+		// D^(n+1) / (n^n * prod(x_i))
+		let d_p = reserves.iter().try_fold(d, |acc, &reserve| {
+			acc.checked_mul(d)?.checked_div(reserve.checked_mul(n)?)
+		})?;
+
+		let d_prev = d;
+
+		let numerator = ann.checked_mul(sum)?.checked_add(d_p.checked_mul(n)?)?.checked_mul(d)?;
+		let denominator = ann
+			.checked_sub(U256::one())?
+			.checked_mul(d)?
+			.checked_add(n.checked_add(U256::one())?.checked_mul(d_p)?)?;
+
+		d = numerator.checked_div(denominator)?;
+
+		if has_converged(d_prev, d) {
+			return Balance::try_from(d).ok();
+		}
+	}
+
+	None
+}
+
+/// Calculate reserve of the asset at `index` which keeps invariant `d` while other reserves are `reserves`.
+///
+/// Reserve at `index` is ignored.
+pub fn calculate_y(reserves: &[Balance], index: usize, d: Balance, amplification: u128) -> Option<Balance> {
+	let n = U256::from(reserves.len());
+	let ann = calculate_ann(reserves.len(), amplification)?;
+	let d = U256::from(d);
+
+	let mut sum = U256::zero();
+	let mut c = d;
+	for (i, &reserve) in reserves.iter().enumerate() {
+		if i == index {
+			continue;
+		}
+		let reserve = U256::from(reserve);
+		sum = sum.checked_add(reserve)?;
+		c = c.checked_mul(d)?.checked_div(reserve.checked_mul(n)?)?;
+	}
+	c = c.checked_mul(d)?.checked_div(ann.checked_mul(n)?)?;
+
+	let b = sum.checked_add(d.checked_div(ann)?)?;
+
+	let mut y = d;
+	for _ in 0..MAX_ITERATIONS {
+		let y_prev = y;
+
+		// y = (y^2 + c) / (2y + b - D)
+		let numerator = y.checked_mul(y)?.checked_add(c)?;
+		let denominator = y.checked_mul(U256::from(2))?.checked_add(b)?.checked_sub(d)?;
+
+		y = numerator.checked_div(denominator)?;
+
+		if has_converged(y_prev, y) {
+			return Balance::try_from(y).ok();
+		}
+	}
+
+	None
+}
+
+/// Calculate amount of asset at `index_out` received for `amount_in` of asset at `index_in`. Fee is not deducted.
+pub fn calculate_out_given_in(
+	reserves: &[Balance],
+	index_in: usize,
+	index_out: usize,
+	amount_in: Balance,
+	amplification: u128,
+) -> Option<Balance> {
+	if index_in == index_out || index_in >= reserves.len() || index_out >= reserves.len() {
+		return None;
+	}
+
+	let d = calculate_d(reserves, amplification)?;
+
+	let mut new_reserves = reserves.to_vec();
+	new_reserves[index_in] = new_reserves[index_in].checked_add(amount_in)?;
+
+	let y = calculate_y(&new_reserves, index_out, d, amplification)?;
+
+	reserves[index_out].checked_sub(y)?.checked_sub(1)
+}
+
+/// Calculate amount of asset at `index_in` required to buy `amount_out` of asset at `index_out`. Fee is not included.
+pub fn calculate_in_given_out(
+	reserves: &[Balance],
+	index_in: usize,
+	index_out: usize,
+	amount_out: Balance,
+	amplification: u128,
+) -> Option<Balance> {
+	if index_in == index_out || index_in >= reserves.len() || index_out >= reserves.len() {
+		return None;
+	}
+
+	let d = calculate_d(reserves, amplification)?;
+
+	let mut new_reserves = reserves.to_vec();
+	new_reserves[index_out] = new_reserves[index_out].checked_sub(amount_out)?;
+
+	let y = calculate_y(&new_reserves, index_in, d, amplification)?;
+
+	y.checked_sub(reserves[index_in])?.checked_add(1)
+}
+
+/// Calculate amount of asset at `index_b` which has the same value as `amount` of asset at `index_a`
+/// at the current state of the pool.
+///
+/// The price is the ratio of partial derivatives of the invariant:
+/// `x_b * (Ann * x_a + D_P) / (x_a * (Ann * x_b + D_P))` where `D_P = D^(n+1) / (n^n * prod(x_i))`.
+pub fn calculate_spot_price(
+	reserves: &[Balance],
+	index_a: usize,
+	index_b: usize,
+	amount: Balance,
+	amplification: u128,
+) -> Option<Balance> {
+	if index_a >= reserves.len() || index_b >= reserves.len() {
+		return None;
+	}
+
+	let n = U256::from(reserves.len());
+	let ann = calculate_ann(reserves.len(), amplification)?;
+	let d = U256::from(calculate_d(reserves, amplification)?);
+
+	let d_p = reserves.iter().try_fold(d, |acc, &reserve| {
+		acc.checked_mul(d)?.checked_div(U256::from(reserve).checked_mul(n)?)
+	})?;
+
+	let (reserve_a, reserve_b) = (U256::from(reserves[index_a]), U256::from(reserves[index_b]));
+
+	let numerator = U256::from(amount)
+		.checked_mul(reserve_b)?
+		.checked_mul(ann.checked_mul(reserve_a)?.checked_add(d_p)?)?;
+	let denominator = reserve_a.checked_mul(ann.checked_mul(reserve_b)?.checked_add(d_p)?)?;
+
+	Balance::try_from(numerator.checked_div(denominator)?).ok()
+}
+
+/// Calculate `A * n^n`.
+fn calculate_ann(n: usize, amplification: u128) -> Option<U256> {
+	let n_u256 = U256::from(n);
+	(0..n).try_fold(U256::from(amplification), |acc, _| acc.checked_mul(n_u256))
+}
+
+fn has_converged(previous: U256, current: U256) -> bool {
+	if current > previous {
+		current - previous <= U256::one()
+	} else {
+		previous - current <= U256::one()
+	}
+}
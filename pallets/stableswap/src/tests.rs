@@ -0,0 +1,633 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+pub use crate::mock::{
+	Currency, Event as TestEvent, ExtBuilder, Origin, Stableswap, System, Test, ALICE, AUSD, BOB, DAI, HDX, USDC, USDT,
+};
+use frame_support::{assert_noop, assert_ok};
+use hydradx_traits::pools::SpotPriceProvider;
+use primitives::Price;
+use sp_runtime::DispatchError::BadOrigin;
+
+const POOL_LIQUIDITY: Balance = 100_000_000_000_000;
+const AMPLIFICATION: u32 = 100;
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut ext = ExtBuilder::default().build();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+fn expect_events(e: Vec<TestEvent>) {
+	e.into_iter().for_each(frame_system::Pallet::<Test>::assert_has_event);
+}
+
+fn pool_fee() -> Permill {
+	Permill::from_parts(3_000)
+}
+
+// Creates a pool of `assets` with `POOL_LIQUIDITY` of each asset provided by ALICE and returns its id.
+fn create_pool_with_liquidity(assets: Vec<AssetId>) -> AssetId {
+	assert_ok!(Stableswap::create_pool(
+		Origin::root(),
+		assets.clone(),
+		AMPLIFICATION,
+		pool_fee()
+	));
+
+	let pool_id = Stableswap::asset_pair_pool((assets[0].min(assets[1]), assets[0].max(assets[1]))).unwrap();
+
+	assert_ok!(Stableswap::add_liquidity(
+		Origin::signed(ALICE),
+		pool_id,
+		assets.into_iter().map(|asset| (asset, POOL_LIQUIDITY)).collect(),
+		0
+	));
+
+	pool_id
+}
+
+#[test]
+fn create_pool_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Stableswap::create_pool(
+			Origin::root(),
+			vec![USDT, AUSD],
+			AMPLIFICATION,
+			pool_fee()
+		));
+
+		let pool_id = Stableswap::asset_pair_pool((AUSD, USDT)).unwrap();
+
+		assert_eq!(
+			Stableswap::pools(pool_id),
+			Some(PoolInfo {
+				assets: vec![AUSD, USDT].try_into().unwrap(),
+				initial_amplification: AMPLIFICATION,
+				final_amplification: AMPLIFICATION,
+				initial_block: 1,
+				final_block: 1,
+				fee: pool_fee(),
+			})
+		);
+
+		expect_events(vec![Event::PoolCreated(
+			pool_id,
+			vec![AUSD, USDT],
+			AMPLIFICATION,
+			pool_fee(),
+			Stableswap::pool_account(pool_id),
+		)
+		.into()]);
+	});
+}
+
+#[test]
+fn create_pool_should_register_all_pairs_of_assets() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Stableswap::create_pool(
+			Origin::root(),
+			vec![USDC, AUSD, USDT],
+			AMPLIFICATION,
+			pool_fee()
+		));
+
+		let pool_id = Stableswap::asset_pair_pool((AUSD, USDT)).unwrap();
+
+		assert_eq!(Stableswap::asset_pair_pool((AUSD, USDC)), Some(pool_id));
+		assert_eq!(Stableswap::asset_pair_pool((USDT, USDC)), Some(pool_id));
+		assert_eq!(
+			Stableswap::get_pool_assets(&Stableswap::pool_account(pool_id)),
+			Some(vec![AUSD, USDT, USDC])
+		);
+		assert_eq!(
+			Stableswap::get_fee(&Stableswap::pool_account(pool_id)),
+			(3_000, 1_000_000)
+		);
+	});
+}
+
+#[test]
+fn create_pool_should_not_work_with_invalid_params() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Stableswap::create_pool(Origin::signed(ALICE), vec![AUSD, USDT], AMPLIFICATION, pool_fee()),
+			BadOrigin
+		);
+		assert_noop!(
+			Stableswap::create_pool(Origin::root(), vec![AUSD], AMPLIFICATION, pool_fee()),
+			Error::<Test>::IncorrectAssets
+		);
+		assert_noop!(
+			Stableswap::create_pool(Origin::root(), vec![AUSD, AUSD], AMPLIFICATION, pool_fee()),
+			Error::<Test>::IncorrectAssets
+		);
+		assert_noop!(
+			Stableswap::create_pool(Origin::root(), vec![AUSD, USDT, USDC, DAI], AMPLIFICATION, pool_fee()),
+			Error::<Test>::MaxAssetsExceeded
+		);
+		assert_noop!(
+			Stableswap::create_pool(Origin::root(), vec![AUSD, USDT], 0, pool_fee()),
+			Error::<Test>::InvalidAmplification
+		);
+		assert_noop!(
+			Stableswap::create_pool(Origin::root(), vec![AUSD, USDT], 10_001, pool_fee()),
+			Error::<Test>::InvalidAmplification
+		);
+	});
+}
+
+#[test]
+fn create_pool_should_not_work_when_pair_of_assets_is_in_another_pool() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Stableswap::create_pool(
+			Origin::root(),
+			vec![AUSD, USDT],
+			AMPLIFICATION,
+			pool_fee()
+		));
+
+		assert_noop!(
+			Stableswap::create_pool(Origin::root(), vec![USDT, USDC, AUSD], AMPLIFICATION, pool_fee()),
+			Error::<Test>::PoolAlreadyExists
+		);
+
+		assert_ok!(Stableswap::create_pool(
+			Origin::root(),
+			vec![USDT, USDC],
+			AMPLIFICATION,
+			pool_fee()
+		));
+	});
+}
+
+#[test]
+fn add_initial_liquidity_should_work() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_liquidity(vec![AUSD, USDT]);
+		let pool_account = Stableswap::pool_account(pool_id);
+
+		// invariant of balanced pool is the sum of reserves
+		assert_eq!(Currency::free_balance(pool_id, &ALICE), 2 * POOL_LIQUIDITY);
+		assert_eq!(Currency::free_balance(AUSD, &pool_account), POOL_LIQUIDITY);
+		assert_eq!(Currency::free_balance(USDT, &pool_account), POOL_LIQUIDITY);
+		assert_eq!(
+			Currency::free_balance(AUSD, &ALICE),
+			1_000_000_000_000_000 - POOL_LIQUIDITY
+		);
+
+		expect_events(vec![Event::LiquidityAdded(
+			ALICE,
+			pool_id,
+			vec![(AUSD, POOL_LIQUIDITY), (USDT, POOL_LIQUIDITY)],
+			2 * POOL_LIQUIDITY,
+		)
+		.into()]);
+	});
+}
+
+#[test]
+fn add_initial_liquidity_should_not_work_without_all_assets() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Stableswap::create_pool(
+			Origin::root(),
+			vec![AUSD, USDT],
+			AMPLIFICATION,
+			pool_fee()
+		));
+		let pool_id = Stableswap::asset_pair_pool((AUSD, USDT)).unwrap();
+
+		assert_noop!(
+			Stableswap::add_liquidity(Origin::signed(ALICE), pool_id, vec![(AUSD, POOL_LIQUIDITY)], 0),
+			Error::<Test>::InitialLiquidityMissing
+		);
+	});
+}
+
+#[test]
+fn add_liquidity_should_not_work_with_invalid_assets() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_liquidity(vec![AUSD, USDT]);
+
+		assert_noop!(
+			Stableswap::add_liquidity(Origin::signed(BOB), pool_id, vec![(USDC, POOL_LIQUIDITY)], 0),
+			Error::<Test>::AssetNotInPool
+		);
+		assert_noop!(
+			Stableswap::add_liquidity(
+				Origin::signed(BOB),
+				pool_id,
+				vec![(AUSD, POOL_LIQUIDITY), (AUSD, POOL_LIQUIDITY)],
+				0
+			),
+			Error::<Test>::IncorrectAssets
+		);
+		assert_noop!(
+			Stableswap::add_liquidity(Origin::signed(BOB), HDX, vec![(AUSD, POOL_LIQUIDITY)], 0),
+			Error::<Test>::PoolNotFound
+		);
+		assert_noop!(
+			Stableswap::add_liquidity(Origin::signed(BOB), pool_id, vec![(AUSD, 2_000_000_000_000_000)], 0),
+			Error::<Test>::InsufficientAssetBalance
+		);
+	});
+}
+
+#[test]
+fn add_balanced_liquidity_should_issue_proportional_shares() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_liquidity(vec![AUSD, USDT]);
+
+		assert_ok!(Stableswap::add_liquidity(
+			Origin::signed(BOB),
+			pool_id,
+			vec![(AUSD, 10_000_000_000_000), (USDT, 10_000_000_000_000)],
+			20_000_000_000_000
+		));
+
+		assert_eq!(Currency::free_balance(pool_id, &BOB), 20_000_000_000_000);
+	});
+}
+
+#[test]
+fn add_imbalanced_liquidity_should_charge_fee() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_liquidity(vec![AUSD, USDT]);
+
+		assert_noop!(
+			Stableswap::add_liquidity(
+				Origin::signed(BOB),
+				pool_id,
+				vec![(AUSD, 10_000_000_000_000)],
+				9_983_812_689_730
+			),
+			Error::<Test>::SharesAmountNotReachedLimit
+		);
+
+		assert_ok!(Stableswap::add_liquidity(
+			Origin::signed(BOB),
+			pool_id,
+			vec![(AUSD, 10_000_000_000_000)],
+			9_983_812_689_729
+		));
+
+		assert_eq!(Currency::free_balance(pool_id, &BOB), 9_983_812_689_729);
+		assert_eq!(
+			Currency::free_balance(AUSD, &Stableswap::pool_account(pool_id)),
+			POOL_LIQUIDITY + 10_000_000_000_000
+		);
+	});
+}
+
+#[test]
+fn remove_liquidity_should_work() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_liquidity(vec![AUSD, USDT]);
+		let pool_account = Stableswap::pool_account(pool_id);
+
+		assert_ok!(Stableswap::remove_liquidity(
+			Origin::signed(ALICE),
+			pool_id,
+			50_000_000_000_000,
+			vec![(AUSD, 25_000_000_000_000), (USDT, 25_000_000_000_000)]
+		));
+
+		assert_eq!(Currency::free_balance(pool_id, &ALICE), 150_000_000_000_000);
+		assert_eq!(Currency::free_balance(AUSD, &pool_account), 75_000_000_000_000);
+		assert_eq!(Currency::free_balance(USDT, &pool_account), 75_000_000_000_000);
+		assert_eq!(
+			Currency::free_balance(USDT, &ALICE),
+			1_000_000_000_000_000 - 75_000_000_000_000
+		);
+
+		expect_events(vec![Event::LiquidityRemoved(
+			ALICE,
+			pool_id,
+			50_000_000_000_000,
+			vec![(AUSD, 25_000_000_000_000), (USDT, 25_000_000_000_000)],
+		)
+		.into()]);
+
+		assert_ok!(Stableswap::remove_liquidity(
+			Origin::signed(ALICE),
+			pool_id,
+			150_000_000_000_000,
+			vec![]
+		));
+
+		assert_eq!(Currency::free_balance(AUSD, &pool_account), 0);
+		assert_eq!(Currency::free_balance(USDT, &pool_account), 0);
+		assert_eq!(Currency::free_balance(AUSD, &ALICE), 1_000_000_000_000_000);
+	});
+}
+
+#[test]
+fn remove_liquidity_should_not_work_with_invalid_amount() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_liquidity(vec![AUSD, USDT]);
+
+		assert_noop!(
+			Stableswap::remove_liquidity(Origin::signed(ALICE), pool_id, 0, vec![]),
+			Error::<Test>::ZeroShares
+		);
+		assert_noop!(
+			Stableswap::remove_liquidity(Origin::signed(BOB), pool_id, 1_000_000, vec![]),
+			Error::<Test>::InsufficientShares
+		);
+		assert_noop!(
+			Stableswap::remove_liquidity(Origin::signed(ALICE), pool_id, 2 * POOL_LIQUIDITY - 999, vec![]),
+			Error::<Test>::InsufficientLiquidityRemaining
+		);
+	});
+}
+
+#[test]
+fn sell_should_work() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_liquidity(vec![AUSD, USDT]);
+		let pool_account = Stableswap::pool_account(pool_id);
+
+		assert_ok!(Stableswap::sell(
+			Origin::signed(BOB),
+			AUSD,
+			USDT,
+			10_000_000_000_000,
+			9_964_992_743_975
+		));
+
+		assert_eq!(
+			Currency::free_balance(AUSD, &BOB),
+			1_000_000_000_000_000 - 10_000_000_000_000
+		);
+		assert_eq!(
+			Currency::free_balance(USDT, &BOB),
+			1_000_000_000_000_000 + 9_964_992_743_975
+		);
+		assert_eq!(
+			Currency::free_balance(USDT, &pool_account),
+			POOL_LIQUIDITY - 9_964_992_743_975
+		);
+
+		expect_events(vec![Event::SellExecuted(
+			BOB,
+			AUSD,
+			USDT,
+			10_000_000_000_000,
+			9_964_992_743_975,
+			USDT,
+			29_984_933_032,
+			pool_id,
+		)
+		.into()]);
+	});
+}
+
+#[test]
+fn sell_should_work_in_pool_of_more_assets() {
+	new_test_ext().execute_with(|| {
+		create_pool_with_liquidity(vec![AUSD, USDT, USDC]);
+
+		assert_ok!(Stableswap::sell(
+			Origin::signed(BOB),
+			USDC,
+			AUSD,
+			10_000_000_000_000,
+			9_968_882_426_186
+		));
+
+		assert_eq!(
+			Currency::free_balance(AUSD, &BOB),
+			1_000_000_000_000_000 + 9_968_882_426_186
+		);
+	});
+}
+
+#[test]
+fn sell_should_not_work_when_limit_is_not_reached() {
+	new_test_ext().execute_with(|| {
+		create_pool_with_liquidity(vec![AUSD, USDT]);
+
+		assert_noop!(
+			Stableswap::sell(Origin::signed(BOB), AUSD, USDT, 10_000_000_000_000, 9_964_992_743_976),
+			Error::<Test>::AssetAmountNotReachedLimit
+		);
+		assert_noop!(
+			Stableswap::sell(Origin::signed(BOB), AUSD, USDC, 10_000_000_000_000, 0),
+			Error::<Test>::PoolNotFound
+		);
+		assert_noop!(
+			Stableswap::sell(Origin::signed(BOB), AUSD, USDT, 999, 0),
+			Error::<Test>::InsufficientTradingAmount
+		);
+		assert_noop!(
+			Stableswap::sell(Origin::signed(BOB), AUSD, USDT, POOL_LIQUIDITY / 3 + 1, 0),
+			Error::<Test>::MaxInRatioExceeded
+		);
+	});
+}
+
+#[test]
+fn buy_should_work() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_liquidity(vec![AUSD, USDT]);
+
+		assert_noop!(
+			Stableswap::buy(Origin::signed(BOB), USDT, AUSD, 10_000_000_000_000, 10_035_042_505_624),
+			Error::<Test>::AssetAmountExceededLimit
+		);
+
+		assert_ok!(Stableswap::buy(
+			Origin::signed(BOB),
+			USDT,
+			AUSD,
+			10_000_000_000_000,
+			10_035_042_505_625
+		));
+
+		assert_eq!(
+			Currency::free_balance(AUSD, &BOB),
+			1_000_000_000_000_000 - 10_035_042_505_625
+		);
+		assert_eq!(
+			Currency::free_balance(USDT, &BOB),
+			1_000_000_000_000_000 + 10_000_000_000_000
+		);
+
+		expect_events(vec![Event::BuyExecuted(
+			BOB,
+			USDT,
+			AUSD,
+			10_000_000_000_000,
+			10_005_027_423_354,
+			AUSD,
+			30_015_082_271,
+			pool_id,
+		)
+		.into()]);
+	});
+}
+
+#[test]
+fn trade_calculation_should_match_executed_trades() {
+	new_test_ext().execute_with(|| {
+		create_pool_with_liquidity(vec![AUSD, USDT]);
+
+		assert_eq!(
+			Stableswap::calculate_sell(AssetPair::new(AUSD, USDT), 10_000_000_000_000),
+			Ok(9_964_992_743_975)
+		);
+		assert_eq!(
+			Stableswap::calculate_buy(AssetPair::new(AUSD, USDT), 10_000_000_000_000),
+			Ok(10_035_042_505_625)
+		);
+	});
+}
+
+#[test]
+fn trade_with_discount_should_not_work() {
+	new_test_ext().execute_with(|| {
+		create_pool_with_liquidity(vec![AUSD, USDT]);
+
+		assert_noop!(
+			Stableswap::validate_sell(&BOB, AssetPair::new(AUSD, USDT), 10_000_000_000_000, 0, true).map(|_| ()),
+			Error::<Test>::DiscountNotSupported
+		);
+	});
+}
+
+#[test]
+fn spot_price_of_balanced_pool_should_be_one() {
+	new_test_ext().execute_with(|| {
+		create_pool_with_liquidity(vec![AUSD, USDT]);
+
+		assert_eq!(
+			Stableswap::get_spot_price_unchecked(AUSD, USDT, 1_000_000_000_000),
+			1_000_000_000_000
+		);
+		assert_eq!(
+			StableswapSpotPrice::<Test>::spot_price(AUSD, USDT),
+			Some(Price::from(1))
+		);
+		assert_eq!(StableswapSpotPrice::<Test>::spot_price(AUSD, USDC), None);
+	});
+}
+
+#[test]
+fn remove_liquidity_should_not_work_when_min_amounts_are_not_reached() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_liquidity(vec![AUSD, USDT]);
+
+		assert_noop!(
+			Stableswap::remove_liquidity(
+				Origin::signed(ALICE),
+				pool_id,
+				50_000_000_000_000,
+				vec![(USDT, 25_000_000_000_001)]
+			),
+			Error::<Test>::AssetAmountNotReachedLimit
+		);
+		assert_noop!(
+			Stableswap::remove_liquidity(Origin::signed(ALICE), pool_id, 50_000_000_000_000, vec![(DAI, 0)]),
+			Error::<Test>::AssetNotInPool
+		);
+		assert_noop!(
+			Stableswap::remove_liquidity(
+				Origin::signed(ALICE),
+				pool_id,
+				50_000_000_000_000,
+				vec![(AUSD, 0), (USDT, 0), (AUSD, 0)]
+			),
+			Error::<Test>::IncorrectAssets
+		);
+	});
+}
+
+#[test]
+fn ramp_amplification_should_work() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_liquidity(vec![AUSD, USDT]);
+
+		assert_ok!(Stableswap::ramp_amplification(Origin::root(), pool_id, 200, 11));
+
+		expect_events(vec![Event::AmplificationRampStarted(pool_id, 100, 200, 1, 11).into()]);
+
+		let amount_out_before_ramp =
+			Stableswap::calculate_sell(AssetPair::new(AUSD, USDT), 10_000_000_000_000).unwrap();
+
+		System::set_block_number(6);
+		assert_eq!(Stableswap::amplification(&Stableswap::pools(pool_id).unwrap()), 150);
+
+		// higher amplification means lower price impact
+		let amount_out_during_ramp =
+			Stableswap::calculate_sell(AssetPair::new(AUSD, USDT), 10_000_000_000_000).unwrap();
+		assert!(amount_out_during_ramp > amount_out_before_ramp);
+
+		// ramp in progress is replaced from the current amplification
+		assert_ok!(Stableswap::ramp_amplification(Origin::root(), pool_id, 50, 16));
+		expect_events(vec![Event::AmplificationRampStarted(pool_id, 150, 50, 6, 16).into()]);
+
+		System::set_block_number(20);
+		assert_eq!(Stableswap::amplification(&Stableswap::pools(pool_id).unwrap()), 50);
+	});
+}
+
+#[test]
+fn ramp_amplification_should_not_work_with_invalid_params() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_liquidity(vec![AUSD, USDT]);
+
+		assert_noop!(
+			Stableswap::ramp_amplification(Origin::signed(ALICE), pool_id, 200, 11),
+			BadOrigin
+		);
+		assert_noop!(
+			Stableswap::ramp_amplification(Origin::root(), pool_id, 200, 1),
+			Error::<Test>::InvalidRampBlock
+		);
+		assert_noop!(
+			Stableswap::ramp_amplification(Origin::root(), pool_id, 200, 10),
+			Error::<Test>::InvalidRampBlock
+		);
+		assert_noop!(
+			Stableswap::ramp_amplification(Origin::root(), pool_id, 1_001, 11),
+			Error::<Test>::AmplificationChangeTooLarge
+		);
+		assert_noop!(
+			Stableswap::ramp_amplification(Origin::root(), pool_id, 9, 11),
+			Error::<Test>::AmplificationChangeTooLarge
+		);
+		assert_noop!(
+			Stableswap::ramp_amplification(Origin::root(), pool_id, 10_001, 11),
+			Error::<Test>::InvalidAmplification
+		);
+		assert_noop!(
+			Stableswap::ramp_amplification(Origin::root(), HDX, 200, 11),
+			Error::<Test>::PoolNotFound
+		);
+	});
+}
+
+#[test]
+fn calculate_d_should_be_sum_of_balanced_reserves() {
+	assert_eq!(
+		math::calculate_d(&[POOL_LIQUIDITY, POOL_LIQUIDITY, POOL_LIQUIDITY], 100),
+		Some(3 * POOL_LIQUIDITY)
+	);
+	assert_eq!(math::calculate_d(&[0, 0], 100), Some(0));
+	assert_eq!(math::calculate_d(&[POOL_LIQUIDITY, 0], 100), None);
+}
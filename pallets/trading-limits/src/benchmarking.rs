@@ -0,0 +1,68 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_benchmarking::{account, benchmarks};
+use frame_system::RawOrigin;
+use sp_std::prelude::*;
+
+use crate::Pallet as TradingLimitsPallet;
+
+const SEED: u32 = 1;
+
+fn limits() -> TradingLimits {
+	TradingLimits {
+		min_trading_limit: 2_000,
+		min_pool_liquidity: 5_000,
+		max_in_ratio: 5,
+		max_out_ratio: 4,
+	}
+}
+
+benchmarks! {
+	set_default_limits {
+	}: _(RawOrigin::Root, limits())
+	verify {
+		assert_eq!(TradingLimitsPallet::<T>::default_trading_limits(), limits());
+	}
+
+	set_pool_limits {
+		let pool_account: T::AccountId = account("pool", 0, SEED);
+
+	}: _(RawOrigin::Root, pool_account.clone(), Some(limits()))
+	verify {
+		assert_eq!(TradingLimitsPallet::<T>::pool_trading_limits(&pool_account), Some(limits()));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::tests::{new_test_ext, Test};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_benchmarks() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Pallet::<Test>::test_benchmark_set_default_limits());
+			assert_ok!(Pallet::<Test>::test_benchmark_set_pool_limits());
+		});
+	}
+}
@@ -0,0 +1,195 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Trading Limits Pallet
+//!
+//! ## Overview
+//!
+//! Trading limits pallet stores limits of trades and liquidity in pools - minimum trading amount,
+//! minimum pool liquidity and max fractions of pool reserves traded in a single transaction.
+//!
+//! Limits can be changed by governance without a runtime upgrade. Default limits apply to all pools,
+//! they can be overridden for a single pool identified by its account.
+//!
+//! AMM pallets read the limits via `TradingLimitsProvider` trait implemented by this pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{dispatch::DispatchResult, ensure, sp_runtime::traits::Zero, traits::Get};
+use primitives::{traits::TradingLimitsProvider, Balance, TradingLimits};
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+mod benchmarking;
+
+pub mod weights;
+
+use weights::WeightInfo;
+
+// Re-export pallet items so that they can be accessed from the crate namespace.
+pub use pallet::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::OriginFor;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The origin which can change trading limits
+		type AuthorityOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Minimum trading limit used until default limits are changed
+		#[pallet::constant]
+		type DefaultMinTradingLimit: Get<Balance>;
+
+		/// Minimum pool liquidity used until default limits are changed
+		#[pallet::constant]
+		type DefaultMinPoolLiquidity: Get<Balance>;
+
+		/// Max fraction of pool to sell in single transaction used until default limits are changed
+		#[pallet::constant]
+		type DefaultMaxInRatio: Get<u128>;
+
+		/// Max fraction of pool to buy in single transaction used until default limits are changed
+		#[pallet::constant]
+		type DefaultMaxOutRatio: Get<u128>;
+
+		/// Weight information for the extrinsics.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Minimum pool liquidity and max ratios have to be non-zero.
+		InvalidTradingLimits,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Default trading limits were updated. [limits]
+		DefaultLimitsUpdated(TradingLimits),
+
+		/// Trading limits of a pool were updated, `None` if default limits apply. [pool account id, limits]
+		PoolLimitsUpdated(T::AccountId, Option<TradingLimits>),
+	}
+
+	#[pallet::type_value]
+	pub fn DefaultTradingLimits<T: Config>() -> TradingLimits {
+		TradingLimits {
+			min_trading_limit: T::DefaultMinTradingLimit::get(),
+			min_pool_liquidity: T::DefaultMinPoolLiquidity::get(),
+			max_in_ratio: T::DefaultMaxInRatio::get(),
+			max_out_ratio: T::DefaultMaxOutRatio::get(),
+		}
+	}
+
+	/// Trading limits of pools without their own limits.
+	#[pallet::storage]
+	#[pallet::getter(fn default_trading_limits)]
+	pub type DefaultLimits<T: Config> = StorageValue<_, TradingLimits, ValueQuery, DefaultTradingLimits<T>>;
+
+	/// Trading limits of pools which override the default limits.
+	#[pallet::storage]
+	#[pallet::getter(fn pool_trading_limits)]
+	pub type PoolLimits<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, TradingLimits, OptionQuery>;
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set trading limits of all pools which don't have their own limits.
+		///
+		/// The dispatch origin for this call must be `T::AuthorityOrigin`.
+		///
+		/// Emits `DefaultLimitsUpdated` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::set_default_limits())]
+		pub fn set_default_limits(origin: OriginFor<T>, limits: TradingLimits) -> DispatchResult {
+			T::AuthorityOrigin::ensure_origin(origin)?;
+
+			Self::ensure_valid_limits(&limits)?;
+
+			<DefaultLimits<T>>::put(limits);
+
+			Self::deposit_event(Event::DefaultLimitsUpdated(limits));
+
+			Ok(())
+		}
+
+		/// Set trading limits of the pool identified by `pool_account`.
+		///
+		/// `None` removes limits of the pool, default limits apply to the pool afterwards.
+		///
+		/// The dispatch origin for this call must be `T::AuthorityOrigin`.
+		///
+		/// Emits `PoolLimitsUpdated` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::set_pool_limits())]
+		pub fn set_pool_limits(
+			origin: OriginFor<T>,
+			pool_account: T::AccountId,
+			limits: Option<TradingLimits>,
+		) -> DispatchResult {
+			T::AuthorityOrigin::ensure_origin(origin)?;
+
+			match limits {
+				Some(limits) => {
+					Self::ensure_valid_limits(&limits)?;
+					<PoolLimits<T>>::insert(&pool_account, limits);
+				}
+				None => <PoolLimits<T>>::remove(&pool_account),
+			}
+
+			Self::deposit_event(Event::PoolLimitsUpdated(pool_account, limits));
+
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	fn ensure_valid_limits(limits: &TradingLimits) -> DispatchResult {
+		ensure!(
+			!limits.min_pool_liquidity.is_zero() && !limits.max_in_ratio.is_zero() && !limits.max_out_ratio.is_zero(),
+			Error::<T>::InvalidTradingLimits
+		);
+
+		Ok(())
+	}
+}
+
+impl<T: Config> TradingLimitsProvider<T::AccountId> for Pallet<T> {
+	fn default_limits() -> TradingLimits {
+		Self::default_trading_limits()
+	}
+
+	fn pool_limits(pool_account: &T::AccountId) -> TradingLimits {
+		Self::pool_trading_limits(pool_account).unwrap_or_else(Self::default_trading_limits)
+	}
+}
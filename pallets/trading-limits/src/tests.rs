@@ -0,0 +1,190 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+pub use crate::mock::{
+	Event as TestEvent, ExtBuilder, Origin, System, Test, TradingLimits as TradingLimitsPallet, ALICE, OTHER_POOL, POOL,
+};
+use frame_support::{assert_noop, assert_ok};
+use primitives::constants::chain::{MAX_IN_RATIO, MAX_OUT_RATIO, MIN_POOL_LIQUIDITY, MIN_TRADING_LIMIT};
+use sp_runtime::DispatchError::BadOrigin;
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut ext = ExtBuilder::default().build();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+fn expect_events(e: Vec<TestEvent>) {
+	e.into_iter().for_each(frame_system::Pallet::<Test>::assert_has_event);
+}
+
+fn limits() -> TradingLimits {
+	TradingLimits {
+		min_trading_limit: 2_000,
+		min_pool_liquidity: 5_000,
+		max_in_ratio: 5,
+		max_out_ratio: 4,
+	}
+}
+
+#[test]
+fn default_limits_should_be_constants_until_changed() {
+	new_test_ext().execute_with(|| {
+		let default_limits = TradingLimits {
+			min_trading_limit: MIN_TRADING_LIMIT,
+			min_pool_liquidity: MIN_POOL_LIQUIDITY,
+			max_in_ratio: MAX_IN_RATIO,
+			max_out_ratio: MAX_OUT_RATIO,
+		};
+
+		assert_eq!(TradingLimitsPallet::default_limits(), default_limits);
+		assert_eq!(TradingLimitsPallet::pool_limits(&POOL), default_limits);
+	});
+}
+
+#[test]
+fn set_default_limits_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TradingLimitsPallet::set_default_limits(Origin::root(), limits()));
+
+		assert_eq!(TradingLimitsPallet::default_limits(), limits());
+		assert_eq!(TradingLimitsPallet::pool_limits(&POOL), limits());
+
+		expect_events(vec![Event::DefaultLimitsUpdated(limits()).into()]);
+	});
+}
+
+#[test]
+fn set_default_limits_should_not_work_when_origin_is_not_authority() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TradingLimitsPallet::set_default_limits(Origin::signed(ALICE), limits()),
+			BadOrigin
+		);
+	});
+}
+
+#[test]
+fn set_default_limits_should_not_work_when_limits_are_invalid() {
+	new_test_ext().execute_with(|| {
+		for invalid_limits in [
+			TradingLimits {
+				min_pool_liquidity: 0,
+				..limits()
+			},
+			TradingLimits {
+				max_in_ratio: 0,
+				..limits()
+			},
+			TradingLimits {
+				max_out_ratio: 0,
+				..limits()
+			},
+		] {
+			assert_noop!(
+				TradingLimitsPallet::set_default_limits(Origin::root(), invalid_limits),
+				Error::<Test>::InvalidTradingLimits
+			);
+		}
+
+		// zero min trading limit is allowed
+		assert_ok!(TradingLimitsPallet::set_default_limits(
+			Origin::root(),
+			TradingLimits {
+				min_trading_limit: 0,
+				..limits()
+			}
+		));
+	});
+}
+
+#[test]
+fn set_pool_limits_should_override_default_limits() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TradingLimitsPallet::set_pool_limits(
+			Origin::root(),
+			POOL,
+			Some(limits())
+		));
+
+		assert_eq!(TradingLimitsPallet::pool_limits(&POOL), limits());
+		assert_eq!(
+			TradingLimitsPallet::pool_limits(&OTHER_POOL),
+			TradingLimitsPallet::default_limits()
+		);
+
+		expect_events(vec![Event::PoolLimitsUpdated(POOL, Some(limits())).into()]);
+
+		// default limits don't change limits of the pool
+		let default_limits = TradingLimits {
+			min_trading_limit: 3_000,
+			..limits()
+		};
+		assert_ok!(TradingLimitsPallet::set_default_limits(Origin::root(), default_limits));
+
+		assert_eq!(TradingLimitsPallet::pool_limits(&POOL), limits());
+		assert_eq!(TradingLimitsPallet::pool_limits(&OTHER_POOL), default_limits);
+	});
+}
+
+#[test]
+fn set_pool_limits_should_remove_limits_of_pool_when_limits_are_none() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TradingLimitsPallet::set_pool_limits(
+			Origin::root(),
+			POOL,
+			Some(limits())
+		));
+		assert_ok!(TradingLimitsPallet::set_pool_limits(Origin::root(), POOL, None));
+
+		assert_eq!(TradingLimitsPallet::pool_trading_limits(&POOL), None);
+		assert_eq!(
+			TradingLimitsPallet::pool_limits(&POOL),
+			TradingLimitsPallet::default_limits()
+		);
+
+		expect_events(vec![Event::PoolLimitsUpdated(POOL, None).into()]);
+	});
+}
+
+#[test]
+fn set_pool_limits_should_not_work_when_origin_is_not_authority() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TradingLimitsPallet::set_pool_limits(Origin::signed(ALICE), POOL, Some(limits())),
+			BadOrigin
+		);
+	});
+}
+
+#[test]
+fn set_pool_limits_should_not_work_when_limits_are_invalid() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TradingLimitsPallet::set_pool_limits(
+				Origin::root(),
+				POOL,
+				Some(TradingLimits {
+					max_in_ratio: 0,
+					..limits()
+				})
+			),
+			Error::<Test>::InvalidTradingLimits
+		);
+	});
+}
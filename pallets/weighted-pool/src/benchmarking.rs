@@ -0,0 +1,175 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_benchmarking::{account, benchmarks};
+use frame_system::RawOrigin;
+use sp_std::prelude::*;
+
+use crate::Pallet as WeightedPool;
+
+const SEED: u32 = 1;
+
+// Pools of max number of assets are benchmarked.
+fn pool_assets<T: Config>() -> Vec<AssetId> {
+	(0..T::MaxAssetsInPool::get()).map(|i| 1_000 + i).collect()
+}
+
+// Equal weights of `assets`, the first asset takes the remainder.
+fn pool_weights(assets: &[AssetId]) -> Vec<(AssetId, LBPWeight)> {
+	let weight = MAX_WEIGHT / assets.len() as LBPWeight;
+	let remainder = MAX_WEIGHT - weight * assets.len() as LBPWeight;
+
+	assets
+		.iter()
+		.enumerate()
+		.map(|(i, asset)| (*asset, if i == 0 { weight + remainder } else { weight }))
+		.collect()
+}
+
+fn funded_account<T: Config>(name: &'static str, index: u32, assets: &[AssetId]) -> T::AccountId {
+	let caller: T::AccountId = account(name, index, SEED);
+	for asset in assets {
+		T::Currency::update_balance(*asset, &caller, 1_000_000_000_000_000).unwrap();
+	}
+	caller
+}
+
+fn create_pool_with_liquidity<T: Config>(assets: &[AssetId]) -> Result<AssetId, DispatchError> {
+	let maker = funded_account::<T>("maker", 0, assets);
+
+	WeightedPool::<T>::create_pool(RawOrigin::Root.into(), pool_weights(assets), Permill::from_parts(3_000))?;
+
+	let pool_id = WeightedPool::<T>::asset_pair_pool((assets[0], assets[1])).ok_or(Error::<T>::PoolNotFound)?;
+
+	WeightedPool::<T>::add_liquidity(
+		RawOrigin::Signed(maker).into(),
+		pool_id,
+		assets.iter().map(|asset| (*asset, 1_000_000_000_000_000)).collect(),
+		0,
+	)?;
+
+	Ok(pool_id)
+}
+
+benchmarks! {
+	create_pool {
+		let assets = pool_assets::<T>();
+
+	}: _(RawOrigin::Root, pool_weights(&assets), Permill::from_parts(3_000))
+	verify {
+		assert!(WeightedPool::<T>::asset_pair_pool((assets[0], assets[1])).is_some());
+	}
+
+	add_liquidity {
+		let assets = pool_assets::<T>();
+		let pool_id = create_pool_with_liquidity::<T>(&assets)?;
+
+		let caller = funded_account::<T>("caller", 0, &assets);
+		let liquidity = assets.iter().map(|asset| (*asset, 10_000_000_000_000)).collect();
+
+	}: _(RawOrigin::Signed(caller.clone()), pool_id, liquidity, 0)
+	verify {
+		assert!(T::Currency::free_balance(pool_id, &caller) > 0);
+	}
+
+	add_liquidity_single_asset {
+		let assets = pool_assets::<T>();
+		let pool_id = create_pool_with_liquidity::<T>(&assets)?;
+
+		let caller = funded_account::<T>("caller", 0, &assets[..1]);
+
+	}: _(RawOrigin::Signed(caller.clone()), pool_id, assets[0], 10_000_000_000_000, 0)
+	verify {
+		assert!(T::Currency::free_balance(pool_id, &caller) > 0);
+	}
+
+	remove_liquidity {
+		let assets = pool_assets::<T>();
+		let pool_id = create_pool_with_liquidity::<T>(&assets)?;
+
+		let caller = funded_account::<T>("caller", 0, &assets);
+		let liquidity = assets.iter().map(|asset| (*asset, 10_000_000_000_000)).collect();
+		WeightedPool::<T>::add_liquidity(RawOrigin::Signed(caller.clone()).into(), pool_id, liquidity, 0)?;
+
+		let shares = T::Currency::free_balance(pool_id, &caller);
+
+	}: _(RawOrigin::Signed(caller.clone()), pool_id, shares)
+	verify {
+		assert_eq!(T::Currency::free_balance(pool_id, &caller), 0);
+	}
+
+	remove_liquidity_single_asset {
+		let assets = pool_assets::<T>();
+		let pool_id = create_pool_with_liquidity::<T>(&assets)?;
+
+		let caller = funded_account::<T>("caller", 0, &assets);
+		let liquidity = assets.iter().map(|asset| (*asset, 10_000_000_000_000)).collect();
+		WeightedPool::<T>::add_liquidity(RawOrigin::Signed(caller.clone()).into(), pool_id, liquidity, 0)?;
+
+		let shares = T::Currency::free_balance(pool_id, &caller);
+
+	}: _(RawOrigin::Signed(caller.clone()), pool_id, assets[0], shares, 0)
+	verify {
+		assert_eq!(T::Currency::free_balance(pool_id, &caller), 0);
+	}
+
+	sell {
+		let assets = pool_assets::<T>();
+		create_pool_with_liquidity::<T>(&assets)?;
+
+		let caller = funded_account::<T>("caller", 0, &assets[..1]);
+
+	}: _(RawOrigin::Signed(caller.clone()), assets[0], assets[1], 10_000_000_000_000, 0)
+	verify {
+		assert!(T::Currency::free_balance(assets[1], &caller) > 0);
+	}
+
+	buy {
+		let assets = pool_assets::<T>();
+		create_pool_with_liquidity::<T>(&assets)?;
+
+		let caller = funded_account::<T>("caller", 0, &assets[..1]);
+
+	}: _(RawOrigin::Signed(caller.clone()), assets[1], assets[0], 10_000_000_000_000, 20_000_000_000_000)
+	verify {
+		assert_eq!(T::Currency::free_balance(assets[1], &caller), 10_000_000_000_000);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::tests::{new_test_ext, Test};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_benchmarks() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Pallet::<Test>::test_benchmark_create_pool());
+			assert_ok!(Pallet::<Test>::test_benchmark_add_liquidity());
+			assert_ok!(Pallet::<Test>::test_benchmark_add_liquidity_single_asset());
+			assert_ok!(Pallet::<Test>::test_benchmark_remove_liquidity());
+			assert_ok!(Pallet::<Test>::test_benchmark_remove_liquidity_single_asset());
+			assert_ok!(Pallet::<Test>::test_benchmark_sell());
+			assert_ok!(Pallet::<Test>::test_benchmark_buy());
+		});
+	}
+}
@@ -0,0 +1,27 @@
+use frame_support::sp_runtime::FixedPointNumber;
+use hydradx_traits::pools::SpotPriceProvider;
+use hydradx_traits::AMM;
+use primitives::asset::AssetPair;
+use primitives::{AssetId, Price};
+use sp_std::marker::PhantomData;
+
+pub struct WeightedPoolSpotPrice<T>(PhantomData<T>);
+
+impl<T: crate::Config> SpotPriceProvider<AssetId> for WeightedPoolSpotPrice<T> {
+	type Price = Price;
+
+	fn pair_exists(asset_a: AssetId, asset_b: AssetId) -> bool {
+		<crate::Pallet<T>>::exists(AssetPair::new(asset_b, asset_a))
+	}
+
+	fn spot_price(asset_a: AssetId, asset_b: AssetId) -> Option<Self::Price> {
+		if Self::pair_exists(asset_a, asset_b) {
+			// Amount of `asset_b` for one unit of price accuracy of `asset_a` is the inner value of the price.
+			let price = <crate::Pallet<T>>::get_spot_price_unchecked(asset_a, asset_b, Price::accuracy());
+
+			(price != 0).then(|| Price::from_inner(price))
+		} else {
+			None
+		}
+	}
+}
@@ -0,0 +1,1069 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Weighted Pool Pallet
+//!
+//! ## Overview
+//!
+//! Weighted pool pallet provides functionality for managing Balancer-style liquidity pools of 2 or more assets
+//! with fixed weights (e.g. 80/20) and executing trades in them.
+//!
+//! The pool keeps the value of each asset's reserve at its weight of the total value of the pool.
+//! Prices are calculated by the same weighted math as in the LBP pallet, but weights of a pool never change.
+//!
+//! Liquidity can be added and removed either in all assets of the pool in proportion to their reserves, or in
+//! a single asset. Part of a single asset liquidity which would have to be swapped to the other assets is charged
+//! the trade fee of the pool.
+//!
+//! Pools are created by governance. Each pair of assets can be traded in at most one pool,
+//! so the pool of a trade is determined by the traded assets.
+//!
+//! This pallet implements AMM Api trait therefore it is possible to plug this pool implementation
+//! into the exchange pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+#![allow(clippy::upper_case_acronyms)]
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::sp_runtime::{
+	helpers_128bit::multiply_by_rational,
+	traits::{AccountIdConversion, Zero},
+	DispatchError, Permill, RuntimeDebug,
+};
+use frame_support::{dispatch::DispatchResult, ensure, traits::Get, transactional, BoundedVec, PalletId};
+use frame_system::ensure_signed;
+use hydra_dx_math::types::LBPWeight;
+use hydradx_traits::{AMMTransfer, AMM};
+use orml_traits::{MultiCurrency, MultiCurrencyExtended};
+use primitive_types::U256;
+use primitives::{
+	asset::AssetPair,
+	traits::{AMMTradeCalculation, TradingLimitsProvider},
+	Amount, AssetId, Balance, TradingLimits,
+};
+use scale_info::TypeInfo;
+use sp_std::{vec, vec::Vec};
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+mod benchmarking;
+
+mod impls;
+pub mod weights;
+
+pub use impls::WeightedPoolSpotPrice;
+
+use weights::WeightInfo;
+
+// Re-export pallet items so that they can be accessed from the crate namespace.
+pub use pallet::*;
+
+/// Weight of the whole pool - weights of pool assets have to sum up to it.
+pub const MAX_WEIGHT: LBPWeight = 100_000_000;
+
+/// Min weight of an asset in a pool (1%).
+pub const MIN_WEIGHT: LBPWeight = 1_000_000;
+
+/// Information about a pool.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub struct PoolInfo<Assets, Weights> {
+	/// Assets of the pool, sorted by id.
+	pub assets: Assets,
+	/// Weights of the assets, in the same order. Weights sum up to `MAX_WEIGHT`.
+	pub weights: Weights,
+	/// Trade fee - in `asset_out` for sell, in `asset_in` for buy.
+	pub fee: Permill,
+}
+
+pub type PoolInfoOf<T> = PoolInfo<
+	BoundedVec<AssetId, <T as Config>::MaxAssetsInPool>,
+	BoundedVec<LBPWeight, <T as Config>::MaxAssetsInPool>,
+>;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::OriginFor;
+	use hydradx_traits::ShareTokenRegistry;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Registry support
+		type AssetRegistry: ShareTokenRegistry<AssetId, Vec<u8>, Balance, DispatchError>;
+
+		/// Multi currency for transfer of currencies
+		type Currency: MultiCurrencyExtended<Self::AccountId, CurrencyId = AssetId, Balance = Balance, Amount = Amount>;
+
+		/// Pallet id used to derive pool accounts
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// The origin which can create pools
+		type AuthorityOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Max number of assets in a single pool
+		#[pallet::constant]
+		type MaxAssetsInPool: Get<u32>;
+
+		/// Trading limits of pools
+		type TradingLimits: TradingLimitsProvider<Self::AccountId>;
+
+		/// Weight information for the extrinsics.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Pool has to consist of at least two distinct assets.
+		IncorrectAssets,
+
+		/// Max number of assets in a pool has been exceeded.
+		MaxAssetsExceeded,
+
+		/// Weights have to be at least `MIN_WEIGHT` and sum up to `MAX_WEIGHT`.
+		InvalidWeights,
+
+		/// Pool of given assets already exists.
+		PoolAlreadyExists,
+
+		/// Pool does not exist.
+		PoolNotFound,
+
+		/// Asset is not in the pool.
+		AssetNotInPool,
+
+		/// Liquidity has not reached the required minimum.
+		InsufficientLiquidity,
+
+		/// Remaining liquidity has to be zero or at least the required minimum.
+		InsufficientLiquidityRemaining,
+
+		/// Liquidity has to be provided in all assets of the pool.
+		InitialLiquidityMissing,
+
+		/// Amount is less than min trading limit.
+		InsufficientTradingAmount,
+
+		/// Asset balance is not sufficient.
+		InsufficientAssetBalance,
+
+		/// Not enough asset liquidity in the pool.
+		InsufficientPoolAssetBalance,
+
+		/// Share balance is not sufficient.
+		InsufficientShares,
+
+		/// Amount of shares is zero.
+		ZeroShares,
+
+		/// Asset amount has exceeded given limit.
+		AssetAmountExceededLimit,
+
+		/// Asset amount has not reached given limit.
+		AssetAmountNotReachedLimit,
+
+		/// Amount of shares has not reached given limit.
+		SharesAmountNotReachedLimit,
+
+		/// Max fraction of pool to buy in single transaction has been exceeded.
+		MaxOutRatioExceeded,
+
+		/// Max fraction of pool to sell in single transaction has been exceeded.
+		MaxInRatioExceeded,
+
+		/// Trade fee discount is not supported by weighted pools.
+		DiscountNotSupported,
+
+		/// Weighted math calculation has failed.
+		MathError,
+
+		/// Overflow
+		Overflow,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Pool was created. [pool id, assets and weights, fee, pool account id]
+		PoolCreated(AssetId, Vec<(AssetId, LBPWeight)>, Permill, T::AccountId),
+
+		/// Liquidity was added to the pool. [who, pool id, assets and amounts, shares]
+		LiquidityAdded(T::AccountId, AssetId, Vec<(AssetId, Balance)>, Balance),
+
+		/// Liquidity was removed from the pool. [who, pool id, shares, assets and amounts]
+		LiquidityRemoved(T::AccountId, AssetId, Balance, Vec<(AssetId, Balance)>),
+
+		/// Asset sale executed. [who, asset in, asset out, amount, sale price, fee asset, fee amount, pool id]
+		SellExecuted(
+			T::AccountId,
+			AssetId,
+			AssetId,
+			Balance,
+			Balance,
+			AssetId,
+			Balance,
+			AssetId,
+		),
+
+		/// Asset purchase executed. [who, asset out, asset in, amount, buy price, fee asset, fee amount, pool id]
+		BuyExecuted(
+			T::AccountId,
+			AssetId,
+			AssetId,
+			Balance,
+			Balance,
+			AssetId,
+			Balance,
+			AssetId,
+		),
+	}
+
+	/// Pools by pool id, which is the asset id of the pool's share token
+	#[pallet::storage]
+	#[pallet::getter(fn pools)]
+	pub type Pools<T: Config> = StorageMap<_, Blake2_128Concat, AssetId, PoolInfoOf<T>, OptionQuery>;
+
+	/// Pool id of each ordered pair of assets which can be traded in a pool
+	#[pallet::storage]
+	#[pallet::getter(fn asset_pair_pool)]
+	pub type AssetPairPools<T: Config> = StorageMap<_, Blake2_128Concat, (AssetId, AssetId), AssetId, OptionQuery>;
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Create a pool of `assets` with fixed weights without liquidity.
+		///
+		/// Share token of the pool is registered in asset registry, its id is also the id of the pool.
+		/// None of the pairs of `assets` can be part of another pool.
+		///
+		/// The dispatch origin for this call must be `T::AuthorityOrigin`.
+		///
+		/// Parameters:
+		/// - `assets`: assets of the pool and their weights, at least 2 and at most `MaxAssetsInPool` assets.
+		///   Each weight has to be at least `MIN_WEIGHT` and the weights have to sum up to `MAX_WEIGHT`.
+		/// - `fee`: trade fee
+		///
+		/// Emits `PoolCreated` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::create_pool())]
+		#[transactional]
+		pub fn create_pool(origin: OriginFor<T>, assets: Vec<(AssetId, LBPWeight)>, fee: Permill) -> DispatchResult {
+			T::AuthorityOrigin::ensure_origin(origin)?;
+
+			ensure!(
+				assets.len() <= T::MaxAssetsInPool::get() as usize,
+				Error::<T>::MaxAssetsExceeded
+			);
+
+			let mut assets = assets;
+			assets.sort_unstable_by_key(|(asset, _)| *asset);
+
+			ensure!(
+				assets.len() >= 2 && assets.windows(2).all(|pair| pair[0].0 != pair[1].0),
+				Error::<T>::IncorrectAssets
+			);
+
+			ensure!(
+				assets.iter().all(|(_, weight)| *weight >= MIN_WEIGHT)
+					&& assets
+						.iter()
+						.try_fold(LBPWeight::zero(), |acc, (_, weight)| acc.checked_add(*weight))
+						== Some(MAX_WEIGHT),
+				Error::<T>::InvalidWeights
+			);
+
+			let (pool_assets, weights): (Vec<AssetId>, Vec<LBPWeight>) = assets.iter().cloned().unzip();
+
+			let pairs = Self::asset_pairs(&pool_assets);
+
+			ensure!(
+				pairs.iter().all(|pair| !<AssetPairPools<T>>::contains_key(pair)),
+				Error::<T>::PoolAlreadyExists
+			);
+
+			let pool_id = T::AssetRegistry::get_or_create_shared_asset(
+				Self::share_token_name(&pool_assets),
+				pool_assets.clone(),
+				T::TradingLimits::default_limits().min_pool_liquidity,
+			)?;
+
+			ensure!(!<Pools<T>>::contains_key(pool_id), Error::<T>::PoolAlreadyExists);
+
+			let pool: PoolInfoOf<T> = PoolInfo {
+				assets: pool_assets.try_into().map_err(|_| Error::<T>::MaxAssetsExceeded)?,
+				weights: weights.try_into().map_err(|_| Error::<T>::MaxAssetsExceeded)?,
+				fee,
+			};
+
+			<Pools<T>>::insert(pool_id, pool);
+
+			for pair in pairs {
+				<AssetPairPools<T>>::insert(pair, pool_id);
+			}
+
+			Self::deposit_event(Event::PoolCreated(pool_id, assets, fee, Self::pool_account(pool_id)));
+
+			Ok(())
+		}
+
+		/// Add liquidity to a pool in all its assets, in proportion to their reserves.
+		///
+		/// Initial liquidity can be provided in any ratio, which determines the initial prices of the pool,
+		/// and the amount of shares is the sum of the amounts. Afterwards, `assets` are the maximum amounts
+		/// to be added - shares are issued for the largest liquidity in the ratio of reserves which does not exceed
+		/// any of them and only the amounts of this liquidity are transferred.
+		///
+		/// `min_shares` - minimum amount of shares to be received.
+		///
+		/// Emits `LiquidityAdded` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::add_liquidity())]
+		#[transactional]
+		pub fn add_liquidity(
+			origin: OriginFor<T>,
+			pool_id: AssetId,
+			assets: Vec<(AssetId, Balance)>,
+			min_shares: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let pool = Self::pools(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+			let limits = Self::trading_limits(pool_id);
+
+			let mut amounts = vec![Balance::zero(); pool.assets.len()];
+			for (asset, amount) in assets.iter() {
+				let index = Self::asset_index(&pool, *asset)?;
+
+				ensure!(amounts[index].is_zero(), Error::<T>::IncorrectAssets);
+				ensure!(
+					*amount >= limits.min_trading_limit,
+					Error::<T>::InsufficientTradingAmount
+				);
+				ensure!(
+					T::Currency::free_balance(*asset, &who) >= *amount,
+					Error::<T>::InsufficientAssetBalance
+				);
+
+				amounts[index] = *amount;
+			}
+
+			ensure!(
+				amounts.iter().all(|amount| !amount.is_zero()),
+				Error::<T>::InitialLiquidityMissing
+			);
+
+			let pool_account = Self::pool_account(pool_id);
+			let reserves = Self::pool_reserves(&pool_account, &pool.assets);
+			let share_issuance = T::Currency::total_issuance(pool_id);
+
+			let shares = if share_issuance.is_zero() {
+				let shares = amounts
+					.iter()
+					.try_fold(Balance::zero(), |acc, amount| acc.checked_add(*amount))
+					.ok_or(Error::<T>::Overflow)?;
+
+				ensure!(shares >= limits.min_pool_liquidity, Error::<T>::InsufficientLiquidity);
+
+				shares
+			} else {
+				let mut shares = Balance::MAX;
+				for (reserve, amount) in reserves.iter().zip(amounts.iter()) {
+					let asset_shares =
+						multiply_by_rational(*amount, share_issuance, *reserve).map_err(|_| Error::<T>::Overflow)?;
+					shares = shares.min(asset_shares);
+				}
+
+				// Amounts of the liquidity in the ratio of reserves are rounded up in favour of the pool.
+				for (reserve, amount) in reserves.iter().zip(amounts.iter_mut()) {
+					*amount = Self::multiply_by_rational_ceil(shares, *reserve, share_issuance)?;
+				}
+
+				shares
+			};
+
+			ensure!(!shares.is_zero(), Error::<T>::ZeroShares);
+			ensure!(shares >= min_shares, Error::<T>::SharesAmountNotReachedLimit);
+
+			for (asset, amount) in pool.assets.iter().zip(amounts.iter()) {
+				T::Currency::transfer(*asset, &who, &pool_account, *amount)?;
+			}
+
+			T::Currency::deposit(pool_id, &who, shares)?;
+
+			let added = pool.assets.iter().copied().zip(amounts.into_iter()).collect();
+
+			Self::deposit_event(Event::LiquidityAdded(who, pool_id, added, shares));
+
+			Ok(())
+		}
+
+		/// Add liquidity to a pool in a single asset.
+		///
+		/// Shares are issued for the relative increase of the invariant of the pool. Part of the liquidity
+		/// which is not in the weight of `asset` - which would have to be swapped to the other assets - is charged
+		/// the trade fee, which stays in the pool.
+		///
+		/// Pool has to have liquidity already.
+		///
+		/// `min_shares` - minimum amount of shares to be received.
+		///
+		/// Emits `LiquidityAdded` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::add_liquidity_single_asset())]
+		#[transactional]
+		pub fn add_liquidity_single_asset(
+			origin: OriginFor<T>,
+			pool_id: AssetId,
+			asset: AssetId,
+			amount: Balance,
+			min_shares: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let pool = Self::pools(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+			let limits = Self::trading_limits(pool_id);
+			let index = Self::asset_index(&pool, asset)?;
+
+			ensure!(
+				amount >= limits.min_trading_limit,
+				Error::<T>::InsufficientTradingAmount
+			);
+			ensure!(
+				T::Currency::free_balance(asset, &who) >= amount,
+				Error::<T>::InsufficientAssetBalance
+			);
+
+			let pool_account = Self::pool_account(pool_id);
+			let reserve = T::Currency::free_balance(asset, &pool_account);
+			let share_issuance = T::Currency::total_issuance(pool_id);
+
+			ensure!(!share_issuance.is_zero(), Error::<T>::InitialLiquidityMissing);
+
+			ensure!(
+				amount <= reserve.checked_div(limits.max_in_ratio).ok_or(Error::<T>::Overflow)?,
+				Error::<T>::MaxInRatioExceeded
+			);
+
+			let fee = Self::single_asset_fee(pool.fee, pool.weights[index], amount)?;
+			let amount_without_fee = amount.checked_sub(fee).ok_or(Error::<T>::Overflow)?;
+			let new_reserve = reserve.checked_add(amount_without_fee).ok_or(Error::<T>::Overflow)?;
+
+			// Shares are the amount of pool "asset" of weight `MAX_WEIGHT` and reserve of share issuance
+			// paid for buying `amount_without_fee` out of the reserve increased by it:
+			// issuance * ((new_reserve / reserve) ^ weight - 1)
+			let shares = hydra_dx_math::lbp::calculate_in_given_out(
+				share_issuance,
+				new_reserve,
+				MAX_WEIGHT,
+				pool.weights[index],
+				amount_without_fee,
+			)
+			.map_err(|_| Error::<T>::MathError)?;
+
+			ensure!(!shares.is_zero(), Error::<T>::ZeroShares);
+			ensure!(shares >= min_shares, Error::<T>::SharesAmountNotReachedLimit);
+
+			T::Currency::transfer(asset, &who, &pool_account, amount)?;
+
+			T::Currency::deposit(pool_id, &who, shares)?;
+
+			Self::deposit_event(Event::LiquidityAdded(who, pool_id, vec![(asset, amount)], shares));
+
+			Ok(())
+		}
+
+		/// Remove liquidity from a pool in all its assets, in proportion to their reserves.
+		///
+		/// Remaining shares of the pool have to be zero or at least minimum pool liquidity of its trading limits.
+		///
+		/// Emits `LiquidityRemoved` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::remove_liquidity())]
+		#[transactional]
+		pub fn remove_liquidity(origin: OriginFor<T>, pool_id: AssetId, shares: Balance) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let pool = Self::pools(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+			let limits = Self::trading_limits(pool_id);
+
+			let share_issuance = Self::ensure_shares_can_be_removed(&who, pool_id, shares)?;
+			let remaining_shares = share_issuance.checked_sub(shares).ok_or(Error::<T>::Overflow)?;
+
+			ensure!(
+				remaining_shares.is_zero() || remaining_shares >= limits.min_pool_liquidity,
+				Error::<T>::InsufficientLiquidityRemaining
+			);
+
+			let pool_account = Self::pool_account(pool_id);
+			let reserves = Self::pool_reserves(&pool_account, &pool.assets);
+
+			let mut removed = Vec::with_capacity(pool.assets.len());
+			for (asset, reserve) in pool.assets.iter().zip(reserves.into_iter()) {
+				let amount = multiply_by_rational(reserve, shares, share_issuance).map_err(|_| Error::<T>::Overflow)?;
+
+				T::Currency::transfer(*asset, &pool_account, &who, amount)?;
+
+				removed.push((*asset, amount));
+			}
+
+			T::Currency::withdraw(pool_id, &who, shares)?;
+
+			Self::deposit_event(Event::LiquidityRemoved(who, pool_id, shares, removed));
+
+			Ok(())
+		}
+
+		/// Remove liquidity from a pool in a single asset.
+		///
+		/// Amount of `asset` is given by the relative decrease of the invariant of the pool. Part of the amount
+		/// which is not in the weight of `asset` - which would have to be swapped from the other assets - is charged
+		/// the trade fee, which stays in the pool.
+		///
+		/// Remaining shares of the pool have to be at least minimum pool liquidity of its trading limits.
+		///
+		/// `min_amount` - minimum amount of `asset` to be received.
+		///
+		/// Emits `LiquidityRemoved` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::remove_liquidity_single_asset())]
+		#[transactional]
+		pub fn remove_liquidity_single_asset(
+			origin: OriginFor<T>,
+			pool_id: AssetId,
+			asset: AssetId,
+			shares: Balance,
+			min_amount: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let pool = Self::pools(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+			let limits = Self::trading_limits(pool_id);
+			let index = Self::asset_index(&pool, asset)?;
+
+			let share_issuance = Self::ensure_shares_can_be_removed(&who, pool_id, shares)?;
+			let remaining_shares = share_issuance.checked_sub(shares).ok_or(Error::<T>::Overflow)?;
+
+			ensure!(
+				remaining_shares >= limits.min_pool_liquidity,
+				Error::<T>::InsufficientLiquidityRemaining
+			);
+
+			let pool_account = Self::pool_account(pool_id);
+			let reserve = T::Currency::free_balance(asset, &pool_account);
+
+			// Amount is the amount of `asset` received for selling `shares` of pool "asset" of weight `MAX_WEIGHT`
+			// and reserve of remaining shares: reserve * (1 - (remaining_shares / issuance) ^ (1 / weight))
+			let amount_out = hydra_dx_math::lbp::calculate_out_given_in(
+				remaining_shares,
+				reserve,
+				MAX_WEIGHT,
+				pool.weights[index],
+				shares,
+			)
+			.map_err(|_| Error::<T>::MathError)?;
+
+			let fee = Self::single_asset_fee(pool.fee, pool.weights[index], amount_out)?;
+			let amount = amount_out.checked_sub(fee).ok_or(Error::<T>::Overflow)?;
+
+			ensure!(
+				amount <= reserve.checked_div(limits.max_out_ratio).ok_or(Error::<T>::Overflow)?,
+				Error::<T>::MaxOutRatioExceeded
+			);
+			ensure!(amount >= min_amount, Error::<T>::AssetAmountNotReachedLimit);
+
+			T::Currency::transfer(asset, &pool_account, &who, amount)?;
+
+			T::Currency::withdraw(pool_id, &who, shares)?;
+
+			Self::deposit_event(Event::LiquidityRemoved(who, pool_id, shares, vec![(asset, amount)]));
+
+			Ok(())
+		}
+
+		/// Trade asset in for asset out.
+		///
+		/// Executes a swap of `asset_in` for `asset_out`. Price is determined by the pool and is
+		/// affected by the amount and proportion of the pool assets and by their weights.
+		///
+		/// `max_limit` - minimum amount of `asset_out` to be obtained from the pool in exchange for `asset_in`.
+		///
+		/// Emits `SellExecuted` when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::sell())]
+		pub fn sell(
+			origin: OriginFor<T>,
+			asset_in: AssetId,
+			asset_out: AssetId,
+			amount: Balance,
+			max_limit: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			<Self as AMM<_, _, _, _>>::sell(&who, AssetPair { asset_in, asset_out }, amount, max_limit, false)?;
+
+			Ok(())
+		}
+
+		/// Trade asset in for asset out.
+		///
+		/// Executes a swap of `asset_in` for `asset_out`. Price is determined by the pool and is
+		/// affected by the amount and proportion of the pool assets and by their weights.
+		///
+		/// `max_limit` - maximum amount of `asset_in` to be sold in exchange for `asset_out`.
+		///
+		/// Emits `BuyExecuted` when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::buy())]
+		pub fn buy(
+			origin: OriginFor<T>,
+			asset_out: AssetId,
+			asset_in: AssetId,
+			amount: Balance,
+			max_limit: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			<Self as AMM<_, _, _, _>>::buy(&who, AssetPair { asset_in, asset_out }, amount, max_limit, false)?;
+
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Account holding reserves of the pool.
+	pub fn pool_account(pool_id: AssetId) -> T::AccountId {
+		T::PalletId::get().into_sub_account(pool_id)
+	}
+
+	/// Trading limits of the pool, limits set for the pool take precedence over the default ones.
+	fn trading_limits(pool_id: AssetId) -> TradingLimits {
+		T::TradingLimits::pool_limits(&Self::pool_account(pool_id))
+	}
+
+	/// Id of the pool of given pool account.
+	fn pool_id_from_account(pool_account: &T::AccountId) -> Option<AssetId> {
+		match PalletId::try_from_sub_account::<AssetId>(pool_account) {
+			Some((pallet_id, pool_id)) if pallet_id == T::PalletId::get() => Some(pool_id),
+			_ => None,
+		}
+	}
+
+	/// Id of the pool in which `assets` can be traded.
+	fn pool_id(assets: AssetPair) -> Option<AssetId> {
+		Self::asset_pair_pool(assets.ordered_pair())
+	}
+
+	/// Reserves of `assets` in the pool, in the same order.
+	pub fn pool_reserves(pool_account: &T::AccountId, assets: &[AssetId]) -> Vec<Balance> {
+		assets
+			.iter()
+			.map(|asset| T::Currency::free_balance(*asset, pool_account))
+			.collect()
+	}
+
+	fn asset_index(pool: &PoolInfoOf<T>, asset: AssetId) -> Result<usize, DispatchError> {
+		pool.assets
+			.iter()
+			.position(|a| *a == asset)
+			.ok_or_else(|| Error::<T>::AssetNotInPool.into())
+	}
+
+	/// All ordered pairs of sorted `assets`.
+	fn asset_pairs(assets: &[AssetId]) -> Vec<(AssetId, AssetId)> {
+		let mut pairs = Vec::new();
+		for (i, asset_a) in assets.iter().enumerate() {
+			for asset_b in assets.iter().skip(i + 1) {
+				pairs.push((*asset_a, *asset_b));
+			}
+		}
+		pairs
+	}
+
+	fn share_token_name(assets: &[AssetId]) -> Vec<u8> {
+		let mut buf: Vec<u8> = Vec::new();
+
+		buf.extend_from_slice(b"WP");
+		for asset in assets {
+			buf.extend_from_slice(&asset.to_le_bytes());
+		}
+
+		buf
+	}
+
+	/// Check that `who` can remove `shares` of the pool and return total issuance of the shares.
+	fn ensure_shares_can_be_removed(
+		who: &T::AccountId,
+		pool_id: AssetId,
+		shares: Balance,
+	) -> Result<Balance, DispatchError> {
+		ensure!(!shares.is_zero(), Error::<T>::ZeroShares);
+
+		ensure!(
+			T::Currency::free_balance(pool_id, who) >= shares,
+			Error::<T>::InsufficientShares
+		);
+
+		Ok(T::Currency::total_issuance(pool_id))
+	}
+
+	/// Fee of a single asset liquidity `amount` of asset of `weight`.
+	///
+	/// Only the part of the amount out of the weight of the asset is charged, as the rest does not change
+	/// the ratio of reserves.
+	fn single_asset_fee(fee: Permill, weight: LBPWeight, amount: Balance) -> Result<Balance, DispatchError> {
+		multiply_by_rational(
+			fee.mul_ceil(amount),
+			MAX_WEIGHT.saturating_sub(weight).into(),
+			MAX_WEIGHT.into(),
+		)
+		.map_err(|_| Error::<T>::Overflow.into())
+	}
+
+	/// `a * b / c` rounded up.
+	fn multiply_by_rational_ceil(a: Balance, b: Balance, c: Balance) -> Result<Balance, DispatchError> {
+		let result = U256::from(a)
+			.checked_mul(U256::from(b))
+			.and_then(|v| v.checked_add(U256::from(c)))
+			.and_then(|v| v.checked_sub(U256::one()))
+			.and_then(|v| v.checked_div(U256::from(c)))
+			.ok_or(Error::<T>::Overflow)?;
+
+		Balance::try_from(result).map_err(|_| Error::<T>::Overflow.into())
+	}
+
+	/// Reserves and weights of `asset_in` and `asset_out` in the pool of `assets` and fee of the pool.
+	fn trade_state(
+		assets: AssetPair,
+	) -> Result<(AssetId, PoolInfoOf<T>, (Balance, LBPWeight), (Balance, LBPWeight)), DispatchError> {
+		let pool_id = Self::pool_id(assets).ok_or(Error::<T>::PoolNotFound)?;
+		let pool = Self::pools(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+
+		let index_in = Self::asset_index(&pool, assets.asset_in)?;
+		let index_out = Self::asset_index(&pool, assets.asset_out)?;
+
+		let pool_account = Self::pool_account(pool_id);
+		let reserve_in = T::Currency::free_balance(assets.asset_in, &pool_account);
+		let reserve_out = T::Currency::free_balance(assets.asset_out, &pool_account);
+
+		ensure!(
+			!reserve_in.is_zero() && !reserve_out.is_zero(),
+			Error::<T>::InsufficientPoolAssetBalance
+		);
+
+		let (weight_in, weight_out) = (pool.weights[index_in], pool.weights[index_out]);
+
+		Ok((pool_id, pool, (reserve_in, weight_in), (reserve_out, weight_out)))
+	}
+
+	/// Perform all checks and calculations of a sell. Checks of trader's balances are skipped if `who` is `None`.
+	///
+	/// Return amount of `asset_out` to be received (fee deducted) and trade fee.
+	fn calculate_sell_trade(
+		who: Option<&T::AccountId>,
+		assets: AssetPair,
+		amount: Balance,
+		min_bought: Balance,
+	) -> Result<(Balance, Balance), DispatchError> {
+		let limits = T::TradingLimits::pool_limits(&Self::get_pair_id(assets));
+
+		ensure!(
+			amount >= limits.min_trading_limit,
+			Error::<T>::InsufficientTradingAmount
+		);
+
+		let (_, pool, (reserve_in, weight_in), (reserve_out, weight_out)) = Self::trade_state(assets)?;
+
+		if let Some(who) = who {
+			ensure!(
+				T::Currency::free_balance(assets.asset_in, who) >= amount,
+				Error::<T>::InsufficientAssetBalance
+			);
+		}
+
+		ensure!(
+			amount
+				<= reserve_in
+					.checked_div(limits.max_in_ratio)
+					.ok_or(Error::<T>::Overflow)?,
+			Error::<T>::MaxInRatioExceeded
+		);
+
+		let amount_out =
+			hydra_dx_math::lbp::calculate_out_given_in(reserve_in, reserve_out, weight_in, weight_out, amount)
+				.map_err(|_| Error::<T>::MathError)?;
+
+		let transfer_fee = pool.fee.mul_ceil(amount_out);
+
+		let amount_out_without_fee = amount_out.checked_sub(transfer_fee).ok_or(Error::<T>::Overflow)?;
+
+		ensure!(reserve_out > amount_out, Error::<T>::InsufficientPoolAssetBalance);
+
+		ensure!(
+			min_bought <= amount_out_without_fee,
+			Error::<T>::AssetAmountNotReachedLimit
+		);
+
+		Ok((amount_out_without_fee, transfer_fee))
+	}
+
+	/// Perform all checks and calculations of a buy. Checks of trader's balances are skipped if `who` is `None`.
+	///
+	/// Return amount of `asset_in` to be paid (fee excluded) and trade fee.
+	fn calculate_buy_trade(
+		who: Option<&T::AccountId>,
+		assets: AssetPair,
+		amount: Balance,
+		max_limit: Balance,
+	) -> Result<(Balance, Balance), DispatchError> {
+		let limits = T::TradingLimits::pool_limits(&Self::get_pair_id(assets));
+
+		ensure!(
+			amount >= limits.min_trading_limit,
+			Error::<T>::InsufficientTradingAmount
+		);
+
+		let (_, pool, (reserve_in, weight_in), (reserve_out, weight_out)) = Self::trade_state(assets)?;
+
+		ensure!(
+			amount
+				<= reserve_out
+					.checked_div(limits.max_out_ratio)
+					.ok_or(Error::<T>::Overflow)?,
+			Error::<T>::MaxOutRatioExceeded
+		);
+
+		let amount_in =
+			hydra_dx_math::lbp::calculate_in_given_out(reserve_in, reserve_out, weight_in, weight_out, amount)
+				.map_err(|_| Error::<T>::MathError)?;
+
+		let transfer_fee = pool.fee.mul_ceil(amount_in);
+
+		let amount_in_with_fee = amount_in.checked_add(transfer_fee).ok_or(Error::<T>::Overflow)?;
+
+		ensure!(max_limit >= amount_in_with_fee, Error::<T>::AssetAmountExceededLimit);
+
+		if let Some(who) = who {
+			ensure!(
+				T::Currency::free_balance(assets.asset_in, who) >= amount_in_with_fee,
+				Error::<T>::InsufficientAssetBalance
+			);
+		}
+
+		Ok((amount_in, transfer_fee))
+	}
+}
+
+// Implementation of AMM API which makes possible to plug the AMM pool into the exchange pallet.
+impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
+	fn exists(assets: AssetPair) -> bool {
+		Self::pool_id(assets).is_some()
+	}
+
+	/// Return account of the pool of `assets`, or account of the pallet if the pool does not exist.
+	fn get_pair_id(assets: AssetPair) -> T::AccountId {
+		Self::pool_id(assets)
+			.map(Self::pool_account)
+			.unwrap_or_else(|| T::PalletId::get().into_account())
+	}
+
+	fn get_share_token(assets: AssetPair) -> AssetId {
+		Self::pool_id(assets).unwrap_or_default()
+	}
+
+	fn get_pool_assets(pool_account_id: &T::AccountId) -> Option<Vec<AssetId>> {
+		let pool_id = Self::pool_id_from_account(pool_account_id)?;
+		Self::pools(pool_id).map(|pool| pool.assets.into_inner())
+	}
+
+	fn get_spot_price_unchecked(asset_a: AssetId, asset_b: AssetId, amount: Balance) -> Balance {
+		Self::trade_state(AssetPair::new(asset_a, asset_b))
+			.ok()
+			.and_then(|(_, _, (reserve_a, weight_a), (reserve_b, weight_b))| {
+				hydra_dx_math::lbp::calculate_spot_price(reserve_a, reserve_b, weight_a, weight_b, amount).ok()
+			})
+			.unwrap_or_else(Balance::zero)
+	}
+
+	/// Validate a sell. Perform all necessary checks and calculations.
+	/// No storage changes are performed yet.
+	///
+	/// Return `AMMTransfer` with all info needed to execute the transaction.
+	fn validate_sell(
+		who: &T::AccountId,
+		assets: AssetPair,
+		amount: Balance,
+		min_bought: Balance,
+		discount: bool,
+	) -> Result<AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>, DispatchError> {
+		ensure!(!discount, Error::<T>::DiscountNotSupported);
+
+		let (amount_out, transfer_fee) = Self::calculate_sell_trade(Some(who), assets, amount, min_bought)?;
+
+		Ok(AMMTransfer {
+			origin: who.clone(),
+			assets,
+			amount,
+			amount_out,
+			discount,
+			discount_amount: Balance::zero(),
+			fee: (assets.asset_out, transfer_fee),
+		})
+	}
+
+	/// Execute sell. validate_sell must be called first.
+	/// Perform necessary storage/state changes.
+	/// Note : the execution should not return error as everything was previously verified and validated.
+	#[transactional]
+	fn execute_sell(transfer: &AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>) -> DispatchResult {
+		let pool_id = Self::pool_id(transfer.assets).ok_or(Error::<T>::PoolNotFound)?;
+		let pool_account = Self::pool_account(pool_id);
+
+		T::Currency::transfer(
+			transfer.assets.asset_in,
+			&transfer.origin,
+			&pool_account,
+			transfer.amount,
+		)?;
+		T::Currency::transfer(
+			transfer.assets.asset_out,
+			&pool_account,
+			&transfer.origin,
+			transfer.amount_out,
+		)?;
+
+		Self::deposit_event(Event::<T>::SellExecuted(
+			transfer.origin.clone(),
+			transfer.assets.asset_in,
+			transfer.assets.asset_out,
+			transfer.amount,
+			transfer.amount_out,
+			transfer.fee.0,
+			transfer.fee.1,
+			pool_id,
+		));
+
+		Ok(())
+	}
+
+	/// Validate a buy. Perform all necessary checks and calculations.
+	/// No storage changes are performed yet.
+	///
+	/// Return `AMMTransfer` with all info needed to execute the transaction.
+	fn validate_buy(
+		who: &T::AccountId,
+		assets: AssetPair,
+		amount: Balance,
+		max_limit: Balance,
+		discount: bool,
+	) -> Result<AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>, DispatchError> {
+		ensure!(!discount, Error::<T>::DiscountNotSupported);
+
+		let (amount_in, transfer_fee) = Self::calculate_buy_trade(Some(who), assets, amount, max_limit)?;
+
+		Ok(AMMTransfer {
+			origin: who.clone(),
+			assets,
+			amount,
+			amount_out: amount_in,
+			discount,
+			discount_amount: Balance::zero(),
+			fee: (assets.asset_in, transfer_fee),
+		})
+	}
+
+	/// Execute buy. validate_buy must be called first.
+	/// Perform necessary storage/state changes.
+	/// Note : the execution should not return error as everything was previously verified and validated.
+	#[transactional]
+	fn execute_buy(transfer: &AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>) -> DispatchResult {
+		let pool_id = Self::pool_id(transfer.assets).ok_or(Error::<T>::PoolNotFound)?;
+		let pool_account = Self::pool_account(pool_id);
+
+		T::Currency::transfer(
+			transfer.assets.asset_out,
+			&pool_account,
+			&transfer.origin,
+			transfer.amount,
+		)?;
+		T::Currency::transfer(
+			transfer.assets.asset_in,
+			&transfer.origin,
+			&pool_account,
+			transfer.amount_out + transfer.fee.1,
+		)?;
+
+		Self::deposit_event(Event::<T>::BuyExecuted(
+			transfer.origin.clone(),
+			transfer.assets.asset_out,
+			transfer.assets.asset_in,
+			transfer.amount,
+			transfer.amount_out,
+			transfer.fee.0,
+			transfer.fee.1,
+			pool_id,
+		));
+
+		Ok(())
+	}
+
+	fn get_min_trading_limit() -> Balance {
+		T::TradingLimits::default_limits().min_trading_limit
+	}
+
+	fn get_min_pool_liquidity() -> Balance {
+		T::TradingLimits::default_limits().min_pool_liquidity
+	}
+
+	fn get_max_in_ratio() -> u128 {
+		T::TradingLimits::default_limits().max_in_ratio
+	}
+
+	fn get_max_out_ratio() -> u128 {
+		T::TradingLimits::default_limits().max_out_ratio
+	}
+
+	fn get_fee(pool_account_id: &T::AccountId) -> (u32, u32) {
+		let fee = Self::pool_id_from_account(pool_account_id)
+			.and_then(Self::pools)
+			.map(|pool| pool.fee)
+			.unwrap_or_default();
+
+		(fee.deconstruct(), Permill::one().deconstruct())
+	}
+}
+
+// Implementation of trade calculation which makes possible to price trades of a route before executing them.
+impl<T: Config> AMMTradeCalculation<AssetPair, Balance> for Pallet<T> {
+	fn calculate_sell(assets: AssetPair, amount: Balance) -> Result<Balance, DispatchError> {
+		let (amount_out, _) = Self::calculate_sell_trade(None, assets, amount, Balance::zero())?;
+
+		Ok(amount_out)
+	}
+
+	fn calculate_buy(assets: AssetPair, amount: Balance) -> Result<Balance, DispatchError> {
+		let (amount_in, transfer_fee) = Self::calculate_buy_trade(None, assets, amount, Balance::MAX)?;
+
+		Ok(amount_in.saturating_add(transfer_fee))
+	}
+}
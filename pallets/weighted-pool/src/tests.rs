@@ -0,0 +1,623 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+pub use crate::mock::{
+	Currency, Event as TestEvent, ExtBuilder, Origin, System, Test, WeightedPool, ALICE, AUSD, BOB, DOT, HDX, KSM,
+};
+use frame_support::{assert_noop, assert_ok};
+use hydradx_traits::pools::SpotPriceProvider;
+use primitives::Price;
+use sp_runtime::{DispatchError::BadOrigin, FixedPointNumber};
+
+const HDX_LIQUIDITY: Balance = 400_000_000_000_000;
+const KSM_LIQUIDITY: Balance = 50_000_000_000_000;
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut ext = ExtBuilder::default().build();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+fn expect_events(e: Vec<TestEvent>) {
+	e.into_iter().for_each(frame_system::Pallet::<Test>::assert_has_event);
+}
+
+fn pool_fee() -> Permill {
+	Permill::from_parts(3_000)
+}
+
+// Results of weighted math are compared with values calculated in arbitrary precision up to 10 ppm.
+fn assert_approx_eq(actual: Balance, expected: Balance) {
+	let tolerance = expected / 100_000;
+	assert!(
+		actual.max(expected) - actual.min(expected) <= tolerance,
+		"{} is not approximately {}",
+		actual,
+		expected
+	);
+}
+
+// Creates 80/20 HDX/KSM pool with liquidity provided by ALICE and returns its id.
+// Spot price of HDX is 0.5 KSM.
+fn create_pool_with_liquidity() -> AssetId {
+	assert_ok!(WeightedPool::create_pool(
+		Origin::root(),
+		vec![(HDX, 80_000_000), (KSM, 20_000_000)],
+		pool_fee()
+	));
+
+	let pool_id = WeightedPool::asset_pair_pool((HDX, KSM)).unwrap();
+
+	assert_ok!(WeightedPool::add_liquidity(
+		Origin::signed(ALICE),
+		pool_id,
+		vec![(HDX, HDX_LIQUIDITY), (KSM, KSM_LIQUIDITY)],
+		0
+	));
+
+	pool_id
+}
+
+#[test]
+fn create_pool_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(WeightedPool::create_pool(
+			Origin::root(),
+			vec![(KSM, 20_000_000), (HDX, 80_000_000)],
+			pool_fee()
+		));
+
+		let pool_id = WeightedPool::asset_pair_pool((HDX, KSM)).unwrap();
+
+		assert_eq!(
+			WeightedPool::pools(pool_id),
+			Some(PoolInfo {
+				assets: vec![HDX, KSM].try_into().unwrap(),
+				weights: vec![80_000_000, 20_000_000].try_into().unwrap(),
+				fee: pool_fee(),
+			})
+		);
+
+		expect_events(vec![Event::PoolCreated(
+			pool_id,
+			vec![(HDX, 80_000_000), (KSM, 20_000_000)],
+			pool_fee(),
+			WeightedPool::pool_account(pool_id),
+		)
+		.into()]);
+	});
+}
+
+#[test]
+fn create_pool_should_register_all_pairs_of_assets() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(WeightedPool::create_pool(
+			Origin::root(),
+			vec![(DOT, 40_000_000), (HDX, 30_000_000), (KSM, 30_000_000)],
+			pool_fee()
+		));
+
+		let pool_id = WeightedPool::asset_pair_pool((HDX, KSM)).unwrap();
+
+		assert_eq!(WeightedPool::asset_pair_pool((HDX, DOT)), Some(pool_id));
+		assert_eq!(WeightedPool::asset_pair_pool((KSM, DOT)), Some(pool_id));
+		assert_eq!(
+			WeightedPool::get_pool_assets(&WeightedPool::pool_account(pool_id)),
+			Some(vec![HDX, KSM, DOT])
+		);
+		assert_eq!(
+			WeightedPool::get_fee(&WeightedPool::pool_account(pool_id)),
+			(3_000, 1_000_000)
+		);
+	});
+}
+
+#[test]
+fn create_pool_should_not_work_with_invalid_params() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			WeightedPool::create_pool(
+				Origin::signed(ALICE),
+				vec![(HDX, 50_000_000), (KSM, 50_000_000)],
+				pool_fee()
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			WeightedPool::create_pool(Origin::root(), vec![(HDX, 100_000_000)], pool_fee()),
+			Error::<Test>::IncorrectAssets
+		);
+		assert_noop!(
+			WeightedPool::create_pool(Origin::root(), vec![(HDX, 50_000_000), (HDX, 50_000_000)], pool_fee()),
+			Error::<Test>::IncorrectAssets
+		);
+		assert_noop!(
+			WeightedPool::create_pool(
+				Origin::root(),
+				vec![
+					(HDX, 25_000_000),
+					(KSM, 25_000_000),
+					(AUSD, 25_000_000),
+					(DOT, 25_000_000)
+				],
+				pool_fee()
+			),
+			Error::<Test>::MaxAssetsExceeded
+		);
+		assert_noop!(
+			WeightedPool::create_pool(Origin::root(), vec![(HDX, 50_000_000), (KSM, 40_000_000)], pool_fee()),
+			Error::<Test>::InvalidWeights
+		);
+		assert_noop!(
+			WeightedPool::create_pool(Origin::root(), vec![(HDX, 99_500_000), (KSM, 500_000)], pool_fee()),
+			Error::<Test>::InvalidWeights
+		);
+	});
+}
+
+#[test]
+fn create_pool_should_not_work_when_pair_of_assets_is_in_another_pool() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(WeightedPool::create_pool(
+			Origin::root(),
+			vec![(HDX, 50_000_000), (KSM, 50_000_000)],
+			pool_fee()
+		));
+
+		assert_noop!(
+			WeightedPool::create_pool(
+				Origin::root(),
+				vec![(HDX, 40_000_000), (KSM, 30_000_000), (DOT, 30_000_000)],
+				pool_fee()
+			),
+			Error::<Test>::PoolAlreadyExists
+		);
+	});
+}
+
+#[test]
+fn add_initial_liquidity_should_work() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_liquidity();
+		let pool_account = WeightedPool::pool_account(pool_id);
+
+		assert_eq!(Currency::free_balance(HDX, &pool_account), HDX_LIQUIDITY);
+		assert_eq!(Currency::free_balance(KSM, &pool_account), KSM_LIQUIDITY);
+		assert_eq!(Currency::free_balance(pool_id, &ALICE), HDX_LIQUIDITY + KSM_LIQUIDITY);
+
+		expect_events(vec![Event::LiquidityAdded(
+			ALICE,
+			pool_id,
+			vec![(HDX, HDX_LIQUIDITY), (KSM, KSM_LIQUIDITY)],
+			HDX_LIQUIDITY + KSM_LIQUIDITY,
+		)
+		.into()]);
+	});
+}
+
+#[test]
+fn add_liquidity_should_not_work_without_all_assets() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_liquidity();
+
+		assert_noop!(
+			WeightedPool::add_liquidity(Origin::signed(BOB), pool_id, vec![(HDX, 1_000_000_000_000)], 0),
+			Error::<Test>::InitialLiquidityMissing
+		);
+		assert_noop!(
+			WeightedPool::add_liquidity(
+				Origin::signed(BOB),
+				pool_id,
+				vec![(HDX, 1_000_000_000_000), (DOT, 1_000_000_000_000)],
+				0
+			),
+			Error::<Test>::AssetNotInPool
+		);
+	});
+}
+
+#[test]
+fn add_liquidity_should_take_amounts_in_ratio_of_reserves() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_liquidity();
+
+		let hdx_balance = Currency::free_balance(HDX, &BOB);
+		let ksm_balance = Currency::free_balance(KSM, &BOB);
+
+		// 10% of HDX reserve and 20% of KSM reserve are offered, 10% of both is taken
+		assert_ok!(WeightedPool::add_liquidity(
+			Origin::signed(BOB),
+			pool_id,
+			vec![(HDX, 40_000_000_000_000), (KSM, 10_000_000_000_000)],
+			0
+		));
+
+		assert_eq!(Currency::free_balance(pool_id, &BOB), 45_000_000_000_000);
+		assert_eq!(Currency::free_balance(HDX, &BOB), hdx_balance - 40_000_000_000_000);
+		assert_eq!(Currency::free_balance(KSM, &BOB), ksm_balance - 5_000_000_000_000);
+
+		expect_events(vec![Event::LiquidityAdded(
+			BOB,
+			pool_id,
+			vec![(HDX, 40_000_000_000_000), (KSM, 5_000_000_000_000)],
+			45_000_000_000_000,
+		)
+		.into()]);
+
+		assert_noop!(
+			WeightedPool::add_liquidity(
+				Origin::signed(BOB),
+				pool_id,
+				vec![(HDX, 40_000_000_000_000), (KSM, 10_000_000_000_000)],
+				50_000_000_000_000
+			),
+			Error::<Test>::SharesAmountNotReachedLimit
+		);
+	});
+}
+
+#[test]
+fn add_liquidity_single_asset_should_work() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_liquidity();
+		let pool_account = WeightedPool::pool_account(pool_id);
+
+		let ksm_balance = Currency::free_balance(KSM, &BOB);
+
+		assert_ok!(WeightedPool::add_liquidity_single_asset(
+			Origin::signed(BOB),
+			pool_id,
+			KSM,
+			10_000_000_000_000,
+			0
+		));
+
+		// 450e12 * ((1 + (10e12 - 24e9) / 50e12) ^ 0.2 - 1), fee is charged on 80% of the amount
+		let shares = Currency::free_balance(pool_id, &BOB);
+		assert_approx_eq(shares, 16_674_437_283_730);
+
+		assert_eq!(Currency::free_balance(KSM, &BOB), ksm_balance - 10_000_000_000_000);
+		assert_eq!(
+			Currency::free_balance(KSM, &pool_account),
+			KSM_LIQUIDITY + 10_000_000_000_000
+		);
+
+		expect_events(vec![Event::LiquidityAdded(
+			BOB,
+			pool_id,
+			vec![(KSM, 10_000_000_000_000)],
+			shares,
+		)
+		.into()]);
+	});
+}
+
+#[test]
+fn add_liquidity_single_asset_should_not_work_with_invalid_params() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(WeightedPool::create_pool(
+			Origin::root(),
+			vec![(HDX, 80_000_000), (KSM, 20_000_000)],
+			pool_fee()
+		));
+		let pool_id = WeightedPool::asset_pair_pool((HDX, KSM)).unwrap();
+
+		assert_noop!(
+			WeightedPool::add_liquidity_single_asset(Origin::signed(BOB), pool_id, KSM, 1_000_000_000_000, 0),
+			Error::<Test>::InitialLiquidityMissing
+		);
+
+		assert_ok!(WeightedPool::add_liquidity(
+			Origin::signed(ALICE),
+			pool_id,
+			vec![(HDX, HDX_LIQUIDITY), (KSM, KSM_LIQUIDITY)],
+			0
+		));
+
+		assert_noop!(
+			WeightedPool::add_liquidity_single_asset(Origin::signed(BOB), pool_id, DOT, 1_000_000_000_000, 0),
+			Error::<Test>::AssetNotInPool
+		);
+		assert_noop!(
+			WeightedPool::add_liquidity_single_asset(Origin::signed(BOB), pool_id, KSM, 20_000_000_000_000, 0),
+			Error::<Test>::MaxInRatioExceeded
+		);
+		assert_noop!(
+			WeightedPool::add_liquidity_single_asset(
+				Origin::signed(BOB),
+				pool_id,
+				KSM,
+				10_000_000_000_000,
+				17_000_000_000_000
+			),
+			Error::<Test>::SharesAmountNotReachedLimit
+		);
+	});
+}
+
+#[test]
+fn remove_liquidity_should_work() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_liquidity();
+
+		let hdx_balance = Currency::free_balance(HDX, &ALICE);
+		let ksm_balance = Currency::free_balance(KSM, &ALICE);
+
+		assert_ok!(WeightedPool::remove_liquidity(
+			Origin::signed(ALICE),
+			pool_id,
+			45_000_000_000_000
+		));
+
+		assert_eq!(Currency::free_balance(pool_id, &ALICE), 405_000_000_000_000);
+		assert_eq!(Currency::free_balance(HDX, &ALICE), hdx_balance + 40_000_000_000_000);
+		assert_eq!(Currency::free_balance(KSM, &ALICE), ksm_balance + 5_000_000_000_000);
+
+		expect_events(vec![Event::LiquidityRemoved(
+			ALICE,
+			pool_id,
+			45_000_000_000_000,
+			vec![(HDX, 40_000_000_000_000), (KSM, 5_000_000_000_000)],
+		)
+		.into()]);
+
+		assert_noop!(
+			WeightedPool::remove_liquidity(Origin::signed(ALICE), pool_id, 404_999_999_999_999),
+			Error::<Test>::InsufficientLiquidityRemaining
+		);
+		assert_noop!(
+			WeightedPool::remove_liquidity(Origin::signed(BOB), pool_id, 1_000_000_000_000),
+			Error::<Test>::InsufficientShares
+		);
+	});
+}
+
+#[test]
+fn remove_liquidity_single_asset_should_work() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_liquidity();
+
+		let hdx_balance = Currency::free_balance(HDX, &ALICE);
+
+		assert_ok!(WeightedPool::remove_liquidity_single_asset(
+			Origin::signed(ALICE),
+			pool_id,
+			HDX,
+			45_000_000_000_000,
+			0
+		));
+
+		// 400e12 * (1 - 0.9 ^ (1 / 0.8)) minus fee charged on 20% of the amount
+		let received = Currency::free_balance(HDX, &ALICE) - hdx_balance;
+		assert_approx_eq(received, 49_358_651_286_893 - 29_615_190_772);
+
+		assert_eq!(Currency::free_balance(pool_id, &ALICE), 405_000_000_000_000);
+
+		expect_events(vec![Event::LiquidityRemoved(
+			ALICE,
+			pool_id,
+			45_000_000_000_000,
+			vec![(HDX, received)],
+		)
+		.into()]);
+	});
+}
+
+#[test]
+fn remove_liquidity_single_asset_should_not_work_with_invalid_params() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_liquidity();
+
+		assert_noop!(
+			WeightedPool::remove_liquidity_single_asset(
+				Origin::signed(ALICE),
+				pool_id,
+				HDX,
+				HDX_LIQUIDITY + KSM_LIQUIDITY,
+				0
+			),
+			Error::<Test>::InsufficientLiquidityRemaining
+		);
+		assert_noop!(
+			WeightedPool::remove_liquidity_single_asset(Origin::signed(ALICE), pool_id, HDX, 0, 0),
+			Error::<Test>::ZeroShares
+		);
+		assert_noop!(
+			WeightedPool::remove_liquidity_single_asset(
+				Origin::signed(ALICE),
+				pool_id,
+				HDX,
+				45_000_000_000_000,
+				50_000_000_000_000
+			),
+			Error::<Test>::AssetAmountNotReachedLimit
+		);
+		assert_noop!(
+			WeightedPool::remove_liquidity_single_asset(Origin::signed(ALICE), pool_id, KSM, 200_000_000_000_000, 0),
+			Error::<Test>::MaxOutRatioExceeded
+		);
+	});
+}
+
+#[test]
+fn single_asset_liquidity_round_trip_should_charge_fee() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_liquidity();
+
+		let ksm_balance = Currency::free_balance(KSM, &BOB);
+
+		assert_ok!(WeightedPool::add_liquidity_single_asset(
+			Origin::signed(BOB),
+			pool_id,
+			KSM,
+			10_000_000_000_000,
+			0
+		));
+
+		let shares = Currency::free_balance(pool_id, &BOB);
+
+		assert_ok!(WeightedPool::remove_liquidity_single_asset(
+			Origin::signed(BOB),
+			pool_id,
+			KSM,
+			shares,
+			0
+		));
+
+		let received = Currency::free_balance(KSM, &BOB) - (ksm_balance - 10_000_000_000_000);
+
+		// 60e12 * (1 - 50 / 59.976) minus fee, the fee of the deposit stays in the pool
+		assert!(received < 10_000_000_000_000);
+		assert_approx_eq(received, 9_979_991_996_799 - 23_951_980_793);
+	});
+}
+
+#[test]
+fn sell_should_work() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_liquidity();
+		let pool_account = WeightedPool::pool_account(pool_id);
+
+		let pair = AssetPair {
+			asset_in: KSM,
+			asset_out: HDX,
+		};
+		let expected = WeightedPool::calculate_sell(pair, 10_000_000_000_000).unwrap();
+
+		// 400e12 * (1 - (50 / 60) ^ (0.2 / 0.8)) minus fee
+		assert_approx_eq(expected, 17_822_883_118_253 - 53_468_649_355);
+
+		let hdx_balance = Currency::free_balance(HDX, &BOB);
+		let ksm_balance = Currency::free_balance(KSM, &BOB);
+
+		assert_ok!(WeightedPool::sell(
+			Origin::signed(BOB),
+			KSM,
+			HDX,
+			10_000_000_000_000,
+			expected
+		));
+
+		assert_eq!(Currency::free_balance(HDX, &BOB), hdx_balance + expected);
+		assert_eq!(Currency::free_balance(KSM, &BOB), ksm_balance - 10_000_000_000_000);
+		assert_eq!(Currency::free_balance(HDX, &pool_account), HDX_LIQUIDITY - expected);
+		assert_eq!(
+			Currency::free_balance(KSM, &pool_account),
+			KSM_LIQUIDITY + 10_000_000_000_000
+		);
+	});
+}
+
+#[test]
+fn sell_should_not_work_when_limit_is_not_reached() {
+	new_test_ext().execute_with(|| {
+		create_pool_with_liquidity();
+
+		assert_noop!(
+			WeightedPool::sell(Origin::signed(BOB), KSM, HDX, 10_000_000_000_000, 18_000_000_000_000),
+			Error::<Test>::AssetAmountNotReachedLimit
+		);
+		assert_noop!(
+			WeightedPool::sell(Origin::signed(BOB), KSM, DOT, 10_000_000_000_000, 0),
+			Error::<Test>::PoolNotFound
+		);
+	});
+}
+
+#[test]
+fn buy_should_work() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_liquidity();
+		let pool_account = WeightedPool::pool_account(pool_id);
+
+		let pair = AssetPair {
+			asset_in: KSM,
+			asset_out: HDX,
+		};
+		let expected = WeightedPool::calculate_buy(pair, 10_000_000_000_000).unwrap();
+
+		// 50e12 * ((400 / 390) ^ (0.8 / 0.2) - 1) plus fee
+		assert_approx_eq(expected, 5_328_837_000_814 + 15_986_511_003);
+
+		let hdx_balance = Currency::free_balance(HDX, &BOB);
+		let ksm_balance = Currency::free_balance(KSM, &BOB);
+
+		assert_ok!(WeightedPool::buy(
+			Origin::signed(BOB),
+			HDX,
+			KSM,
+			10_000_000_000_000,
+			expected
+		));
+
+		assert_eq!(Currency::free_balance(HDX, &BOB), hdx_balance + 10_000_000_000_000);
+		assert_eq!(Currency::free_balance(KSM, &BOB), ksm_balance - expected);
+		assert_eq!(
+			Currency::free_balance(HDX, &pool_account),
+			HDX_LIQUIDITY - 10_000_000_000_000
+		);
+		assert_eq!(Currency::free_balance(KSM, &pool_account), KSM_LIQUIDITY + expected);
+
+		assert_noop!(
+			WeightedPool::buy(Origin::signed(BOB), HDX, KSM, 10_000_000_000_000, 5_000_000_000_000),
+			Error::<Test>::AssetAmountExceededLimit
+		);
+	});
+}
+
+#[test]
+fn trade_with_discount_should_not_work() {
+	new_test_ext().execute_with(|| {
+		create_pool_with_liquidity();
+
+		let pair = AssetPair {
+			asset_in: KSM,
+			asset_out: HDX,
+		};
+
+		assert_noop!(
+			WeightedPool::validate_sell(&BOB, pair, 10_000_000_000_000, 0, true).map(|_| ()),
+			Error::<Test>::DiscountNotSupported
+		);
+		assert_noop!(
+			WeightedPool::validate_buy(&BOB, pair, 10_000_000_000_000, Balance::MAX, true).map(|_| ()),
+			Error::<Test>::DiscountNotSupported
+		);
+	});
+}
+
+#[test]
+fn spot_price_should_respect_weights() {
+	new_test_ext().execute_with(|| {
+		create_pool_with_liquidity();
+
+		assert_approx_eq(
+			WeightedPool::get_spot_price_unchecked(HDX, KSM, 1_000_000_000_000),
+			500_000_000_000,
+		);
+		assert_approx_eq(
+			WeightedPool::get_spot_price_unchecked(KSM, HDX, 1_000_000_000_000),
+			2_000_000_000_000,
+		);
+
+		let price = WeightedPoolSpotPrice::<Test>::spot_price(HDX, KSM).unwrap();
+		assert_approx_eq(price.into_inner(), Price::saturating_from_rational(1, 2).into_inner());
+
+		assert_eq!(WeightedPoolSpotPrice::<Test>::spot_price(HDX, DOT), None);
+	});
+}
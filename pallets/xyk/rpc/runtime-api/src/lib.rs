@@ -24,9 +24,11 @@
 #![allow(clippy::upper_case_acronyms)]
 
 use codec::{Codec, Decode, Encode};
+pub use primitives::PoolStats;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use sp_runtime::traits::{MaybeDisplay, MaybeFromStr};
+use sp_runtime::traits::{MaybeDisplay, MaybeFromStr, NumberFor};
+use sp_runtime::{DispatchError, FixedU128, Permill};
 use sp_std::prelude::*;
 
 #[derive(Eq, PartialEq, Encode, Decode, Default)]
@@ -42,6 +44,72 @@ pub struct BalanceInfo<AssetId, Balance> {
 	pub asset: Option<AssetId>,
 }
 
+/// Quote of a trade. Spot prices are amounts of asset out per one unit of asset in.
+#[derive(Eq, PartialEq, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct TradeQuote<Balance> {
+	/// Amount received (fee deducted) for sell, amount paid (fee included) for buy.
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub amount: Balance,
+
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub fee: Balance,
+
+	/// Part of `fee` transferred to protocol fee receiver instead of staying in the pool.
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub protocol_fee: Balance,
+
+	pub spot_price_before: FixedU128,
+
+	pub spot_price_after: FixedU128,
+
+	pub price_impact: Permill,
+}
+
+/// Information about a pool.
+#[derive(Eq, PartialEq, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct PoolInfo<AccountId, AssetId, Balance> {
+	pub pool_account: AccountId,
+
+	pub asset_a: AssetId,
+
+	pub asset_b: AssetId,
+
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub reserve_a: Balance,
+
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub reserve_b: Balance,
+
+	pub share_token: AssetId,
+
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub total_shares: Balance,
+
+	pub fee: (u32, u32),
+}
+
 #[cfg(feature = "std")]
 fn serialize_as_string<S: Serializer, T: std::fmt::Display>(t: &T, serializer: S) -> Result<S::Ok, S::Error> {
 	serializer.serialize_str(&t.to_string())
@@ -55,6 +123,7 @@ fn deserialize_from_string<'de, D: Deserializer<'de>, T: std::str::FromStr>(dese
 }
 
 sp_api::decl_runtime_apis! {
+	#[api_version(7)]
 	pub trait XYKApi<AccountId, AssetId, Balance> where
 		AccountId: Codec,
 		AssetId: Codec,
@@ -68,5 +137,52 @@ sp_api::decl_runtime_apis! {
 			asset_a: AssetId,
 			asset_b: AssetId
 		) -> AccountId;
+
+		fn quote_sell(
+			asset_in: AssetId,
+			asset_out: AssetId,
+			amount: Balance
+		) -> Result<TradeQuote<Balance>, DispatchError>;
+
+		fn quote_buy(
+			asset_out: AssetId,
+			asset_in: AssetId,
+			amount: Balance
+		) -> Result<TradeQuote<Balance>, DispatchError>;
+
+		fn get_pools(
+			start_after: Option<AccountId>,
+			limit: u32
+		) -> Vec<PoolInfo<AccountId, AssetId, Balance>>;
+
+		fn get_pool(
+			asset_a: AssetId,
+			asset_b: AssetId
+		) -> Option<PoolInfo<AccountId, AssetId, Balance>>;
+
+		/// Pool which uses given share token.
+		fn get_pool_by_share_token(
+			share_token: AssetId
+		) -> Option<PoolInfo<AccountId, AssetId, Balance>>;
+
+		/// Blocks of stored price observations of the pool of given assets, oldest first.
+		fn get_price_observation_blocks(
+			asset_a: AssetId,
+			asset_b: AssetId
+		) -> Vec<NumberFor<Block>>;
+
+		/// Time-weighted average price of `asset_a` in `asset_b` between two stored price observations.
+		fn get_twap(
+			asset_a: AssetId,
+			asset_b: AssetId,
+			from: NumberFor<Block>,
+			to: NumberFor<Block>
+		) -> Option<FixedU128>;
+
+		/// Trade statistics of the pool of given assets as of current block.
+		fn get_pool_stats(
+			asset_a: AssetId,
+			asset_b: AssetId
+		) -> Option<PoolStats<NumberFor<Block>>>;
 	}
 }
@@ -20,13 +20,14 @@
 use codec::Codec;
 use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
 use jsonrpc_derive::rpc;
-use pallet_xyk_rpc_runtime_api::BalanceInfo;
+use pallet_xyk_rpc_runtime_api::{BalanceInfo, PoolInfo, TradeQuote};
 use serde::{Deserialize, Serialize};
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
 use sp_runtime::{
 	generic::BlockId,
 	traits::{Block as BlockT, MaybeDisplay, MaybeFromStr},
+	DispatchError,
 };
 use std::sync::Arc;
 
@@ -40,13 +41,50 @@ pub struct BalanceRequest<Balance> {
 	amount: Balance,
 }
 
+/// Number of pools returned by `xyk_getPools` when no limit is given.
+pub const DEFAULT_POOLS_PAGE_SIZE: u32 = 100;
+/// Max number of pools returned by a single `xyk_getPools` call.
+pub const MAX_POOLS_PAGE_SIZE: u32 = 1000;
+
 #[rpc]
-pub trait XYKApi<BlockHash, AccountId, AssetId, Balance, ResponseType> {
+pub trait XYKApi<BlockHash, AccountId, AssetId, Balance, ResponseType, QuoteType, PoolType> {
 	#[rpc(name = "xyk_getPoolBalances")]
 	fn get_pool_balances(&self, pool_address: AccountId, at: Option<BlockHash>) -> Result<Vec<ResponseType>>;
 
 	#[rpc(name = "xyk_getPoolAccount")]
 	fn get_pool_id(&self, asset_a: AssetId, asset_b: AssetId) -> Result<AccountId>;
+
+	#[rpc(name = "xyk_quoteSell")]
+	fn quote_sell(
+		&self,
+		asset_in: AssetId,
+		asset_out: AssetId,
+		amount: Balance,
+		at: Option<BlockHash>,
+	) -> Result<QuoteType>;
+
+	#[rpc(name = "xyk_quoteBuy")]
+	fn quote_buy(
+		&self,
+		asset_out: AssetId,
+		asset_in: AssetId,
+		amount: Balance,
+		at: Option<BlockHash>,
+	) -> Result<QuoteType>;
+
+	#[rpc(name = "xyk_getPools")]
+	fn get_pools(
+		&self,
+		start_after: Option<AccountId>,
+		limit: Option<u32>,
+		at: Option<BlockHash>,
+	) -> Result<Vec<PoolType>>;
+
+	#[rpc(name = "xyk_getPool")]
+	fn get_pool(&self, asset_a: AssetId, asset_b: AssetId, at: Option<BlockHash>) -> Result<Option<PoolType>>;
+
+	#[rpc(name = "xyk_getPoolByShareToken")]
+	fn get_pool_by_share_token(&self, share_token: AssetId, at: Option<BlockHash>) -> Result<Option<PoolType>>;
 }
 
 /// A struct that implements the [`XYKApi`].
@@ -68,18 +106,37 @@ impl<C, B> XYK<C, B> {
 pub enum Error {
 	/// The call to runtime failed.
 	RuntimeError,
+	/// The quoted trade would fail.
+	InvalidTrade,
 }
 
 impl From<Error> for i64 {
 	fn from(e: Error) -> i64 {
 		match e {
 			Error::RuntimeError => 1,
+			Error::InvalidTrade => 2,
 		}
 	}
 }
 
+fn invalid_trade_error(e: DispatchError) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(Error::InvalidTrade.into()),
+		message: "Trade would fail.".into(),
+		data: Some(format!("{:?}", e).into()),
+	}
+}
+
 impl<C, Block, AccountId, AssetId, Balance>
-	XYKApi<<Block as BlockT>::Hash, AccountId, AssetId, Balance, BalanceInfo<AssetId, Balance>> for XYK<C, Block>
+	XYKApi<
+		<Block as BlockT>::Hash,
+		AccountId,
+		AssetId,
+		Balance,
+		BalanceInfo<AssetId, Balance>,
+		TradeQuote<Balance>,
+		PoolInfo<AccountId, AssetId, Balance>,
+	> for XYK<C, Block>
 where
 	Block: BlockT,
 	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
@@ -115,4 +172,100 @@ where
 			data: Some(format!("{:?}", e).into()),
 		})
 	}
+
+	fn quote_sell(
+		&self,
+		asset_in: AssetId,
+		asset_out: AssetId,
+		amount: Balance,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<TradeQuote<Balance>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.quote_sell(&at, asset_in, asset_out, amount)
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError.into()),
+				message: "Unable to quote sell.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})?
+			.map_err(invalid_trade_error)
+	}
+
+	fn quote_buy(
+		&self,
+		asset_out: AssetId,
+		asset_in: AssetId,
+		amount: Balance,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<TradeQuote<Balance>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.quote_buy(&at, asset_out, asset_in, amount)
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError.into()),
+				message: "Unable to quote buy.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})?
+			.map_err(invalid_trade_error)
+	}
+	fn get_pools(
+		&self,
+		start_after: Option<AccountId>,
+		limit: Option<u32>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<PoolInfo<AccountId, AssetId, Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		let limit = limit.unwrap_or(DEFAULT_POOLS_PAGE_SIZE).min(MAX_POOLS_PAGE_SIZE);
+
+		api.get_pools(&at, start_after, limit).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to retrieve pools.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn get_pool(
+		&self,
+		asset_a: AssetId,
+		asset_b: AssetId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<PoolInfo<AccountId, AssetId, Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.get_pool(&at, asset_a, asset_b).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to retrieve pool.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn get_pool_by_share_token(
+		&self,
+		share_token: AssetId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<PoolInfo<AccountId, AssetId, Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.get_pool_by_share_token(&at, share_token).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to retrieve pool.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
 }
@@ -33,6 +33,7 @@ fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
 	let caller: T::AccountId = account(name, index, SEED);
 	T::Currency::update_balance(1, &caller, 1_000_000_000_000_000).unwrap();
 	T::Currency::update_balance(2, &caller, 1_000_000_000_000_000).unwrap();
+	T::Currency::update_balance(T::NativeAssetId::get(), &caller, T::PoolDeposit::get().saturated_into()).unwrap();
 	caller
 }
 
@@ -44,8 +45,14 @@ benchmarks! {
 		let asset_b: AssetId = 2;
 		let amount : Balance = 10 * 1_000_000_000;
 		let initial_price : Price = Price::from(2);
+		let fee = (3, 1_000);
 
-	}: _(RawOrigin::Signed(caller.clone()), asset_a, asset_b, amount, initial_price)
+		XYK::<T>::set_fee_tiers(RawOrigin::Root.into(), vec![fee])?;
+		XYK::<T>::set_require_allowed_assets(RawOrigin::Root.into(), true)?;
+		XYK::<T>::set_asset_pool_permission(RawOrigin::Root.into(), asset_a, Some(AssetPoolPermission::Allowed))?;
+		XYK::<T>::set_asset_pool_permission(RawOrigin::Root.into(), asset_b, Some(AssetPoolPermission::Allowed))?;
+
+	}: _(RawOrigin::Signed(caller.clone()), asset_a, asset_b, amount, initial_price, Some(fee))
 	verify {
 		assert_eq!(T::Currency::free_balance(asset_a, &caller), 999990000000000);
 	}
@@ -59,9 +66,9 @@ benchmarks! {
 		let amount : Balance = 10 * 1_000_000_000;
 		let max_limit : Balance = 10 * 1_000_000_000_000;
 
-		XYK::<T>::create_pool(RawOrigin::Signed(maker).into(), asset_a,asset_b, 1_000_000_000, Price::from(1))?;
+		XYK::<T>::create_pool(RawOrigin::Signed(maker).into(), asset_a, asset_b, 1_000_000_000, Price::from(1), None)?;
 
-	}: _(RawOrigin::Signed(caller.clone()), asset_a, asset_b, amount, max_limit)
+	}: _(RawOrigin::Signed(caller.clone()), asset_a, asset_b, amount, max_limit, 0, None)
 	verify {
 		assert_eq!(T::Currency::free_balance(asset_a, &caller), 999990000000000);
 		assert_eq!(T::Currency::free_balance(asset_b, &caller), 999990000000000);
@@ -75,18 +82,53 @@ benchmarks! {
 		let asset_b: AssetId = 2;
 		let amount : Balance = 1_000_000_000;
 
-		XYK::<T>::create_pool(RawOrigin::Signed(maker).into(), 1, 2, 10_000_000_000, Price::from(2))?;
-		XYK::<T>::add_liquidity(RawOrigin::Signed(caller.clone()).into(), 1, 2, 5_000_000_000, 10_000_000_000)?;
+		XYK::<T>::create_pool(RawOrigin::Signed(maker).into(), 1, 2, 10_000_000_000, Price::from(2), None)?;
+		XYK::<T>::add_liquidity(RawOrigin::Signed(caller.clone()).into(), 1, 2, 5_000_000_000, 10_000_000_000, 0, None)?;
 
 		assert_eq!(T::Currency::free_balance(asset_a, &caller), 999995000000000);
 		assert_eq!(T::Currency::free_balance(asset_b, &caller), 999990000000000);
 
-	}: _(RawOrigin::Signed(caller.clone()), asset_a, asset_b, amount)
+	}: _(RawOrigin::Signed(caller.clone()), asset_a, asset_b, amount, 0, 0, None)
 	verify {
 		assert_eq!(T::Currency::free_balance(asset_a, &caller), 999996000000000);
 		assert_eq!(T::Currency::free_balance(asset_b, &caller), 999992000000000);
 	}
 
+	add_liquidity_single_asset {
+		let maker = funded_account::<T>("maker", 0);
+		let caller = funded_account::<T>("caller", 0);
+
+		let asset_a: AssetId = 1;
+		let asset_b: AssetId = 2;
+		let amount : Balance = 10 * 1_000_000_000;
+
+		XYK::<T>::create_pool(RawOrigin::Signed(maker).into(), asset_a, asset_b, 1_000_000_000_000, Price::from(1), None)?;
+
+		let share_token = XYK::<T>::share_token(XYK::<T>::pair_account_from_assets(asset_a, asset_b));
+
+	}: _(RawOrigin::Signed(caller.clone()), asset_a, asset_b, amount, 0)
+	verify {
+		assert!(T::Currency::free_balance(asset_a, &caller) >= 999990000000000);
+		assert!(T::Currency::free_balance(share_token, &caller) > 0);
+	}
+
+	remove_liquidity_single_asset {
+		let maker = funded_account::<T>("maker", 0);
+		let caller = funded_account::<T>("caller", 0);
+
+		let asset_a: AssetId = 1;
+		let asset_b: AssetId = 2;
+		let amount : Balance = 1_000_000_000;
+
+		XYK::<T>::create_pool(RawOrigin::Signed(maker).into(), 1, 2, 10_000_000_000, Price::from(2), None)?;
+		XYK::<T>::add_liquidity(RawOrigin::Signed(caller.clone()).into(), 1, 2, 5_000_000_000, 10_000_000_000, 0, None)?;
+
+	}: _(RawOrigin::Signed(caller.clone()), asset_a, asset_b, amount, 0)
+	verify {
+		assert!(T::Currency::free_balance(asset_a, &caller) > 999996000000000);
+		assert_eq!(T::Currency::free_balance(asset_b, &caller), 999990000000000);
+	}
+
 	sell {
 		let maker = funded_account::<T>("maker", 0);
 		let caller = funded_account::<T>("caller", 0);
@@ -98,9 +140,10 @@ benchmarks! {
 
 		let min_bought: Balance = 10 * 1_000;
 
-		XYK::<T>::create_pool(RawOrigin::Signed(maker).into(), asset_a, asset_b, 1_000_000_000_000, Price::from(3))?;
+		XYK::<T>::create_pool(RawOrigin::Signed(maker).into(), asset_a, asset_b, 1_000_000_000_000, Price::from(3), None)?;
+		XYK::<T>::set_protocol_fee(RawOrigin::Root.into(), Permill::from_percent(50))?;
 
-	}: _(RawOrigin::Signed(caller.clone()), asset_a, asset_b, amount, min_bought, discount)
+	}: _(RawOrigin::Signed(caller.clone()), asset_a, asset_b, amount, min_bought, discount, None, Some(Permill::one()))
 	verify{
 		assert_eq!(T::Currency::free_balance(asset_a, &caller), 999999000000000);
 		assert_eq!(T::Currency::free_balance(asset_b, &caller), 1000002991008993);
@@ -117,13 +160,111 @@ benchmarks! {
 
 		let max_sold: Balance = 6_000_000_000;
 
-		XYK::<T>::create_pool(RawOrigin::Signed(maker).into(), asset_a, asset_b, 1_000_000_000_000, Price::from(3))?;
+		XYK::<T>::create_pool(RawOrigin::Signed(maker).into(), asset_a, asset_b, 1_000_000_000_000, Price::from(3), None)?;
+		XYK::<T>::set_protocol_fee(RawOrigin::Root.into(), Permill::from_percent(50))?;
 
-	}: _(RawOrigin::Signed(caller.clone()), asset_a, asset_b, amount, max_sold, discount)
+	}: _(RawOrigin::Signed(caller.clone()), asset_a, asset_b, amount, max_sold, discount, None, Some(Permill::one()))
 	verify{
 		assert_eq!(T::Currency::free_balance(asset_a, &caller), 1000001000000000);
 		assert_eq!(T::Currency::free_balance(asset_b, &caller), 999996990990990);
 	}
+
+	set_fee_tiers {
+		let fee_tiers = vec![(1, 1_000); T::MaxFeeTiers::get() as usize];
+
+	}: _(RawOrigin::Root, fee_tiers.clone())
+	verify {
+		assert_eq!(XYK::<T>::fee_tiers().to_vec(), fee_tiers);
+	}
+
+	set_pool_fee {
+		let maker = funded_account::<T>("maker", 0);
+
+		let asset_a: AssetId = 1;
+		let asset_b: AssetId = 2;
+		let fee = (5, 1_000);
+
+		XYK::<T>::create_pool(RawOrigin::Signed(maker).into(), asset_a, asset_b, 1_000_000_000_000, Price::from(3), None)?;
+
+	}: _(RawOrigin::Root, asset_a, asset_b, fee)
+	verify {
+		assert_eq!(XYK::<T>::get_fee(&XYK::<T>::pair_account_from_assets(asset_a, asset_b)), fee);
+	}
+
+	set_protocol_fee {
+		let protocol_fee = Permill::from_percent(20);
+
+	}: _(RawOrigin::Root, protocol_fee)
+	verify {
+		assert_eq!(XYK::<T>::protocol_fee(), protocol_fee);
+	}
+
+	set_asset_pool_permission {
+		let asset_id: AssetId = 1;
+
+	}: _(RawOrigin::Root, asset_id, Some(AssetPoolPermission::Denied))
+	verify {
+		assert!(!XYK::<T>::is_asset_permitted(asset_id));
+	}
+
+	set_require_allowed_assets {
+	}: _(RawOrigin::Root, true)
+	verify {
+		assert!(XYK::<T>::require_allowed_assets());
+	}
+
+	batch_trade {
+		let n in 0 .. T::MaxBatchSize::get() / 4;
+		let l in 0 .. T::MaxBatchSize::get();
+
+		let maker = funded_account::<T>("maker", 0);
+		let caller = funded_account::<T>("caller", 0);
+
+		let asset_a: AssetId = 1;
+		let asset_b: AssetId = 2;
+		let liquidity: Balance = 1_000_000_000_000;
+		let amount: Balance = 1_000_000_000;
+
+		XYK::<T>::create_pool(RawOrigin::Signed(maker).into(), asset_a, asset_b, liquidity, Price::from(1), None)?;
+
+		// `n` instructions of each kind
+		let mut instructions = Vec::new();
+		for _ in 0..n {
+			instructions.push(TradeInstruction::AddLiquidity {
+				asset_a,
+				asset_b,
+				amount_a: amount,
+				amount_b_max_limit: 2 * amount,
+				min_shares: 0,
+			});
+			instructions.push(TradeInstruction::Sell {
+				asset_in: asset_a,
+				asset_out: asset_b,
+				amount,
+				max_limit: 0,
+			});
+			instructions.push(TradeInstruction::Buy {
+				asset_out: asset_a,
+				asset_in: asset_b,
+				amount,
+				max_limit: 2 * amount,
+			});
+			instructions.push(TradeInstruction::RemoveLiquidity {
+				asset_a,
+				asset_b,
+				liquidity_amount: amount / 2,
+				min_amount_a: 0,
+				min_amount_b: 0,
+			});
+		}
+
+		let aggregate_limits: Vec<(AssetId, Amount)> = (0..l).map(|asset_id| (asset_id, Amount::MIN)).collect();
+
+	}: _(RawOrigin::Signed(caller.clone()), instructions, aggregate_limits, None)
+	verify {
+		let share_token = XYK::<T>::share_token(XYK::<T>::pair_account_from_assets(asset_a, asset_b));
+		assert!(n == 0 || T::Currency::free_balance(share_token, &caller) > 0);
+	}
 }
 
 #[cfg(test)]
@@ -138,8 +279,16 @@ mod tests {
 			assert_ok!(Pallet::<Test>::test_benchmark_create_pool());
 			assert_ok!(Pallet::<Test>::test_benchmark_add_liquidity());
 			assert_ok!(Pallet::<Test>::test_benchmark_remove_liquidity());
+			assert_ok!(Pallet::<Test>::test_benchmark_add_liquidity_single_asset());
+			assert_ok!(Pallet::<Test>::test_benchmark_remove_liquidity_single_asset());
 			assert_ok!(Pallet::<Test>::test_benchmark_sell());
 			assert_ok!(Pallet::<Test>::test_benchmark_buy());
+			assert_ok!(Pallet::<Test>::test_benchmark_set_fee_tiers());
+			assert_ok!(Pallet::<Test>::test_benchmark_set_pool_fee());
+			assert_ok!(Pallet::<Test>::test_benchmark_set_protocol_fee());
+			assert_ok!(Pallet::<Test>::test_benchmark_set_asset_pool_permission());
+			assert_ok!(Pallet::<Test>::test_benchmark_set_require_allowed_assets());
+			assert_ok!(Pallet::<Test>::test_benchmark_batch_trade());
 		});
 	}
 }
@@ -2,11 +2,26 @@ use hydradx_traits::pools::SpotPriceProvider;
 use hydradx_traits::AMM;
 use orml_traits::MultiCurrency;
 use primitives::asset::AssetPair;
+use primitives::traits::TwapProvider;
 use primitives::{AssetId, Price};
+use sp_runtime::traits::{CheckedAdd, CheckedMul, One};
+use sp_runtime::FixedPointNumber;
 use sp_std::marker::PhantomData;
 
 pub struct XYKSpotPrice<T>(PhantomData<T>);
 
+impl<T: crate::Config> XYKSpotPrice<T> {
+	/// Trading fee of the pool of given assets, so the spot price can be adjusted by the pool's own fee.
+	pub fn pool_fee(asset_a: AssetId, asset_b: AssetId) -> Option<(u32, u32)> {
+		if Self::pair_exists(asset_a, asset_b) {
+			let pair_account = <crate::Pallet<T>>::get_pair_id(AssetPair::new(asset_b, asset_a));
+			Some(<crate::Pallet<T>>::get_fee(&pair_account))
+		} else {
+			None
+		}
+	}
+}
+
 impl<T: crate::Config> SpotPriceProvider<AssetId> for XYKSpotPrice<T> {
 	type Price = Price;
 
@@ -29,3 +44,46 @@ impl<T: crate::Config> SpotPriceProvider<AssetId> for XYKSpotPrice<T> {
 		}
 	}
 }
+
+/// Spot price provider which includes the trading fee of the pool, so the price of `asset_a` in `asset_b`
+/// is what a trader pays in `asset_b` for a small amount of `asset_a` bought in the pool.
+pub struct XYKFeeAdjustedSpotPrice<T>(PhantomData<T>);
+
+impl<T: crate::Config> SpotPriceProvider<AssetId> for XYKFeeAdjustedSpotPrice<T> {
+	type Price = Price;
+
+	fn pair_exists(asset_a: AssetId, asset_b: AssetId) -> bool {
+		XYKSpotPrice::<T>::pair_exists(asset_a, asset_b)
+	}
+
+	fn spot_price(asset_a: AssetId, asset_b: AssetId) -> Option<Self::Price> {
+		let price = XYKSpotPrice::<T>::spot_price(asset_a, asset_b)?;
+		let (numerator, denominator) = XYKSpotPrice::<T>::pool_fee(asset_a, asset_b)?;
+		let fee = Price::checked_from_rational(numerator, denominator)?;
+
+		Price::one().checked_add(&fee).and_then(|x| price.checked_mul(&x))
+	}
+}
+
+/// Spot price provider which returns the time-weighted average price between the oldest and the newest stored
+/// price observation of the pool, so the price cannot be moved by trades in the current block.
+///
+/// Spot price is used when the pool does not have two observations yet.
+pub struct XYKTwapPrice<T>(PhantomData<T>);
+
+impl<T: crate::Config> SpotPriceProvider<AssetId> for XYKTwapPrice<T> {
+	type Price = Price;
+
+	fn pair_exists(asset_a: AssetId, asset_b: AssetId) -> bool {
+		XYKSpotPrice::<T>::pair_exists(asset_a, asset_b)
+	}
+
+	fn spot_price(asset_a: AssetId, asset_b: AssetId) -> Option<Self::Price> {
+		let observation_blocks = <crate::Pallet<T>>::get_price_observation_blocks(asset_a, asset_b);
+
+		match (observation_blocks.first(), observation_blocks.last()) {
+			(Some(&from), Some(&to)) if from < to => <crate::Pallet<T>>::twap(asset_a, asset_b, from, to),
+			_ => XYKSpotPrice::<T>::spot_price(asset_a, asset_b),
+		}
+	}
+}
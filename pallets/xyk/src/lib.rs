@@ -28,15 +28,31 @@
 #![allow(clippy::unused_unit)]
 #![allow(clippy::upper_case_acronyms)]
 
-use frame_support::sp_runtime::{traits::Zero, DispatchError};
-use frame_support::{dispatch::DispatchResult, ensure, traits::Get, transactional};
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::sp_runtime::{
+	helpers_128bit::multiply_by_rational,
+	traits::{SaturatedConversion, Saturating, TrailingZeroInput, Zero},
+	DispatchError, Permill, RuntimeDebug,
+};
+use frame_support::{dispatch::DispatchResult, ensure, traits::Get, transactional, weights::Weight};
 use frame_system::ensure_signed;
-use hydradx_traits::{AMMTransfer, AssetPairAccountIdFor, CanCreatePool, OnCreatePoolHandler, OnTradeHandler, AMM};
-use primitives::{asset::AssetPair, AssetId, Balance, Price};
-use sp_std::{vec, vec::Vec};
+use hydradx_traits::{
+	AMMTransfer, AssetPairAccountIdFor, CanCreatePool, OnCreatePoolHandler, OnTradeHandler, Registry, AMM,
+};
+use primitives::{
+	asset::AssetPair,
+	traits::{
+		AMMTradeCalculation, AssetMetadataRegistry, CircuitBreaker, FlashSwapHandler, OnLiquidityChangedHandler,
+		OnPoolDestroyedHandler, TradingLimitsProvider, TwapProvider,
+	},
+	AssetId, Balance, PoolStats, Price, TradingLimits,
+};
+use scale_info::TypeInfo;
+use sp_std::{marker::PhantomData, vec, vec::Vec};
 
 use frame_support::sp_runtime::FixedPointNumber;
-use orml_traits::{MultiCurrency, MultiCurrencyExtended};
+use orml_traits::{MultiCurrency, MultiCurrencyExtended, MultiReservableCurrency};
+use primitive_types::U256;
 use primitives::Amount;
 
 #[cfg(test)]
@@ -48,15 +64,104 @@ mod tests;
 mod benchmarking;
 
 mod impls;
+pub mod migration;
 pub mod weights;
 
-pub use impls::XYKSpotPrice;
+pub use impls::{XYKFeeAdjustedSpotPrice, XYKSpotPrice, XYKTwapPrice};
 
 use weights::WeightInfo;
 
 // Re-export pallet items so that they can be accessed from the crate namespace.
 pub use pallet::*;
 
+/// Decimals of share tokens, same as decimals of the native asset.
+pub const SHARE_TOKEN_DECIMALS: u8 = 12;
+
+/// Quote of a trade calculated without executing the trade.
+///
+/// Spot prices are amounts of `asset_out` per one unit of `asset_in`.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct TradeQuote {
+	/// Amount of `asset_out` received (fee deducted) for sell, amount of `asset_in` paid (fee included) for buy.
+	pub amount: Balance,
+	/// Trade fee - in `asset_out` for sell, in `asset_in` for buy.
+	pub fee: Balance,
+	/// Part of `fee` transferred to protocol fee receiver instead of staying in the pool.
+	pub protocol_fee: Balance,
+	pub spot_price_before: Price,
+	pub spot_price_after: Price,
+	/// Relative change of spot price caused by the trade.
+	pub price_impact: Permill,
+}
+
+/// Information about a pool.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct PoolInfo<AccountId> {
+	pub pool_account: AccountId,
+	pub assets: (AssetId, AssetId),
+	/// Reserves of `assets`, in the same order.
+	pub reserves: (Balance, Balance),
+	pub share_token: AssetId,
+	pub total_liquidity: Balance,
+	pub fee: (u32, u32),
+	/// Trading limits of the pool, limits set for the pool take precedence over the default ones.
+	pub trading_limits: TradingLimits,
+}
+
+/// Cumulative prices of the assets of a pool at a block.
+///
+/// Cumulative price is the sum of the pool price over all blocks since the first observation,
+/// so the average price between two observations is the difference of their cumulative prices
+/// divided by the number of blocks between them. Cumulative prices wrap around on overflow.
+#[derive(Encode, Decode, Clone, Copy, Default, PartialEq, Eq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub struct PriceObservation<BlockNumber> {
+	pub block: BlockNumber,
+	/// Cumulative price of the first asset of the pool in the second one, as inner value of `Price`.
+	pub price_a_cumulative: u128,
+	/// Cumulative price of the second asset of the pool in the first one, as inner value of `Price`.
+	pub price_b_cumulative: u128,
+}
+
+/// Operation executed as a part of `batch_trade`.
+///
+/// Limits of each operation have the same meaning as limits of the corresponding call.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum TradeInstruction {
+	Sell {
+		asset_in: AssetId,
+		asset_out: AssetId,
+		amount: Balance,
+		max_limit: Balance,
+	},
+	Buy {
+		asset_out: AssetId,
+		asset_in: AssetId,
+		amount: Balance,
+		max_limit: Balance,
+	},
+	AddLiquidity {
+		asset_a: AssetId,
+		asset_b: AssetId,
+		amount_a: Balance,
+		amount_b_max_limit: Balance,
+		min_shares: Balance,
+	},
+	RemoveLiquidity {
+		asset_a: AssetId,
+		asset_b: AssetId,
+		liquidity_amount: Balance,
+		min_amount_a: Balance,
+		min_amount_b: Balance,
+	},
+}
+
+/// Permission of an asset to be used in new pools.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub enum AssetPoolPermission {
+	Allowed,
+	Denied,
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -64,11 +169,18 @@ pub mod pallet {
 	use frame_system::pallet_prelude::OriginFor;
 	use hydradx_traits::ShareTokenRegistry;
 
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_runtime_upgrade() -> frame_support::weights::Weight {
+			migration::name_share_tokens::<T>().saturating_add(migration::set_liquidity_floors::<T>())
+		}
+	}
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
@@ -77,11 +189,15 @@ pub mod pallet {
 		/// Registry support
 		type AssetRegistry: ShareTokenRegistry<AssetId, Vec<u8>, Balance, DispatchError>;
 
+		/// Names and metadata of assets, used to name share tokens
+		type AssetMetadata: AssetMetadataRegistry<AssetId>;
+
 		/// Share token support
 		type AssetPairAccountId: AssetPairAccountIdFor<AssetId, Self::AccountId>;
 
 		/// Multi currency for transfer of currencies
-		type Currency: MultiCurrencyExtended<Self::AccountId, CurrencyId = AssetId, Balance = Balance, Amount = Amount>;
+		type Currency: MultiCurrencyExtended<Self::AccountId, CurrencyId = AssetId, Balance = Balance, Amount = Amount>
+			+ MultiReservableCurrency<Self::AccountId>;
 
 		/// Native Asset Id
 		#[pallet::constant]
@@ -90,31 +206,57 @@ pub mod pallet {
 		/// Weight information for the extrinsics.
 		type WeightInfo: WeightInfo;
 
-		/// Trading fee rate
+		/// Default trading fee rate, used when pool is created without a specific fee tier
 		#[pallet::constant]
 		type GetExchangeFee: Get<(u32, u32)>;
 
-		/// Minimum trading limit
+		/// Max number of fee tiers which can be approved for pool creation
 		#[pallet::constant]
-		type MinTradingLimit: Get<Balance>;
+		type MaxFeeTiers: Get<u32>;
 
-		/// Minimum pool liquidity
+		/// Max number of price observations stored per pool
 		#[pallet::constant]
-		type MinPoolLiquidity: Get<Balance>;
+		type MaxPriceObservations: Get<u32>;
 
-		/// Max fraction of pool to sell in single transaction
+		/// Number of blocks of a window of pool trade statistics
 		#[pallet::constant]
-		type MaxInRatio: Get<u128>;
+		type StatsWindow: Get<Self::BlockNumber>;
 
-		/// Max fraction of pool to buy in single transaction
+		/// The origin which can update fee tiers, fees of existing pools and protocol fee
+		type AuthorityOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Account which receives protocol fee share of trade fees
 		#[pallet::constant]
-		type MaxOutRatio: Get<u128>;
+		type ProtocolFeeReceiver: Get<Self::AccountId>;
+
+		/// Trading limits of pools
+		type TradingLimits: TradingLimitsProvider<Self::AccountId>;
+
+		/// Circuit breaker which can halt trading and changes of liquidity in pools
+		type CircuitBreaker: CircuitBreaker<Self::AccountId>;
 
 		/// Called to ensure that pool can be created
 		type CanCreatePool: CanCreatePool<AssetId>;
 
+		/// Deposit in native currency reserved from pool creator and refunded when the pool is destroyed
+		#[pallet::constant]
+		type PoolDeposit: Get<Balance>;
+
 		/// AMM handlers
 		type AMMHandler: OnCreatePoolHandler<AssetId> + OnTradeHandler<AssetId, Balance>;
+
+		/// Handler called when liquidity is added to or removed from a pool
+		type LiquidityChangedHandler: OnLiquidityChangedHandler<AssetId, Balance>;
+
+		/// Handler called when a pool is destroyed after its last unlocked shares were removed
+		type PoolDestroyedHandler: OnPoolDestroyedHandler<Self::AccountId, AssetId>;
+
+		/// Handler called with assets borrowed by flash swap
+		type FlashSwapHandler: FlashSwapHandler<Self::AccountId, AssetId, Balance>;
+
+		/// Max number of instructions and of aggregate limits of a batch trade
+		#[pallet::constant]
+		type MaxBatchSize: Get<u32>;
 	}
 
 	#[pallet::error]
@@ -189,6 +331,51 @@ pub mod pallet {
 
 		/// Pool cannot be created due to outside factors.
 		CannotCreatePool,
+
+		/// Fee is not one of the approved fee tiers.
+		FeeTierNotAllowed,
+
+		/// Fee numerator has to be lower than non-zero denominator.
+		InvalidFee,
+
+		/// Max number of fee tiers has been exceeded.
+		MaxFeeTiersExceeded,
+
+		/// Pool invariant increased by trade fee does not hold after flash swap.
+		InsufficientFlashSwapRepayment,
+
+		/// Liquidity of the pool was changed during flash swap.
+		FlashSwapLiquidityChanged,
+
+		/// Native currency balance is not sufficient for pool deposit.
+		InsufficientPoolDepositBalance,
+
+		/// Liquidity locked on pool creation cannot be removed.
+		CannotRemoveLockedLiquidity,
+
+		/// Trading is not allowed in current state of the pool.
+		TradingNotAllowed,
+
+		/// Change of liquidity is not allowed in current state of the pool.
+		LiquidityChangeNotAllowed,
+
+		/// Price impact of the trade exceeds given maximum.
+		MaxPriceImpactExceeded,
+
+		/// Max number of instructions or aggregate limits of a batch trade has been exceeded.
+		MaxBatchSizeExceeded,
+
+		/// Net change of an asset balance after a batch trade has not reached given limit.
+		AggregateLimitNotReached,
+
+		/// Amount of shares has not reached given limit.
+		SharesAmountNotReachedLimit,
+
+		/// Amount of asset removed from the pool has not reached given limit.
+		RemoveAssetAmountNotReachedLimit,
+
+		/// Deadline of the operation has passed.
+		DeadlineExpired,
 	}
 
 	#[pallet::event]
@@ -206,7 +393,7 @@ pub mod pallet {
 		/// Pool was destroyed. [who, asset a, asset b, share token, pool account id]
 		PoolDestroyed(T::AccountId, AssetId, AssetId, AssetId, T::AccountId),
 
-		/// Asset sale executed. [who, asset in, asset out, amount, sale price, fee asset, fee amount, pool account id]
+		/// Asset sale executed. [who, asset in, asset out, amount, sale price, fee asset, fee amount, pool account id, price impact]
 		SellExecuted(
 			T::AccountId,
 			AssetId,
@@ -216,9 +403,10 @@ pub mod pallet {
 			AssetId,
 			Balance,
 			T::AccountId,
+			Permill,
 		),
 
-		/// Asset purchase executed. [who, asset out, asset in, amount, buy price, fee asset, fee amount]
+		/// Asset purchase executed. [who, asset out, asset in, amount, buy price, fee asset, fee amount, pool account id, price impact]
 		BuyExecuted(
 			T::AccountId,
 			AssetId,
@@ -228,7 +416,32 @@ pub mod pallet {
 			AssetId,
 			Balance,
 			T::AccountId,
+			Permill,
 		),
+
+		/// Trading fee of a pool was updated. [asset a, asset b, fee, pool account id]
+		PoolFeeUpdated(AssetId, AssetId, (u32, u32), T::AccountId),
+
+		/// List of approved fee tiers was updated. [fee tiers]
+		FeeTiersUpdated(Vec<(u32, u32)>),
+
+		/// Protocol fee share of trade fees was updated. [protocol fee]
+		ProtocolFeeUpdated(Permill),
+
+		/// Protocol fee share of a trade fee was transferred. [pool account id, fee asset, amount, receiver]
+		ProtocolFeeTransferred(T::AccountId, AssetId, Balance, T::AccountId),
+
+		/// Flash swap was executed. [who, asset out, asset in, amount out, pool account id]
+		FlashSwapExecuted(T::AccountId, AssetId, AssetId, Balance, T::AccountId),
+
+		/// Permission of an asset to be used in new pools was updated. [asset id, permission]
+		AssetPoolPermissionUpdated(AssetId, Option<AssetPoolPermission>),
+
+		/// Requirement of allowed assets in new pools was updated. [required]
+		RequireAllowedAssetsUpdated(bool),
+
+		/// Batch of trade instructions was executed. [who, number of instructions]
+		BatchTradeExecuted(T::AccountId, u32),
 	}
 
 	/// Asset id storage for shared pool tokens
@@ -236,6 +449,11 @@ pub mod pallet {
 	#[pallet::getter(fn share_token)]
 	pub(crate) type ShareToken<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, AssetId, ValueQuery>;
 
+	/// Pool account of a shared pool token.
+	#[pallet::storage]
+	#[pallet::getter(fn share_token_pool)]
+	pub(crate) type ShareTokenPool<T: Config> = StorageMap<_, Blake2_128Concat, AssetId, T::AccountId, OptionQuery>;
+
 	/// Total liquidity in a pool.
 	#[pallet::storage]
 	#[pallet::getter(fn total_liquidity)]
@@ -247,15 +465,81 @@ pub mod pallet {
 	pub(crate) type PoolAssets<T: Config> =
 		StorageMap<_, Blake2_128Concat, T::AccountId, (AssetId, AssetId), OptionQuery>;
 
+	/// Trading fee of a pool.
+	/// Pools without a stored fee use `GetExchangeFee`.
+	#[pallet::storage]
+	#[pallet::getter(fn pool_fee)]
+	pub(crate) type PoolFee<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, (u32, u32), OptionQuery>;
+
+	/// Fee tiers approved by governance which can be selected when a pool is created.
+	#[pallet::storage]
+	#[pallet::getter(fn fee_tiers)]
+	pub(crate) type FeeTiers<T: Config> = StorageValue<_, BoundedVec<(u32, u32), T::MaxFeeTiers>, ValueQuery>;
+
+	/// Share of trade fees transferred to `ProtocolFeeReceiver`. The rest of the fees stays in the pools.
+	#[pallet::storage]
+	#[pallet::getter(fn protocol_fee)]
+	pub(crate) type ProtocolFee<T: Config> = StorageValue<_, Permill, ValueQuery>;
+
+	/// Account which created a pool and its deposit reserved until the pool is destroyed.
+	#[pallet::storage]
+	#[pallet::getter(fn pool_deposit)]
+	pub(crate) type PoolDeposits<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, (T::AccountId, Balance), OptionQuery>;
+
+	/// Minimum total liquidity of a pool. Liquidity can't be removed below it
+	/// unless only the locked shares are left and the pool is destroyed.
+	#[pallet::storage]
+	#[pallet::getter(fn liquidity_floor)]
+	pub(crate) type LiquidityFloor<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, Balance, ValueQuery>;
+
+	/// Permissions of assets to be used in new pools, set by governance.
+	#[pallet::storage]
+	#[pallet::getter(fn asset_pool_permission)]
+	pub(crate) type AssetPoolPermissions<T: Config> =
+		StorageMap<_, Blake2_128Concat, AssetId, AssetPoolPermission, OptionQuery>;
+
+	/// If set, only assets with `Allowed` permission can be used in new pools.
+	/// Otherwise all assets except those with `Denied` permission can be used.
+	#[pallet::storage]
+	#[pallet::getter(fn require_allowed_assets)]
+	pub(crate) type RequireAllowedAssets<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	/// Price observations of a pool, oldest first.
+	/// Observation is added in the first block of each trade or liquidity change of the pool.
+	#[pallet::storage]
+	#[pallet::getter(fn price_observations)]
+	pub(crate) type PriceObservations<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<PriceObservation<T::BlockNumber>, T::MaxPriceObservations>,
+		ValueQuery,
+	>;
+
+	/// Statistics of sells and buys executed in a pool.
+	#[pallet::storage]
+	#[pallet::getter(fn pool_trade_stats)]
+	pub(crate) type PoolTradeStats<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, PoolStats<T::BlockNumber>, ValueQuery>;
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Create new pool for given asset pair.
 		///
 		/// Registers new pool for given asset pair (`asset a` and `asset b`) in asset registry.
 		/// Asset registry creates new id or returns previously created one if such pool existed before.
+		/// Share token is named after registered names of the assets, e.g. `BSX-KSM LP`, if both are known.
 		///
 		/// Pool is created with initial liquidity provided by `origin`.
 		/// Shares are issued with specified initial price and represents proportion of asset in the pool.
+		/// Minimum pool liquidity of the pool's trading limits is locked in `locked_liquidity_account`
+		/// for the lifetime of the pool, the rest of shares is issued to `origin`.
+		///
+		/// `fee` - trading fee of the pool. It has to be one of the approved fee tiers.
+		/// Default `GetExchangeFee` is used if not specified.
+		///
+		/// `PoolDeposit` in native currency is reserved from `origin` and refunded when the pool is destroyed.
 		///
 		/// Emits `PoolCreated` event when successful.
 		#[pallet::weight(<T as Config>::WeightInfo::create_pool())]
@@ -266,25 +550,38 @@ pub mod pallet {
 			asset_b: AssetId,
 			amount: Balance,
 			initial_price: Price,
+			fee: Option<(u32, u32)>,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
+			let fee = match fee {
+				Some(fee) => {
+					ensure!(Self::is_fee_tier_allowed(fee), Error::<T>::FeeTierNotAllowed);
+					fee
+				}
+				None => T::GetExchangeFee::get(),
+			};
+
 			ensure!(
 				T::CanCreatePool::can_create(asset_a, asset_b),
 				Error::<T>::CannotCreatePool
 			);
 
-			ensure!(amount >= T::MinPoolLiquidity::get(), Error::<T>::InsufficientLiquidity);
-
-			ensure!(initial_price != Price::zero(), Error::<T>::ZeroInitialPrice);
-
-			ensure!(asset_a != asset_b, Error::<T>::CannotCreatePoolWithSameAssets);
-
 			let asset_pair = AssetPair {
 				asset_in: asset_a,
 				asset_out: asset_b,
 			};
 
+			let pair_account = Self::get_pair_id(asset_pair);
+
+			let limits = T::TradingLimits::pool_limits(&pair_account);
+
+			ensure!(amount >= limits.min_pool_liquidity, Error::<T>::InsufficientLiquidity);
+
+			ensure!(initial_price != Price::zero(), Error::<T>::ZeroInitialPrice);
+
+			ensure!(asset_a != asset_b, Error::<T>::CannotCreatePoolWithSameAssets);
+
 			ensure!(!Self::exists(asset_pair), Error::<T>::TokenPoolAlreadyExists);
 
 			let asset_b_amount = initial_price
@@ -292,12 +589,20 @@ pub mod pallet {
 				.ok_or(Error::<T>::CreatePoolAssetAmountInvalid)?;
 
 			ensure!(
-				asset_b_amount >= T::MinPoolLiquidity::get(),
+				asset_b_amount >= limits.min_pool_liquidity,
 				Error::<T>::InsufficientLiquidity
 			);
 
 			let shares_added = if asset_a < asset_b { amount } else { asset_b_amount };
 
+			let locked_shares = limits.min_pool_liquidity;
+			let creator_shares = shares_added.saturating_sub(locked_shares);
+
+			ensure!(
+				creator_shares >= limits.min_pool_liquidity,
+				Error::<T>::InsufficientLiquidity
+			);
+
 			ensure!(
 				T::Currency::free_balance(asset_a, &who) >= amount,
 				Error::<T>::InsufficientAssetBalance
@@ -308,20 +613,24 @@ pub mod pallet {
 				Error::<T>::InsufficientAssetBalance
 			);
 
-			let pair_account = Self::get_pair_id(asset_pair);
-
-			let token_name = asset_pair.name();
+			let token_name = Self::share_token_name(asset_a, asset_b);
 
 			let share_token = T::AssetRegistry::get_or_create_shared_asset(
-				token_name,
+				token_name.clone().unwrap_or_else(|| asset_pair.name()),
 				vec![asset_a, asset_b],
-				T::MinPoolLiquidity::get(),
+				limits.min_pool_liquidity,
 			)?;
 
+			if let Some(symbol) = token_name {
+				T::AssetMetadata::set_asset_metadata(share_token, symbol, SHARE_TOKEN_DECIMALS)?;
+			}
+
 			let _ = T::AMMHandler::on_create_pool(asset_pair.asset_in, asset_pair.asset_out)?;
 
 			<ShareToken<T>>::insert(&pair_account, &share_token);
+			<ShareTokenPool<T>>::insert(share_token, &pair_account);
 			<PoolAssets<T>>::insert(&pair_account, (asset_a, asset_b));
+			<PoolFee<T>>::insert(&pair_account, fee);
 
 			Self::deposit_event(Event::PoolCreated(
 				who.clone(),
@@ -332,10 +641,21 @@ pub mod pallet {
 				pair_account.clone(),
 			));
 
+			Self::update_price_observations(&pair_account);
+
 			T::Currency::transfer(asset_a, &who, &pair_account, amount)?;
 			T::Currency::transfer(asset_b, &who, &pair_account, asset_b_amount)?;
 
-			T::Currency::deposit(share_token, &who, shares_added)?;
+			let deposit = T::PoolDeposit::get();
+			if !deposit.is_zero() {
+				T::Currency::reserve(T::NativeAssetId::get(), &who, deposit)
+					.map_err(|_| Error::<T>::InsufficientPoolDepositBalance)?;
+				<PoolDeposits<T>>::insert(&pair_account, (who.clone(), deposit));
+			}
+
+			T::Currency::deposit(share_token, &who, creator_shares)?;
+			T::Currency::deposit(share_token, &Self::locked_liquidity_account(), locked_shares)?;
+			<LiquidityFloor<T>>::insert(&pair_account, locked_shares);
 
 			<TotalLiquidity<T>>::insert(&pair_account, shares_added);
 
@@ -346,8 +666,12 @@ pub mod pallet {
 		///
 		/// Shares are issued with current price.
 		///
+		/// `min_shares` - minimum amount of shares to be received.
+		///
+		/// `deadline` - last block in which the liquidity can be added.
+		///
 		/// Emits `LiquidityAdded` event when successful.
-		#[pallet::weight(<T as Config>::WeightInfo::add_liquidity())]
+		#[pallet::weight(Pallet::<T>::add_liquidity_weight())]
 		#[transactional]
 		pub fn add_liquidity(
 			origin: OriginFor<T>,
@@ -355,173 +679,46 @@ pub mod pallet {
 			asset_b: AssetId,
 			amount_a: Balance,
 			amount_b_max_limit: Balance,
+			min_shares: Balance,
+			deadline: Option<T::BlockNumber>,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
-			let asset_pair = AssetPair {
-				asset_in: asset_a,
-				asset_out: asset_b,
-			};
-
-			ensure!(Self::exists(asset_pair), Error::<T>::TokenPoolNotFound);
-
-			ensure!(
-				amount_a >= T::MinTradingLimit::get(),
-				Error::<T>::InsufficientTradingAmount
-			);
-
-			ensure!(!amount_b_max_limit.is_zero(), Error::<T>::ZeroLiquidity);
-
-			ensure!(
-				T::Currency::free_balance(asset_a, &who) >= amount_a,
-				Error::<T>::InsufficientAssetBalance
-			);
-
-			ensure!(
-				T::Currency::free_balance(asset_b, &who) >= amount_b_max_limit,
-				Error::<T>::InsufficientAssetBalance
-			);
-
-			let pair_account = Self::get_pair_id(asset_pair);
-
-			let share_token = Self::share_token(&pair_account);
-
-			let account_shares = T::Currency::free_balance(share_token, &who);
-
-			let asset_a_reserve = T::Currency::free_balance(asset_a, &pair_account);
-			let asset_b_reserve = T::Currency::free_balance(asset_b, &pair_account);
-			let total_liquidity = Self::total_liquidity(&pair_account);
-
-			let amount_b_required =
-				hydra_dx_math::xyk::calculate_liquidity_in(asset_a_reserve, asset_b_reserve, amount_a)
-					.map_err(|_| Error::<T>::AddAssetAmountInvalid)?;
+			Self::ensure_deadline(deadline)?;
 
-			let shares_added = if asset_a < asset_b { amount_a } else { amount_b_required };
-
-			ensure!(
-				amount_b_required <= amount_b_max_limit,
-				Error::<T>::AssetAmountExceededLimit
-			);
-
-			ensure!(!shares_added.is_zero(), Error::<T>::InvalidMintedLiquidity);
-
-			// Make sure that account share liquidity is at least MinPoolLiquidity
-			ensure!(
-				account_shares
-					.checked_add(shares_added)
-					.ok_or(Error::<T>::InvalidMintedLiquidity)?
-					>= T::MinPoolLiquidity::get(),
-				Error::<T>::InsufficientLiquidity
-			);
-
-			let liquidity_amount = total_liquidity
-				.checked_add(shares_added)
-				.ok_or(Error::<T>::InvalidLiquidityAmount)?;
-
-			T::Currency::transfer(asset_a, &who, &pair_account, amount_a)?;
-			T::Currency::transfer(asset_b, &who, &pair_account, amount_b_required)?;
-
-			T::Currency::deposit(share_token, &who, shares_added)?;
-
-			<TotalLiquidity<T>>::insert(&pair_account, liquidity_amount);
-
-			Self::deposit_event(Event::LiquidityAdded(
-				who,
-				asset_a,
-				asset_b,
-				amount_a,
-				amount_b_required,
-			));
+			Self::do_add_liquidity(&who, asset_a, asset_b, amount_a, amount_b_max_limit, min_shares)?;
 
 			Ok(())
 		}
 
 		/// Remove liquidity from specific liquidity pool in the form of burning shares.
 		///
-		/// If liquidity in the pool reaches 0, it is destroyed.
+		/// Total liquidity of the pool can't be removed below its liquidity floor. Pool is destroyed
+		/// when only the shares locked on pool creation are left, the locked shares are burned and
+		/// the last liquidity provider receives all remaining reserves.
+		///
+		/// `min_amount_a`, `min_amount_b` - minimum amounts of `asset_a` and `asset_b` to be received.
+		///
+		/// `deadline` - last block in which the liquidity can be removed.
 		///
 		/// Emits 'LiquidityRemoved' when successful.
 		/// Emits 'PoolDestroyed' when pool is destroyed.
-		#[pallet::weight(<T as Config>::WeightInfo::remove_liquidity())]
+		#[pallet::weight(Pallet::<T>::remove_liquidity_weight())]
 		#[transactional]
 		pub fn remove_liquidity(
 			origin: OriginFor<T>,
 			asset_a: AssetId,
 			asset_b: AssetId,
 			liquidity_amount: Balance,
+			min_amount_a: Balance,
+			min_amount_b: Balance,
+			deadline: Option<T::BlockNumber>,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
-			let asset_pair = AssetPair {
-				asset_in: asset_a,
-				asset_out: asset_b,
-			};
-
-			ensure!(!liquidity_amount.is_zero(), Error::<T>::ZeroLiquidity);
-
-			ensure!(Self::exists(asset_pair), Error::<T>::TokenPoolNotFound);
-
-			let pair_account = Self::get_pair_id(asset_pair);
-
-			let share_token = Self::share_token(&pair_account);
-
-			let total_shares = Self::total_liquidity(&pair_account);
+			Self::ensure_deadline(deadline)?;
 
-			let account_shares = T::Currency::free_balance(share_token, &who);
-
-			ensure!(total_shares >= liquidity_amount, Error::<T>::InsufficientLiquidity);
-
-			ensure!(account_shares >= liquidity_amount, Error::<T>::InsufficientAssetBalance);
-
-			// Account's liquidity left should be either 0 or at least MinPoolLiquidity
-			ensure!(
-				(account_shares.saturating_sub(liquidity_amount)) >= T::MinPoolLiquidity::get()
-					|| (account_shares == liquidity_amount),
-				Error::<T>::InsufficientLiquidity
-			);
-
-			let asset_a_reserve = T::Currency::free_balance(asset_a, &pair_account);
-			let asset_b_reserve = T::Currency::free_balance(asset_b, &pair_account);
-
-			let liquidity_out = hydra_dx_math::xyk::calculate_liquidity_out(
-				asset_a_reserve,
-				asset_b_reserve,
-				liquidity_amount,
-				total_shares,
-			)
-			.map_err(|_| Error::<T>::RemoveAssetAmountInvalid)?;
-
-			let (remove_amount_a, remove_amount_b) = liquidity_out;
-
-			ensure!(
-				T::Currency::free_balance(asset_a, &pair_account) >= remove_amount_a,
-				Error::<T>::InsufficientPoolAssetBalance
-			);
-			ensure!(
-				T::Currency::free_balance(asset_b, &pair_account) >= remove_amount_b,
-				Error::<T>::InsufficientPoolAssetBalance
-			);
-
-			let liquidity_left = total_shares
-				.checked_sub(liquidity_amount)
-				.ok_or(Error::<T>::InvalidLiquidityAmount)?;
-
-			T::Currency::transfer(asset_a, &pair_account, &who, remove_amount_a)?;
-			T::Currency::transfer(asset_b, &pair_account, &who, remove_amount_b)?;
-
-			T::Currency::withdraw(share_token, &who, liquidity_amount)?;
-
-			<TotalLiquidity<T>>::insert(&pair_account, liquidity_left);
-
-			Self::deposit_event(Event::LiquidityRemoved(who.clone(), asset_a, asset_b, liquidity_amount));
-
-			if liquidity_left == 0 {
-				<ShareToken<T>>::remove(&pair_account);
-				<PoolAssets<T>>::remove(&pair_account);
-				<TotalLiquidity<T>>::remove(&pair_account);
-
-				Self::deposit_event(Event::PoolDestroyed(who, asset_a, asset_b, share_token, pair_account));
-			}
+			Self::do_remove_liquidity(&who, asset_a, asset_b, liquidity_amount, min_amount_a, min_amount_b)?;
 
 			Ok(())
 		}
@@ -532,6 +729,10 @@ pub mod pallet {
 		///
 		/// `max_limit` - minimum amount of `asset_out` / amount of asset_out to be obtained from the pool in exchange for `asset_in`.
 		///
+		/// `deadline` - last block in which the trade can be executed.
+		///
+		/// `max_price_impact` - maximum deviation of the execution price (fee excluded) from the spot price of the pool.
+		///
 		/// Emits `SellExecuted` when successful.
 		#[pallet::weight(<T as Config>::WeightInfo::sell() + <T as Config>::AMMHandler::on_trade_weight())]
 		pub fn sell(
@@ -541,12 +742,21 @@ pub mod pallet {
 			amount: Balance,
 			max_limit: Balance,
 			discount: bool,
+			deadline: Option<T::BlockNumber>,
+			max_price_impact: Option<Permill>,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
-			<Self as AMM<_, _, _, _>>::sell(&who, AssetPair { asset_in, asset_out }, amount, max_limit, discount)?;
+			Self::ensure_deadline(deadline)?;
 
-			Ok(())
+			Self::do_sell(
+				&who,
+				AssetPair { asset_in, asset_out },
+				amount,
+				max_limit,
+				discount,
+				max_price_impact,
+			)
 		}
 
 		/// Trade asset in for asset out.
@@ -555,6 +765,10 @@ pub mod pallet {
 		///
 		/// `max_limit` - maximum amount of `asset_in` to be sold in exchange for `asset_out`.
 		///
+		/// `deadline` - last block in which the trade can be executed.
+		///
+		/// `max_price_impact` - maximum deviation of the execution price (fee excluded) from the spot price of the pool.
+		///
 		/// Emits `BuyExecuted` when successful.
 		#[pallet::weight(<T as Config>::WeightInfo::buy() + <T as Config>::AMMHandler::on_trade_weight())]
 		pub fn buy(
@@ -564,105 +778,1301 @@ pub mod pallet {
 			amount: Balance,
 			max_limit: Balance,
 			discount: bool,
+			deadline: Option<T::BlockNumber>,
+			max_price_impact: Option<Permill>,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
-			<Self as AMM<_, _, _, _>>::buy(&who, AssetPair { asset_in, asset_out }, amount, max_limit, discount)?;
+			Self::ensure_deadline(deadline)?;
 
-			Ok(())
+			Self::do_buy(
+				&who,
+				AssetPair { asset_in, asset_out },
+				amount,
+				max_limit,
+				discount,
+				max_price_impact,
+			)
 		}
-	}
-}
-
-impl<T: Config> Pallet<T> {
-	/// Return balance of each asset in selected liquidity pool.
-	pub fn get_pool_balances(pool_address: T::AccountId) -> Option<Vec<(AssetId, Balance)>> {
-		let mut balances = Vec::new();
 
-		if let Some(assets) = Self::get_pool_assets(&pool_address) {
-			for item in &assets {
-				let reserve = T::Currency::free_balance(*item, &pool_address);
-				balances.push((*item, reserve));
-			}
-		}
-		Some(balances)
-	}
-	/// Calculate discounted trade fee
-	fn calculate_discounted_fee(amount: Balance) -> Result<Balance, DispatchError> {
-		Ok(
-			hydra_dx_math::fee::calculate_pool_trade_fee(amount, (7, 10_000)) // 0.07%
-				.ok_or::<Error<T>>(Error::<T>::FeeAmountInvalid)?,
-		)
-	}
+		/// Update the list of fee tiers which can be selected when a pool is created.
+		///
+		/// Default `GetExchangeFee` is always allowed and does not have to be in the list.
+		///
+		/// The dispatch origin for this call must be `T::AuthorityOrigin`.
+		///
+		/// Emits `FeeTiersUpdated` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::set_fee_tiers())]
+		pub fn set_fee_tiers(origin: OriginFor<T>, fee_tiers: Vec<(u32, u32)>) -> DispatchResult {
+			T::AuthorityOrigin::ensure_origin(origin)?;
 
-	/// Calculate trade fee
-	fn calculate_fee(amount: Balance) -> Result<Balance, DispatchError> {
-		let fee = T::GetExchangeFee::get();
-		Ok(hydra_dx_math::fee::calculate_pool_trade_fee(amount, (fee.0, fee.1))
-			.ok_or::<Error<T>>(Error::<T>::FeeAmountInvalid)?)
-	}
+			ensure!(
+				fee_tiers.iter().all(|fee| Self::is_fee_valid(*fee)),
+				Error::<T>::InvalidFee
+			);
 
-	pub fn pair_account_from_assets(asset_a: AssetId, asset_b: AssetId) -> T::AccountId {
-		T::AssetPairAccountId::from_assets(asset_a, asset_b, "xyk")
-	}
-}
+			let tiers: BoundedVec<(u32, u32), T::MaxFeeTiers> = fee_tiers
+				.clone()
+				.try_into()
+				.map_err(|_| Error::<T>::MaxFeeTiersExceeded)?;
 
-// Implementation of AMM API which makes possible to plug the AMM pool into the exchange pallet.
-impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
-	fn exists(assets: AssetPair) -> bool {
-		<ShareToken<T>>::contains_key(&Self::get_pair_id(assets))
-	}
+			<FeeTiers<T>>::put(tiers);
 
-	fn get_pair_id(assets: AssetPair) -> T::AccountId {
-		Self::pair_account_from_assets(assets.asset_in, assets.asset_out)
-	}
+			Self::deposit_event(Event::FeeTiersUpdated(fee_tiers));
 
-	fn get_share_token(assets: AssetPair) -> AssetId {
-		let pair_account = Self::get_pair_id(assets);
-		Self::share_token(&pair_account)
-	}
+			Ok(())
+		}
 
-	fn get_pool_assets(pool_account_id: &T::AccountId) -> Option<Vec<AssetId>> {
-		let maybe_assets = <PoolAssets<T>>::get(pool_account_id);
-		maybe_assets.map(|assets| vec![assets.0, assets.1])
-	}
+		/// Update trading fee of an existing pool.
+		///
+		/// The fee does not have to be one of the approved fee tiers.
+		///
+		/// The dispatch origin for this call must be `T::AuthorityOrigin`.
+		///
+		/// Emits `PoolFeeUpdated` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::set_pool_fee())]
+		pub fn set_pool_fee(
+			origin: OriginFor<T>,
+			asset_a: AssetId,
+			asset_b: AssetId,
+			fee: (u32, u32),
+		) -> DispatchResult {
+			T::AuthorityOrigin::ensure_origin(origin)?;
 
-	fn get_spot_price_unchecked(asset_a: AssetId, asset_b: AssetId, amount: Balance) -> Balance {
-		let pair_account = Self::get_pair_id(AssetPair {
-			asset_out: asset_a,
-			asset_in: asset_b,
+			let asset_pair = AssetPair {
+				asset_in: asset_a,
+				asset_out: asset_b,
+			};
+
+			ensure!(Self::exists(asset_pair), Error::<T>::TokenPoolNotFound);
+
+			ensure!(Self::is_fee_valid(fee), Error::<T>::InvalidFee);
+
+			let pair_account = Self::get_pair_id(asset_pair);
+
+			<PoolFee<T>>::insert(&pair_account, fee);
+
+			Self::deposit_event(Event::PoolFeeUpdated(asset_a, asset_b, fee, pair_account));
+
+			Ok(())
+		}
+
+		/// Add liquidity to previously created asset pair pool providing only `asset_a`.
+		///
+		/// Part of `amount_a` is sold for `asset_b` in the pool first, so that the rest of `amount_a`
+		/// and the amount of `asset_b` received are in the ratio of the pool after the sale.
+		/// Both are then added as liquidity. Amounts left over due to rounding stay with the provider.
+		///
+		/// `min_shares` - minimum amount of shares to be received.
+		///
+		/// Emits `SellExecuted` and `LiquidityAdded` events when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::add_liquidity_single_asset()
+			+ <T as Config>::AMMHandler::on_trade_weight()
+			+ <T as Config>::LiquidityChangedHandler::on_liquidity_changed_weight())]
+		#[transactional]
+		pub fn add_liquidity_single_asset(
+			origin: OriginFor<T>,
+			asset_a: AssetId,
+			asset_b: AssetId,
+			amount_a: Balance,
+			min_shares: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let asset_pair = AssetPair {
+				asset_in: asset_a,
+				asset_out: asset_b,
+			};
+
+			ensure!(Self::exists(asset_pair), Error::<T>::TokenPoolNotFound);
+
+			ensure!(
+				T::Currency::free_balance(asset_a, &who) >= amount_a,
+				Error::<T>::InsufficientAssetBalance
+			);
+
+			let pair_account = Self::get_pair_id(asset_pair);
+
+			let amount_to_sell = Self::calculate_single_asset_sell_amount(
+				T::Currency::free_balance(asset_a, &pair_account),
+				amount_a,
+				Self::get_fee(&pair_account),
+			)?;
+
+			let transfer =
+				<Self as AMM<_, _, _, _>>::validate_sell(&who, asset_pair, amount_to_sell, Balance::zero(), false)?;
+			<Self as AMM<_, _, _, _>>::execute_sell(&transfer)?;
+
+			let amount_b = transfer.amount_out;
+
+			// Rounding of the sold amount can leave slightly less of asset b than needed for the rest of asset a.
+			let amount_a_left = amount_a
+				.checked_sub(amount_to_sell)
+				.ok_or(Error::<T>::AddAssetAmountInvalid)?;
+			let amount_a_supported = multiply_by_rational(
+				amount_b,
+				T::Currency::free_balance(asset_a, &pair_account),
+				T::Currency::free_balance(asset_b, &pair_account),
+			)
+			.map_err(|_| Error::<T>::Overflow)?;
+
+			Self::do_add_liquidity(
+				&who,
+				asset_a,
+				asset_b,
+				amount_a_left.min(amount_a_supported),
+				amount_b,
+				min_shares,
+			)?;
+
+			Ok(())
+		}
+
+		/// Remove liquidity from specific liquidity pool and receive only `asset_a`.
+		///
+		/// Shares are burned as in `remove_liquidity` and all of `asset_b` received is sold for `asset_a`
+		/// in the same pool. If the removal destroys the pool, `asset_b` is kept by the liquidity provider.
+		///
+		/// `min_amount_a` - minimum amount of `asset_a` to be received in total.
+		///
+		/// Emits `LiquidityRemoved` and `SellExecuted` events when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::remove_liquidity_single_asset()
+			+ <T as Config>::AMMHandler::on_trade_weight()
+			+ <T as Config>::LiquidityChangedHandler::on_liquidity_changed_weight()
+			+ <T as Config>::PoolDestroyedHandler::on_pool_destroyed_weight())]
+		#[transactional]
+		pub fn remove_liquidity_single_asset(
+			origin: OriginFor<T>,
+			asset_a: AssetId,
+			asset_b: AssetId,
+			liquidity_amount: Balance,
+			min_amount_a: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let (amount_a, amount_b) = Self::do_remove_liquidity(
+				&who,
+				asset_a,
+				asset_b,
+				liquidity_amount,
+				Balance::zero(),
+				Balance::zero(),
+			)?;
+
+			let assets = AssetPair {
+				asset_in: asset_b,
+				asset_out: asset_a,
+			};
+
+			// Last liquidity provider destroys the pool, there is nothing left to sell `asset_b` into.
+			let amount_a_received = if <Self as AMM<_, _, _, _>>::exists(assets) {
+				let transfer =
+					<Self as AMM<_, _, _, _>>::validate_sell(&who, assets, amount_b, Balance::zero(), false)?;
+				<Self as AMM<_, _, _, _>>::execute_sell(&transfer)?;
+
+				amount_a.checked_add(transfer.amount_out).ok_or(Error::<T>::Overflow)?
+			} else {
+				amount_a
+			};
+
+			ensure!(
+				amount_a_received >= min_amount_a,
+				Error::<T>::AssetAmountNotReachedLimit
+			);
+
+			Ok(())
+		}
+
+		/// Update share of trade fees which is transferred to `T::ProtocolFeeReceiver`.
+		///
+		/// Trade fee paid by traders does not change, the protocol fee is taken from the part of the fee
+		/// which would otherwise stay in the pool.
+		///
+		/// The dispatch origin for this call must be `T::AuthorityOrigin`.
+		///
+		/// Emits `ProtocolFeeUpdated` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::set_protocol_fee())]
+		pub fn set_protocol_fee(origin: OriginFor<T>, protocol_fee: Permill) -> DispatchResult {
+			T::AuthorityOrigin::ensure_origin(origin)?;
+
+			<ProtocolFee<T>>::put(protocol_fee);
+
+			Self::deposit_event(Event::ProtocolFeeUpdated(protocol_fee));
+
+			Ok(())
+		}
+
+		/// Borrow `amount_out` of `asset_out` from the pool of `asset_out` and `asset_in`.
+		///
+		/// Borrowed amount is transferred to origin and `T::FlashSwapHandler` is called with `data`.
+		/// The handler has to transfer assets back to the pool so that the product of pool reserves,
+		/// with trade fee deducted from amounts transferred in, is not lower than before the flash swap.
+		/// The flash swap is reverted otherwise. Protocol fee share of the trade fee is transferred
+		/// to `T::ProtocolFeeReceiver` as for other trades.
+		///
+		/// Emits `FlashSwapExecuted` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::flash_swap()
+			+ <T as Config>::FlashSwapHandler::on_flash_swap_weight()
+			+ <T as Config>::AMMHandler::on_trade_weight())]
+		#[transactional]
+		pub fn flash_swap(
+			origin: OriginFor<T>,
+			asset_out: AssetId,
+			asset_in: AssetId,
+			amount_out: Balance,
+			data: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let assets = AssetPair { asset_in, asset_out };
+
+			let pair_account = Self::get_pair_id(assets);
+
+			ensure!(
+				amount_out >= T::TradingLimits::pool_limits(&pair_account).min_trading_limit,
+				Error::<T>::InsufficientTradingAmount
+			);
+
+			ensure!(Self::exists(assets), Error::<T>::TokenPoolNotFound);
+
+			ensure!(
+				T::CircuitBreaker::pool_state(&pair_account).is_trading_allowed(),
+				Error::<T>::TradingNotAllowed
+			);
+
+			let reserves_before = (
+				T::Currency::free_balance(asset_out, &pair_account),
+				T::Currency::free_balance(asset_in, &pair_account),
+			);
+			let total_liquidity = Self::total_liquidity(&pair_account);
+
+			ensure!(reserves_before.0 > amount_out, Error::<T>::InsufficientPoolAssetBalance);
+
+			Self::update_price_observations(&pair_account);
+
+			let pool_reserves_before = Self::pool_reserves(&pair_account);
+
+			T::Currency::transfer(asset_out, &pair_account, &who, amount_out)?;
+
+			T::FlashSwapHandler::on_flash_swap(&who, &pair_account, asset_out, amount_out, asset_in, &data)?;
+
+			ensure!(
+				Self::total_liquidity(&pair_account) == total_liquidity,
+				Error::<T>::FlashSwapLiquidityChanged
+			);
+
+			let reserves_after = (
+				T::Currency::free_balance(asset_out, &pair_account),
+				T::Currency::free_balance(asset_in, &pair_account),
+			);
+
+			ensure!(
+				Self::is_flash_swap_repaid(
+					reserves_before,
+					reserves_after,
+					amount_out,
+					Self::get_fee(&pair_account)
+				)?,
+				Error::<T>::InsufficientFlashSwapRepayment
+			);
+
+			let (asset_out_repaid, asset_in_repaid) =
+				Self::flash_swap_repaid_amounts(reserves_before, reserves_after, amount_out);
+			for (asset, repaid) in [(asset_out, asset_out_repaid), (asset_in, asset_in_repaid)] {
+				if !repaid.is_zero() {
+					Self::transfer_protocol_fee(&pair_account, asset, Self::calculate_fee(&pair_account, repaid)?)?;
+				}
+			}
+
+			// Flash swap repaid only in the borrowed asset does not trade `asset_in` for `asset_out`.
+			let net_amount_out = reserves_before.0.saturating_sub(reserves_after.0);
+			if !net_amount_out.is_zero() && !asset_in_repaid.is_zero() {
+				T::AMMHandler::on_trade(asset_in, asset_out, asset_in_repaid, net_amount_out, total_liquidity);
+			}
+
+			T::CircuitBreaker::on_reserves_change(
+				&pair_account,
+				pool_reserves_before,
+				Self::pool_reserves(&pair_account),
+			);
+
+			Self::deposit_event(Event::FlashSwapExecuted(
+				who,
+				asset_out,
+				asset_in,
+				amount_out,
+				pair_account,
+			));
+
+			Ok(())
+		}
+
+		/// Set or remove permission of an asset to be used in new pools.
+		///
+		/// Permissions are checked by `DisallowNotPermittedAssets` if it is used as `T::CanCreatePool`.
+		/// Existing pools are not affected.
+		///
+		/// The dispatch origin for this call must be `T::AuthorityOrigin`.
+		///
+		/// Emits `AssetPoolPermissionUpdated` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::set_asset_pool_permission())]
+		pub fn set_asset_pool_permission(
+			origin: OriginFor<T>,
+			asset_id: AssetId,
+			permission: Option<AssetPoolPermission>,
+		) -> DispatchResult {
+			T::AuthorityOrigin::ensure_origin(origin)?;
+
+			<AssetPoolPermissions<T>>::set(asset_id, permission);
+
+			Self::deposit_event(Event::AssetPoolPermissionUpdated(asset_id, permission));
+
+			Ok(())
+		}
+
+		/// Set whether only assets with `Allowed` permission can be used in new pools.
+		///
+		/// The dispatch origin for this call must be `T::AuthorityOrigin`.
+		///
+		/// Emits `RequireAllowedAssetsUpdated` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::set_require_allowed_assets())]
+		pub fn set_require_allowed_assets(origin: OriginFor<T>, required: bool) -> DispatchResult {
+			T::AuthorityOrigin::ensure_origin(origin)?;
+
+			<RequireAllowedAssets<T>>::put(required);
+
+			Self::deposit_event(Event::RequireAllowedAssetsUpdated(required));
+
+			Ok(())
+		}
+
+		/// Execute a sequence of sells, buys and liquidity operations in a single transaction.
+		///
+		/// Instructions are executed in order, each of them has to satisfy its own limits.
+		///
+		/// `aggregate_limits` - minimum net change of balance of the origin in each listed asset over the whole batch.
+		/// Negative limit is the maximum amount of the asset which can be spent by the batch.
+		///
+		/// `deadline` - last block in which the batch can be executed.
+		///
+		/// The batch is reverted if any instruction or aggregate limit fails.
+		///
+		/// Emits `BatchTradeExecuted` event and events of executed instructions when successful.
+		#[pallet::weight(Pallet::<T>::batch_trade_weight(instructions, aggregate_limits.len()))]
+		#[transactional]
+		pub fn batch_trade(
+			origin: OriginFor<T>,
+			instructions: Vec<TradeInstruction>,
+			aggregate_limits: Vec<(AssetId, Amount)>,
+			deadline: Option<T::BlockNumber>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::ensure_deadline(deadline)?;
+
+			let max_batch_size = T::MaxBatchSize::get() as usize;
+			ensure!(
+				instructions.len() <= max_batch_size && aggregate_limits.len() <= max_batch_size,
+				Error::<T>::MaxBatchSizeExceeded
+			);
+
+			let balances_before: Vec<Balance> = aggregate_limits
+				.iter()
+				.map(|(asset, _)| T::Currency::free_balance(*asset, &who))
+				.collect();
+
+			for instruction in instructions.iter() {
+				Self::execute_instruction(&who, instruction)?;
+			}
+
+			for ((asset, limit), balance_before) in aggregate_limits.iter().zip(balances_before) {
+				let balance_after = T::Currency::free_balance(*asset, &who);
+				let net_change = if balance_after >= balance_before {
+					balance_after.saturating_sub(balance_before).saturated_into::<Amount>()
+				} else {
+					balance_before
+						.saturating_sub(balance_after)
+						.saturated_into::<Amount>()
+						.saturating_neg()
+				};
+
+				ensure!(net_change >= *limit, Error::<T>::AggregateLimitNotReached);
+			}
+
+			Self::deposit_event(Event::BatchTradeExecuted(who, instructions.len() as u32));
+
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Return balance of each asset in selected liquidity pool.
+	pub fn get_pool_balances(pool_address: T::AccountId) -> Option<Vec<(AssetId, Balance)>> {
+		let mut balances = Vec::new();
+
+		if let Some(assets) = Self::get_pool_assets(&pool_address) {
+			for item in &assets {
+				let reserve = T::Currency::free_balance(*item, &pool_address);
+				balances.push((*item, reserve));
+			}
+		}
+		Some(balances)
+	}
+
+	/// Return information about a pool, or `None` if the pool does not exist.
+	pub fn get_pool_info(pool_account: T::AccountId) -> Option<PoolInfo<T::AccountId>> {
+		let assets = Self::pool_assets(&pool_account)?;
+
+		Some(PoolInfo {
+			reserves: (
+				T::Currency::free_balance(assets.0, &pool_account),
+				T::Currency::free_balance(assets.1, &pool_account),
+			),
+			share_token: Self::share_token(&pool_account),
+			total_liquidity: Self::total_liquidity(&pool_account),
+			fee: Self::get_fee(&pool_account),
+			trading_limits: T::TradingLimits::pool_limits(&pool_account),
+			assets,
+			pool_account,
+		})
+	}
+
+	/// Return information about the pool of given share token, or `None` if no pool uses the token.
+	pub fn get_pool_by_share_token(share_token: AssetId) -> Option<PoolInfo<T::AccountId>> {
+		Self::share_token_pool(share_token).and_then(Self::get_pool_info)
+	}
+
+	/// Return human-readable name of the share token of given assets, e.g. `BSX-KSM LP`.
+	///
+	/// The name is built from registered names of the assets in the order of asset ids.
+	/// Returns `None` if a name of any of the assets is unknown, the name is too long
+	/// or the name is already used by an asset other than the share token of given assets.
+	pub fn share_token_name(asset_a: AssetId, asset_b: AssetId) -> Option<Vec<u8>> {
+		let assets = AssetPair::new(asset_a, asset_b).ordered_pair();
+
+		let mut name = T::AssetMetadata::asset_name(assets.0)?;
+		name.push(b'-');
+		name.extend(T::AssetMetadata::asset_name(assets.1)?);
+		name.extend_from_slice(b" LP");
+
+		if name.len() > T::AssetMetadata::max_name_length() as usize {
+			return None;
+		}
+
+		if let Ok(asset_id) = T::AssetRegistry::retrieve_asset(&name) {
+			let (share_asset_a, share_asset_b) = T::AssetMetadata::pool_share_assets(asset_id)?;
+			if AssetPair::new(share_asset_a, share_asset_b).ordered_pair() != assets {
+				return None;
+			}
+		}
+
+		Some(name)
+	}
+
+	/// Return information about up to `limit` pools.
+	///
+	/// Pools are returned in storage order, starting after pool `start_after` if provided.
+	pub fn get_pools_info(start_after: Option<T::AccountId>, limit: u32) -> Vec<PoolInfo<T::AccountId>> {
+		let pools = match start_after {
+			Some(pool_account) => <PoolAssets<T>>::iter_keys_from(<PoolAssets<T>>::hashed_key_for(pool_account)),
+			None => <PoolAssets<T>>::iter_keys(),
+		};
+
+		pools.take(limit as usize).filter_map(Self::get_pool_info).collect()
+	}
+
+	/// Calculate discounted trade fee
+	fn calculate_discounted_fee(amount: Balance) -> Result<Balance, DispatchError> {
+		Ok(
+			hydra_dx_math::fee::calculate_pool_trade_fee(amount, (7, 10_000)) // 0.07%
+				.ok_or::<Error<T>>(Error::<T>::FeeAmountInvalid)?,
+		)
+	}
+
+	/// Calculate trade fee using the fee of given pool
+	fn calculate_fee(pool_account: &T::AccountId, amount: Balance) -> Result<Balance, DispatchError> {
+		let fee = Self::get_fee(pool_account);
+		Ok(hydra_dx_math::fee::calculate_pool_trade_fee(amount, (fee.0, fee.1))
+			.ok_or::<Error<T>>(Error::<T>::FeeAmountInvalid)?)
+	}
+
+	/// Calculate part of trade fee `fee` of `asset` which is transferred to protocol fee receiver.
+	///
+	/// Protocol fee is zero if the amount would not reach existential deposit of the receiver's account.
+	fn calculate_protocol_fee(asset: AssetId, fee: Balance) -> Balance {
+		let amount = Self::protocol_fee().mul_floor(fee);
+		let receiver = T::ProtocolFeeReceiver::get();
+
+		if T::Currency::total_balance(asset, &receiver).saturating_add(amount) < T::Currency::minimum_balance(asset) {
+			return Balance::zero();
+		}
+
+		amount
+	}
+
+	/// Transfer protocol fee share of trade fee `fee` of `asset` from the pool to protocol fee receiver.
+	fn transfer_protocol_fee(pool_account: &T::AccountId, asset: AssetId, fee: Balance) -> DispatchResult {
+		let amount = Self::calculate_protocol_fee(asset, fee);
+		let receiver = T::ProtocolFeeReceiver::get();
+
+		if amount.is_zero() {
+			return Ok(());
+		}
+
+		T::Currency::transfer(asset, pool_account, &receiver, amount)?;
+
+		Self::deposit_event(Event::ProtocolFeeTransferred(
+			pool_account.clone(),
+			asset,
+			amount,
+			receiver,
+		));
+
+		Ok(())
+	}
+
+	/// Check that pool reserves `(asset_out reserve, asset_in reserve)` after a flash swap of `amount_out`
+	/// satisfy the pool invariant increased by trade fee.
+	///
+	/// Trade fee is deducted from the amounts transferred into the pool after `amount_out` was borrowed.
+	fn is_flash_swap_repaid(
+		reserves_before: (Balance, Balance),
+		reserves_after: (Balance, Balance),
+		amount_out: Balance,
+		fee: (u32, u32),
+	) -> Result<bool, DispatchError> {
+		let (asset_out_repaid, asset_in_repaid) =
+			Self::flash_swap_repaid_amounts(reserves_before, reserves_after, amount_out);
+
+		let (fee_numerator, fee_denominator) = (U256::from(fee.0), U256::from(fee.1));
+
+		// reserve * fee denominator - repaid amount * fee numerator
+		let reserve_without_fee = |reserve: Balance, repaid: Balance| -> Option<U256> {
+			U256::from(reserve)
+				.checked_mul(fee_denominator)?
+				.checked_sub(U256::from(repaid).checked_mul(fee_numerator)?)
+		};
+
+		let invariant_after = reserve_without_fee(reserves_after.0, asset_out_repaid)
+			.zip(reserve_without_fee(reserves_after.1, asset_in_repaid))
+			.and_then(|(asset_out_reserve, asset_in_reserve)| asset_out_reserve.checked_mul(asset_in_reserve))
+			.ok_or(Error::<T>::Overflow)?;
+
+		let invariant_before = U256::from(reserves_before.0)
+			.checked_mul(U256::from(reserves_before.1))
+			.and_then(|invariant| invariant.checked_mul(fee_denominator.checked_mul(fee_denominator)?))
+			.ok_or(Error::<T>::Overflow)?;
+
+		Ok(invariant_after >= invariant_before)
+	}
+
+	/// Amounts `(asset_out, asset_in)` transferred into the pool after `amount_out` was borrowed by a flash swap.
+	fn flash_swap_repaid_amounts(
+		reserves_before: (Balance, Balance),
+		reserves_after: (Balance, Balance),
+		amount_out: Balance,
+	) -> (Balance, Balance) {
+		(
+			reserves_after
+				.0
+				.saturating_sub(reserves_before.0.saturating_sub(amount_out)),
+			reserves_after.1.saturating_sub(reserves_before.1),
+		)
+	}
+
+	/// Return true if `asset_id` can be used in new pools according to permissions set by governance.
+	pub fn is_asset_permitted(asset_id: AssetId) -> bool {
+		match Self::asset_pool_permission(asset_id) {
+			Some(AssetPoolPermission::Allowed) => true,
+			Some(AssetPoolPermission::Denied) => false,
+			None => !Self::require_allowed_assets(),
+		}
+	}
+
+	/// Fee is valid if its denominator is not zero and the fee is lower than 100%.
+	fn is_fee_valid(fee: (u32, u32)) -> bool {
+		fee.1 != 0 && fee.0 < fee.1
+	}
+
+	/// Fee can be selected by pool creator if it is the default fee or one of the approved fee tiers.
+	fn is_fee_tier_allowed(fee: (u32, u32)) -> bool {
+		fee == T::GetExchangeFee::get() || Self::fee_tiers().contains(&fee)
+	}
+
+	pub fn pair_account_from_assets(asset_a: AssetId, asset_b: AssetId) -> T::AccountId {
+		T::AssetPairAccountId::from_assets(asset_a, asset_b, "xyk")
+	}
+
+	/// Account holding the shares locked on pool creation. Nobody knows its private key.
+	pub fn locked_liquidity_account() -> T::AccountId {
+		T::AccountId::decode(&mut TrailingZeroInput::zeroes())
+			.expect("infinite length input; no invalid inputs for type; qed")
+	}
+
+	/// Reserves of the pool in the order of its assets.
+	fn pool_reserves(pool_account: &T::AccountId) -> (Balance, Balance) {
+		Self::pool_assets(pool_account)
+			.map(|(asset_a, asset_b)| {
+				(
+					T::Currency::free_balance(asset_a, pool_account),
+					T::Currency::free_balance(asset_b, pool_account),
+				)
+			})
+			.unwrap_or_default()
+	}
+
+	/// Validate and execute a sell, checking its price impact if `max_price_impact` is provided.
+	fn do_sell(
+		who: &T::AccountId,
+		assets: AssetPair,
+		amount: Balance,
+		max_limit: Balance,
+		discount: bool,
+		max_price_impact: Option<Permill>,
+	) -> DispatchResult {
+		let transfer = <Self as AMM<_, _, _, _>>::validate_sell(who, assets, amount, max_limit, discount)?;
+
+		if let Some(max_price_impact) = max_price_impact {
+			ensure!(
+				Self::price_impact(assets, amount, transfer.amount_out.saturating_add(transfer.fee.1))
+					<= max_price_impact,
+				Error::<T>::MaxPriceImpactExceeded
+			);
+		}
+
+		<Self as AMM<_, _, _, _>>::execute_sell(&transfer)
+	}
+
+	/// Validate and execute a buy, checking its price impact if `max_price_impact` is provided.
+	fn do_buy(
+		who: &T::AccountId,
+		assets: AssetPair,
+		amount: Balance,
+		max_limit: Balance,
+		discount: bool,
+		max_price_impact: Option<Permill>,
+	) -> DispatchResult {
+		let transfer = <Self as AMM<_, _, _, _>>::validate_buy(who, assets, amount, max_limit, discount)?;
+
+		if let Some(max_price_impact) = max_price_impact {
+			ensure!(
+				Self::price_impact(assets, transfer.amount_out, amount) <= max_price_impact,
+				Error::<T>::MaxPriceImpactExceeded
+			);
+		}
+
+		<Self as AMM<_, _, _, _>>::execute_buy(&transfer)
+	}
+
+	/// Execute single instruction of a batch trade.
+	fn execute_instruction(who: &T::AccountId, instruction: &TradeInstruction) -> DispatchResult {
+		match *instruction {
+			TradeInstruction::Sell {
+				asset_in,
+				asset_out,
+				amount,
+				max_limit,
+			} => Self::do_sell(who, AssetPair { asset_in, asset_out }, amount, max_limit, false, None),
+			TradeInstruction::Buy {
+				asset_out,
+				asset_in,
+				amount,
+				max_limit,
+			} => Self::do_buy(who, AssetPair { asset_in, asset_out }, amount, max_limit, false, None),
+			TradeInstruction::AddLiquidity {
+				asset_a,
+				asset_b,
+				amount_a,
+				amount_b_max_limit,
+				min_shares,
+			} => Self::do_add_liquidity(who, asset_a, asset_b, amount_a, amount_b_max_limit, min_shares).map(|_| ()),
+			TradeInstruction::RemoveLiquidity {
+				asset_a,
+				asset_b,
+				liquidity_amount,
+				min_amount_a,
+				min_amount_b,
+			} => Self::do_remove_liquidity(who, asset_a, asset_b, liquidity_amount, min_amount_a, min_amount_b)
+				.map(|_| ()),
+		}
+	}
+
+	/// Weight of a batch trade with `limits` aggregate limits and weights of the handlers called by its instructions.
+	///
+	/// The batch is benchmarked with the same number of instructions of each kind, the number of instructions
+	/// of the most frequent kind in `instructions` is used.
+	fn batch_trade_weight(instructions: &[TradeInstruction], limits: usize) -> Weight {
+		let mut counts = [0u32; 4];
+
+		let handlers_weight = instructions.iter().fold(0, |weight: Weight, instruction| {
+			let (kind, handler_weight) = match instruction {
+				TradeInstruction::Sell { .. } => (0, T::AMMHandler::on_trade_weight()),
+				TradeInstruction::Buy { .. } => (1, T::AMMHandler::on_trade_weight()),
+				TradeInstruction::AddLiquidity { .. } => (2, T::LiquidityChangedHandler::on_liquidity_changed_weight()),
+				TradeInstruction::RemoveLiquidity { .. } => (
+					3,
+					T::LiquidityChangedHandler::on_liquidity_changed_weight()
+						.saturating_add(T::PoolDestroyedHandler::on_pool_destroyed_weight()),
+				),
+			};
+			counts[kind] = counts[kind].saturating_add(1);
+			weight.saturating_add(handler_weight)
 		});
 
+		let instructions_of_each_kind = counts.iter().copied().max().unwrap_or_default();
+
+		T::WeightInfo::batch_trade(instructions_of_each_kind, limits.saturated_into()).saturating_add(handlers_weight)
+	}
+
+	/// Weight of `add_liquidity` including the liquidity change handler.
+	fn add_liquidity_weight() -> Weight {
+		T::WeightInfo::add_liquidity().saturating_add(T::LiquidityChangedHandler::on_liquidity_changed_weight())
+	}
+
+	/// Weight of `remove_liquidity` including the liquidity change and pool destruction handlers.
+	fn remove_liquidity_weight() -> Weight {
+		T::WeightInfo::remove_liquidity()
+			.saturating_add(T::LiquidityChangedHandler::on_liquidity_changed_weight())
+			.saturating_add(T::PoolDestroyedHandler::on_pool_destroyed_weight())
+	}
+
+	/// Add liquidity to the pool of `asset_a` and `asset_b`. Return amount of shares issued.
+	fn do_add_liquidity(
+		who: &T::AccountId,
+		asset_a: AssetId,
+		asset_b: AssetId,
+		amount_a: Balance,
+		amount_b_max_limit: Balance,
+		min_shares: Balance,
+	) -> Result<Balance, DispatchError> {
+		let asset_pair = AssetPair {
+			asset_in: asset_a,
+			asset_out: asset_b,
+		};
+
+		ensure!(Self::exists(asset_pair), Error::<T>::TokenPoolNotFound);
+
+		let pair_account = Self::get_pair_id(asset_pair);
+
+		ensure!(
+			T::CircuitBreaker::pool_state(&pair_account).is_adding_liquidity_allowed(),
+			Error::<T>::LiquidityChangeNotAllowed
+		);
+
+		let limits = T::TradingLimits::pool_limits(&pair_account);
+
+		ensure!(
+			amount_a >= limits.min_trading_limit,
+			Error::<T>::InsufficientTradingAmount
+		);
+
+		ensure!(!amount_b_max_limit.is_zero(), Error::<T>::ZeroLiquidity);
+
+		ensure!(
+			T::Currency::free_balance(asset_a, who) >= amount_a,
+			Error::<T>::InsufficientAssetBalance
+		);
+
+		ensure!(
+			T::Currency::free_balance(asset_b, who) >= amount_b_max_limit,
+			Error::<T>::InsufficientAssetBalance
+		);
+
+		let share_token = Self::share_token(&pair_account);
+
+		let account_shares = T::Currency::free_balance(share_token, who);
+
 		let asset_a_reserve = T::Currency::free_balance(asset_a, &pair_account);
 		let asset_b_reserve = T::Currency::free_balance(asset_b, &pair_account);
+		let total_liquidity = Self::total_liquidity(&pair_account);
 
-		hydra_dx_math::xyk::calculate_spot_price(asset_a_reserve, asset_b_reserve, amount)
-			.unwrap_or_else(|_| Balance::zero())
+		let amount_b_required = hydra_dx_math::xyk::calculate_liquidity_in(asset_a_reserve, asset_b_reserve, amount_a)
+			.map_err(|_| Error::<T>::AddAssetAmountInvalid)?;
+
+		let shares_added = if asset_a < asset_b { amount_a } else { amount_b_required };
+
+		ensure!(
+			amount_b_required <= amount_b_max_limit,
+			Error::<T>::AssetAmountExceededLimit
+		);
+
+		ensure!(!shares_added.is_zero(), Error::<T>::InvalidMintedLiquidity);
+
+		ensure!(shares_added >= min_shares, Error::<T>::SharesAmountNotReachedLimit);
+
+		// Make sure that account share liquidity is at least min pool liquidity
+		ensure!(
+			account_shares
+				.checked_add(shares_added)
+				.ok_or(Error::<T>::InvalidMintedLiquidity)?
+				>= limits.min_pool_liquidity,
+			Error::<T>::InsufficientLiquidity
+		);
+
+		let liquidity_amount = total_liquidity
+			.checked_add(shares_added)
+			.ok_or(Error::<T>::InvalidLiquidityAmount)?;
+
+		Self::update_price_observations(&pair_account);
+
+		let reserves_before = Self::pool_reserves(&pair_account);
+
+		T::Currency::transfer(asset_a, who, &pair_account, amount_a)?;
+		T::Currency::transfer(asset_b, who, &pair_account, amount_b_required)?;
+
+		T::Currency::deposit(share_token, who, shares_added)?;
+
+		<TotalLiquidity<T>>::insert(&pair_account, liquidity_amount);
+
+		T::LiquidityChangedHandler::on_liquidity_changed(
+			asset_a,
+			asset_b,
+			amount_a,
+			amount_b_required,
+			liquidity_amount,
+		);
+
+		T::CircuitBreaker::on_liquidity_change(&pair_account, reserves_before, Self::pool_reserves(&pair_account));
+
+		Self::deposit_event(Event::LiquidityAdded(
+			who.clone(),
+			asset_a,
+			asset_b,
+			amount_a,
+			amount_b_required,
+		));
+
+		Ok(shares_added)
 	}
 
-	/// Validate a sell. Perform all necessary checks and calculations.
-	/// No storage changes are performed yet.
-	///
-	/// Return `AMMTransfer` with all info needed to execute the transaction.
-	fn validate_sell(
+	/// Remove liquidity from the pool of `asset_a` and `asset_b`. Return amounts of `asset_a` and `asset_b` removed.
+	fn do_remove_liquidity(
 		who: &T::AccountId,
+		asset_a: AssetId,
+		asset_b: AssetId,
+		liquidity_amount: Balance,
+		min_amount_a: Balance,
+		min_amount_b: Balance,
+	) -> Result<(Balance, Balance), DispatchError> {
+		let asset_pair = AssetPair {
+			asset_in: asset_a,
+			asset_out: asset_b,
+		};
+
+		ensure!(!liquidity_amount.is_zero(), Error::<T>::ZeroLiquidity);
+
+		ensure!(Self::exists(asset_pair), Error::<T>::TokenPoolNotFound);
+
+		let pair_account = Self::get_pair_id(asset_pair);
+
+		ensure!(
+			T::CircuitBreaker::pool_state(&pair_account).is_removing_liquidity_allowed(),
+			Error::<T>::LiquidityChangeNotAllowed
+		);
+
+		let share_token = Self::share_token(&pair_account);
+
+		let total_shares = Self::total_liquidity(&pair_account);
+
+		let account_shares = T::Currency::free_balance(share_token, who);
+
+		let locked_account = Self::locked_liquidity_account();
+		let locked_shares = T::Currency::free_balance(share_token, &locked_account);
+
+		ensure!(*who != locked_account, Error::<T>::CannotRemoveLockedLiquidity);
+
+		ensure!(total_shares >= liquidity_amount, Error::<T>::InsufficientLiquidity);
+
+		ensure!(account_shares >= liquidity_amount, Error::<T>::InsufficientAssetBalance);
+
+		// Account's liquidity left should be either 0 or at least min pool liquidity
+		ensure!(
+			(account_shares.saturating_sub(liquidity_amount))
+				>= T::TradingLimits::pool_limits(&pair_account).min_pool_liquidity
+				|| (account_shares == liquidity_amount),
+			Error::<T>::InsufficientLiquidity
+		);
+
+		let liquidity_left = total_shares
+			.checked_sub(liquidity_amount)
+			.ok_or(Error::<T>::InvalidLiquidityAmount)?;
+
+		// Pool is destroyed when only the locked shares are left.
+		let destroy_pool = liquidity_left == locked_shares;
+
+		ensure!(
+			destroy_pool || liquidity_left >= Self::liquidity_floor(&pair_account),
+			Error::<T>::CannotRemoveLockedLiquidity
+		);
+
+		let asset_a_reserve = T::Currency::free_balance(asset_a, &pair_account);
+		let asset_b_reserve = T::Currency::free_balance(asset_b, &pair_account);
+
+		// Last liquidity provider receives also the reserves backing the locked shares.
+		let (remove_amount_a, remove_amount_b) = if destroy_pool {
+			(asset_a_reserve, asset_b_reserve)
+		} else {
+			hydra_dx_math::xyk::calculate_liquidity_out(
+				asset_a_reserve,
+				asset_b_reserve,
+				liquidity_amount,
+				total_shares,
+			)
+			.map_err(|_| Error::<T>::RemoveAssetAmountInvalid)?
+		};
+
+		ensure!(
+			remove_amount_a >= min_amount_a && remove_amount_b >= min_amount_b,
+			Error::<T>::RemoveAssetAmountNotReachedLimit
+		);
+
+		ensure!(
+			T::Currency::free_balance(asset_a, &pair_account) >= remove_amount_a,
+			Error::<T>::InsufficientPoolAssetBalance
+		);
+		ensure!(
+			T::Currency::free_balance(asset_b, &pair_account) >= remove_amount_b,
+			Error::<T>::InsufficientPoolAssetBalance
+		);
+
+		Self::update_price_observations(&pair_account);
+
+		let reserves_before = Self::pool_reserves(&pair_account);
+
+		T::Currency::transfer(asset_a, &pair_account, who, remove_amount_a)?;
+		T::Currency::transfer(asset_b, &pair_account, who, remove_amount_b)?;
+
+		T::Currency::withdraw(share_token, who, liquidity_amount)?;
+
+		let liquidity_left = if destroy_pool {
+			T::Currency::withdraw(share_token, &locked_account, locked_shares)?;
+			Balance::zero()
+		} else {
+			liquidity_left
+		};
+
+		<TotalLiquidity<T>>::insert(&pair_account, liquidity_left);
+
+		T::LiquidityChangedHandler::on_liquidity_changed(
+			asset_a,
+			asset_b,
+			remove_amount_a,
+			remove_amount_b,
+			liquidity_left,
+		);
+
+		Self::deposit_event(Event::LiquidityRemoved(who.clone(), asset_a, asset_b, liquidity_amount));
+
+		if destroy_pool {
+			<ShareToken<T>>::remove(&pair_account);
+			<ShareTokenPool<T>>::remove(share_token);
+			<PoolAssets<T>>::remove(&pair_account);
+			<TotalLiquidity<T>>::remove(&pair_account);
+			<LiquidityFloor<T>>::remove(&pair_account);
+			<PoolFee<T>>::remove(&pair_account);
+			<PriceObservations<T>>::remove(&pair_account);
+			<PoolTradeStats<T>>::remove(&pair_account);
+
+			if let Some((depositor, deposit)) = <PoolDeposits<T>>::take(&pair_account) {
+				T::Currency::unreserve(T::NativeAssetId::get(), &depositor, deposit);
+			}
+
+			T::PoolDestroyedHandler::on_pool_destroyed(&pair_account, asset_a, asset_b);
+
+			Self::deposit_event(Event::PoolDestroyed(
+				who.clone(),
+				asset_a,
+				asset_b,
+				share_token,
+				pair_account,
+			));
+		} else {
+			T::CircuitBreaker::on_liquidity_change(&pair_account, reserves_before, Self::pool_reserves(&pair_account));
+		}
+
+		Ok((remove_amount_a, remove_amount_b))
+	}
+
+	/// Add price observation of the pool with the price before any change of the pool in current block.
+	///
+	/// Only the first call in a block adds an observation. Changes of the price later in the block
+	/// are accounted for in the next observation.
+	fn update_price_observations(pool_account: &T::AccountId) {
+		let now = frame_system::Pallet::<T>::block_number();
+
+		<PriceObservations<T>>::mutate(pool_account, |observations| {
+			let observation = match (observations.last(), Self::pool_assets(pool_account)) {
+				(Some(last), _) if last.block == now => return,
+				(Some(last), Some((asset_a, asset_b))) => {
+					let asset_a_reserve = T::Currency::free_balance(asset_a, pool_account);
+					let asset_b_reserve = T::Currency::free_balance(asset_b, pool_account);
+					let elapsed = now.saturating_sub(last.block).saturated_into::<u128>();
+
+					let price_a = Price::checked_from_rational(asset_b_reserve, asset_a_reserve).unwrap_or_default();
+					let price_b = Price::checked_from_rational(asset_a_reserve, asset_b_reserve).unwrap_or_default();
+
+					PriceObservation {
+						block: now,
+						price_a_cumulative: last
+							.price_a_cumulative
+							.wrapping_add(price_a.into_inner().wrapping_mul(elapsed)),
+						price_b_cumulative: last
+							.price_b_cumulative
+							.wrapping_add(price_b.into_inner().wrapping_mul(elapsed)),
+					}
+				}
+				_ => PriceObservation {
+					block: now,
+					..Default::default()
+				},
+			};
+
+			if observations.len() >= T::MaxPriceObservations::get() as usize && !observations.is_empty() {
+				observations.remove(0);
+			}
+			let _ = observations.try_push(observation);
+		});
+	}
+
+	/// Add a trade of `assets` to statistics of the pool.
+	///
+	/// `amount_in` and `amount_out` are amounts paid and received by the trader, `fee` is the trade fee.
+	fn update_pool_stats(
+		pool_account: &T::AccountId,
+		assets: AssetPair,
+		amount_in: Balance,
+		amount_out: Balance,
+		fee: (AssetId, Balance),
+	) {
+		let asset_in_first = Self::pool_assets(pool_account).map_or(true, |(asset_a, _)| asset_a == assets.asset_in);
+		let in_pool_order = |(amount_in, amount_out): (Balance, Balance)| {
+			if asset_in_first {
+				(amount_in, amount_out)
+			} else {
+				(amount_out, amount_in)
+			}
+		};
+		let fees = if fee.0 == assets.asset_in {
+			(fee.1, 0)
+		} else {
+			(0, fee.1)
+		};
+
+		<PoolTradeStats<T>>::mutate(pool_account, |stats| {
+			stats.add_trade(
+				frame_system::Pallet::<T>::block_number(),
+				T::StatsWindow::get(),
+				in_pool_order((amount_in, amount_out)),
+				in_pool_order(fees),
+			)
+		});
+	}
+
+	/// Return trade statistics of a pool as of current block, or `None` if the pool does not exist.
+	pub fn get_pool_stats(pool_account: T::AccountId) -> Option<PoolStats<T::BlockNumber>> {
+		Self::pool_assets(&pool_account)?;
+
+		let mut stats = Self::pool_trade_stats(&pool_account);
+		stats.roll_window(frame_system::Pallet::<T>::block_number(), T::StatsWindow::get());
+
+		Some(stats)
+	}
+
+	/// Return blocks of stored price observations of the pool of given assets.
+	pub fn get_price_observation_blocks(asset_a: AssetId, asset_b: AssetId) -> Vec<T::BlockNumber> {
+		Self::price_observations(Self::pair_account_from_assets(asset_a, asset_b))
+			.iter()
+			.map(|observation| observation.block)
+			.collect()
+	}
+
+	/// Ensure that `deadline`, if provided, has not passed yet.
+	fn ensure_deadline(deadline: Option<T::BlockNumber>) -> DispatchResult {
+		if let Some(deadline) = deadline {
+			ensure!(
+				frame_system::Pallet::<T>::block_number() <= deadline,
+				Error::<T>::DeadlineExpired
+			);
+		}
+
+		Ok(())
+	}
+
+	/// Calculate amount of `amount` to be sold in a pool with `reserve` of the sold asset, so that the rest of `amount`
+	/// and the amount received are in the ratio of the pool after the sale.
+	///
+	/// With fee `f` deducted from the amount received, the amount sold `s` is the positive root of
+	/// `s^2 + (reserve * (2 - f) - amount * f) * s - amount * reserve = 0`.
+	fn calculate_single_asset_sell_amount(
+		reserve: Balance,
+		amount: Balance,
+		fee: (u32, u32),
+	) -> Result<Balance, DispatchError> {
+		let (reserve, amount) = (U256::from(reserve), U256::from(amount));
+		let (fee_numerator, fee_denominator) = (U256::from(fee.0), U256::from(fee.1));
+
+		// All terms are multiplied by the fee denominator to keep the calculation in integers.
+		let b_positive = reserve
+			.checked_mul(fee_denominator * 2 - fee_numerator)
+			.ok_or(Error::<T>::Overflow)?;
+		let b_negative = amount.checked_mul(fee_numerator).ok_or(Error::<T>::Overflow)?;
+		let b_abs = if b_positive >= b_negative {
+			b_positive - b_negative
+		} else {
+			b_negative - b_positive
+		};
+
+		let four_ac = amount
+			.checked_mul(reserve)
+			.and_then(|v| v.checked_mul(fee_denominator * fee_denominator * 4))
+			.ok_or(Error::<T>::Overflow)?;
+		let discriminant = b_abs
+			.checked_mul(b_abs)
+			.and_then(|v| v.checked_add(four_ac))
+			.ok_or(Error::<T>::Overflow)?;
+		let root = discriminant.integer_sqrt();
+
+		let numerator = if b_positive >= b_negative {
+			root.saturating_sub(b_abs)
+		} else {
+			root.checked_add(b_abs).ok_or(Error::<T>::Overflow)?
+		};
+
+		let amount_to_sell = numerator.checked_div(fee_denominator * 2).ok_or(Error::<T>::Overflow)?;
+
+		Balance::try_from(amount_to_sell).map_err(|_| Error::<T>::Overflow.into())
+	}
+
+	/// Quote a sell of `amount` of `asset_in` for `asset_out`.
+	///
+	/// Quote is calculated by the same code as the sell itself, except the checks of trader's balances.
+	pub fn quote_sell(asset_in: AssetId, asset_out: AssetId, amount: Balance) -> Result<TradeQuote, DispatchError> {
+		let assets = AssetPair { asset_in, asset_out };
+
+		let (amount_out, transfer_fee, _) = Self::calculate_sell_trade(None, assets, amount, Balance::zero(), false)?;
+		let protocol_fee = Self::calculate_protocol_fee(asset_out, transfer_fee);
+
+		let pair_account = Self::get_pair_id(assets);
+		let asset_in_reserve = T::Currency::free_balance(asset_in, &pair_account);
+		let asset_out_reserve = T::Currency::free_balance(asset_out, &pair_account);
+
+		Self::trade_quote(
+			amount_out,
+			transfer_fee,
+			protocol_fee,
+			(asset_in_reserve, asset_out_reserve),
+			(
+				asset_in_reserve.saturating_add(amount),
+				asset_out_reserve
+					.saturating_sub(amount_out)
+					.saturating_sub(protocol_fee),
+			),
+		)
+	}
+
+	/// Quote a buy of `amount` of `asset_out` for `asset_in`.
+	///
+	/// Quote is calculated by the same code as the buy itself, except the checks of trader's balances.
+	pub fn quote_buy(asset_out: AssetId, asset_in: AssetId, amount: Balance) -> Result<TradeQuote, DispatchError> {
+		let assets = AssetPair { asset_in, asset_out };
+
+		let (buy_price, transfer_fee, _) = Self::calculate_buy_trade(None, assets, amount, Balance::MAX, false)?;
+		let amount_in = buy_price.saturating_add(transfer_fee);
+		let protocol_fee = Self::calculate_protocol_fee(asset_in, transfer_fee);
+
+		let pair_account = Self::get_pair_id(assets);
+		let asset_in_reserve = T::Currency::free_balance(asset_in, &pair_account);
+		let asset_out_reserve = T::Currency::free_balance(asset_out, &pair_account);
+
+		Self::trade_quote(
+			amount_in,
+			transfer_fee,
+			protocol_fee,
+			(asset_in_reserve, asset_out_reserve),
+			(
+				asset_in_reserve.saturating_add(amount_in).saturating_sub(protocol_fee),
+				asset_out_reserve.saturating_sub(amount),
+			),
+		)
+	}
+
+	/// Relative deviation of the execution price of a trade of `amount_in` for `amount_out` (fee excluded)
+	/// from the current spot price of the pool.
+	pub fn price_impact(assets: AssetPair, amount_in: Balance, amount_out: Balance) -> Permill {
+		let spot_amount_out =
+			<Self as AMM<_, _, _, _>>::get_spot_price_unchecked(assets.asset_in, assets.asset_out, amount_in);
+
+		if spot_amount_out.is_zero() {
+			return Permill::zero();
+		}
+
+		Permill::from_rational(spot_amount_out.saturating_sub(amount_out), spot_amount_out)
+	}
+
+	/// Build trade quote from pool reserves `(asset_in reserve, asset_out reserve)` before and after the trade.
+	fn trade_quote(
+		amount: Balance,
+		fee: Balance,
+		protocol_fee: Balance,
+		reserves_before: (Balance, Balance),
+		reserves_after: (Balance, Balance),
+	) -> Result<TradeQuote, DispatchError> {
+		let spot_price_before =
+			Price::checked_from_rational(reserves_before.1, reserves_before.0).ok_or(Error::<T>::Overflow)?;
+		let spot_price_after =
+			Price::checked_from_rational(reserves_after.1, reserves_after.0).ok_or(Error::<T>::Overflow)?;
+
+		let price_impact = Permill::from_rational(
+			spot_price_before
+				.into_inner()
+				.saturating_sub(spot_price_after.into_inner()),
+			spot_price_before.into_inner(),
+		);
+
+		Ok(TradeQuote {
+			amount,
+			fee,
+			protocol_fee,
+			spot_price_before,
+			spot_price_after,
+			price_impact,
+		})
+	}
+
+	/// Perform all checks and calculations of a sell. Checks of trader's balances are skipped if `who` is `None`.
+	///
+	/// Return amount of `asset_out` to be received (fee deducted), trade fee and discount fee in native asset.
+	fn calculate_sell_trade(
+		who: Option<&T::AccountId>,
 		assets: AssetPair,
 		amount: Balance,
 		min_bought: Balance,
 		discount: bool,
-	) -> Result<AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>, sp_runtime::DispatchError> {
+	) -> Result<(Balance, Balance, Balance), DispatchError> {
+		let pair_account = Self::get_pair_id(assets);
+
+		let limits = T::TradingLimits::pool_limits(&pair_account);
+
 		ensure!(
-			amount >= T::MinTradingLimit::get(),
+			amount >= limits.min_trading_limit,
 			Error::<T>::InsufficientTradingAmount
 		);
 
 		ensure!(Self::exists(assets), Error::<T>::TokenPoolNotFound);
 
 		ensure!(
-			T::Currency::free_balance(assets.asset_in, who) >= amount,
-			Error::<T>::InsufficientAssetBalance
+			T::CircuitBreaker::pool_state(&pair_account).is_trading_allowed(),
+			Error::<T>::TradingNotAllowed
 		);
 
+		if let Some(who) = who {
+			ensure!(
+				T::Currency::free_balance(assets.asset_in, who) >= amount,
+				Error::<T>::InsufficientAssetBalance
+			);
+		}
+
 		// If discount, pool for Sell asset and native asset must exist
 		if discount {
 			ensure!(
@@ -674,15 +2084,13 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
 			);
 		}
 
-		let pair_account = Self::get_pair_id(assets);
-
 		let asset_in_reserve = T::Currency::free_balance(assets.asset_in, &pair_account);
 		let asset_out_reserve = T::Currency::free_balance(assets.asset_out, &pair_account);
 
 		ensure!(
 			amount
 				<= asset_in_reserve
-					.checked_div(T::MaxInRatio::get())
+					.checked_div(limits.max_in_ratio)
 					.ok_or(Error::<T>::Overflow)?,
 			Error::<T>::MaxInRatioExceeded
 		);
@@ -693,7 +2101,7 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
 		let transfer_fee = if discount {
 			Self::calculate_discounted_fee(amount_out)?
 		} else {
-			Self::calculate_fee(amount_out)?
+			Self::calculate_fee(&pair_account, amount_out)?
 		};
 
 		let amount_out_without_fee = amount_out
@@ -722,21 +2130,175 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
 				hydra_dx_math::xyk::calculate_spot_price(asset_reserve, native_reserve, transfer_fee)
 					.map_err(|_| Error::<T>::CannotApplyDiscount)?;
 
+			if let Some(who) = who {
+				ensure!(
+					T::Currency::free_balance(native_asset, who) >= native_fee_spot_price,
+					Error::<T>::InsufficientNativeCurrencyBalance
+				);
+			}
+
+			native_fee_spot_price
+		} else {
+			Balance::zero()
+		};
+
+		Ok((amount_out_without_fee, transfer_fee, discount_fee))
+	}
+
+	/// Perform all checks and calculations of a buy. Checks of trader's balances are skipped if `who` is `None`.
+	///
+	/// Return amount of `asset_in` to be paid (fee excluded), trade fee and discount fee in native asset.
+	fn calculate_buy_trade(
+		who: Option<&T::AccountId>,
+		assets: AssetPair,
+		amount: Balance,
+		max_limit: Balance,
+		discount: bool,
+	) -> Result<(Balance, Balance, Balance), DispatchError> {
+		let pair_account = Self::get_pair_id(assets);
+
+		let limits = T::TradingLimits::pool_limits(&pair_account);
+
+		ensure!(
+			amount >= limits.min_trading_limit,
+			Error::<T>::InsufficientTradingAmount
+		);
+
+		ensure!(Self::exists(assets), Error::<T>::TokenPoolNotFound);
+
+		ensure!(
+			T::CircuitBreaker::pool_state(&pair_account).is_trading_allowed(),
+			Error::<T>::TradingNotAllowed
+		);
+
+		let asset_out_reserve = T::Currency::free_balance(assets.asset_out, &pair_account);
+		let asset_in_reserve = T::Currency::free_balance(assets.asset_in, &pair_account);
+
+		ensure!(asset_out_reserve > amount, Error::<T>::InsufficientPoolAssetBalance);
+
+		ensure!(
+			amount
+				<= asset_out_reserve
+					.checked_div(limits.max_out_ratio)
+					.ok_or(Error::<T>::Overflow)?,
+			Error::<T>::MaxOutRatioExceeded
+		);
+
+		// If discount, pool for Sell asset and native asset must exist
+		if discount {
+			ensure!(
+				Self::exists(AssetPair {
+					asset_in: assets.asset_out,
+					asset_out: T::NativeAssetId::get()
+				}),
+				Error::<T>::CannotApplyDiscount
+			);
+		}
+
+		let buy_price = hydra_dx_math::xyk::calculate_in_given_out(asset_out_reserve, asset_in_reserve, amount)
+			.map_err(|_| Error::<T>::BuyAssetAmountInvalid)?;
+
+		let transfer_fee = if discount {
+			Self::calculate_discounted_fee(buy_price)?
+		} else {
+			Self::calculate_fee(&pair_account, buy_price)?
+		};
+
+		let buy_price_with_fee = buy_price
+			.checked_add(transfer_fee)
+			.ok_or(Error::<T>::BuyAssetAmountInvalid)?;
+
+		ensure!(max_limit >= buy_price_with_fee, Error::<T>::AssetAmountExceededLimit);
+
+		if let Some(who) = who {
 			ensure!(
-				T::Currency::free_balance(native_asset, who) >= native_fee_spot_price,
-				Error::<T>::InsufficientNativeCurrencyBalance
+				T::Currency::free_balance(assets.asset_in, who) >= buy_price_with_fee,
+				Error::<T>::InsufficientAssetBalance
 			);
+		}
+
+		let discount_fee = if discount {
+			let native_asset = T::NativeAssetId::get();
+
+			let native_pair_account = Self::get_pair_id(AssetPair {
+				asset_in: assets.asset_out,
+				asset_out: native_asset,
+			});
+
+			let native_reserve = T::Currency::free_balance(native_asset, &native_pair_account);
+			let asset_reserve = T::Currency::free_balance(assets.asset_out, &native_pair_account);
+
+			let native_fee_spot_price =
+				hydra_dx_math::xyk::calculate_spot_price(asset_reserve, native_reserve, transfer_fee)
+					.map_err(|_| Error::<T>::CannotApplyDiscount)?;
 
+			if let Some(who) = who {
+				ensure!(
+					T::Currency::free_balance(native_asset, who) >= native_fee_spot_price,
+					Error::<T>::InsufficientNativeCurrencyBalance
+				);
+			}
 			native_fee_spot_price
 		} else {
 			Balance::zero()
 		};
 
+		Ok((buy_price, transfer_fee, discount_fee))
+	}
+}
+
+// Implementation of AMM API which makes possible to plug the AMM pool into the exchange pallet.
+impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
+	fn exists(assets: AssetPair) -> bool {
+		<ShareToken<T>>::contains_key(&Self::get_pair_id(assets))
+	}
+
+	fn get_pair_id(assets: AssetPair) -> T::AccountId {
+		Self::pair_account_from_assets(assets.asset_in, assets.asset_out)
+	}
+
+	fn get_share_token(assets: AssetPair) -> AssetId {
+		let pair_account = Self::get_pair_id(assets);
+		Self::share_token(&pair_account)
+	}
+
+	fn get_pool_assets(pool_account_id: &T::AccountId) -> Option<Vec<AssetId>> {
+		let maybe_assets = <PoolAssets<T>>::get(pool_account_id);
+		maybe_assets.map(|assets| vec![assets.0, assets.1])
+	}
+
+	fn get_spot_price_unchecked(asset_a: AssetId, asset_b: AssetId, amount: Balance) -> Balance {
+		let pair_account = Self::get_pair_id(AssetPair {
+			asset_out: asset_a,
+			asset_in: asset_b,
+		});
+
+		let asset_a_reserve = T::Currency::free_balance(asset_a, &pair_account);
+		let asset_b_reserve = T::Currency::free_balance(asset_b, &pair_account);
+
+		hydra_dx_math::xyk::calculate_spot_price(asset_a_reserve, asset_b_reserve, amount)
+			.unwrap_or_else(|_| Balance::zero())
+	}
+
+	/// Validate a sell. Perform all necessary checks and calculations.
+	/// No storage changes are performed yet.
+	///
+	/// Return `AMMTransfer` with all info needed to execute the transaction.
+	fn validate_sell(
+		who: &T::AccountId,
+		assets: AssetPair,
+		amount: Balance,
+		min_bought: Balance,
+		discount: bool,
+	) -> Result<AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>, sp_runtime::DispatchError> {
+		let (amount_out, transfer_fee, discount_fee) =
+			Self::calculate_sell_trade(Some(who), assets, amount, min_bought, discount)?;
+
 		let transfer = AMMTransfer {
 			origin: who.clone(),
 			assets,
 			amount,
-			amount_out: amount_out_without_fee,
+			amount_out,
 			discount,
 			discount_amount: discount_fee,
 			fee: (assets.asset_out, transfer_fee),
@@ -744,7 +2306,6 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
 
 		Ok(transfer)
 	}
-
 	/// Execute sell. validate_sell must be called first.
 	/// Perform necessary storage/state changes.
 	/// Note : the execution should not return error as everything was previously verified and validated.
@@ -752,6 +2313,16 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
 	fn execute_sell(transfer: &AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>) -> DispatchResult {
 		let pair_account = Self::get_pair_id(transfer.assets);
 
+		Self::update_price_observations(&pair_account);
+
+		let reserves_before = Self::pool_reserves(&pair_account);
+
+		let price_impact = Self::price_impact(
+			transfer.assets,
+			transfer.amount,
+			transfer.amount_out.saturating_add(transfer.fee.1),
+		);
+
 		let total_liquidity = Self::total_liquidity(&pair_account);
 		T::AMMHandler::on_trade(
 			transfer.assets.asset_in,
@@ -779,6 +2350,18 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
 			transfer.amount_out,
 		)?;
 
+		Self::transfer_protocol_fee(&pair_account, transfer.fee.0, transfer.fee.1)?;
+
+		T::CircuitBreaker::on_reserves_change(&pair_account, reserves_before, Self::pool_reserves(&pair_account));
+
+		Self::update_pool_stats(
+			&pair_account,
+			transfer.assets,
+			transfer.amount,
+			transfer.amount_out,
+			transfer.fee,
+		);
+
 		Self::deposit_event(Event::<T>::SellExecuted(
 			transfer.origin.clone(),
 			transfer.assets.asset_in,
@@ -788,6 +2371,7 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
 			transfer.fee.0,
 			transfer.fee.1,
 			pair_account,
+			price_impact,
 		));
 
 		Ok(())
@@ -804,82 +2388,8 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
 		max_limit: Balance,
 		discount: bool,
 	) -> Result<AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>, DispatchError> {
-		ensure!(
-			amount >= T::MinTradingLimit::get(),
-			Error::<T>::InsufficientTradingAmount
-		);
-
-		ensure!(Self::exists(assets), Error::<T>::TokenPoolNotFound);
-
-		let pair_account = Self::get_pair_id(assets);
-
-		let asset_out_reserve = T::Currency::free_balance(assets.asset_out, &pair_account);
-		let asset_in_reserve = T::Currency::free_balance(assets.asset_in, &pair_account);
-
-		ensure!(asset_out_reserve > amount, Error::<T>::InsufficientPoolAssetBalance);
-
-		ensure!(
-			amount
-				<= asset_out_reserve
-					.checked_div(T::MaxOutRatio::get())
-					.ok_or(Error::<T>::Overflow)?,
-			Error::<T>::MaxOutRatioExceeded
-		);
-
-		// If discount, pool for Sell asset and native asset must exist
-		if discount {
-			ensure!(
-				Self::exists(AssetPair {
-					asset_in: assets.asset_out,
-					asset_out: T::NativeAssetId::get()
-				}),
-				Error::<T>::CannotApplyDiscount
-			);
-		}
-
-		let buy_price = hydra_dx_math::xyk::calculate_in_given_out(asset_out_reserve, asset_in_reserve, amount)
-			.map_err(|_| Error::<T>::BuyAssetAmountInvalid)?;
-
-		let transfer_fee = if discount {
-			Self::calculate_discounted_fee(buy_price)?
-		} else {
-			Self::calculate_fee(buy_price)?
-		};
-
-		let buy_price_with_fee = buy_price
-			.checked_add(transfer_fee)
-			.ok_or(Error::<T>::BuyAssetAmountInvalid)?;
-
-		ensure!(max_limit >= buy_price_with_fee, Error::<T>::AssetAmountExceededLimit);
-
-		ensure!(
-			T::Currency::free_balance(assets.asset_in, who) >= buy_price_with_fee,
-			Error::<T>::InsufficientAssetBalance
-		);
-
-		let discount_fee = if discount {
-			let native_asset = T::NativeAssetId::get();
-
-			let native_pair_account = Self::get_pair_id(AssetPair {
-				asset_in: assets.asset_out,
-				asset_out: native_asset,
-			});
-
-			let native_reserve = T::Currency::free_balance(native_asset, &native_pair_account);
-			let asset_reserve = T::Currency::free_balance(assets.asset_out, &native_pair_account);
-
-			let native_fee_spot_price =
-				hydra_dx_math::xyk::calculate_spot_price(asset_reserve, native_reserve, transfer_fee)
-					.map_err(|_| Error::<T>::CannotApplyDiscount)?;
-
-			ensure!(
-				T::Currency::free_balance(native_asset, who) >= native_fee_spot_price,
-				Error::<T>::InsufficientNativeCurrencyBalance
-			);
-			native_fee_spot_price
-		} else {
-			Balance::zero()
-		};
+		let (buy_price, transfer_fee, discount_fee) =
+			Self::calculate_buy_trade(Some(who), assets, amount, max_limit, discount)?;
 
 		let transfer = AMMTransfer {
 			origin: who.clone(),
@@ -893,7 +2403,6 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
 
 		Ok(transfer)
 	}
-
 	/// Execute buy. validate_buy must be called first.
 	/// Perform necessary storage/state changes.
 	/// Note : the execution should not return error as everything was previously verified and validated.
@@ -901,6 +2410,12 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
 	fn execute_buy(transfer: &AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>) -> DispatchResult {
 		let pair_account = Self::get_pair_id(transfer.assets);
 
+		Self::update_price_observations(&pair_account);
+
+		let reserves_before = Self::pool_reserves(&pair_account);
+
+		let price_impact = Self::price_impact(transfer.assets, transfer.amount_out, transfer.amount);
+
 		let total_liquidity = Self::total_liquidity(&pair_account);
 		T::AMMHandler::on_trade(
 			transfer.assets.asset_in,
@@ -928,6 +2443,18 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
 			transfer.amount_out + transfer.fee.1,
 		)?;
 
+		Self::transfer_protocol_fee(&pair_account, transfer.fee.0, transfer.fee.1)?;
+
+		T::CircuitBreaker::on_reserves_change(&pair_account, reserves_before, Self::pool_reserves(&pair_account));
+
+		Self::update_pool_stats(
+			&pair_account,
+			transfer.assets,
+			transfer.amount_out + transfer.fee.1,
+			transfer.amount,
+			transfer.fee,
+		);
+
 		Self::deposit_event(Event::<T>::BuyExecuted(
 			transfer.origin.clone(),
 			transfer.assets.asset_out,
@@ -937,29 +2464,77 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
 			transfer.fee.0,
 			transfer.fee.1,
 			pair_account,
+			price_impact,
 		));
 
 		Ok(())
 	}
 
+	// `AMM` limit getters are not given a pool, they return limits of pools without their own limits.
+	// Limits of a pool are resolved by its pool account, see `get_pool_info`.
 	fn get_min_trading_limit() -> Balance {
-		T::MinTradingLimit::get()
+		T::TradingLimits::default_limits().min_trading_limit
 	}
 
 	fn get_min_pool_liquidity() -> Balance {
-		T::MinPoolLiquidity::get()
+		T::TradingLimits::default_limits().min_pool_liquidity
 	}
 
 	fn get_max_in_ratio() -> u128 {
-		T::MaxInRatio::get()
+		T::TradingLimits::default_limits().max_in_ratio
 	}
 
 	fn get_max_out_ratio() -> u128 {
-		T::MaxOutRatio::get()
+		T::TradingLimits::default_limits().max_out_ratio
+	}
+
+	fn get_fee(pool_account_id: &T::AccountId) -> (u32, u32) {
+		Self::pool_fee(pool_account_id).unwrap_or_else(T::GetExchangeFee::get)
+	}
+}
+
+// Implementation of trade calculation which makes possible to price trades of a route before executing them.
+impl<T: Config> AMMTradeCalculation<AssetPair, Balance> for Pallet<T> {
+	fn calculate_sell(assets: AssetPair, amount: Balance) -> Result<Balance, DispatchError> {
+		let (amount_out, _, _) = Self::calculate_sell_trade(None, assets, amount, Balance::zero(), false)?;
+
+		Ok(amount_out)
+	}
+
+	fn calculate_buy(assets: AssetPair, amount: Balance) -> Result<Balance, DispatchError> {
+		let (buy_price, transfer_fee, _) = Self::calculate_buy_trade(None, assets, amount, Balance::MAX, false)?;
+
+		Ok(buy_price.saturating_add(transfer_fee))
 	}
+}
+
+// Implementation of time-weighted average prices calculated from stored price observations of pools.
+impl<T: Config> TwapProvider<AssetId, T::BlockNumber> for Pallet<T> {
+	type Price = Price;
+
+	fn twap(asset_a: AssetId, asset_b: AssetId, from: T::BlockNumber, to: T::BlockNumber) -> Option<Price> {
+		if from >= to {
+			return None;
+		}
+
+		let pair_account = Self::pair_account_from_assets(asset_a, asset_b);
+		let (pool_asset_a, _) = Self::pool_assets(&pair_account)?;
+
+		let observations = Self::price_observations(&pair_account);
+		let start = observations.iter().find(|observation| observation.block == from)?;
+		let end = observations.iter().find(|observation| observation.block == to)?;
 
-	fn get_fee(_pool_account_id: &T::AccountId) -> (u32, u32) {
-		T::GetExchangeFee::get()
+		let (start_cumulative, end_cumulative) = if asset_a == pool_asset_a {
+			(start.price_a_cumulative, end.price_a_cumulative)
+		} else {
+			(start.price_b_cumulative, end.price_b_cumulative)
+		};
+
+		let elapsed = to.saturating_sub(from).saturated_into::<u128>();
+
+		Some(Price::from_inner(
+			end_cumulative.wrapping_sub(start_cumulative) / elapsed,
+		))
 	}
 }
 
@@ -970,3 +2545,14 @@ impl CanCreatePool<AssetId> for AllowAllPools {
 		true
 	}
 }
+
+/// Disallows pools of assets which are not permitted by governance, pools of permitted assets are checked by `C`.
+pub struct DisallowNotPermittedAssets<T, C>(PhantomData<(T, C)>);
+
+impl<T: Config, C: CanCreatePool<AssetId>> CanCreatePool<AssetId> for DisallowNotPermittedAssets<T, C> {
+	fn can_create(asset_a: AssetId, asset_b: AssetId) -> bool {
+		Pallet::<T>::is_asset_permitted(asset_a)
+			&& Pallet::<T>::is_asset_permitted(asset_b)
+			&& C::can_create(asset_a, asset_b)
+	}
+}
@@ -0,0 +1,145 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2021  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use frame_support::traits::StorageVersion;
+
+/// Name share tokens of existing pools after their assets and fill the share token to pool lookup.
+pub fn name_share_tokens<T: Config>() -> frame_support::weights::Weight {
+	let version = StorageVersion::get::<Pallet<T>>();
+
+	if version == 0 {
+		let mut reads: u64 = 1;
+		let mut writes: u64 = 1;
+
+		for (pair_account, (asset_a, asset_b)) in <PoolAssets<T>>::iter() {
+			let share_token = <ShareToken<T>>::get(&pair_account);
+			<ShareTokenPool<T>>::insert(share_token, &pair_account);
+			reads += 5;
+			writes += 1;
+
+			if let Some(name) = Pallet::<T>::share_token_name(asset_a, asset_b) {
+				if T::AssetMetadata::set_asset_name(share_token, name.clone()).is_ok()
+					&& T::AssetMetadata::set_asset_metadata(share_token, name, SHARE_TOKEN_DECIMALS).is_ok()
+				{
+					reads += 2;
+					writes += 4;
+				}
+			}
+		}
+
+		StorageVersion::new(1).put::<Pallet<T>>();
+
+		T::DbWeight::get().reads_writes(reads, writes)
+	} else {
+		0
+	}
+}
+
+/// Set liquidity floor of existing pools to their minimum pool liquidity.
+/// No shares are locked for pools created before the liquidity was locked, the floor protects them instead.
+pub fn set_liquidity_floors<T: Config>() -> frame_support::weights::Weight {
+	let version = StorageVersion::get::<Pallet<T>>();
+
+	if version == 1 {
+		let mut reads: u64 = 1;
+		let mut writes: u64 = 1;
+
+		for pair_account in <PoolAssets<T>>::iter_keys() {
+			reads += 3;
+
+			if !<LiquidityFloor<T>>::contains_key(&pair_account) {
+				let floor = T::TradingLimits::pool_limits(&pair_account).min_pool_liquidity;
+				<LiquidityFloor<T>>::insert(&pair_account, floor);
+				writes += 1;
+			}
+		}
+
+		StorageVersion::new(2).put::<Pallet<T>>();
+
+		T::DbWeight::get().reads_writes(reads, writes)
+	} else {
+		0
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{set_asset_name, ExtBuilder, MockAssetMetadata, Origin, Test, ALICE, DOT, HDX, XYK};
+	use frame_support::assert_ok;
+	use primitives::constants::chain::MIN_POOL_LIQUIDITY;
+
+	#[test]
+	fn name_share_tokens_migration_should_work() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(XYK::create_pool(
+				Origin::signed(ALICE),
+				HDX,
+				DOT,
+				100_000_000,
+				Price::from(2),
+				None
+			));
+
+			let pair_account = XYK::pair_account_from_assets(HDX, DOT);
+			let share_token = XYK::share_token(pair_account);
+
+			// state of a pool created before share tokens were named after the assets
+			<ShareTokenPool<Test>>::remove(share_token);
+			StorageVersion::new(0).put::<Pallet<Test>>();
+
+			set_asset_name(HDX, b"HDX");
+			set_asset_name(DOT, b"DOT");
+
+			name_share_tokens::<Test>();
+
+			assert_eq!(MockAssetMetadata::asset_name(share_token), Some(b"HDX-DOT LP".to_vec()));
+			assert_eq!(XYK::share_token_pool(share_token), Some(pair_account));
+			assert_eq!(StorageVersion::get::<Pallet<Test>>(), 1);
+
+			assert_eq!(name_share_tokens::<Test>(), 0);
+		});
+	}
+
+	#[test]
+	fn set_liquidity_floors_migration_should_work() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(XYK::create_pool(
+				Origin::signed(ALICE),
+				HDX,
+				DOT,
+				100_000_000,
+				Price::from(2),
+				None
+			));
+
+			let pair_account = XYK::pair_account_from_assets(HDX, DOT);
+
+			// state of a pool created before liquidity floors were stored
+			<LiquidityFloor<Test>>::remove(&pair_account);
+			StorageVersion::new(1).put::<Pallet<Test>>();
+
+			set_liquidity_floors::<Test>();
+
+			assert_eq!(XYK::liquidity_floor(&pair_account), MIN_POOL_LIQUIDITY);
+			assert_eq!(StorageVersion::get::<Pallet<Test>>(), 2);
+
+			assert_eq!(set_liquidity_floors::<Test>(), 0);
+		});
+	}
+}
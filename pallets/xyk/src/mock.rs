@@ -16,8 +16,9 @@
 // limitations under the License.
 
 use crate as xyk;
-use crate::Config;
-use frame_support::parameter_types;
+use crate::{Config, DisallowNotPermittedAssets};
+use codec::Decode;
+use frame_support::{dispatch::DispatchResult, parameter_types, weights::Weight};
 use frame_system as system;
 use orml_traits::parameter_type_with_key;
 use sp_core::H256;
@@ -28,12 +29,18 @@ use sp_runtime::{
 
 use frame_support::traits::{Everything, GenesisBuild, Get, Nothing};
 use hydradx_traits::{AssetPairAccountIdFor, CanCreatePool};
+use orml_traits::MultiCurrency;
 use primitives::{
 	constants::chain::{MAX_IN_RATIO, MAX_OUT_RATIO, MIN_POOL_LIQUIDITY, MIN_TRADING_LIMIT},
-	AssetId, Balance,
+	traits::{
+		AssetMetadataRegistry, CircuitBreaker, ConstantTradingLimits, FlashSwapHandler, OnLiquidityChangedHandler,
+		OnPoolDestroyedHandler, TradingLimitsProvider,
+	},
+	AssetId, Balance, PoolState, TradingLimits,
 };
 
-use frame_system::EnsureSigned;
+use frame_system::{EnsureRoot, EnsureSigned};
+use pallet_asset_registry::AssetType;
 use std::cell::RefCell;
 
 pub type Amount = i128;
@@ -41,6 +48,7 @@ pub type AccountId = u64;
 
 pub const ALICE: AccountId = 1;
 pub const BOB: AccountId = 2;
+pub const TREASURY: AccountId = 99;
 
 pub const HDX: AssetId = 1000;
 pub const DOT: AssetId = 2000;
@@ -67,8 +75,18 @@ frame_support::construct_runtime!(
 
 thread_local! {
 		static EXCHANGE_FEE: RefCell<(u32, u32)> = RefCell::new((2, 1_000));
+		static POOL_DEPOSIT: RefCell<Balance> = RefCell::new(0);
+		static POOL_TRADING_LIMITS: RefCell<Vec<(AccountId, TradingLimits)>> = RefCell::new(vec![]);
+		static POOL_STATES: RefCell<Vec<(AccountId, PoolState)>> = RefCell::new(vec![]);
+		static RESERVE_CHANGES: RefCell<Vec<ReserveChange>> = RefCell::new(vec![]);
+		static ASSET_NAMES: RefCell<Vec<(AssetId, Vec<u8>)>> = RefCell::new(vec![]);
+		static LIQUIDITY_CHANGES: RefCell<Vec<LiquidityChange>> = RefCell::new(vec![]);
+		static DESTROYED_POOLS: RefCell<Vec<(AccountId, AssetId, AssetId)>> = RefCell::new(vec![]);
 }
 
+type ReserveChange = (AccountId, (Balance, Balance), (Balance, Balance));
+type LiquidityChange = (AssetId, AssetId, Balance, Balance, Balance);
+
 struct ExchangeFee;
 impl Get<(u32, u32)> for ExchangeFee {
 	fn get() -> (u32, u32) {
@@ -76,6 +94,13 @@ impl Get<(u32, u32)> for ExchangeFee {
 	}
 }
 
+struct PoolCreationDeposit;
+impl Get<Balance> for PoolCreationDeposit {
+	fn get() -> Balance {
+		POOL_DEPOSIT.with(|v| *v.borrow())
+	}
+}
+
 parameter_types! {
 	pub const BlockHashCount: u64 = 250;
 	pub const SS58Prefix: u8 = 63;
@@ -158,6 +183,12 @@ parameter_types! {
 	pub const MinPoolLiquidity: Balance = MIN_POOL_LIQUIDITY;
 	pub const MaxInRatio: u128 = MAX_IN_RATIO;
 	pub const MaxOutRatio: u128 = MAX_OUT_RATIO;
+	pub const MaxFeeTiers: u32 = 5;
+	pub const MaxPriceObservations: u32 = 5;
+	pub const StatsWindow: u64 = 10;
+	pub const MaxBatchSize: u32 = 5;
+	pub const ProtocolFeeReceiver: AccountId = TREASURY;
+	pub PoolDeposit: Balance = PoolCreationDeposit::get();
 }
 
 pub struct Disallow10_10Pool();
@@ -168,20 +199,190 @@ impl CanCreatePool<AssetId> for Disallow10_10Pool {
 	}
 }
 
+type DefaultTradingLimits = ConstantTradingLimits<MinTradingLimit, MinPoolLiquidity, MaxInRatio, MaxOutRatio>;
+
+/// Constant trading limits which can be overridden for a pool by `ExtBuilder::with_pool_trading_limits`.
+pub struct PoolTradingLimits;
+
+impl TradingLimitsProvider<AccountId> for PoolTradingLimits {
+	fn default_limits() -> TradingLimits {
+		DefaultTradingLimits::default_limits()
+	}
+
+	fn pool_limits(pool_account: &AccountId) -> TradingLimits {
+		POOL_TRADING_LIMITS
+			.with(|v| {
+				v.borrow()
+					.iter()
+					.find(|(account, _)| account == pool_account)
+					.map(|(_, limits)| *limits)
+			})
+			.unwrap_or_else(Self::default_limits)
+	}
+}
+
+/// Circuit breaker with states of pools set by `set_pool_state` which records changes of reserves by trades.
+pub struct MockCircuitBreaker;
+
+impl CircuitBreaker<AccountId> for MockCircuitBreaker {
+	fn pool_state(pool_account: &AccountId) -> PoolState {
+		POOL_STATES
+			.with(|v| {
+				v.borrow()
+					.iter()
+					.rev()
+					.find(|(account, _)| account == pool_account)
+					.map(|(_, state)| *state)
+			})
+			.unwrap_or_default()
+	}
+
+	fn on_reserves_change(
+		pool_account: &AccountId,
+		reserves_before: (Balance, Balance),
+		reserves_after: (Balance, Balance),
+	) {
+		RESERVE_CHANGES.with(|v| v.borrow_mut().push((*pool_account, reserves_before, reserves_after)));
+	}
+
+	fn on_liquidity_change(_: &AccountId, _: (Balance, Balance), _: (Balance, Balance)) {}
+}
+
+pub fn set_pool_state(pool_account: AccountId, state: PoolState) {
+	POOL_STATES.with(|v| v.borrow_mut().push((pool_account, state)));
+}
+
+pub fn reserve_changes() -> Vec<ReserveChange> {
+	RESERVE_CHANGES.with(|v| v.borrow().clone())
+}
+
+pub fn set_asset_name(asset_id: AssetId, name: &[u8]) {
+	ASSET_NAMES.with(|v| v.borrow_mut().push((asset_id, name.to_vec())));
+}
+
+/// Flash swap handler which transfers `(asset, amount)` encoded in `data` from the borrower to the pool.
+pub struct RepayFlashSwap;
+
+impl FlashSwapHandler<AccountId, AssetId, Balance> for RepayFlashSwap {
+	fn on_flash_swap(
+		who: &AccountId,
+		pool_account: &AccountId,
+		_asset_out: AssetId,
+		_amount_out: Balance,
+		_asset_in: AssetId,
+		data: &[u8],
+	) -> DispatchResult {
+		let (asset, amount) = <(AssetId, Balance)>::decode(&mut &data[..])
+			.map_err(|_| sp_runtime::DispatchError::Other("invalid flash swap data"))?;
+
+		Currency::transfer(asset, who, pool_account, amount)
+	}
+
+	fn on_flash_swap_weight() -> Weight {
+		0
+	}
+}
+
+/// Asset metadata with names of assets set by `ExtBuilder::with_asset_name` and names of share tokens
+/// kept in the asset registry.
+pub struct MockAssetMetadata;
+
+impl AssetMetadataRegistry<AssetId> for MockAssetMetadata {
+	fn asset_name(asset_id: AssetId) -> Option<Vec<u8>> {
+		ASSET_NAMES
+			.with(|v| {
+				v.borrow()
+					.iter()
+					.find(|(asset, _)| *asset == asset_id)
+					.map(|(_, name)| name.clone())
+			})
+			.or_else(|| AssetRegistry::assets(asset_id).map(|details| details.name.into_inner()))
+	}
+
+	fn pool_share_assets(asset_id: AssetId) -> Option<(AssetId, AssetId)> {
+		match AssetRegistry::assets(asset_id)?.asset_type {
+			AssetType::PoolShare(asset_a, asset_b) => Some((asset_a, asset_b)),
+			_ => None,
+		}
+	}
+
+	fn max_name_length() -> u32 {
+		RegistryStringLimit::get()
+	}
+
+	fn set_asset_name(asset_id: AssetId, name: Vec<u8>) -> DispatchResult {
+		let details = AssetRegistry::assets(asset_id).ok_or(sp_runtime::DispatchError::Other("unknown asset"))?;
+
+		AssetRegistry::update(Origin::signed(ALICE), asset_id, name, details.asset_type, None)
+	}
+
+	fn set_asset_metadata(asset_id: AssetId, symbol: Vec<u8>, decimals: u8) -> DispatchResult {
+		AssetRegistry::set_metadata(Origin::signed(ALICE), asset_id, symbol, decimals)
+	}
+}
+
+/// Handler which records liquidity changes and destroyed pools.
+pub struct RecordLiquidityChanges;
+
+impl OnLiquidityChangedHandler<AssetId, Balance> for RecordLiquidityChanges {
+	fn on_liquidity_changed(
+		asset_a: AssetId,
+		asset_b: AssetId,
+		amount_a: Balance,
+		amount_b: Balance,
+		liquidity: Balance,
+	) -> Weight {
+		LIQUIDITY_CHANGES.with(|v| v.borrow_mut().push((asset_a, asset_b, amount_a, amount_b, liquidity)));
+		0
+	}
+
+	fn on_liquidity_changed_weight() -> Weight {
+		0
+	}
+}
+
+impl OnPoolDestroyedHandler<AccountId, AssetId> for RecordLiquidityChanges {
+	fn on_pool_destroyed(pool_account: &AccountId, asset_a: AssetId, asset_b: AssetId) -> Weight {
+		DESTROYED_POOLS.with(|v| v.borrow_mut().push((*pool_account, asset_a, asset_b)));
+		0
+	}
+
+	fn on_pool_destroyed_weight() -> Weight {
+		0
+	}
+}
+
+pub fn liquidity_changes() -> Vec<LiquidityChange> {
+	LIQUIDITY_CHANGES.with(|v| v.borrow().clone())
+}
+
+pub fn destroyed_pools() -> Vec<(AccountId, AssetId, AssetId)> {
+	DESTROYED_POOLS.with(|v| v.borrow().clone())
+}
+
 impl Config for Test {
 	type Event = Event;
 	type AssetRegistry = AssetRegistry;
+	type AssetMetadata = MockAssetMetadata;
 	type AssetPairAccountId = AssetPairAccountIdTest;
 	type Currency = Currency;
 	type NativeAssetId = NativeAssetId;
 	type WeightInfo = ();
 	type GetExchangeFee = ExchangeFeeRate;
-	type MinTradingLimit = MinTradingLimit;
-	type MinPoolLiquidity = MinPoolLiquidity;
-	type MaxInRatio = MaxInRatio;
-	type MaxOutRatio = MaxOutRatio;
-	type CanCreatePool = Disallow10_10Pool;
+	type MaxFeeTiers = MaxFeeTiers;
+	type MaxPriceObservations = MaxPriceObservations;
+	type StatsWindow = StatsWindow;
+	type AuthorityOrigin = EnsureRoot<AccountId>;
+	type ProtocolFeeReceiver = ProtocolFeeReceiver;
+	type TradingLimits = PoolTradingLimits;
+	type CircuitBreaker = MockCircuitBreaker;
+	type CanCreatePool = DisallowNotPermittedAssets<Test, Disallow10_10Pool>;
+	type PoolDeposit = PoolDeposit;
 	type AMMHandler = ();
+	type LiquidityChangedHandler = RecordLiquidityChanges;
+	type PoolDestroyedHandler = RecordLiquidityChanges;
+	type FlashSwapHandler = RepayFlashSwap;
+	type MaxBatchSize = MaxBatchSize;
 }
 
 pub struct ExtBuilder {
@@ -217,6 +418,21 @@ impl ExtBuilder {
 		self
 	}
 
+	pub fn with_pool_deposit(self, deposit: Balance) -> Self {
+		POOL_DEPOSIT.with(|v| *v.borrow_mut() = deposit);
+		self
+	}
+
+	pub fn with_asset_name(self, asset_id: AssetId, name: &[u8]) -> Self {
+		set_asset_name(asset_id, name);
+		self
+	}
+
+	pub fn with_pool_trading_limits(self, pool_account: AccountId, limits: TradingLimits) -> Self {
+		POOL_TRADING_LIMITS.with(|v| v.borrow_mut().push((pool_account, limits)));
+		self
+	}
+
 	pub fn build(self) -> sp_io::TestExternalities {
 		let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
 
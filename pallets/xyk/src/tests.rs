@@ -17,13 +17,20 @@
 
 use super::*;
 pub use crate::mock::{
-	Currency, Event as TestEvent, ExtBuilder, Origin, System, Test, ACA, ALICE, BOB, DOT, HDX, HDX_DOT_POOL_ID, XYK,
+	destroyed_pools, liquidity_changes, reserve_changes, set_pool_state, AssetRegistry, Currency, Event as TestEvent,
+	ExtBuilder, MockAssetMetadata, Origin, PoolTradingLimits, System, Test, ACA, ALICE, BOB, DOT, HDX, HDX_DOT_POOL_ID,
+	TREASURY, XYK,
 };
 use frame_support::BoundedVec;
 use frame_support::{assert_noop, assert_ok};
 use hydra_dx_math::MathError;
+use hydradx_traits::pools::SpotPriceProvider;
 use hydradx_traits::AMM as AmmPool;
 use pallet_asset_registry::AssetType;
+use primitives::{
+	constants::chain::{MAX_IN_RATIO, MIN_TRADING_LIMIT},
+	PoolState, PoolStats, TradeStats, TradingLimits,
+};
 use sp_std::convert::TryInto;
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
@@ -36,6 +43,16 @@ fn expect_events(e: Vec<TestEvent>) {
 	e.into_iter().for_each(frame_system::Pallet::<Test>::assert_has_event);
 }
 
+// State of a pool created before minimum liquidity was locked.
+fn unlock_pool_liquidity(pair_account: &u64, share_token: AssetId) {
+	let locked_account = XYK::locked_liquidity_account();
+	let locked_shares = Currency::free_balance(share_token, &locked_account);
+
+	assert_ok!(Currency::withdraw(share_token, &locked_account, locked_shares));
+	<TotalLiquidity<Test>>::mutate(pair_account, |liquidity| *liquidity -= locked_shares);
+	<LiquidityFloor<Test>>::remove(pair_account);
+}
+
 #[test]
 fn create_pool_should_work() {
 	new_test_ext().execute_with(|| {
@@ -46,7 +63,8 @@ fn create_pool_should_work() {
 			asset_a,
 			asset_b,
 			100_000_000_000_000,
-			Price::from(10)
+			Price::from(10),
+			None
 		));
 
 		let pair_account = XYK::get_pair_id(AssetPair {
@@ -61,7 +79,7 @@ fn create_pool_should_work() {
 		assert_eq!(Currency::free_balance(asset_b, &pair_account), 1000000000000000);
 		assert_eq!(Currency::free_balance(asset_a, &ALICE), 900000000000000);
 		assert_eq!(Currency::free_balance(asset_b, &ALICE), 0);
-		assert_eq!(Currency::free_balance(share_token, &ALICE), 100000000000000);
+		assert_eq!(Currency::free_balance(share_token, &ALICE), 99999999999000);
 		assert_eq!(XYK::total_liquidity(&pair_account), 100000000000000);
 
 		expect_events(vec![Event::PoolCreated(
@@ -88,22 +106,23 @@ fn create_same_pool_should_not_work() {
 			asset_b,
 			asset_a,
 			1000,
-			Price::from(2)
+			Price::from(2),
+			None
 		));
 		assert_noop!(
-			XYK::create_pool(Origin::signed(user), asset_b, asset_a, 999, Price::from(2)),
+			XYK::create_pool(Origin::signed(user), asset_b, asset_a, 999, Price::from(2), None),
 			Error::<Test>::InsufficientLiquidity
 		);
 		assert_noop!(
-			XYK::create_pool(Origin::signed(user), asset_b, asset_a, 1000, Price::from(0)),
+			XYK::create_pool(Origin::signed(user), asset_b, asset_a, 1000, Price::from(0), None),
 			Error::<Test>::ZeroInitialPrice
 		);
 		assert_noop!(
-			XYK::create_pool(Origin::signed(user), asset_a, asset_a, 1000, Price::from(2)),
+			XYK::create_pool(Origin::signed(user), asset_a, asset_a, 1000, Price::from(2), None),
 			Error::<Test>::CannotCreatePoolWithSameAssets
 		);
 		assert_noop!(
-			XYK::create_pool(Origin::signed(user), asset_b, asset_a, 1000, Price::from(2)),
+			XYK::create_pool(Origin::signed(user), asset_b, asset_a, 1000, Price::from(2), None),
 			Error::<Test>::TokenPoolAlreadyExists
 		);
 
@@ -138,7 +157,8 @@ fn create_pool_overflowing_amount_should_not_work() {
 				asset_b,
 				asset_a,
 				u128::MAX as u128,
-				Price::from(2)
+				Price::from(2),
+				None
 			),
 			Error::<Test>::CreatePoolAssetAmountInvalid
 		);
@@ -157,7 +177,8 @@ fn create_pool_with_insufficient_balance_should_not_work() {
 				4000,
 				asset_a,
 				100_000_000_000_000,
-				Price::from(10)
+				Price::from(10),
+				None
 			),
 			Error::<Test>::InsufficientAssetBalance
 		);
@@ -168,7 +189,8 @@ fn create_pool_with_insufficient_balance_should_not_work() {
 				asset_a,
 				4000,
 				100_000_000_000_000,
-				Price::from(10)
+				Price::from(10),
+				None
 			),
 			Error::<Test>::InsufficientAssetBalance
 		);
@@ -187,7 +209,8 @@ fn add_liquidity_should_work() {
 			asset_a,
 			asset_b,
 			100_000_000,
-			Price::from(10_000)
+			Price::from(10_000),
+			None
 		));
 
 		assert_ok!(XYK::add_liquidity(
@@ -195,7 +218,9 @@ fn add_liquidity_should_work() {
 			asset_a,
 			asset_b,
 			400_000,
-			1_000_000_000_000
+			1_000_000_000_000,
+			0,
+			None
 		));
 
 		let pair_account = XYK::get_pair_id(AssetPair {
@@ -207,7 +232,7 @@ fn add_liquidity_should_work() {
 		assert_eq!(Currency::free_balance(asset_b, &pair_account), 1004000000000);
 		assert_eq!(Currency::free_balance(asset_a, &pair_account), 100400000);
 		assert_eq!(Currency::free_balance(asset_a, &user), 999999899600000);
-		assert_eq!(Currency::free_balance(share_token, &user), 1004000000000);
+		assert_eq!(Currency::free_balance(share_token, &user), 1003999999000);
 		assert_eq!(XYK::total_liquidity(&pair_account), 1004000000000);
 
 		expect_events(vec![
@@ -228,14 +253,17 @@ fn add_liquidity_as_another_user_should_work() {
 			asset_b,
 			asset_a,
 			100_000_000,
-			Price::from(10_000)
+			Price::from(10_000),
+			None
 		));
 		assert_ok!(XYK::add_liquidity(
 			Origin::signed(user),
 			asset_b,
 			asset_a,
 			400_000,
-			1_000_000_000_000
+			1_000_000_000_000,
+			0,
+			None
 		));
 
 		let pair_account = XYK::get_pair_id(AssetPair {
@@ -247,7 +275,7 @@ fn add_liquidity_as_another_user_should_work() {
 		assert_eq!(Currency::free_balance(asset_a, &pair_account), 1004000000000);
 		assert_eq!(Currency::free_balance(asset_b, &pair_account), 100400000);
 		assert_eq!(Currency::free_balance(asset_b, &user), 999999899600000);
-		assert_eq!(Currency::free_balance(share_token, &user), 1004000000000);
+		assert_eq!(Currency::free_balance(share_token, &user), 1003999999000);
 		assert_eq!(XYK::total_liquidity(&pair_account), 1004000000000);
 
 		assert_ok!(XYK::add_liquidity(
@@ -255,14 +283,16 @@ fn add_liquidity_as_another_user_should_work() {
 			asset_b,
 			asset_a,
 			1_000_000,
-			1_000_000_000_000
+			1_000_000_000_000,
+			0,
+			None
 		));
 
 		assert_eq!(Currency::free_balance(asset_a, &pair_account), 1014000000000);
 		assert_eq!(Currency::free_balance(asset_b, &pair_account), 101400000);
 		assert_eq!(Currency::free_balance(asset_b, &user), 999999899600000);
 		assert_eq!(Currency::free_balance(asset_b, &BOB), 999999999000000);
-		assert_eq!(Currency::free_balance(share_token, &user), 1004000000000);
+		assert_eq!(Currency::free_balance(share_token, &user), 1003999999000);
 		assert_eq!(Currency::free_balance(share_token, &BOB), 10000000000);
 		assert_eq!(XYK::total_liquidity(&pair_account), 1014000000000);
 
@@ -292,7 +322,8 @@ fn remove_liquidity_should_work() {
 			asset_a,
 			asset_b,
 			100_000_000,
-			Price::from(10_000)
+			Price::from(10_000),
+			None
 		));
 
 		let pair_account = XYK::get_pair_id(AssetPair {
@@ -301,17 +332,25 @@ fn remove_liquidity_should_work() {
 		});
 		let share_token = XYK::share_token(pair_account);
 
-		assert_eq!(Currency::free_balance(share_token, &user), 100000000);
+		assert_eq!(Currency::free_balance(share_token, &user), 99999000);
 		assert_eq!(Currency::free_balance(asset_a, &user), 999999900000000);
 		assert_eq!(Currency::free_balance(asset_a, &pair_account), 100000000);
 		assert_eq!(Currency::free_balance(asset_b, &pair_account), 1000000000000);
 
-		assert_ok!(XYK::remove_liquidity(Origin::signed(user), asset_a, asset_b, 355_000));
+		assert_ok!(XYK::remove_liquidity(
+			Origin::signed(user),
+			asset_a,
+			asset_b,
+			355_000,
+			0,
+			0,
+			None
+		));
 
 		assert_eq!(Currency::free_balance(asset_b, &pair_account), 996450000000);
 		assert_eq!(Currency::free_balance(asset_a, &user), 999999900355000);
 
-		assert_eq!(Currency::free_balance(share_token, &user), 99645000);
+		assert_eq!(Currency::free_balance(share_token, &user), 99644000);
 		assert_eq!(XYK::total_liquidity(&pair_account), 99645000);
 
 		expect_events(vec![
@@ -333,7 +372,8 @@ fn remove_liquidity_without_shares_should_not_work() {
 			asset_a,
 			asset_b,
 			100_000_000,
-			Price::from(1)
+			Price::from(1),
+			None
 		));
 
 		let pair_account = XYK::get_pair_id(AssetPair {
@@ -346,7 +386,7 @@ fn remove_liquidity_without_shares_should_not_work() {
 		assert_ok!(Currency::transfer(Origin::signed(ALICE), BOB, share_token, shares));
 
 		assert_noop!(
-			XYK::remove_liquidity(Origin::signed(user), asset_a, asset_b, 355_000),
+			XYK::remove_liquidity(Origin::signed(user), asset_a, asset_b, 355_000, 0, 0, None),
 			Error::<Test>::InsufficientAssetBalance
 		);
 
@@ -382,7 +422,8 @@ fn remove_liquidity_from_reduced_pool_should_not_work() {
 			asset_a,
 			asset_b,
 			100_000_000,
-			Price::from(1)
+			Price::from(1),
+			None
 		));
 
 		let pair_account = XYK::get_pair_id(AssetPair {
@@ -399,7 +440,7 @@ fn remove_liquidity_from_reduced_pool_should_not_work() {
 		));
 
 		assert_noop!(
-			XYK::remove_liquidity(Origin::signed(user), asset_a, asset_b, 200_000_000),
+			XYK::remove_liquidity(Origin::signed(user), asset_a, asset_b, 200_000_000, 0, 0, None),
 			Error::<Test>::InsufficientLiquidity
 		);
 
@@ -419,7 +460,7 @@ fn remove_liquidity_from_reduced_pool_should_not_work() {
 		));
 
 		assert_noop!(
-			XYK::remove_liquidity(Origin::signed(user), asset_a, asset_b, 200_000_000),
+			XYK::remove_liquidity(Origin::signed(user), asset_a, asset_b, 200_000_000, 0, 0, None),
 			Error::<Test>::InsufficientLiquidity
 		);
 
@@ -464,18 +505,35 @@ fn add_liquidity_more_than_owner_should_not_work() {
 			HDX,
 			ACA,
 			200_000_000,
-			Price::from(3000000)
+			Price::from(3000000),
+			None
 		));
 
 		assert_eq!(Currency::free_balance(ACA, &ALICE), 400000000000000);
 
 		assert_noop!(
-			XYK::add_liquidity(Origin::signed(ALICE), HDX, ACA, 200_000_000_000_000_000, 600_000_000),
+			XYK::add_liquidity(
+				Origin::signed(ALICE),
+				HDX,
+				ACA,
+				200_000_000_000_000_000,
+				600_000_000,
+				0,
+				None
+			),
 			Error::<Test>::InsufficientAssetBalance
 		);
 
 		assert_noop!(
-			XYK::add_liquidity(Origin::signed(ALICE), HDX, ACA, 600_000_000, 200_000_000_000_000_000),
+			XYK::add_liquidity(
+				Origin::signed(ALICE),
+				HDX,
+				ACA,
+				600_000_000,
+				200_000_000_000_000_000,
+				0,
+				None
+			),
 			Error::<Test>::InsufficientAssetBalance
 		);
 	});
@@ -488,22 +546,23 @@ fn add_insufficient_liquidity_should_not_work() {
 			Origin::signed(ALICE),
 			HDX,
 			ACA,
-			1000,
-			Price::from_float(1.5)
+			2000,
+			Price::from_float(1.5),
+			None
 		));
 
 		assert_noop!(
-			XYK::add_liquidity(Origin::signed(ALICE), HDX, ACA, 0, 0),
+			XYK::add_liquidity(Origin::signed(ALICE), HDX, ACA, 0, 0, 0, None),
 			Error::<Test>::InsufficientTradingAmount
 		);
 
 		assert_noop!(
-			XYK::add_liquidity(Origin::signed(ALICE), HDX, ACA, 1000, 0),
+			XYK::add_liquidity(Origin::signed(ALICE), HDX, ACA, 1000, 0, 0, None),
 			Error::<Test>::ZeroLiquidity
 		);
 
 		assert_noop!(
-			XYK::add_liquidity(Origin::signed(BOB), ACA, HDX, 1000, 2000),
+			XYK::add_liquidity(Origin::signed(BOB), ACA, HDX, 1000, 2000, 0, None),
 			Error::<Test>::InsufficientLiquidity
 		);
 	});
@@ -517,11 +576,12 @@ fn add_liquidity_exceeding_max_limit_should_not_work() {
 			HDX,
 			ACA,
 			100_000_000_000_000,
-			Price::from(1)
+			Price::from(1),
+			None
 		));
 
 		assert_noop!(
-			XYK::add_liquidity(Origin::signed(ALICE), HDX, ACA, 10_000_000, 1_000_000),
+			XYK::add_liquidity(Origin::signed(ALICE), HDX, ACA, 10_000_000, 1_000_000, 0, None),
 			Error::<Test>::AssetAmountExceededLimit
 		);
 	});
@@ -533,14 +593,15 @@ fn remove_liquidity_should_respect_min_pool_limit() {
 			Origin::signed(ALICE),
 			HDX,
 			ACA,
-			1000,
-			Price::from_float(1.5)
+			2000,
+			Price::from_float(1.5),
+			None
 		));
 
-		assert_ok!(XYK::add_liquidity(Origin::signed(BOB), ACA, HDX, 2000, 2000));
+		assert_ok!(XYK::add_liquidity(Origin::signed(BOB), ACA, HDX, 2000, 2000, 0, None));
 
 		assert_noop!(
-			XYK::remove_liquidity(Origin::signed(BOB), ACA, HDX, 500),
+			XYK::remove_liquidity(Origin::signed(BOB), ACA, HDX, 500, 0, 0, None),
 			Error::<Test>::InsufficientLiquidity
 		);
 	});
@@ -550,7 +611,7 @@ fn remove_liquidity_should_respect_min_pool_limit() {
 fn remove_zero_liquidity_should_not_work() {
 	new_test_ext().execute_with(|| {
 		assert_noop!(
-			XYK::remove_liquidity(Origin::signed(ALICE), HDX, ACA, 0),
+			XYK::remove_liquidity(Origin::signed(ALICE), HDX, ACA, 0, 0, 0, None),
 			Error::<Test>::ZeroLiquidity
 		);
 	});
@@ -568,7 +629,8 @@ fn sell_test() {
 			asset_a,
 			asset_b,
 			200_000_000_000,
-			Price::from(3000)
+			Price::from(3000),
+			None
 		));
 
 		let pair_account = XYK::get_pair_id(AssetPair {
@@ -579,7 +641,7 @@ fn sell_test() {
 
 		assert_eq!(Currency::free_balance(asset_a, &user_1), 999800000000000);
 		assert_eq!(Currency::free_balance(asset_b, &user_1), 400000000000000);
-		assert_eq!(Currency::free_balance(share_token, &user_1), 600000000000000);
+		assert_eq!(Currency::free_balance(share_token, &user_1), 599999999999000);
 
 		assert_eq!(Currency::free_balance(asset_a, &pair_account), 200000000000);
 		assert_eq!(Currency::free_balance(asset_b, &pair_account), 600000000000000);
@@ -591,11 +653,13 @@ fn sell_test() {
 			456_444_678,
 			1000000000000,
 			false,
+			None,
+			None,
 		));
 
 		assert_eq!(Currency::free_balance(asset_a, &user_1), 999799543555322);
 		assert_eq!(Currency::free_balance(asset_b, &user_1), 401363483591788);
-		assert_eq!(Currency::free_balance(share_token, &user_1), 600000000000000);
+		assert_eq!(Currency::free_balance(share_token, &user_1), 599999999999000);
 		assert_eq!(Currency::free_balance(asset_a, &pair_account), 200456444678);
 		assert_eq!(Currency::free_balance(asset_b, &pair_account), 598636516408212);
 
@@ -610,6 +674,7 @@ fn sell_test() {
 				asset_b,
 				2732432046,
 				pair_account,
+				Permill::from_parts(2_277),
 			)
 			.into(),
 		]);
@@ -640,12 +705,21 @@ fn work_flow_happy_path_should_work() {
 			asset_a,
 			asset_b,
 			350_000_000_000,
-			Price::from(40)
+			Price::from(40),
+			None
 		));
 
 		// User 1 really tries!
 		assert_noop!(
-			XYK::add_liquidity(Origin::signed(user_1), asset_a, asset_b, 800_000_000_000_000_000, 100),
+			XYK::add_liquidity(
+				Origin::signed(user_1),
+				asset_a,
+				asset_b,
+				800_000_000_000_000_000,
+				100,
+				0,
+				None
+			),
 			Error::<Test>::InsufficientAssetBalance
 		);
 
@@ -662,7 +736,7 @@ fn work_flow_happy_path_should_work() {
 		assert_eq!(Currency::free_balance(asset_a, &user_2), 1_000_000_000_000_000);
 		assert_eq!(Currency::free_balance(asset_b, &user_2), 1_000_000_000_000_000);
 
-		assert_eq!(Currency::free_balance(share_token, &user_1), 350_000_000_000);
+		assert_eq!(Currency::free_balance(share_token, &user_1), 349_999_999_000);
 		assert_eq!(Currency::free_balance(share_token, &user_2), 0);
 
 		assert_eq!(Currency::free_balance(asset_a, &pair_account), 350_000_000_000);
@@ -675,7 +749,9 @@ fn work_flow_happy_path_should_work() {
 			asset_a,
 			asset_b,
 			300_000_000_000,
-			current_b_balance
+			current_b_balance,
+			0,
+			None
 		));
 
 		assert_eq!(XYK::total_liquidity(&pair_account), 650_000_000_000);
@@ -687,7 +763,7 @@ fn work_flow_happy_path_should_work() {
 		assert_eq!(Currency::free_balance(asset_a, &user_2), 999_700_000_000_000);
 		assert_eq!(Currency::free_balance(asset_b, &user_2), 988_000_000_000_000);
 
-		assert_eq!(Currency::free_balance(share_token, &user_1), 350_000_000_000);
+		assert_eq!(Currency::free_balance(share_token, &user_1), 349_999_999_000);
 		assert_eq!(Currency::free_balance(share_token, &user_2), 300_000_000_000);
 
 		assert_eq!(Currency::free_balance(asset_a, &pair_account), 650_000_000_000);
@@ -701,6 +777,8 @@ fn work_flow_happy_path_should_work() {
 			216_666_666_666,
 			100_000_000_000,
 			false,
+			None,
+			None,
 		));
 
 		assert_eq!(Currency::free_balance(asset_a, &user_1), 999_650_000_000_000);
@@ -709,7 +787,7 @@ fn work_flow_happy_path_should_work() {
 		assert_eq!(Currency::free_balance(asset_a, &user_2), 999_483_333_333_334);
 		assert_eq!(Currency::free_balance(asset_b, &user_2), 994_486_999_999_986);
 
-		assert_eq!(Currency::free_balance(share_token, &user_1), 350_000_000_000);
+		assert_eq!(Currency::free_balance(share_token, &user_1), 349_999_999_000);
 		assert_eq!(Currency::free_balance(share_token, &user_2), 300_000_000_000);
 
 		assert_eq!(Currency::free_balance(asset_a, &pair_account), 866_666_666_666);
@@ -723,6 +801,8 @@ fn work_flow_happy_path_should_work() {
 			288_888_888_888,
 			100_000_000_000,
 			false,
+			None,
+			None,
 		));
 
 		assert_eq!(Currency::free_balance(asset_a, &user_1), 999_361_111_111_112);
@@ -734,12 +814,20 @@ fn work_flow_happy_path_should_work() {
 		assert_eq!(user_2_original_balance_1, 999_483_333_333_334);
 		assert_eq!(user_2_original_balance_2, 994_486_999_999_986);
 
-		assert_eq!(Currency::free_balance(share_token, &user_1), 350_000_000_000);
+		assert_eq!(Currency::free_balance(share_token, &user_1), 349_999_999_000);
 		assert_eq!(Currency::free_balance(share_token, &user_2), 300_000_000_000);
 
 		// User 2 removes liquidity
 
-		assert_ok!(XYK::remove_liquidity(Origin::signed(user_2), asset_a, asset_b, 10_000));
+		assert_ok!(XYK::remove_liquidity(
+			Origin::signed(user_2),
+			asset_a,
+			asset_b,
+			10_000,
+			0,
+			0,
+			None
+		));
 
 		let user_2_remove_1_balance_1 = Currency::free_balance(asset_a, &user_2);
 		let user_2_remove_1_balance_2 = Currency::free_balance(asset_b, &user_2);
@@ -748,7 +836,15 @@ fn work_flow_happy_path_should_work() {
 		assert_eq!(user_2_remove_1_balance_2, 994_487_000_225_286);
 		assert_eq!(Currency::free_balance(share_token, &user_2), 299_999_990_000);
 
-		assert_ok!(XYK::remove_liquidity(Origin::signed(user_2), asset_b, asset_a, 10_000));
+		assert_ok!(XYK::remove_liquidity(
+			Origin::signed(user_2),
+			asset_b,
+			asset_a,
+			10_000,
+			0,
+			0,
+			None
+		));
 
 		let user_2_remove_2_balance_1 = Currency::free_balance(asset_a, &user_2);
 		let user_2_remove_2_balance_2 = Currency::free_balance(asset_b, &user_2);
@@ -771,7 +867,15 @@ fn work_flow_happy_path_should_work() {
 
 		assert_eq!(XYK::total_liquidity(&pair_account), 649_999_980_000);
 
-		assert_ok!(XYK::remove_liquidity(Origin::signed(user_2), asset_a, asset_b, 18_000));
+		assert_ok!(XYK::remove_liquidity(
+			Origin::signed(user_2),
+			asset_a,
+			asset_b,
+			18_000,
+			0,
+			0,
+			None
+		));
 		assert_eq!(Currency::free_balance(share_token, &user_2), 299_999_962_000);
 
 		assert_eq!(XYK::total_liquidity(&pair_account), 649_999_962_000);
@@ -794,6 +898,7 @@ fn work_flow_happy_path_should_work() {
 				asset_b,
 				12_999_999_998,
 				pair_account,
+				Permill::from_parts(249_999),
 			)
 			.into(),
 			Event::SellExecuted(
@@ -805,6 +910,7 @@ fn work_flow_happy_path_should_work() {
 				asset_b,
 				9_756_499_998,
 				pair_account,
+				Permill::from_parts(249_999),
 			)
 			.into(),
 			Event::LiquidityRemoved(user_2, asset_a, asset_b, 10_000).into(),
@@ -845,7 +951,8 @@ fn sell_with_correct_fees_should_work() {
 			asset_a,
 			asset_b,
 			10_000_000,
-			Price::from(200)
+			Price::from(200),
+			None
 		));
 
 		let pair_account = XYK::get_pair_id(AssetPair {
@@ -860,7 +967,7 @@ fn sell_with_correct_fees_should_work() {
 		assert_eq!(Currency::free_balance(asset_a, &pair_account), 10000000);
 		assert_eq!(Currency::free_balance(asset_b, &pair_account), 2000000000);
 
-		assert_eq!(Currency::free_balance(share_token, &user_1), 2000000000);
+		assert_eq!(Currency::free_balance(share_token, &user_1), 1999999000);
 
 		assert_ok!(XYK::sell(
 			Origin::signed(user_1),
@@ -869,6 +976,8 @@ fn sell_with_correct_fees_should_work() {
 			100_000,
 			1_000_000,
 			false,
+			None,
+			None,
 		));
 
 		assert_eq!(Currency::free_balance(asset_a, &pair_account), 10100000);
@@ -887,6 +996,7 @@ fn sell_with_correct_fees_should_work() {
 				asset_b,
 				39_602,
 				pair_account,
+				Permill::from_parts(9_901),
 			)
 			.into(),
 		]);
@@ -915,14 +1025,16 @@ fn discount_sell_fees_should_work() {
 			asset_a,
 			HDX,
 			5_000,
-			Price::from(2)
+			Price::from(2),
+			None
 		));
 		assert_ok!(XYK::create_pool(
 			Origin::signed(user_1),
 			asset_a,
 			asset_b,
 			30_000,
-			Price::from(2)
+			Price::from(2),
+			None
 		));
 
 		let pair_account = XYK::get_pair_id(AssetPair {
@@ -943,7 +1055,16 @@ fn discount_sell_fees_should_work() {
 		assert_eq!(Currency::free_balance(asset_b, &user_1), 940_000);
 		assert_eq!(Currency::free_balance(HDX, &user_1), 990_000);
 
-		assert_ok!(XYK::sell(Origin::signed(user_1), asset_a, asset_b, 10_000, 1_500, true,));
+		assert_ok!(XYK::sell(
+			Origin::signed(user_1),
+			asset_a,
+			asset_b,
+			10_000,
+			1_500,
+			true,
+			None,
+			None
+		));
 
 		assert_eq!(Currency::free_balance(asset_a, &pair_account), 40_000);
 		assert_eq!(Currency::free_balance(asset_b, &pair_account), 45_007);
@@ -984,7 +1105,18 @@ fn discount_sell_fees_should_work() {
 			}
 			.into(),
 			Event::PoolCreated(user_1, asset_a, asset_b, 60_000, share_token, pair_account).into(),
-			Event::SellExecuted(user_1, asset_a, asset_b, 10_000, 14_993, asset_b, 7, pair_account).into(),
+			Event::SellExecuted(
+				user_1,
+				asset_a,
+				asset_b,
+				10_000,
+				14_993,
+				asset_b,
+				7,
+				pair_account,
+				Permill::from_parts(250_000),
+			)
+			.into(),
 		]);
 	});
 }
@@ -1001,13 +1133,14 @@ fn sell_without_sufficient_balance_should_not_work() {
 			asset_a,
 			asset_b,
 			1_000_000_000,
-			Price::from(1)
+			Price::from(1),
+			None
 		));
 
 		assert_ok!(Currency::transfer(Origin::signed(user), BOB, ACA, 999_998_999_999_999));
 
 		assert_noop!(
-			XYK::sell(Origin::signed(user), ACA, DOT, 1_000, 100, false),
+			XYK::sell(Origin::signed(user), ACA, DOT, 1_000, 100, false, None, None),
 			Error::<Test>::InsufficientAssetBalance
 		);
 	});
@@ -1025,7 +1158,8 @@ fn sell_without_sufficient_discount_balance_should_not_work() {
 			asset_a,
 			asset_b,
 			1_000_000_000_000,
-			Price::from(1)
+			Price::from(1),
+			None
 		));
 
 		assert_ok!(XYK::create_pool(
@@ -1033,13 +1167,14 @@ fn sell_without_sufficient_discount_balance_should_not_work() {
 			asset_a,
 			HDX,
 			1_000_000_000_000,
-			Price::from(1)
+			Price::from(1),
+			None
 		));
 
 		assert_ok!(Currency::transfer(Origin::signed(user), BOB, HDX, 998_999_999_999_999));
 
 		assert_noop!(
-			XYK::sell(Origin::signed(user), ACA, DOT, 1_000_000_000, 100, true),
+			XYK::sell(Origin::signed(user), ACA, DOT, 1_000_000_000, 100, true, None, None),
 			Error::<Test>::InsufficientNativeCurrencyBalance
 		);
 	});
@@ -1057,13 +1192,14 @@ fn buy_without_sufficient_balance_should_not_work() {
 			asset_a,
 			asset_b,
 			1_000_000_000,
-			Price::from(1)
+			Price::from(1),
+			None
 		));
 
 		assert_ok!(Currency::transfer(Origin::signed(user), BOB, ACA, 999_998_999_999_999));
 
 		assert_noop!(
-			XYK::buy(Origin::signed(user), DOT, ACA, 1_000, 10_000, false),
+			XYK::buy(Origin::signed(user), DOT, ACA, 1_000, 10_000, false, None, None),
 			Error::<Test>::InsufficientAssetBalance
 		);
 	});
@@ -1081,7 +1217,8 @@ fn buy_without_sufficient_discount_balance_should_not_work() {
 			asset_a,
 			asset_b,
 			1_000_000_000_000,
-			Price::from(1)
+			Price::from(1),
+			None
 		));
 
 		assert_ok!(XYK::create_pool(
@@ -1089,13 +1226,23 @@ fn buy_without_sufficient_discount_balance_should_not_work() {
 			asset_b,
 			HDX,
 			1_000_000_000_000,
-			Price::from(1)
+			Price::from(1),
+			None
 		));
 
 		assert_ok!(Currency::transfer(Origin::signed(user), BOB, HDX, 998_999_999_999_999));
 
 		assert_noop!(
-			XYK::buy(Origin::signed(user), DOT, ACA, 1_000_000_000, 10_000_000_000, true),
+			XYK::buy(
+				Origin::signed(user),
+				DOT,
+				ACA,
+				1_000_000_000,
+				10_000_000_000,
+				true,
+				None,
+				None
+			),
 			Error::<Test>::InsufficientNativeCurrencyBalance
 		);
 	});
@@ -1113,7 +1260,8 @@ fn single_buy_should_work() {
 			asset_a,
 			asset_b,
 			200_000_000,
-			Price::from(3200)
+			Price::from(3200),
+			None
 		));
 
 		let pair_account = XYK::get_pair_id(AssetPair {
@@ -1124,7 +1272,7 @@ fn single_buy_should_work() {
 
 		assert_eq!(Currency::free_balance(asset_a, &user_1), 999_999_800_000_000);
 		assert_eq!(Currency::free_balance(asset_b, &user_1), 999_360_000_000_000);
-		assert_eq!(Currency::free_balance(share_token, &user_1), 640_000_000_000);
+		assert_eq!(Currency::free_balance(share_token, &user_1), 639_999_999_000);
 
 		assert_eq!(Currency::free_balance(asset_a, &pair_account), 200_000_000);
 		assert_eq!(Currency::free_balance(asset_b, &pair_account), 640_000_000_000);
@@ -1136,11 +1284,13 @@ fn single_buy_should_work() {
 			66_666_666,
 			1_000_000_000_000,
 			false,
+			None,
+			None,
 		));
 
 		assert_eq!(Currency::free_balance(asset_a, &user_1), 999_999_866_666_666);
 		assert_eq!(Currency::free_balance(asset_b, &user_1), 999_039_360_004_809);
-		assert_eq!(Currency::free_balance(share_token, &user_1), 640_000_000_000);
+		assert_eq!(Currency::free_balance(share_token, &user_1), 639_999_999_000);
 		assert_eq!(Currency::free_balance(asset_a, &pair_account), 133_333_334);
 		assert_eq!(Currency::free_balance(asset_b, &pair_account), 960_639_995_191);
 
@@ -1155,6 +1305,7 @@ fn single_buy_should_work() {
 				asset_b,
 				639_999_990,
 				pair_account,
+				Permill::from_parts(333_333),
 			)
 			.into(),
 		]);
@@ -1173,7 +1324,8 @@ fn single_buy_with_discount_should_work() {
 			asset_a,
 			asset_b,
 			200_000_000,
-			Price::from(3200)
+			Price::from(3200),
+			None
 		));
 
 		assert_ok!(XYK::create_pool(
@@ -1181,7 +1333,8 @@ fn single_buy_with_discount_should_work() {
 			asset_a,
 			HDX,
 			50_000_000_000,
-			Price::from(2)
+			Price::from(2),
+			None
 		));
 
 		let native_pair_account = XYK::get_pair_id(AssetPair {
@@ -1201,7 +1354,7 @@ fn single_buy_with_discount_should_work() {
 
 		assert_eq!(Currency::free_balance(HDX, &user_1), 999_900_000_000_000);
 
-		assert_eq!(Currency::free_balance(share_token, &user_1), 640_000_000_000);
+		assert_eq!(Currency::free_balance(share_token, &user_1), 639_999_999_000);
 
 		assert_eq!(Currency::free_balance(asset_a, &pair_account), 200_000_000);
 		assert_eq!(Currency::free_balance(asset_b, &pair_account), 640_000_000_000);
@@ -1213,12 +1366,14 @@ fn single_buy_with_discount_should_work() {
 			66_666_666,
 			1_000_000_000_000,
 			true,
+			None,
+			None,
 		));
 
 		assert_eq!(Currency::free_balance(asset_a, &user_1), 999_949_866_666_666);
 		assert_eq!(Currency::free_balance(asset_b, &user_1), 999_039_776_004_806); // compare to values in previous test to see difference!
 
-		assert_eq!(Currency::free_balance(share_token, &user_1), 640_000_000_000);
+		assert_eq!(Currency::free_balance(share_token, &user_1), 639_999_999_000);
 
 		assert_eq!(Currency::free_balance(asset_a, &pair_account), 133_333_334);
 		assert_eq!(Currency::free_balance(asset_b, &pair_account), 960_223_995_194);
@@ -1271,6 +1426,7 @@ fn single_buy_with_discount_should_work() {
 				asset_b,
 				223_999_993,
 				pair_account,
+				Permill::from_parts(333_333),
 			)
 			.into(),
 		]);
@@ -1281,17 +1437,17 @@ fn single_buy_with_discount_should_work() {
 fn create_pool_with_insufficient_liquidity_should_not_work() {
 	new_test_ext().execute_with(|| {
 		assert_noop!(
-			XYK::create_pool(Origin::signed(ALICE), ACA, HDX, 500, Price::from(3200)),
+			XYK::create_pool(Origin::signed(ALICE), ACA, HDX, 500, Price::from(3200), None),
 			Error::<Test>::InsufficientLiquidity
 		);
 
 		assert_noop!(
-			XYK::create_pool(Origin::signed(ALICE), ACA, HDX, 5000, Price::from_float(0.1f64)),
+			XYK::create_pool(Origin::signed(ALICE), ACA, HDX, 5000, Price::from_float(0.1f64), None),
 			Error::<Test>::InsufficientLiquidity
 		);
 
 		assert_noop!(
-			XYK::create_pool(Origin::signed(ALICE), ACA, HDX, 1000, Price::from(0)),
+			XYK::create_pool(Origin::signed(ALICE), ACA, HDX, 1000, Price::from(0), None),
 			Error::<Test>::ZeroInitialPrice
 		);
 	});
@@ -1301,7 +1457,15 @@ fn create_pool_with_insufficient_liquidity_should_not_work() {
 fn add_liquidity_to_non_existing_pool_should_not_work() {
 	new_test_ext().execute_with(|| {
 		assert_noop!(
-			XYK::add_liquidity(Origin::signed(ALICE), HDX, ACA, 200_000_000_000_000_000, 600_000_000),
+			XYK::add_liquidity(
+				Origin::signed(ALICE),
+				HDX,
+				ACA,
+				200_000_000_000_000_000,
+				600_000_000,
+				0,
+				None
+			),
 			Error::<Test>::TokenPoolNotFound
 		);
 	});
@@ -1311,7 +1475,7 @@ fn add_liquidity_to_non_existing_pool_should_not_work() {
 fn remove_zero_liquidity_from_non_existing_pool_should_not_work() {
 	new_test_ext().execute_with(|| {
 		assert_noop!(
-			XYK::remove_liquidity(Origin::signed(ALICE), HDX, ACA, 100),
+			XYK::remove_liquidity(Origin::signed(ALICE), HDX, ACA, 100, 0, 0, None),
 			Error::<Test>::TokenPoolNotFound
 		);
 	});
@@ -1321,7 +1485,16 @@ fn remove_zero_liquidity_from_non_existing_pool_should_not_work() {
 fn sell_with_non_existing_pool_should_not_work() {
 	new_test_ext().execute_with(|| {
 		assert_noop!(
-			XYK::sell(Origin::signed(ALICE), HDX, DOT, 456_444_678, 1_000_000, false),
+			XYK::sell(
+				Origin::signed(ALICE),
+				HDX,
+				DOT,
+				456_444_678,
+				1_000_000,
+				false,
+				None,
+				None
+			),
 			Error::<Test>::TokenPoolNotFound
 		);
 	});
@@ -1335,11 +1508,21 @@ fn discount_sell_with_no_native_pool_should_not_work() {
 			ACA,
 			DOT,
 			1000,
-			Price::from(3200)
+			Price::from(3200),
+			None
 		));
 
 		assert_noop!(
-			XYK::sell(Origin::signed(ALICE), ACA, DOT, 456_444_678, 1_000_000, true),
+			XYK::sell(
+				Origin::signed(ALICE),
+				ACA,
+				DOT,
+				456_444_678,
+				1_000_000,
+				true,
+				None,
+				None
+			),
 			Error::<Test>::CannotApplyDiscount
 		);
 	});
@@ -1349,7 +1532,16 @@ fn discount_sell_with_no_native_pool_should_not_work() {
 fn buy_with_non_existing_pool_should_not_work() {
 	new_test_ext().execute_with(|| {
 		assert_noop!(
-			XYK::buy(Origin::signed(ALICE), HDX, DOT, 456_444_678, 1_000_000_000, false),
+			XYK::buy(
+				Origin::signed(ALICE),
+				HDX,
+				DOT,
+				456_444_678,
+				1_000_000_000,
+				false,
+				None,
+				None
+			),
 			Error::<Test>::TokenPoolNotFound
 		);
 	});
@@ -1363,11 +1555,12 @@ fn discount_buy_with_no_native_pool_should_not_work() {
 			ACA,
 			DOT,
 			10000,
-			Price::from(3200)
+			Price::from(3200),
+			None
 		));
 
 		assert_noop!(
-			XYK::buy(Origin::signed(ALICE), ACA, DOT, 1000, 1_000_000_000, true),
+			XYK::buy(Origin::signed(ALICE), ACA, DOT, 1000, 1_000_000_000, true, None, None),
 			Error::<Test>::CannotApplyDiscount
 		);
 	});
@@ -1384,7 +1577,8 @@ fn create_pool_small_fixed_point_amount_should_work() {
 			asset_a,
 			asset_b,
 			100_000_000_000_000,
-			Price::from_float(0.00001)
+			Price::from_float(0.00001),
+			None
 		));
 
 		let pair_account = XYK::get_pair_id(AssetPair {
@@ -1397,7 +1591,7 @@ fn create_pool_small_fixed_point_amount_should_work() {
 		assert_eq!(Currency::free_balance(asset_b, &pair_account), 1000000000);
 		assert_eq!(Currency::free_balance(asset_a, &ALICE), 900000000000000);
 		assert_eq!(Currency::free_balance(asset_b, &ALICE), 999999000000000);
-		assert_eq!(Currency::free_balance(share_token, &ALICE), 100000000000000);
+		assert_eq!(Currency::free_balance(share_token, &ALICE), 99999999999000);
 		assert_eq!(XYK::total_liquidity(&pair_account), 100000000000000);
 
 		expect_events(vec![Event::PoolCreated(
@@ -1422,7 +1616,8 @@ fn create_pool_fixed_point_amount_should_work() {
 			asset_a,
 			asset_b,
 			100_000_000_000,
-			Price::from_float(4560.234543)
+			Price::from_float(4560.234543),
+			None
 		));
 
 		let pair_account = XYK::get_pair_id(AssetPair {
@@ -1435,7 +1630,7 @@ fn create_pool_fixed_point_amount_should_work() {
 		assert_eq!(Currency::free_balance(asset_b, &pair_account), 456023454299999);
 		assert_eq!(Currency::free_balance(asset_a, &ALICE), 999900000000000);
 		assert_eq!(Currency::free_balance(asset_b, &ALICE), 543976545700001);
-		assert_eq!(Currency::free_balance(share_token, &ALICE), 100000000000);
+		assert_eq!(Currency::free_balance(share_token, &ALICE), 99999999000);
 		assert_eq!(XYK::total_liquidity(&pair_account), 100000000000);
 
 		expect_events(vec![Event::PoolCreated(
@@ -1465,7 +1660,8 @@ fn money_in_sell_money_out_should_leave_the_same_balance() {
 			asset_a,
 			asset_b,
 			200_000_000_000,
-			Price::from(3000)
+			Price::from(3000),
+			None
 		));
 
 		let pair_account = XYK::get_pair_id(AssetPair {
@@ -1476,7 +1672,7 @@ fn money_in_sell_money_out_should_leave_the_same_balance() {
 
 		assert_eq!(Currency::free_balance(asset_a, &user_1), 999800000000000);
 		assert_eq!(Currency::free_balance(asset_b, &user_1), 400000000000000);
-		assert_eq!(Currency::free_balance(share_token, &user_1), 600000000000000);
+		assert_eq!(Currency::free_balance(share_token, &user_1), 599999999999000);
 
 		assert_eq!(Currency::free_balance(asset_a, &pair_account), 200000000000);
 		assert_eq!(Currency::free_balance(asset_b, &pair_account), 600000000000000);
@@ -1488,11 +1684,13 @@ fn money_in_sell_money_out_should_leave_the_same_balance() {
 			456_444_678,
 			1000000000000,
 			false,
+			None,
+			None,
 		));
 
 		assert_eq!(Currency::free_balance(asset_a, &user_1), 999799543555322);
 		assert_eq!(Currency::free_balance(asset_b, &user_1), 401363483591788);
-		assert_eq!(Currency::free_balance(share_token, &user_1), 600000000000000);
+		assert_eq!(Currency::free_balance(share_token, &user_1), 599999999999000);
 		assert_eq!(Currency::free_balance(asset_a, &pair_account), 200456444678);
 		assert_eq!(Currency::free_balance(asset_b, &pair_account), 598636516408212);
 
@@ -1500,14 +1698,18 @@ fn money_in_sell_money_out_should_leave_the_same_balance() {
 			Origin::signed(user_1),
 			asset_a,
 			asset_b,
-			600000000000000
+			599999999999000,
+			0,
+			0,
+			None
 		));
 
 		let user_1_balance_a_after = Currency::free_balance(asset_a, &user_1);
 		let user_1_balance_b_after = Currency::free_balance(asset_b, &user_1);
 
-		assert_eq!(user_1_balance_a_before, user_1_balance_a_after);
-		assert_eq!(user_1_balance_b_before, user_1_balance_b_after);
+		// pool creator pays for the locked liquidity
+		assert_eq!(user_1_balance_a_before - 1, user_1_balance_a_after);
+		assert_eq!(user_1_balance_b_before - 998, user_1_balance_b_after);
 	});
 }
 
@@ -1529,7 +1731,8 @@ fn money_in_money_out_should_leave_the_same_balance_for_both_accounts() {
 			asset_a,
 			asset_b,
 			100_000_000,
-			Price::from(10_000)
+			Price::from(10_000),
+			None
 		));
 
 		let asset_pair = AssetPair {
@@ -1547,39 +1750,55 @@ fn money_in_money_out_should_leave_the_same_balance_for_both_accounts() {
 			asset_a,
 			asset_b,
 			100_000_000,
-			1_000_000_000_000
+			1_000_000_000_000,
+			0,
+			None
 		));
 
-		assert_eq!(Currency::free_balance(share_token, &user_1), 100_000_000);
+		assert_eq!(Currency::free_balance(share_token, &user_1), 99_999_000);
 		assert_eq!(Currency::free_balance(share_token, &user_2), 100_000_000);
 
 		assert_ok!(XYK::remove_liquidity(
 			Origin::signed(user_1),
 			asset_a,
 			asset_b,
-			100_000_000
+			99_999_000,
+			0,
+			0,
+			None
 		));
 
 		assert_ok!(XYK::remove_liquidity(
 			Origin::signed(user_2),
 			asset_a,
 			asset_b,
-			100_000_000
+			100_000_000,
+			0,
+			0,
+			None
 		));
 
-		assert_eq!(XYK::total_liquidity(&pair_account), 0);
+		// locked liquidity and the reserves it holds stay in the pool
+		assert_eq!(XYK::total_liquidity(&pair_account), 1_000);
+		assert_eq!(
+			Currency::free_balance(share_token, &XYK::locked_liquidity_account()),
+			1_000
+		);
+		assert_eq!(Currency::free_balance(asset_a, &pair_account), 1_000);
+		assert_eq!(Currency::free_balance(asset_b, &pair_account), 10_000_000);
 
 		let user_1_balance_a_after = Currency::free_balance(asset_a, &user_1);
 		let user_1_balance_b_after = Currency::free_balance(asset_b, &user_1);
 		let user_2_balance_a_after = Currency::free_balance(asset_a, &user_2);
 		let user_2_balance_b_after = Currency::free_balance(asset_b, &user_2);
 
-		assert_eq!(user_1_balance_a_before, user_1_balance_a_after);
-		assert_eq!(user_1_balance_b_before, user_1_balance_b_after);
+		// pool creator pays for the locked liquidity
+		assert_eq!(user_1_balance_a_before - 1_000, user_1_balance_a_after);
+		assert_eq!(user_1_balance_b_before - 10_000_000, user_1_balance_b_after);
 		assert_eq!(user_2_balance_a_before, user_2_balance_a_after);
 		assert_eq!(user_2_balance_b_before, user_2_balance_b_after);
 
-		assert!(!XYK::exists(asset_pair));
+		assert!(XYK::exists(asset_pair));
 	});
 }
 
@@ -1595,7 +1814,8 @@ fn destroy_pool_on_remove_liquidity_and_recreate_should_work() {
 			asset_a,
 			asset_b,
 			100_000_000,
-			Price::from(10_000)
+			Price::from(10_000),
+			None
 		));
 
 		let asset_pair = AssetPair {
@@ -1612,10 +1832,16 @@ fn destroy_pool_on_remove_liquidity_and_recreate_should_work() {
 			Origin::signed(user),
 			asset_a,
 			asset_b,
-			100_000_000
+			99_999_000,
+			0,
+			0,
+			None
 		));
 
 		assert_eq!(XYK::total_liquidity(&pair_account), 0);
+		assert_eq!(Currency::free_balance(share_token, &XYK::locked_liquidity_account()), 0);
+		assert_eq!(Currency::free_balance(asset_a, &pair_account), 0);
+		assert_eq!(Currency::free_balance(asset_b, &pair_account), 0);
 
 		assert!(!XYK::exists(asset_pair));
 
@@ -1626,13 +1852,14 @@ fn destroy_pool_on_remove_liquidity_and_recreate_should_work() {
 			asset_a,
 			asset_b,
 			100_000_000,
-			Price::from(10_000)
+			Price::from(10_000),
+			None
 		));
 
 		expect_events(vec![
 			Event::PoolCreated(user, asset_a, asset_b, 100_000_000, share_token, pair_account).into(),
 			frame_system::Event::KilledAccount { account: pair_account }.into(),
-			Event::LiquidityRemoved(user, asset_a, asset_b, 100_000_000).into(),
+			Event::LiquidityRemoved(user, asset_a, asset_b, 99_999_000).into(),
 			Event::PoolDestroyed(user, asset_a, asset_b, share_token, pair_account).into(),
 			frame_system::Event::NewAccount { account: pair_account }.into(),
 			orml_tokens::Event::Endowed {
@@ -1650,7 +1877,13 @@ fn destroy_pool_on_remove_liquidity_and_recreate_should_work() {
 			orml_tokens::Event::Endowed {
 				currency_id: 0,
 				who: 1,
-				amount: 100000000,
+				amount: 99_999_000,
+			}
+			.into(),
+			orml_tokens::Event::Endowed {
+				currency_id: 0,
+				who: 0,
+				amount: 1_000,
 			}
 			.into(),
 			Event::PoolCreated(user, asset_a, asset_b, 100_000_000, share_token, pair_account).into(),
@@ -1665,7 +1898,14 @@ fn create_pool_with_same_assets_should_not_be_allowed() {
 		let asset_a = HDX;
 
 		assert_noop!(
-			XYK::create_pool(Origin::signed(user), asset_a, asset_a, 100_000_000, Price::from(10_000)),
+			XYK::create_pool(
+				Origin::signed(user),
+				asset_a,
+				asset_a,
+				100_000_000,
+				Price::from(10_000),
+				None
+			),
 			Error::<Test>::CannotCreatePoolWithSameAssets
 		);
 	})
@@ -1683,7 +1923,8 @@ fn sell_test_not_reaching_limit() {
 			asset_a,
 			asset_b,
 			200_000_000_000,
-			Price::from(3000)
+			Price::from(3000),
+			None
 		));
 
 		let pair_account = XYK::get_pair_id(AssetPair {
@@ -1694,7 +1935,7 @@ fn sell_test_not_reaching_limit() {
 
 		assert_eq!(Currency::free_balance(asset_a, &user_1), 999800000000000);
 		assert_eq!(Currency::free_balance(asset_b, &user_1), 400000000000000);
-		assert_eq!(Currency::free_balance(share_token, &user_1), 600000000000000);
+		assert_eq!(Currency::free_balance(share_token, &user_1), 599999999999000);
 
 		assert_eq!(Currency::free_balance(asset_a, &pair_account), 200000000000);
 		assert_eq!(Currency::free_balance(asset_b, &pair_account), 600000000000000);
@@ -1707,6 +1948,8 @@ fn sell_test_not_reaching_limit() {
 				456_444_678,
 				1_000_000_000_000_000,
 				false,
+				None,
+				None,
 			),
 			Error::<Test>::AssetAmountNotReachedLimit
 		);
@@ -1731,7 +1974,8 @@ fn buy_test_exceeding_max_limit() {
 			asset_a,
 			asset_b,
 			200_000_000_000,
-			Price::from(3000)
+			Price::from(3000),
+			None
 		));
 
 		let pair_account = XYK::get_pair_id(AssetPair {
@@ -1742,7 +1986,7 @@ fn buy_test_exceeding_max_limit() {
 
 		assert_eq!(Currency::free_balance(asset_a, &user_1), 999800000000000);
 		assert_eq!(Currency::free_balance(asset_b, &user_1), 400000000000000);
-		assert_eq!(Currency::free_balance(share_token, &user_1), 600000000000000);
+		assert_eq!(Currency::free_balance(share_token, &user_1), 599999999999000);
 
 		assert_eq!(Currency::free_balance(asset_a, &pair_account), 200000000000);
 		assert_eq!(Currency::free_balance(asset_b, &pair_account), 600000000000000);
@@ -1755,6 +1999,8 @@ fn buy_test_exceeding_max_limit() {
 				456_444_678,
 				1_000_000_000,
 				false,
+				None,
+				None,
 			),
 			Error::<Test>::AssetAmountExceededLimit
 		);
@@ -1779,7 +2025,8 @@ fn single_buy_more_than_ratio_out_should_not_work() {
 			asset_a,
 			asset_b,
 			200_000_000,
-			Price::from(3200)
+			Price::from(3200),
+			None
 		));
 
 		let pair_account = XYK::get_pair_id(AssetPair {
@@ -1790,7 +2037,7 @@ fn single_buy_more_than_ratio_out_should_not_work() {
 
 		assert_eq!(Currency::free_balance(asset_a, &user_1), 999_999_800_000_000);
 		assert_eq!(Currency::free_balance(asset_b, &user_1), 999_360_000_000_000);
-		assert_eq!(Currency::free_balance(share_token, &user_1), 640_000_000_000);
+		assert_eq!(Currency::free_balance(share_token, &user_1), 639_999_999_000);
 
 		assert_eq!(Currency::free_balance(asset_a, &pair_account), 200_000_000);
 		assert_eq!(Currency::free_balance(asset_b, &pair_account), 640_000_000_000);
@@ -1803,6 +2050,8 @@ fn single_buy_more_than_ratio_out_should_not_work() {
 				66_666_667,
 				1_000_000_000_000,
 				false,
+				None,
+				None,
 			),
 			Error::<Test>::MaxOutRatioExceeded
 		);
@@ -1821,7 +2070,8 @@ fn single_sell_more_than_ratio_in_should_not_work() {
 			asset_a,
 			asset_b,
 			200_000_000_000,
-			Price::from(3000)
+			Price::from(3000),
+			None
 		));
 
 		let pair_account = XYK::get_pair_id(AssetPair {
@@ -1832,7 +2082,7 @@ fn single_sell_more_than_ratio_in_should_not_work() {
 
 		assert_eq!(Currency::free_balance(asset_a, &user_1), 999_800_000_000_000);
 		assert_eq!(Currency::free_balance(asset_b, &user_1), 400_000_000_000_000);
-		assert_eq!(Currency::free_balance(share_token, &user_1), 600_000_000_000_000);
+		assert_eq!(Currency::free_balance(share_token, &user_1), 599_999_999_999_000);
 
 		assert_eq!(Currency::free_balance(asset_a, &pair_account), 200_000_000_000);
 		assert_eq!(Currency::free_balance(asset_b, &pair_account), 600_000_000_000_000);
@@ -1845,12 +2095,236 @@ fn single_sell_more_than_ratio_in_should_not_work() {
 				66_666_666_667,
 				10_000_000,
 				false,
+				None,
+				None,
 			),
 			Error::<Test>::MaxInRatioExceeded
 		);
 	});
 }
 
+#[test]
+fn sell_should_respect_trading_limits_of_pool() {
+	let pool_limits = TradingLimits {
+		min_trading_limit: 1_000_000,
+		min_pool_liquidity: 1_000,
+		max_in_ratio: 2,
+		max_out_ratio: 2,
+	};
+
+	ExtBuilder::default()
+		.with_pool_trading_limits(HDX_DOT_POOL_ID, pool_limits)
+		.build()
+		.execute_with(|| {
+			assert_ok!(XYK::create_pool(
+				Origin::signed(ALICE),
+				HDX,
+				DOT,
+				100_000_000_000,
+				Price::from(2),
+				None
+			));
+			assert_ok!(XYK::create_pool(
+				Origin::signed(ALICE),
+				HDX,
+				ACA,
+				100_000_000_000,
+				Price::from(2),
+				None
+			));
+
+			assert_noop!(
+				XYK::sell(Origin::signed(BOB), HDX, DOT, 999_999, 0, false, None, None),
+				Error::<Test>::InsufficientTradingAmount
+			);
+			assert_ok!(XYK::sell(Origin::signed(BOB), HDX, ACA, 999_999, 0, false, None, None));
+
+			// default max in ratio is 3
+			assert_noop!(
+				XYK::sell(Origin::signed(BOB), HDX, ACA, 40_000_000_000, 0, false, None, None),
+				Error::<Test>::MaxInRatioExceeded
+			);
+			assert_ok!(XYK::sell(
+				Origin::signed(BOB),
+				HDX,
+				DOT,
+				40_000_000_000,
+				0,
+				false,
+				None,
+				None
+			));
+
+			assert_eq!(
+				XYK::get_pool_info(HDX_DOT_POOL_ID).map(|pool| pool.trading_limits),
+				Some(pool_limits)
+			);
+			assert_eq!(
+				XYK::get_pool_info(XYK::pair_account_from_assets(HDX, ACA))
+					.map(|pool| pool.trading_limits.max_in_ratio),
+				Some(MAX_IN_RATIO)
+			);
+
+			assert_eq!(XYK::get_min_trading_limit(), MIN_TRADING_LIMIT);
+			assert_eq!(XYK::get_max_in_ratio(), MAX_IN_RATIO);
+		});
+}
+
+#[test]
+fn operations_should_respect_pool_state() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			100_000_000_000,
+			Price::from(2),
+			None
+		));
+
+		set_pool_state(HDX_DOT_POOL_ID, PoolState::TradingPaused);
+
+		assert_noop!(
+			XYK::sell(Origin::signed(BOB), HDX, DOT, 1_000_000_000, 0, false, None, None),
+			Error::<Test>::TradingNotAllowed
+		);
+		assert_noop!(
+			XYK::buy(
+				Origin::signed(BOB),
+				DOT,
+				HDX,
+				1_000_000_000,
+				1_000_000_000,
+				false,
+				None,
+				None
+			),
+			Error::<Test>::TradingNotAllowed
+		);
+		assert_noop!(
+			XYK::flash_swap(Origin::signed(BOB), DOT, HDX, 1_000_000_000, vec![]),
+			Error::<Test>::TradingNotAllowed
+		);
+		assert_ok!(XYK::add_liquidity(
+			Origin::signed(BOB),
+			HDX,
+			DOT,
+			1_000_000_000,
+			2_000_000_001,
+			0,
+			None
+		));
+
+		set_pool_state(HDX_DOT_POOL_ID, PoolState::LiquidityOnly);
+
+		assert_noop!(
+			XYK::add_liquidity(Origin::signed(BOB), HDX, DOT, 1_000_000_000, 2_000_000_001, 0, None),
+			Error::<Test>::LiquidityChangeNotAllowed
+		);
+		assert_ok!(XYK::remove_liquidity(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000,
+			0,
+			0,
+			None
+		));
+
+		set_pool_state(HDX_DOT_POOL_ID, PoolState::Frozen);
+
+		assert_noop!(
+			XYK::remove_liquidity(Origin::signed(ALICE), HDX, DOT, 1_000_000_000, 0, 0, None),
+			Error::<Test>::LiquidityChangeNotAllowed
+		);
+
+		set_pool_state(HDX_DOT_POOL_ID, PoolState::Active);
+
+		assert_ok!(XYK::sell(
+			Origin::signed(BOB),
+			HDX,
+			DOT,
+			1_000_000_000,
+			0,
+			false,
+			None,
+			None
+		));
+	});
+}
+
+#[test]
+fn sell_should_report_reserve_change_to_circuit_breaker() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			100_000_000_000,
+			Price::from(2),
+			None
+		));
+
+		assert_ok!(XYK::sell(
+			Origin::signed(BOB),
+			HDX,
+			DOT,
+			1_000_000_000,
+			0,
+			false,
+			None,
+			None
+		));
+
+		assert_eq!(
+			reserve_changes(),
+			vec![(
+				HDX_DOT_POOL_ID,
+				(100_000_000_000, 200_000_000_000),
+				(
+					Currency::free_balance(HDX, &HDX_DOT_POOL_ID),
+					Currency::free_balance(DOT, &HDX_DOT_POOL_ID)
+				)
+			)]
+		);
+	});
+}
+
+#[test]
+fn liquidity_changes_should_not_be_reported_to_circuit_breaker_as_trades() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			100_000_000_000,
+			Price::from(2),
+			None
+		));
+
+		assert_ok!(XYK::add_liquidity(
+			Origin::signed(BOB),
+			HDX,
+			DOT,
+			100_000_000_000,
+			200_000_000_001,
+			0,
+			None
+		));
+		assert_ok!(XYK::remove_liquidity(
+			Origin::signed(BOB),
+			HDX,
+			DOT,
+			100_000_000_000,
+			0,
+			0,
+			None
+		));
+
+		assert!(reserve_changes().is_empty());
+	});
+}
+
 #[test]
 fn test_calculate_out_given_in() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -1899,7 +2373,7 @@ fn test_calculate_in_given_out() {
 fn sell_with_low_amount_should_not_work() {
 	new_test_ext().execute_with(|| {
 		assert_noop!(
-			XYK::sell(Origin::signed(ALICE), HDX, DOT, 1, 1_000_000, false),
+			XYK::sell(Origin::signed(ALICE), HDX, DOT, 1, 1_000_000, false, None, None),
 			Error::<Test>::InsufficientTradingAmount
 		);
 	});
@@ -1909,7 +2383,7 @@ fn sell_with_low_amount_should_not_work() {
 fn buy_with_low_amount_should_not_work() {
 	new_test_ext().execute_with(|| {
 		assert_noop!(
-			XYK::buy(Origin::signed(ALICE), HDX, DOT, 1, 1_000_000, false),
+			XYK::buy(Origin::signed(ALICE), HDX, DOT, 1, 1_000_000, false, None, None),
 			Error::<Test>::InsufficientTradingAmount
 		);
 	});
@@ -1923,11 +2397,12 @@ fn buy_with_excesive_amount_should_not_work() {
 			HDX,
 			DOT,
 			10_000,
-			Price::from(1)
+			Price::from(1),
+			None
 		));
 
 		assert_noop!(
-			XYK::buy(Origin::signed(ALICE), HDX, DOT, 20_000, 1_000_000, false),
+			XYK::buy(Origin::signed(ALICE), HDX, DOT, 20_000, 1_000_000, false, None, None),
 			Error::<Test>::InsufficientPoolAssetBalance
 		);
 	});
@@ -1977,7 +2452,8 @@ fn can_create_pool_should_work() {
 				asset_a,
 				asset_b,
 				100_000_000_000_000,
-				Price::from(10)
+				Price::from(10),
+				None
 			),
 			Error::<Test>::CannotCreatePool
 		);
@@ -1992,7 +2468,8 @@ fn get_fee_should_work() {
 			HDX,
 			DOT,
 			1_000_000_000,
-			Price::from(2)
+			Price::from(2),
+			None
 		),);
 
 		// existing pool
@@ -2003,3 +2480,2509 @@ fn get_fee_should_work() {
 		assert_eq!(fee, (2, 1_000));
 	});
 }
+
+#[test]
+fn create_pool_with_fee_tier_should_work() {
+	new_test_ext().execute_with(|| {
+		let asset_a = ACA;
+		let asset_b = DOT;
+
+		assert_ok!(XYK::set_fee_tiers(Origin::root(), vec![(3, 1_000), (1, 100)]));
+
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			asset_a,
+			asset_b,
+			200_000_000_000,
+			Price::from(3000),
+			Some((3, 1_000))
+		));
+
+		let pair_account = XYK::get_pair_id(AssetPair {
+			asset_in: asset_a,
+			asset_out: asset_b,
+		});
+
+		assert_eq!(XYK::pool_fee(&pair_account), Some((3, 1_000)));
+		assert_eq!(XYK::get_fee(&pair_account), (3, 1_000));
+
+		assert_ok!(XYK::sell(
+			Origin::signed(ALICE),
+			asset_a,
+			asset_b,
+			456_444_678,
+			1000000000000,
+			false,
+			None,
+			None,
+		));
+
+		expect_events(vec![Event::SellExecuted(
+			ALICE,
+			asset_a,
+			asset_b,
+			456444678,
+			1362117375765,
+			asset_b,
+			4098648069,
+			pair_account,
+			Permill::from_parts(2_277),
+		)
+		.into()]);
+	});
+}
+
+#[test]
+fn create_pool_with_not_allowed_fee_tier_should_not_work() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			XYK::create_pool(
+				Origin::signed(ALICE),
+				HDX,
+				DOT,
+				1_000_000_000,
+				Price::from(2),
+				Some((3, 1_000))
+			),
+			Error::<Test>::FeeTierNotAllowed
+		);
+
+		// default fee is always allowed
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000,
+			Price::from(2),
+			Some((2, 1_000))
+		));
+	});
+}
+
+#[test]
+fn set_fee_tiers_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::set_fee_tiers(Origin::root(), vec![(3, 1_000), (1, 100)]));
+
+		assert_eq!(XYK::fee_tiers().to_vec(), vec![(3, 1_000), (1, 100)]);
+
+		expect_events(vec![Event::FeeTiersUpdated(vec![(3, 1_000), (1, 100)]).into()]);
+	});
+}
+
+#[test]
+fn set_fee_tiers_should_not_work_when_invalid() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			XYK::set_fee_tiers(Origin::signed(ALICE), vec![(3, 1_000)]),
+			sp_runtime::traits::BadOrigin
+		);
+
+		assert_noop!(
+			XYK::set_fee_tiers(Origin::root(), vec![(3, 1_000), (1, 0)]),
+			Error::<Test>::InvalidFee
+		);
+
+		assert_noop!(
+			XYK::set_fee_tiers(Origin::root(), vec![(1_000, 1_000)]),
+			Error::<Test>::InvalidFee
+		);
+
+		assert_noop!(
+			XYK::set_fee_tiers(Origin::root(), vec![(1, 1_000); 6]),
+			Error::<Test>::MaxFeeTiersExceeded
+		);
+	});
+}
+
+#[test]
+fn set_pool_fee_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000,
+			Price::from(2),
+			None
+		));
+
+		assert_eq!(XYK::get_fee(&HDX_DOT_POOL_ID), (2, 1_000));
+
+		assert_ok!(XYK::set_pool_fee(Origin::root(), HDX, DOT, (5, 1_000)));
+
+		assert_eq!(XYK::get_fee(&HDX_DOT_POOL_ID), (5, 1_000));
+
+		expect_events(vec![Event::PoolFeeUpdated(HDX, DOT, (5, 1_000), HDX_DOT_POOL_ID).into()]);
+	});
+}
+
+#[test]
+fn set_pool_fee_should_not_work_when_invalid() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			XYK::set_pool_fee(Origin::root(), HDX, DOT, (5, 1_000)),
+			Error::<Test>::TokenPoolNotFound
+		);
+
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000,
+			Price::from(2),
+			None
+		));
+
+		assert_noop!(
+			XYK::set_pool_fee(Origin::signed(ALICE), HDX, DOT, (5, 1_000)),
+			sp_runtime::traits::BadOrigin
+		);
+
+		assert_noop!(
+			XYK::set_pool_fee(Origin::root(), HDX, DOT, (5, 0)),
+			Error::<Test>::InvalidFee
+		);
+	});
+}
+
+#[test]
+fn pool_fee_should_be_removed_when_pool_is_destroyed() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::set_fee_tiers(Origin::root(), vec![(3, 1_000)]));
+
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			100_000_000,
+			Price::from(10_000),
+			Some((3, 1_000))
+		));
+
+		assert_ok!(XYK::remove_liquidity(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			99_999_000,
+			0,
+			0,
+			None
+		));
+
+		assert_eq!(XYK::pool_fee(&HDX_DOT_POOL_ID), None);
+		assert_eq!(XYK::get_fee(&HDX_DOT_POOL_ID), (2, 1_000));
+	});
+}
+
+#[test]
+fn calculate_sell_should_match_executed_sell() {
+	new_test_ext().execute_with(|| {
+		let user_1 = ALICE;
+		let asset_a = ACA;
+		let asset_b = DOT;
+
+		assert_ok!(XYK::create_pool(
+			Origin::signed(user_1),
+			asset_a,
+			asset_b,
+			200_000_000_000,
+			Price::from(3000),
+			None
+		));
+
+		let asset_pair = AssetPair {
+			asset_in: asset_a,
+			asset_out: asset_b,
+		};
+
+		assert_eq!(XYK::calculate_sell(asset_pair, 456_444_678), Ok(1363483591788));
+
+		assert_ok!(XYK::sell(
+			Origin::signed(user_1),
+			asset_a,
+			asset_b,
+			456_444_678,
+			1363483591788,
+			false,
+			None,
+			None,
+		));
+
+		assert_eq!(Currency::free_balance(asset_b, &user_1), 401363483591788);
+	});
+}
+
+#[test]
+fn calculate_buy_should_match_executed_buy() {
+	new_test_ext().execute_with(|| {
+		let user_1 = ALICE;
+		let asset_a = ACA;
+		let asset_b = DOT;
+
+		assert_ok!(XYK::create_pool(
+			Origin::signed(user_1),
+			asset_a,
+			asset_b,
+			200_000_000,
+			Price::from(3200),
+			None
+		));
+
+		let asset_pair = AssetPair {
+			asset_in: asset_b,
+			asset_out: asset_a,
+		};
+
+		assert_eq!(XYK::calculate_buy(asset_pair, 66_666_666), Ok(320_639_995_191));
+
+		assert_ok!(XYK::buy(
+			Origin::signed(user_1),
+			asset_a,
+			asset_b,
+			66_666_666,
+			320_639_995_191,
+			false,
+			None,
+			None,
+		));
+
+		assert_eq!(Currency::free_balance(asset_b, &user_1), 999_039_360_004_809);
+	});
+}
+
+#[test]
+fn calculate_trade_should_not_work_when_trade_is_invalid() {
+	new_test_ext().execute_with(|| {
+		let asset_pair = AssetPair {
+			asset_in: ACA,
+			asset_out: DOT,
+		};
+
+		assert_noop!(
+			XYK::calculate_sell(asset_pair, 1_000_000),
+			Error::<Test>::TokenPoolNotFound
+		);
+
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			ACA,
+			DOT,
+			200_000_000,
+			Price::from(3200),
+			None
+		));
+
+		assert_noop!(
+			XYK::calculate_sell(asset_pair, 100),
+			Error::<Test>::InsufficientTradingAmount
+		);
+		assert_noop!(
+			XYK::calculate_sell(asset_pair, 100_000_000),
+			Error::<Test>::MaxInRatioExceeded
+		);
+		assert_noop!(
+			XYK::calculate_buy(asset_pair, 400_000_000_000),
+			Error::<Test>::MaxOutRatioExceeded
+		);
+	});
+}
+
+#[test]
+fn quote_sell_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			ACA,
+			DOT,
+			200_000_000_000,
+			Price::from(3000),
+			None
+		));
+
+		assert_eq!(
+			XYK::quote_sell(ACA, DOT, 456_444_678),
+			Ok(TradeQuote {
+				amount: 1363483591788,
+				fee: 2732432046,
+				protocol_fee: 0,
+				spot_price_before: Price::from(3000),
+				spot_price_after: Price::from_inner(2986367025364648076879),
+				price_impact: Permill::from_parts(4544),
+			})
+		);
+
+		assert_ok!(XYK::sell(
+			Origin::signed(ALICE),
+			ACA,
+			DOT,
+			456_444_678,
+			1363483591788,
+			false,
+			None,
+			None,
+		));
+
+		assert_eq!(
+			XYK::quote_sell(DOT, ACA, 1_000_000).map(|quote| quote.spot_price_before),
+			Ok(Price::from_inner(334855023346601))
+		);
+	});
+}
+
+#[test]
+fn quote_buy_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			ACA,
+			DOT,
+			200_000_000_000,
+			Price::from(3000),
+			None
+		));
+
+		assert_eq!(
+			XYK::quote_buy(DOT, ACA, 1_000_000_000_000),
+			Ok(TradeQuote {
+				amount: 334557595,
+				fee: 667778,
+				protocol_fee: 0,
+				spot_price_before: Price::from(3000),
+				spot_price_after: Price::from_inner(2989998366686936452113),
+				price_impact: Permill::from_parts(3333),
+			})
+		);
+	});
+}
+
+#[test]
+fn trades_should_respect_max_price_impact() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			ACA,
+			DOT,
+			200_000_000_000,
+			Price::from(3000),
+			None
+		));
+
+		let pair_account = XYK::get_pair_id(AssetPair {
+			asset_in: ACA,
+			asset_out: DOT,
+		});
+
+		assert_eq!(
+			XYK::price_impact(
+				AssetPair {
+					asset_in: ACA,
+					asset_out: DOT
+				},
+				456_444_678,
+				1_366_216_023_834
+			),
+			Permill::from_parts(2_277)
+		);
+
+		assert_noop!(
+			XYK::sell(
+				Origin::signed(ALICE),
+				ACA,
+				DOT,
+				456_444_678,
+				0,
+				false,
+				None,
+				Some(Permill::from_parts(2_276)),
+			),
+			Error::<Test>::MaxPriceImpactExceeded
+		);
+		assert_noop!(
+			XYK::buy(
+				Origin::signed(ALICE),
+				DOT,
+				ACA,
+				1_000_000_000_000,
+				1_000_000_000,
+				false,
+				None,
+				Some(Permill::from_parts(1_665)),
+			),
+			Error::<Test>::MaxPriceImpactExceeded
+		);
+
+		assert_ok!(XYK::buy(
+			Origin::signed(ALICE),
+			DOT,
+			ACA,
+			1_000_000_000_000,
+			1_000_000_000,
+			false,
+			None,
+			Some(Permill::from_parts(1_666)),
+		));
+
+		expect_events(vec![Event::BuyExecuted(
+			ALICE,
+			DOT,
+			ACA,
+			1_000_000_000_000,
+			333_889_817,
+			ACA,
+			667_778,
+			pair_account,
+			Permill::from_parts(1_666),
+		)
+		.into()]);
+	});
+}
+
+#[test]
+fn quote_should_report_error_when_trade_would_fail() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(XYK::quote_sell(ACA, DOT, 1_000_000), Error::<Test>::TokenPoolNotFound);
+
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			ACA,
+			DOT,
+			200_000_000_000,
+			Price::from(3000),
+			None
+		));
+
+		assert_noop!(
+			XYK::quote_sell(ACA, DOT, 100_000_000_000),
+			Error::<Test>::MaxInRatioExceeded
+		);
+		assert_noop!(
+			XYK::quote_buy(DOT, ACA, 300_000_000_000_000),
+			Error::<Test>::MaxOutRatioExceeded
+		);
+
+		// Quote does not depend on balances of any account
+		assert_ok!(Currency::transfer(Origin::signed(ALICE), BOB, ACA, 999_800_000_000_000));
+		assert_ok!(XYK::quote_sell(ACA, DOT, 1_000_000));
+	});
+}
+
+#[test]
+fn get_pool_info_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			ACA,
+			100_000_000_000_000,
+			Price::from(10),
+			None
+		));
+
+		let pair_account = XYK::get_pair_id(AssetPair {
+			asset_in: HDX,
+			asset_out: ACA,
+		});
+
+		assert_ok!(XYK::sell(
+			Origin::signed(BOB),
+			HDX,
+			ACA,
+			1_000_000_000_000,
+			0,
+			false,
+			None,
+			None
+		));
+
+		assert_eq!(
+			XYK::get_pool_info(pair_account),
+			Some(PoolInfo {
+				pool_account: pair_account,
+				assets: (HDX, ACA),
+				reserves: (
+					Currency::free_balance(HDX, &pair_account),
+					Currency::free_balance(ACA, &pair_account)
+				),
+				share_token: XYK::share_token(pair_account),
+				total_liquidity: 100_000_000_000_000,
+				fee: (2, 1_000),
+				trading_limits: PoolTradingLimits::default_limits(),
+			})
+		);
+		assert_eq!(Currency::free_balance(HDX, &pair_account), 101_000_000_000_000);
+
+		assert_eq!(XYK::get_pool_info(HDX_DOT_POOL_ID), None);
+	});
+}
+
+#[test]
+fn get_pools_info_should_paginate() {
+	new_test_ext().execute_with(|| {
+		for (asset_a, asset_b) in [(HDX, ACA), (HDX, DOT), (ACA, DOT)] {
+			assert_ok!(XYK::create_pool(
+				Origin::signed(ALICE),
+				asset_a,
+				asset_b,
+				100_000_000_000,
+				Price::from(2),
+				None
+			));
+		}
+
+		let pools = XYK::get_pools_info(None, 10);
+		assert_eq!(pools.len(), 3);
+		assert!(pools.iter().any(|pool| pool.pool_account == HDX_DOT_POOL_ID));
+
+		assert_eq!(XYK::get_pools_info(None, 2), pools[..2].to_vec());
+		assert_eq!(XYK::get_pools_info(Some(pools[1].pool_account), 2), pools[2..].to_vec());
+		assert_eq!(XYK::get_pools_info(Some(pools[2].pool_account), 2), vec![]);
+		assert_eq!(XYK::get_pools_info(None, 0), vec![]);
+	});
+}
+
+#[test]
+fn add_liquidity_single_asset_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			ACA,
+			100_000_000_000_000,
+			Price::from(10),
+			None
+		));
+
+		let pair_account = XYK::get_pair_id(AssetPair {
+			asset_in: HDX,
+			asset_out: ACA,
+		});
+		let share_token = XYK::share_token(pair_account);
+
+		assert_noop!(
+			XYK::add_liquidity_single_asset(Origin::signed(BOB), HDX, ACA, 1_000_000_000_000, 500_742_042_006),
+			Error::<Test>::SharesAmountNotReachedLimit
+		);
+
+		assert_ok!(XYK::add_liquidity_single_asset(
+			Origin::signed(BOB),
+			HDX,
+			ACA,
+			1_000_000_000_000,
+			500_742_042_005
+		));
+
+		// Only rounding leftovers stay with the provider
+		assert_eq!(Currency::free_balance(HDX, &BOB), 999_000_000_000_001);
+		assert_eq!(Currency::free_balance(ACA, &BOB), 1_000_000_000_000_001);
+		assert_eq!(Currency::free_balance(share_token, &BOB), 500_742_042_005);
+
+		assert_eq!(Currency::free_balance(HDX, &pair_account), 100_999_999_999_999);
+		assert_eq!(Currency::free_balance(ACA, &pair_account), 999_999_999_999_999);
+		assert_eq!(XYK::total_liquidity(&pair_account), 100_500_742_042_005);
+
+		expect_events(vec![
+			Event::SellExecuted(
+				BOB,
+				HDX,
+				ACA,
+				499_257_957_994,
+				4_957_842_000_050,
+				ACA,
+				9_935_555_110,
+				pair_account,
+				Permill::from_parts(4_967),
+			)
+			.into(),
+			Event::LiquidityAdded(BOB, HDX, ACA, 500_742_042_005, 4_957_842_000_049).into(),
+		]);
+	});
+}
+
+#[test]
+fn add_liquidity_single_asset_should_not_work_when_pool_does_not_exist() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			XYK::add_liquidity_single_asset(Origin::signed(BOB), HDX, ACA, 1_000_000_000_000, 0),
+			Error::<Test>::TokenPoolNotFound
+		);
+	});
+}
+
+#[test]
+fn remove_liquidity_single_asset_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			ACA,
+			100_000_000_000_000,
+			Price::from(10),
+			None
+		));
+
+		let pair_account = XYK::get_pair_id(AssetPair {
+			asset_in: HDX,
+			asset_out: ACA,
+		});
+		let share_token = XYK::share_token(pair_account);
+
+		assert_noop!(
+			XYK::remove_liquidity_single_asset(Origin::signed(ALICE), HDX, ACA, 1_000_000_000_000, 1_988_020_000_001),
+			Error::<Test>::AssetAmountNotReachedLimit
+		);
+
+		assert_ok!(XYK::remove_liquidity_single_asset(
+			Origin::signed(ALICE),
+			HDX,
+			ACA,
+			1_000_000_000_000,
+			1_988_020_000_000
+		));
+
+		assert_eq!(Currency::free_balance(HDX, &ALICE), 901_988_020_000_000);
+		assert_eq!(Currency::free_balance(ACA, &ALICE), 0);
+		assert_eq!(Currency::free_balance(share_token, &ALICE), 98_999_999_999_000);
+
+		assert_eq!(Currency::free_balance(HDX, &pair_account), 98_011_980_000_000);
+		assert_eq!(Currency::free_balance(ACA, &pair_account), 1_000_000_000_000_000);
+		assert_eq!(XYK::total_liquidity(&pair_account), 99_000_000_000_000);
+
+		expect_events(vec![
+			Event::LiquidityRemoved(ALICE, HDX, ACA, 1_000_000_000_000).into(),
+			Event::SellExecuted(
+				ALICE,
+				ACA,
+				HDX,
+				10_000_000_000_000,
+				988_020_000_000,
+				HDX,
+				1_980_000_000,
+				pair_account,
+				Permill::from_parts(10_000),
+			)
+			.into(),
+		]);
+	});
+}
+
+#[test]
+fn remove_liquidity_single_asset_should_keep_asset_b_when_pool_is_destroyed() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			ACA,
+			100_000_000_000_000,
+			Price::from(10),
+			None
+		));
+
+		let pair_account = XYK::get_pair_id(AssetPair {
+			asset_in: HDX,
+			asset_out: ACA,
+		});
+		let share_token = XYK::share_token(pair_account);
+
+		assert_noop!(
+			XYK::remove_liquidity_single_asset(
+				Origin::signed(ALICE),
+				HDX,
+				ACA,
+				99_999_999_999_000,
+				100_000_000_000_001
+			),
+			Error::<Test>::AssetAmountNotReachedLimit
+		);
+
+		assert_ok!(XYK::remove_liquidity_single_asset(
+			Origin::signed(ALICE),
+			HDX,
+			ACA,
+			99_999_999_999_000,
+			100_000_000_000_000
+		));
+
+		assert!(!XYK::exists(AssetPair {
+			asset_in: HDX,
+			asset_out: ACA,
+		}));
+		assert_eq!(Currency::free_balance(HDX, &ALICE), 1_000_000_000_000_000);
+		assert_eq!(Currency::free_balance(ACA, &ALICE), 1_000_000_000_000_000);
+		assert_eq!(Currency::free_balance(share_token, &ALICE), 0);
+	});
+}
+
+#[test]
+fn add_liquidity_should_not_work_when_min_shares_is_not_reached() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			ACA,
+			100_000_000_000_000,
+			Price::from(10),
+			None
+		));
+
+		assert_noop!(
+			XYK::add_liquidity(
+				Origin::signed(BOB),
+				HDX,
+				ACA,
+				1_000_000_000_000,
+				10_000_000_000_000,
+				1_000_000_000_001,
+				None
+			),
+			Error::<Test>::SharesAmountNotReachedLimit
+		);
+
+		assert_ok!(XYK::add_liquidity(
+			Origin::signed(BOB),
+			HDX,
+			ACA,
+			1_000_000_000_000,
+			10_000_000_000_000,
+			1_000_000_000_000,
+			None
+		));
+	});
+}
+
+#[test]
+fn remove_liquidity_should_not_work_when_min_amounts_are_not_reached() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			ACA,
+			100_000_000_000_000,
+			Price::from(10),
+			None
+		));
+
+		assert_noop!(
+			XYK::remove_liquidity(
+				Origin::signed(ALICE),
+				HDX,
+				ACA,
+				1_000_000_000_000,
+				1_000_000_000_001,
+				0,
+				None
+			),
+			Error::<Test>::RemoveAssetAmountNotReachedLimit
+		);
+
+		assert_noop!(
+			XYK::remove_liquidity(
+				Origin::signed(ALICE),
+				HDX,
+				ACA,
+				1_000_000_000_000,
+				0,
+				10_000_000_000_001,
+				None
+			),
+			Error::<Test>::RemoveAssetAmountNotReachedLimit
+		);
+
+		assert_ok!(XYK::remove_liquidity(
+			Origin::signed(ALICE),
+			HDX,
+			ACA,
+			1_000_000_000_000,
+			1_000_000_000_000,
+			10_000_000_000_000,
+			None
+		));
+	});
+}
+
+#[test]
+fn operations_should_not_work_when_deadline_has_passed() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			ACA,
+			100_000_000_000_000,
+			Price::from(10),
+			None
+		));
+
+		System::set_block_number(5);
+
+		assert_noop!(
+			XYK::sell(Origin::signed(BOB), HDX, ACA, 1_000_000_000, 0, false, Some(4), None),
+			Error::<Test>::DeadlineExpired
+		);
+		assert_noop!(
+			XYK::buy(
+				Origin::signed(BOB),
+				ACA,
+				HDX,
+				1_000_000_000,
+				u128::MAX,
+				false,
+				Some(4),
+				None
+			),
+			Error::<Test>::DeadlineExpired
+		);
+		assert_noop!(
+			XYK::add_liquidity(
+				Origin::signed(BOB),
+				HDX,
+				ACA,
+				1_000_000_000_000,
+				10_000_000_000_000,
+				0,
+				Some(4)
+			),
+			Error::<Test>::DeadlineExpired
+		);
+		assert_noop!(
+			XYK::remove_liquidity(Origin::signed(ALICE), HDX, ACA, 1_000_000_000_000, 0, 0, Some(4)),
+			Error::<Test>::DeadlineExpired
+		);
+
+		// Deadline block itself is still valid
+		assert_ok!(XYK::sell(
+			Origin::signed(BOB),
+			HDX,
+			ACA,
+			1_000_000_000,
+			0,
+			false,
+			Some(5),
+			None
+		));
+		assert_ok!(XYK::buy(
+			Origin::signed(BOB),
+			ACA,
+			HDX,
+			1_000_000_000,
+			u128::MAX,
+			false,
+			Some(5),
+			None
+		));
+	});
+}
+
+#[test]
+fn price_observation_should_be_added_once_per_block() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			ACA,
+			100_000_000_000_000,
+			Price::from(10),
+			None
+		));
+
+		let pair_account = XYK::get_pair_id(AssetPair {
+			asset_in: HDX,
+			asset_out: ACA,
+		});
+
+		assert_eq!(
+			XYK::price_observations(&pair_account).into_inner(),
+			vec![PriceObservation {
+				block: 1,
+				price_a_cumulative: 0,
+				price_b_cumulative: 0,
+			}]
+		);
+
+		System::set_block_number(3);
+
+		assert_ok!(XYK::sell(
+			Origin::signed(BOB),
+			HDX,
+			ACA,
+			1_000_000_000_000,
+			0,
+			false,
+			None,
+			None
+		));
+		assert_ok!(XYK::sell(
+			Origin::signed(BOB),
+			HDX,
+			ACA,
+			1_000_000_000_000,
+			0,
+			false,
+			None,
+			None
+		));
+
+		assert_eq!(XYK::get_price_observation_blocks(HDX, ACA), vec![1, 3]);
+		assert_eq!(
+			XYK::price_observations(&pair_account)[1],
+			PriceObservation {
+				block: 3,
+				price_a_cumulative: 2 * Price::from(10).into_inner(),
+				price_b_cumulative: 2 * Price::from_rational(1, 10).into_inner(),
+			}
+		);
+	});
+}
+
+#[test]
+fn twap_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			ACA,
+			100_000_000_000_000,
+			Price::from(10),
+			None
+		));
+
+		let pair_account = XYK::get_pair_id(AssetPair {
+			asset_in: HDX,
+			asset_out: ACA,
+		});
+
+		System::set_block_number(3);
+
+		assert_ok!(XYK::sell(
+			Origin::signed(BOB),
+			HDX,
+			ACA,
+			10_000_000_000_000,
+			0,
+			false,
+			None,
+			None
+		));
+
+		let price_after_sell = Price::checked_from_rational(
+			Currency::free_balance(ACA, &pair_account),
+			Currency::free_balance(HDX, &pair_account),
+		)
+		.unwrap();
+
+		System::set_block_number(6);
+
+		assert_ok!(XYK::sell(
+			Origin::signed(BOB),
+			HDX,
+			ACA,
+			1_000_000_000_000,
+			0,
+			false,
+			None,
+			None
+		));
+
+		assert_eq!(XYK::get_price_observation_blocks(HDX, ACA), vec![1, 3, 6]);
+
+		assert_eq!(XYK::twap(HDX, ACA, 1, 3), Some(Price::from(10)));
+		assert_eq!(XYK::twap(ACA, HDX, 1, 3), Some(Price::from_rational(1, 10)));
+		assert_eq!(XYK::twap(HDX, ACA, 3, 6), Some(price_after_sell));
+		assert_eq!(
+			XYK::twap(HDX, ACA, 1, 6),
+			Some(Price::from_inner(
+				(2 * Price::from(10).into_inner() + 3 * price_after_sell.into_inner()) / 5
+			))
+		);
+	});
+}
+
+#[test]
+fn twap_should_return_none_when_observations_are_not_stored() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(XYK::twap(HDX, ACA, 1, 3), None);
+
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			ACA,
+			100_000_000_000_000,
+			Price::from(10),
+			None
+		));
+
+		System::set_block_number(3);
+
+		assert_ok!(XYK::sell(
+			Origin::signed(BOB),
+			HDX,
+			ACA,
+			1_000_000_000_000,
+			0,
+			false,
+			None,
+			None
+		));
+
+		assert_eq!(XYK::twap(HDX, ACA, 3, 1), None);
+		assert_eq!(XYK::twap(HDX, ACA, 3, 3), None);
+		assert_eq!(XYK::twap(HDX, ACA, 1, 2), None);
+		assert_eq!(XYK::twap(HDX, ACA, 2, 3), None);
+		assert_eq!(XYK::twap(HDX, DOT, 1, 3), None);
+	});
+}
+
+#[test]
+fn price_observations_should_be_limited() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			ACA,
+			100_000_000_000_000,
+			Price::from(10),
+			None
+		));
+
+		for block in 2..=7 {
+			System::set_block_number(block);
+
+			assert_ok!(XYK::sell(
+				Origin::signed(BOB),
+				HDX,
+				ACA,
+				1_000_000_000_000,
+				0,
+				false,
+				None,
+				None
+			));
+		}
+
+		assert_eq!(XYK::get_price_observation_blocks(HDX, ACA), vec![3, 4, 5, 6, 7]);
+
+		assert_eq!(XYK::twap(HDX, ACA, 1, 7), None);
+		assert!(XYK::twap(HDX, ACA, 3, 7).is_some());
+	});
+}
+
+#[test]
+fn price_observations_should_be_removed_when_pool_is_destroyed() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			ACA,
+			100_000_000_000_000,
+			Price::from(10),
+			None
+		));
+
+		let pair_account = XYK::get_pair_id(AssetPair {
+			asset_in: HDX,
+			asset_out: ACA,
+		});
+
+		System::set_block_number(2);
+
+		assert_ok!(XYK::remove_liquidity(
+			Origin::signed(ALICE),
+			HDX,
+			ACA,
+			99_999_999_999_000,
+			0,
+			0,
+			None
+		));
+
+		assert!(!<PriceObservations<Test>>::contains_key(&pair_account));
+	});
+}
+
+#[test]
+fn pool_stats_should_be_updated_by_trades() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			ACA,
+			DOT,
+			200_000_000,
+			Price::from(3200),
+			None
+		));
+
+		let pair_account = XYK::get_pair_id(AssetPair {
+			asset_in: ACA,
+			asset_out: DOT,
+		});
+
+		System::set_block_number(5);
+
+		assert_ok!(XYK::buy(
+			Origin::signed(ALICE),
+			ACA,
+			DOT,
+			66_666_666,
+			1_000_000_000_000,
+			false,
+			None,
+			None,
+		));
+
+		System::set_block_number(12);
+
+		assert_ok!(XYK::sell(
+			Origin::signed(ALICE),
+			ACA,
+			DOT,
+			10_000_000,
+			0,
+			false,
+			None,
+			None,
+		));
+
+		let total = TradeStats {
+			volume: (76_666_666, 387_527_347_104),
+			fees: (0, 774_042_778),
+			trade_count: 2,
+		};
+
+		assert_eq!(
+			XYK::get_pool_stats(pair_account),
+			Some(PoolStats {
+				total,
+				window_start: 10,
+				window: TradeStats {
+					volume: (10_000_000, 66_887_351_913),
+					fees: (0, 134_042_788),
+					trade_count: 1,
+				},
+				previous_window: TradeStats {
+					volume: (66_666_666, 320_639_995_191),
+					fees: (0, 639_999_990),
+					trade_count: 1,
+				},
+			})
+		);
+
+		System::set_block_number(35);
+
+		assert_eq!(
+			XYK::get_pool_stats(pair_account),
+			Some(PoolStats {
+				total,
+				window_start: 30,
+				window: TradeStats::default(),
+				previous_window: TradeStats::default(),
+			})
+		);
+	});
+}
+
+#[test]
+fn pool_stats_should_be_removed_when_pool_is_destroyed() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			ACA,
+			100_000_000_000_000,
+			Price::from(10),
+			None
+		));
+
+		let pair_account = XYK::get_pair_id(AssetPair {
+			asset_in: HDX,
+			asset_out: ACA,
+		});
+
+		assert_ok!(XYK::sell(
+			Origin::signed(BOB),
+			HDX,
+			ACA,
+			1_000_000_000_000,
+			0,
+			false,
+			None,
+			None
+		));
+
+		assert_eq!(
+			XYK::get_pool_stats(pair_account).map(|stats| stats.total.trade_count),
+			Some(1)
+		);
+
+		let shares = Currency::free_balance(XYK::share_token(pair_account), &ALICE);
+
+		assert_ok!(XYK::remove_liquidity(
+			Origin::signed(ALICE),
+			HDX,
+			ACA,
+			shares,
+			0,
+			0,
+			None
+		));
+
+		assert!(!<PoolTradeStats<Test>>::contains_key(&pair_account));
+		assert_eq!(XYK::get_pool_stats(pair_account), None);
+	});
+}
+
+#[test]
+fn twap_spot_price_should_fall_back_to_spot_price() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			ACA,
+			100_000_000_000_000,
+			Price::from(10),
+			None
+		));
+
+		assert_eq!(
+			XYKTwapPrice::<Test>::spot_price(HDX, ACA),
+			XYKSpotPrice::<Test>::spot_price(HDX, ACA)
+		);
+
+		System::set_block_number(3);
+
+		assert_ok!(XYK::sell(
+			Origin::signed(BOB),
+			HDX,
+			ACA,
+			10_000_000_000_000,
+			0,
+			false,
+			None,
+			None
+		));
+
+		assert_eq!(XYKTwapPrice::<Test>::spot_price(HDX, ACA), Some(Price::from(10)));
+	});
+}
+
+#[test]
+fn fee_adjusted_spot_price_should_include_pool_fee() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			ACA,
+			100_000_000_000_000,
+			Price::from(10),
+			None
+		));
+
+		// Default fee is 0.2%
+		assert_eq!(
+			XYKFeeAdjustedSpotPrice::<Test>::spot_price(HDX, ACA),
+			Some(Price::from_inner(10_020_000_000_000_000_000))
+		);
+
+		assert_ok!(XYK::set_pool_fee(Origin::root(), HDX, ACA, (5, 1_000)));
+
+		assert_eq!(
+			XYKFeeAdjustedSpotPrice::<Test>::spot_price(HDX, ACA),
+			Some(Price::from_inner(10_050_000_000_000_000_000))
+		);
+		assert_eq!(XYKFeeAdjustedSpotPrice::<Test>::spot_price(HDX, DOT), None);
+	});
+}
+
+#[test]
+fn set_protocol_fee_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			XYK::set_protocol_fee(Origin::signed(ALICE), Permill::from_percent(20)),
+			sp_runtime::DispatchError::BadOrigin
+		);
+
+		assert_ok!(XYK::set_protocol_fee(Origin::root(), Permill::from_percent(20)));
+
+		assert_eq!(XYK::protocol_fee(), Permill::from_percent(20));
+
+		expect_events(vec![Event::ProtocolFeeUpdated(Permill::from_percent(20)).into()]);
+	});
+}
+
+#[test]
+fn sell_should_transfer_protocol_fee() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			ACA,
+			DOT,
+			200_000_000_000,
+			Price::from(3000),
+			None
+		));
+		assert_ok!(XYK::set_protocol_fee(Origin::root(), Permill::from_percent(50)));
+
+		let pair_account = XYK::get_pair_id(AssetPair {
+			asset_in: ACA,
+			asset_out: DOT,
+		});
+
+		let quote = XYK::quote_sell(ACA, DOT, 456_444_678).unwrap();
+
+		assert_eq!(quote.amount, 1363483591788);
+		assert_eq!(quote.fee, 2732432046);
+		assert_eq!(quote.protocol_fee, 1366216023);
+
+		assert_ok!(XYK::sell(
+			Origin::signed(BOB),
+			ACA,
+			DOT,
+			456_444_678,
+			quote.amount,
+			false,
+			None,
+			None,
+		));
+
+		assert_eq!(Currency::free_balance(DOT, &BOB), 1_000_000_000_000_000 + quote.amount);
+		assert_eq!(Currency::free_balance(DOT, &TREASURY), quote.protocol_fee);
+		assert_eq!(
+			Price::checked_from_rational(
+				Currency::free_balance(DOT, &pair_account),
+				Currency::free_balance(ACA, &pair_account)
+			),
+			Some(quote.spot_price_after)
+		);
+
+		expect_events(vec![Event::ProtocolFeeTransferred(
+			pair_account,
+			DOT,
+			quote.protocol_fee,
+			TREASURY,
+		)
+		.into()]);
+	});
+}
+
+#[test]
+fn buy_should_transfer_protocol_fee() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			ACA,
+			DOT,
+			200_000_000_000,
+			Price::from(3000),
+			None
+		));
+		assert_ok!(XYK::set_protocol_fee(Origin::root(), Permill::from_percent(50)));
+
+		let pair_account = XYK::get_pair_id(AssetPair {
+			asset_in: ACA,
+			asset_out: DOT,
+		});
+
+		let quote = XYK::quote_buy(DOT, ACA, 1_000_000_000_000).unwrap();
+
+		assert_eq!(quote.amount, 334557595);
+		assert_eq!(quote.fee, 667778);
+		assert_eq!(quote.protocol_fee, 333889);
+
+		assert_ok!(XYK::buy(
+			Origin::signed(BOB),
+			DOT,
+			ACA,
+			1_000_000_000_000,
+			quote.amount,
+			false,
+			None,
+			None,
+		));
+
+		assert_eq!(Currency::free_balance(ACA, &BOB), 1_000_000_000_000_000 - quote.amount);
+		assert_eq!(Currency::free_balance(ACA, &TREASURY), quote.protocol_fee);
+		assert_eq!(
+			Price::checked_from_rational(
+				Currency::free_balance(DOT, &pair_account),
+				Currency::free_balance(ACA, &pair_account)
+			),
+			Some(quote.spot_price_after)
+		);
+
+		expect_events(vec![Event::ProtocolFeeTransferred(
+			pair_account,
+			ACA,
+			quote.protocol_fee,
+			TREASURY,
+		)
+		.into()]);
+	});
+}
+
+#[test]
+fn flash_swap_repaid_in_borrowed_asset_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			100_000_000_000_000,
+			Price::from(2),
+			None
+		));
+
+		assert_noop!(
+			XYK::flash_swap(
+				Origin::signed(BOB),
+				DOT,
+				HDX,
+				10_000_000_000_000,
+				(DOT, 10_020_040_080_160u128).encode()
+			),
+			Error::<Test>::InsufficientFlashSwapRepayment
+		);
+
+		assert_ok!(XYK::flash_swap(
+			Origin::signed(BOB),
+			DOT,
+			HDX,
+			10_000_000_000_000,
+			(DOT, 10_020_040_080_161u128).encode()
+		));
+
+		assert_eq!(
+			Currency::free_balance(DOT, &BOB),
+			1_000_000_000_000_000 - 20_040_080_161
+		);
+		assert_eq!(
+			Currency::free_balance(DOT, &HDX_DOT_POOL_ID),
+			200_000_000_000_000 + 20_040_080_161
+		);
+		assert_eq!(Currency::free_balance(HDX, &HDX_DOT_POOL_ID), 100_000_000_000_000);
+
+		expect_events(vec![Event::FlashSwapExecuted(
+			BOB,
+			DOT,
+			HDX,
+			10_000_000_000_000,
+			HDX_DOT_POOL_ID,
+		)
+		.into()]);
+	});
+}
+
+#[test]
+fn flash_swap_repaid_in_other_asset_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			100_000_000_000_000,
+			Price::from(2),
+			None
+		));
+
+		assert_noop!(
+			XYK::flash_swap(
+				Origin::signed(BOB),
+				DOT,
+				HDX,
+				10_000_000_000_000,
+				(HDX, 5_273_705_305_347u128).encode()
+			),
+			Error::<Test>::InsufficientFlashSwapRepayment
+		);
+
+		assert_ok!(XYK::flash_swap(
+			Origin::signed(BOB),
+			DOT,
+			HDX,
+			10_000_000_000_000,
+			(HDX, 5_273_705_305_348u128).encode()
+		));
+
+		assert_eq!(
+			Currency::free_balance(DOT, &BOB),
+			1_000_000_000_000_000 + 10_000_000_000_000
+		);
+		assert_eq!(
+			Currency::free_balance(HDX, &BOB),
+			1_000_000_000_000_000 - 5_273_705_305_348
+		);
+	});
+}
+
+#[test]
+fn flash_swap_should_transfer_protocol_fee() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			100_000_000_000_000,
+			Price::from(2),
+			None
+		));
+		assert_ok!(XYK::set_protocol_fee(Origin::root(), Permill::from_percent(50)));
+
+		assert_ok!(XYK::flash_swap(
+			Origin::signed(BOB),
+			DOT,
+			HDX,
+			10_000_000_000_000,
+			(DOT, 10_020_040_080_161u128).encode()
+		));
+
+		let protocol_fee = Currency::free_balance(DOT, &TREASURY);
+		assert!(protocol_fee > 0);
+		assert_eq!(
+			Currency::free_balance(DOT, &HDX_DOT_POOL_ID) + protocol_fee,
+			200_000_000_000_000 + 20_040_080_161
+		);
+
+		expect_events(vec![
+			Event::ProtocolFeeTransferred(HDX_DOT_POOL_ID, DOT, protocol_fee, TREASURY).into(),
+			Event::FlashSwapExecuted(BOB, DOT, HDX, 10_000_000_000_000, HDX_DOT_POOL_ID).into(),
+		]);
+	});
+}
+
+#[test]
+fn flash_swap_should_not_work_when_pool_cannot_lend_amount() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			XYK::flash_swap(
+				Origin::signed(BOB),
+				DOT,
+				HDX,
+				10_000_000_000_000,
+				(DOT, 10_100_000_000_000u128).encode()
+			),
+			Error::<Test>::TokenPoolNotFound
+		);
+
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			100_000_000_000_000,
+			Price::from(2),
+			None
+		));
+
+		assert_noop!(
+			XYK::flash_swap(
+				Origin::signed(BOB),
+				DOT,
+				HDX,
+				200_000_000_000_000,
+				(DOT, 201_000_000_000_000u128).encode()
+			),
+			Error::<Test>::InsufficientPoolAssetBalance
+		);
+		assert_noop!(
+			XYK::flash_swap(Origin::signed(BOB), DOT, HDX, 10, (DOT, 11u128).encode()),
+			Error::<Test>::InsufficientTradingAmount
+		);
+	});
+}
+
+#[test]
+fn pool_deposit_should_be_reserved_and_refunded_when_pool_is_destroyed() {
+	let mut ext = ExtBuilder::default().with_pool_deposit(1_000_000_000_000).build();
+	ext.execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			ACA,
+			DOT,
+			100_000_000_000_000,
+			Price::from(2),
+			None
+		));
+
+		let pair_account = XYK::get_pair_id(AssetPair {
+			asset_in: ACA,
+			asset_out: DOT,
+		});
+
+		assert_eq!(Currency::reserved_balance(HDX, &ALICE), 1_000_000_000_000);
+		assert_eq!(
+			Currency::free_balance(HDX, &ALICE),
+			1_000_000_000_000_000 - 1_000_000_000_000
+		);
+		assert_eq!(XYK::pool_deposit(&pair_account), Some((ALICE, 1_000_000_000_000)));
+
+		let share_token = XYK::share_token(pair_account);
+
+		assert_ok!(XYK::remove_liquidity(
+			Origin::signed(ALICE),
+			ACA,
+			DOT,
+			Currency::free_balance(share_token, &ALICE),
+			0,
+			0,
+			None
+		));
+
+		assert_eq!(Currency::reserved_balance(HDX, &ALICE), 0);
+		assert_eq!(Currency::free_balance(HDX, &ALICE), 1_000_000_000_000_000);
+		assert_eq!(XYK::pool_deposit(&pair_account), None);
+	});
+}
+
+#[test]
+fn create_pool_should_not_work_when_pool_deposit_cannot_be_reserved() {
+	let mut ext = ExtBuilder::default().with_pool_deposit(2_000_000_000_000_000).build();
+	ext.execute_with(|| {
+		System::set_block_number(1);
+
+		assert_noop!(
+			XYK::create_pool(
+				Origin::signed(ALICE),
+				ACA,
+				DOT,
+				100_000_000_000_000,
+				Price::from(2),
+				None
+			),
+			Error::<Test>::InsufficientPoolDepositBalance
+		);
+	});
+}
+
+#[test]
+fn create_pool_should_respect_asset_pool_permissions() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			XYK::set_asset_pool_permission(Origin::signed(ALICE), ACA, Some(AssetPoolPermission::Denied)),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_noop!(
+			XYK::set_require_allowed_assets(Origin::signed(ALICE), true),
+			sp_runtime::DispatchError::BadOrigin
+		);
+
+		assert_ok!(XYK::set_asset_pool_permission(
+			Origin::root(),
+			ACA,
+			Some(AssetPoolPermission::Denied)
+		));
+
+		expect_events(vec![Event::AssetPoolPermissionUpdated(
+			ACA,
+			Some(AssetPoolPermission::Denied),
+		)
+		.into()]);
+
+		assert_noop!(
+			XYK::create_pool(
+				Origin::signed(ALICE),
+				ACA,
+				DOT,
+				100_000_000_000_000,
+				Price::from(2),
+				None
+			),
+			Error::<Test>::CannotCreatePool
+		);
+
+		assert_ok!(XYK::set_require_allowed_assets(Origin::root(), true));
+
+		expect_events(vec![Event::RequireAllowedAssetsUpdated(true).into()]);
+
+		assert_noop!(
+			XYK::create_pool(
+				Origin::signed(ALICE),
+				HDX,
+				DOT,
+				100_000_000_000_000,
+				Price::from(2),
+				None
+			),
+			Error::<Test>::CannotCreatePool
+		);
+
+		assert_ok!(XYK::set_asset_pool_permission(
+			Origin::root(),
+			HDX,
+			Some(AssetPoolPermission::Allowed)
+		));
+		assert_ok!(XYK::set_asset_pool_permission(
+			Origin::root(),
+			DOT,
+			Some(AssetPoolPermission::Allowed)
+		));
+
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			100_000_000_000_000,
+			Price::from(2),
+			None
+		));
+
+		assert_ok!(XYK::set_require_allowed_assets(Origin::root(), false));
+		assert_ok!(XYK::set_asset_pool_permission(Origin::root(), ACA, None));
+
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			ACA,
+			DOT,
+			100_000_000_000_000,
+			Price::from(2),
+			None
+		));
+	});
+}
+
+#[test]
+fn create_pool_should_lock_minimum_liquidity() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			XYK::create_pool(Origin::signed(ALICE), HDX, DOT, 1_999, Price::from(1), None),
+			Error::<Test>::InsufficientLiquidity
+		);
+
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			2_000,
+			Price::from(1),
+			None
+		));
+
+		let pair_account = XYK::get_pair_id(AssetPair {
+			asset_in: HDX,
+			asset_out: DOT,
+		});
+		let share_token = XYK::share_token(pair_account);
+
+		assert_eq!(Currency::free_balance(share_token, &ALICE), 1_000);
+		assert_eq!(
+			Currency::free_balance(share_token, &XYK::locked_liquidity_account()),
+			1_000
+		);
+		assert_eq!(XYK::total_liquidity(&pair_account), 2_000);
+
+		expect_events(vec![
+			Event::PoolCreated(ALICE, HDX, DOT, 2_000, share_token, pair_account).into(),
+			orml_tokens::Event::Endowed {
+				currency_id: share_token,
+				who: XYK::locked_liquidity_account(),
+				amount: 1_000,
+			}
+			.into(),
+		]);
+	});
+}
+
+#[test]
+fn remove_liquidity_should_not_remove_locked_liquidity() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			100_000_000,
+			Price::from(1),
+			None
+		));
+
+		assert_ok!(XYK::add_liquidity(
+			Origin::signed(BOB),
+			HDX,
+			DOT,
+			100_000_000,
+			100_000_001,
+			0,
+			None
+		));
+
+		let pair_account = XYK::get_pair_id(AssetPair {
+			asset_in: HDX,
+			asset_out: DOT,
+		});
+		let share_token = XYK::share_token(pair_account);
+
+		assert_noop!(
+			XYK::remove_liquidity(
+				Origin::signed(XYK::locked_liquidity_account()),
+				HDX,
+				DOT,
+				1_000,
+				0,
+				0,
+				None
+			),
+			Error::<Test>::CannotRemoveLockedLiquidity
+		);
+
+		// all liquidity of the pool creator can be removed, locked shares stay in the pool
+		assert_ok!(XYK::remove_liquidity(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			99_999_000,
+			0,
+			0,
+			None
+		));
+
+		assert!(XYK::exists(AssetPair {
+			asset_in: HDX,
+			asset_out: DOT,
+		}));
+		assert_eq!(
+			Currency::free_balance(share_token, &XYK::locked_liquidity_account()),
+			1_000
+		);
+		assert_eq!(XYK::total_liquidity(&pair_account), 100_001_000);
+		assert_eq!(Currency::free_balance(HDX, &pair_account), 100_001_000);
+	});
+}
+
+#[test]
+fn pool_should_be_destroyed_when_only_locked_liquidity_is_left() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			100_000_000,
+			Price::from(1),
+			None
+		));
+
+		assert_ok!(XYK::add_liquidity(
+			Origin::signed(BOB),
+			HDX,
+			DOT,
+			100_000_000,
+			100_000_001,
+			0,
+			None
+		));
+
+		let pair_account = XYK::pair_account_from_assets(HDX, DOT);
+		let share_token = XYK::share_token(pair_account);
+
+		assert_ok!(XYK::remove_liquidity(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			99_999_000,
+			0,
+			0,
+			None
+		));
+
+		let bob_hdx_balance = Currency::free_balance(HDX, &BOB);
+
+		// last liquidity provider receives also the reserves backing the locked shares
+		assert_ok!(XYK::remove_liquidity(
+			Origin::signed(BOB),
+			HDX,
+			DOT,
+			100_000_000,
+			0,
+			0,
+			None
+		));
+
+		assert!(!XYK::exists(AssetPair {
+			asset_in: HDX,
+			asset_out: DOT,
+		}));
+		assert_eq!(Currency::free_balance(HDX, &BOB), bob_hdx_balance + 100_001_000);
+		assert_eq!(Currency::free_balance(HDX, &pair_account), 0);
+		assert_eq!(Currency::free_balance(DOT, &pair_account), 0);
+		assert_eq!(Currency::free_balance(share_token, &XYK::locked_liquidity_account()), 0);
+		assert_eq!(Currency::total_issuance(share_token), 0);
+		assert_eq!(XYK::total_liquidity(&pair_account), 0);
+		assert_eq!(XYK::liquidity_floor(&pair_account), 0);
+		assert_eq!(XYK::pool_deposit(&pair_account), None);
+		assert_eq!(destroyed_pools(), vec![(pair_account, HDX, DOT)]);
+	});
+}
+
+#[test]
+fn liquidity_floor_should_protect_pool_created_before_liquidity_was_locked() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			100_000_000,
+			Price::from(1),
+			None
+		));
+
+		let pair_account = XYK::pair_account_from_assets(HDX, DOT);
+		let share_token = XYK::share_token(pair_account);
+
+		unlock_pool_liquidity(&pair_account, share_token);
+		frame_support::traits::StorageVersion::new(1).put::<Pallet<Test>>();
+
+		crate::migration::set_liquidity_floors::<Test>();
+
+		assert_eq!(XYK::liquidity_floor(&pair_account), 1_000);
+		assert_eq!(XYK::total_liquidity(&pair_account), 99_999_000);
+
+		assert_ok!(Currency::transfer(Origin::signed(ALICE), BOB, share_token, 999));
+
+		assert_noop!(
+			XYK::remove_liquidity(Origin::signed(ALICE), HDX, DOT, 99_998_001, 0, 0, None),
+			Error::<Test>::CannotRemoveLockedLiquidity
+		);
+
+		assert_ok!(XYK::remove_liquidity(Origin::signed(BOB), HDX, DOT, 999, 0, 0, None));
+
+		// last shares of the pool are removed without any locked shares
+		assert_ok!(XYK::remove_liquidity(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			99_998_001,
+			0,
+			0,
+			None
+		));
+
+		assert!(!XYK::exists(AssetPair {
+			asset_in: HDX,
+			asset_out: DOT,
+		}));
+		assert_eq!(Currency::free_balance(HDX, &pair_account), 0);
+		assert_eq!(destroyed_pools(), vec![(pair_account, HDX, DOT)]);
+	});
+}
+
+#[test]
+fn donation_before_deposit_should_not_dilute_shares_of_depositor() {
+	new_test_ext().execute_with(|| {
+		let attacker = ALICE;
+		let victim = BOB;
+
+		let pair_account = XYK::get_pair_id(AssetPair {
+			asset_in: HDX,
+			asset_out: DOT,
+		});
+
+		// attacker creates the pool with minimum liquidity
+		assert_ok!(XYK::create_pool(
+			Origin::signed(attacker),
+			HDX,
+			DOT,
+			2_000,
+			Price::from(1),
+			None
+		));
+
+		let share_token = XYK::share_token(pair_account);
+		assert_eq!(Currency::free_balance(share_token, &attacker), 1_000);
+
+		// and inflates value of the shares by donating directly to the pool account
+		assert_ok!(Currency::transfer(
+			Origin::signed(attacker),
+			pair_account,
+			HDX,
+			1_000_000_000_000
+		));
+		assert_ok!(Currency::transfer(
+			Origin::signed(attacker),
+			pair_account,
+			DOT,
+			1_000_000_000_000
+		));
+
+		// victim receives full amount of shares for the deposit
+		assert_ok!(XYK::add_liquidity(
+			Origin::signed(victim),
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			1_000_000_000_001,
+			1_000_000_000_000,
+			None
+		));
+		assert_eq!(Currency::free_balance(share_token, &victim), 1_000_000_000_000);
+
+		// attacker loses the donation
+		assert_ok!(XYK::remove_liquidity(
+			Origin::signed(attacker),
+			HDX,
+			DOT,
+			1_000,
+			0,
+			0,
+			None
+		));
+		assert_eq!(Currency::free_balance(HDX, &attacker), 998_999_999_999_999);
+
+		// victim as the last liquidity provider withdraws more than the deposit and destroys the pool
+		assert_ok!(XYK::remove_liquidity(
+			Origin::signed(victim),
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			1_000_000_000_000,
+			1_000_000_000_000,
+			None
+		));
+		assert_eq!(Currency::free_balance(HDX, &victim), 1_001_000_000_000_001);
+
+		assert!(!XYK::exists(AssetPair {
+			asset_in: HDX,
+			asset_out: DOT,
+		}));
+		assert_eq!(Currency::free_balance(HDX, &pair_account), 0);
+	});
+}
+
+fn sell_instruction(amount: Balance) -> TradeInstruction {
+	TradeInstruction::Sell {
+		asset_in: HDX,
+		asset_out: ACA,
+		amount,
+		max_limit: 0,
+	}
+}
+
+#[test]
+fn batch_trade_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			ACA,
+			100_000_000_000_000,
+			Price::from(10),
+			None
+		));
+
+		assert_ok!(XYK::batch_trade(
+			Origin::signed(BOB),
+			vec![sell_instruction(1_000_000_000_000), sell_instruction(1_000_000_000_000)],
+			vec![(HDX, -2_000_000_000_000), (ACA, 19_568_821_199_766)],
+			None
+		));
+
+		assert_eq!(Currency::free_balance(HDX, &BOB), 998_000_000_000_000);
+		assert_eq!(Currency::free_balance(ACA, &BOB), 1_019_568_821_199_766);
+
+		let pair_account = XYK::get_pair_id(AssetPair {
+			asset_in: HDX,
+			asset_out: ACA,
+		});
+
+		expect_events(vec![
+			Event::SellExecuted(
+				BOB,
+				HDX,
+				ACA,
+				1_000_000_000_000,
+				9_687_633_080_955,
+				ACA,
+				19_414_094_350,
+				pair_account,
+				Permill::from_parts(9_803),
+			)
+			.into(),
+			Event::BatchTradeExecuted(BOB, 2).into(),
+		]);
+	});
+}
+
+#[test]
+fn batch_trade_should_not_work_when_aggregate_limit_is_not_reached() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			ACA,
+			100_000_000_000_000,
+			Price::from(10),
+			None
+		));
+
+		let instructions = vec![sell_instruction(1_000_000_000_000), sell_instruction(1_000_000_000_000)];
+
+		assert_noop!(
+			XYK::batch_trade(
+				Origin::signed(BOB),
+				instructions.clone(),
+				vec![(HDX, -2_000_000_000_000), (ACA, 19_568_821_199_767)],
+				None
+			),
+			Error::<Test>::AggregateLimitNotReached
+		);
+
+		assert_noop!(
+			XYK::batch_trade(
+				Origin::signed(BOB),
+				instructions,
+				vec![(ACA, 19_568_821_199_766), (HDX, -1_999_999_999_999)],
+				None
+			),
+			Error::<Test>::AggregateLimitNotReached
+		);
+	});
+}
+
+#[test]
+fn batch_trade_should_be_reverted_when_instruction_fails() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			ACA,
+			100_000_000_000_000,
+			Price::from(10),
+			None
+		));
+
+		assert_noop!(
+			XYK::batch_trade(
+				Origin::signed(BOB),
+				vec![
+					sell_instruction(1_000_000_000_000),
+					TradeInstruction::Buy {
+						asset_out: ACA,
+						asset_in: HDX,
+						amount: 1_000_000_000_000,
+						max_limit: 1,
+					}
+				],
+				vec![],
+				None
+			),
+			Error::<Test>::AssetAmountExceededLimit
+		);
+	});
+}
+
+#[test]
+fn batch_trade_should_execute_liquidity_instructions() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			ACA,
+			100_000_000_000_000,
+			Price::from(10),
+			None
+		));
+
+		let pair_account = XYK::get_pair_id(AssetPair {
+			asset_in: HDX,
+			asset_out: ACA,
+		});
+		let share_token = XYK::share_token(pair_account);
+		let total_liquidity = XYK::total_liquidity(pair_account);
+
+		assert_ok!(XYK::batch_trade(
+			Origin::signed(BOB),
+			vec![
+				TradeInstruction::AddLiquidity {
+					asset_a: HDX,
+					asset_b: ACA,
+					amount_a: 1_000_000_000_000,
+					amount_b_max_limit: 20_000_000_000_000,
+					min_shares: 1_000_000_000_000,
+				},
+				TradeInstruction::RemoveLiquidity {
+					asset_a: HDX,
+					asset_b: ACA,
+					liquidity_amount: 1_000_000_000_000,
+					min_amount_a: 0,
+					min_amount_b: 0,
+				},
+			],
+			vec![(share_token, 0)],
+			None
+		));
+
+		assert_eq!(Currency::free_balance(share_token, &BOB), 0);
+		assert_eq!(XYK::total_liquidity(pair_account), total_liquidity);
+
+		expect_events(vec![
+			Event::LiquidityRemoved(BOB, HDX, ACA, 1_000_000_000_000).into(),
+			Event::BatchTradeExecuted(BOB, 2).into(),
+		]);
+	});
+}
+
+#[test]
+fn batch_trade_should_respect_max_batch_size() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			ACA,
+			100_000_000_000_000,
+			Price::from(10),
+			None
+		));
+
+		assert_noop!(
+			XYK::batch_trade(
+				Origin::signed(BOB),
+				vec![sell_instruction(1_000_000_000_000); 6],
+				vec![],
+				None
+			),
+			Error::<Test>::MaxBatchSizeExceeded
+		);
+
+		assert_noop!(
+			XYK::batch_trade(
+				Origin::signed(BOB),
+				vec![sell_instruction(1_000_000_000_000)],
+				vec![(HDX, Amount::MIN); 6],
+				None
+			),
+			Error::<Test>::MaxBatchSizeExceeded
+		);
+	});
+}
+
+#[test]
+fn create_pool_should_name_share_token_after_assets() {
+	let mut ext = ExtBuilder::default()
+		.with_asset_name(HDX, b"HDX")
+		.with_asset_name(DOT, b"DOT")
+		.build();
+	ext.execute_with(|| System::set_block_number(1));
+	ext.execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			DOT,
+			HDX,
+			100_000_000,
+			Price::from(2),
+			None
+		));
+
+		let pair_account = XYK::pair_account_from_assets(DOT, HDX);
+		let share_token = XYK::share_token(pair_account);
+
+		let bounded_name: BoundedVec<u8, <Test as pallet_asset_registry::Config>::StringLimit> =
+			b"HDX-DOT LP".to_vec().try_into().unwrap();
+
+		expect_events(vec![
+			pallet_asset_registry::Event::Registered(share_token, bounded_name.clone(), AssetType::PoolShare(DOT, HDX))
+				.into(),
+			pallet_asset_registry::Event::MetadataSet(share_token, bounded_name, SHARE_TOKEN_DECIMALS).into(),
+		]);
+
+		assert_eq!(MockAssetMetadata::asset_name(share_token), Some(b"HDX-DOT LP".to_vec()));
+	});
+}
+
+#[test]
+fn create_pool_should_use_legacy_share_token_name_when_asset_name_is_unknown() {
+	let mut ext = ExtBuilder::default().with_asset_name(HDX, b"HDX").build();
+	ext.execute_with(|| System::set_block_number(1));
+	ext.execute_with(|| {
+		assert_eq!(XYK::share_token_name(HDX, DOT), None);
+
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			100_000_000,
+			Price::from(2),
+			None
+		));
+
+		let share_token = XYK::share_token(XYK::pair_account_from_assets(HDX, DOT));
+
+		assert_eq!(
+			MockAssetMetadata::asset_name(share_token),
+			Some(AssetPair::new(HDX, DOT).name())
+		);
+	});
+}
+
+#[test]
+fn create_pool_should_not_use_share_token_name_registered_for_another_asset() {
+	let mut ext = ExtBuilder::default()
+		.with_asset_name(HDX, b"HDX")
+		.with_asset_name(DOT, b"DOT")
+		.build();
+	ext.execute_with(|| System::set_block_number(1));
+	ext.execute_with(|| {
+		let token = AssetRegistry::register_asset(
+			AssetRegistry::to_bounded_name(b"HDX-DOT LP".to_vec()).unwrap(),
+			AssetType::Token,
+			1_000,
+		)
+		.unwrap();
+
+		assert_eq!(XYK::share_token_name(HDX, DOT), None);
+
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			100_000_000,
+			Price::from(2),
+			None
+		));
+
+		let share_token = XYK::share_token(XYK::pair_account_from_assets(HDX, DOT));
+
+		assert_ne!(share_token, token);
+		assert_eq!(
+			MockAssetMetadata::asset_name(share_token),
+			Some(AssetPair::new(HDX, DOT).name())
+		);
+	});
+}
+
+#[test]
+fn get_pool_by_share_token_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			100_000_000,
+			Price::from(2),
+			None
+		));
+
+		let pair_account = XYK::pair_account_from_assets(HDX, DOT);
+		let share_token = XYK::share_token(pair_account);
+
+		assert_eq!(XYK::share_token_pool(share_token), Some(pair_account));
+		assert_eq!(
+			XYK::get_pool_by_share_token(share_token),
+			XYK::get_pool_info(pair_account)
+		);
+		assert_eq!(
+			XYK::get_pool_by_share_token(share_token).map(|pool| pool.assets),
+			Some((HDX, DOT))
+		);
+		assert_eq!(XYK::get_pool_by_share_token(HDX), None);
+
+		assert_ok!(XYK::remove_liquidity(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			99_999_000,
+			0,
+			0,
+			None
+		));
+
+		assert_eq!(XYK::share_token_pool(share_token), None);
+		assert_eq!(XYK::get_pool_by_share_token(share_token), None);
+	});
+}
+
+#[test]
+fn liquidity_changed_handler_should_be_called_when_liquidity_changes() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			100_000_000,
+			Price::from(2),
+			None
+		));
+
+		let pair_account = XYK::pair_account_from_assets(HDX, DOT);
+
+		let dot_balance = Currency::free_balance(DOT, &BOB);
+		assert_ok!(XYK::add_liquidity(
+			Origin::signed(BOB),
+			HDX,
+			DOT,
+			1_000_000,
+			3_000_000,
+			0,
+			None
+		));
+		let dot_added = dot_balance - Currency::free_balance(DOT, &BOB);
+
+		let (hdx_balance, dot_balance) = (Currency::free_balance(HDX, &BOB), Currency::free_balance(DOT, &BOB));
+		assert_ok!(XYK::remove_liquidity(
+			Origin::signed(BOB),
+			DOT,
+			HDX,
+			1_000_000,
+			0,
+			0,
+			None
+		));
+		let hdx_removed = Currency::free_balance(HDX, &BOB) - hdx_balance;
+		let dot_removed = Currency::free_balance(DOT, &BOB) - dot_balance;
+
+		assert_eq!(
+			liquidity_changes(),
+			vec![
+				(HDX, DOT, 1_000_000, dot_added, 101_000_000),
+				(DOT, HDX, dot_removed, hdx_removed, 100_000_000),
+			]
+		);
+		assert_eq!(XYK::total_liquidity(&pair_account), 100_000_000);
+		assert!(destroyed_pools().is_empty());
+	});
+}
+
+#[test]
+fn pool_destroyed_handler_should_be_called_when_pool_is_destroyed() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			100_000_000,
+			Price::from(2),
+			None
+		));
+
+		assert_ok!(XYK::add_liquidity(
+			Origin::signed(BOB),
+			HDX,
+			DOT,
+			1_000_000,
+			3_000_000,
+			0,
+			None
+		));
+
+		let pair_account = XYK::pair_account_from_assets(HDX, DOT);
+
+		assert_ok!(XYK::remove_liquidity(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			99_999_000,
+			0,
+			0,
+			None
+		));
+
+		assert!(destroyed_pools().is_empty());
+
+		// last liquidity provider exits, only the locked shares are left
+		assert_ok!(XYK::remove_liquidity(
+			Origin::signed(BOB),
+			HDX,
+			DOT,
+			1_000_000,
+			0,
+			0,
+			None
+		));
+
+		assert_eq!(liquidity_changes().last().map(|change| change.4), Some(0));
+		assert_eq!(destroyed_pools(), vec![(pair_account, HDX, DOT)]);
+	});
+}
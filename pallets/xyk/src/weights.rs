@@ -50,8 +50,17 @@ pub trait WeightInfo {
 	fn create_pool() -> Weight;
 	fn add_liquidity() -> Weight;
 	fn remove_liquidity() -> Weight;
+	fn add_liquidity_single_asset() -> Weight;
+	fn remove_liquidity_single_asset() -> Weight;
 	fn sell() -> Weight;
 	fn buy() -> Weight;
+	fn set_fee_tiers() -> Weight;
+	fn set_pool_fee() -> Weight;
+	fn set_protocol_fee() -> Weight;
+	fn flash_swap() -> Weight;
+	fn set_asset_pool_permission() -> Weight;
+	fn set_require_allowed_assets() -> Weight;
+	fn batch_trade(n: u32, l: u32) -> Weight;
 }
 
 /// Weights for amm using the hydraDX node and recommended hardware.
@@ -60,28 +69,68 @@ pub struct HydraWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for HydraWeight<T> {
 	fn create_pool() -> Weight {
 		(189_645_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(11 as Weight))
-			.saturating_add(T::DbWeight::get().writes(13 as Weight))
+			.saturating_add(T::DbWeight::get().reads(22 as Weight))
+			.saturating_add(T::DbWeight::get().writes(19 as Weight))
 	}
 	fn add_liquidity() -> Weight {
 		(171_602_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(9 as Weight))
-			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+			.saturating_add(T::DbWeight::get().reads(14 as Weight))
+			.saturating_add(T::DbWeight::get().writes(11 as Weight))
 	}
 	fn remove_liquidity() -> Weight {
 		(170_846_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(8 as Weight))
-			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+			.saturating_add(T::DbWeight::get().reads(15 as Weight))
+			.saturating_add(T::DbWeight::get().writes(14 as Weight))
+	}
+	fn add_liquidity_single_asset() -> Weight {
+		(293_727_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(22 as Weight))
+			.saturating_add(T::DbWeight::get().writes(17 as Weight))
+	}
+	fn remove_liquidity_single_asset() -> Weight {
+		(292_971_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(23 as Weight))
+			.saturating_add(T::DbWeight::get().writes(18 as Weight))
 	}
 	fn sell() -> Weight {
 		(122_125_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(5 as Weight))
-			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+			.saturating_add(T::DbWeight::get().reads(13 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
 	}
 	fn buy() -> Weight {
 		(121_289_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(5 as Weight))
-			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+			.saturating_add(T::DbWeight::get().reads(13 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
+	fn set_fee_tiers() -> Weight {
+		(21_483_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_pool_fee() -> Weight {
+		(28_760_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_protocol_fee() -> Weight {
+		(20_815_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn flash_swap() -> Weight {
+		(119_713_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(12 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	fn set_asset_pool_permission() -> Weight {
+		(19_968_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_require_allowed_assets() -> Weight {
+		(18_647_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn batch_trade(n: u32, l: u32) -> Weight {
+		(14_283_000 as Weight) // Standard Error: 1_702_000
+			.saturating_add((561_218_000 as Weight).saturating_mul(n as Weight)) // Standard Error: 9_000
+			.saturating_add((5_342_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(T::DbWeight::get().reads((74 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(l as Weight)))
+			.saturating_add(T::DbWeight::get().writes((45 as Weight).saturating_mul(n as Weight)))
 	}
 }
 
@@ -89,27 +138,67 @@ impl<T: frame_system::Config> WeightInfo for HydraWeight<T> {
 impl WeightInfo for () {
 	fn create_pool() -> Weight {
 		(189_645_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(11 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(13 as Weight))
+			.saturating_add(RocksDbWeight::get().reads(22 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(19 as Weight))
 	}
 	fn add_liquidity() -> Weight {
 		(171_602_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(9 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+			.saturating_add(RocksDbWeight::get().reads(14 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(11 as Weight))
 	}
 	fn remove_liquidity() -> Weight {
 		(170_846_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+			.saturating_add(RocksDbWeight::get().reads(15 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(14 as Weight))
+	}
+	fn add_liquidity_single_asset() -> Weight {
+		(293_727_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(22 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(17 as Weight))
+	}
+	fn remove_liquidity_single_asset() -> Weight {
+		(292_971_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(23 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(18 as Weight))
 	}
 	fn sell() -> Weight {
 		(122_125_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+			.saturating_add(RocksDbWeight::get().reads(13 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
 	}
 	fn buy() -> Weight {
 		(121_289_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+			.saturating_add(RocksDbWeight::get().reads(13 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+	}
+	fn set_fee_tiers() -> Weight {
+		(21_483_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_pool_fee() -> Weight {
+		(28_760_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_protocol_fee() -> Weight {
+		(20_815_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn flash_swap() -> Weight {
+		(119_713_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(12 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	fn set_asset_pool_permission() -> Weight {
+		(19_968_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_require_allowed_assets() -> Weight {
+		(18_647_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn batch_trade(n: u32, l: u32) -> Weight {
+		(14_283_000 as Weight) // Standard Error: 1_702_000
+			.saturating_add((561_218_000 as Weight).saturating_mul(n as Weight)) // Standard Error: 9_000
+			.saturating_add((5_342_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(RocksDbWeight::get().reads((74 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(l as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((45 as Weight).saturating_mul(n as Weight)))
 	}
 }
@@ -26,11 +26,14 @@ use serde::{Deserialize, Serialize};
 
 use scale_info::TypeInfo;
 
-use frame_support::sp_runtime::FixedU128;
+use frame_support::sp_runtime::{FixedU128, Permill};
+use sp_runtime::traits::{AtLeast32BitUnsigned, One, Saturating, Zero};
 use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
 
 pub mod asset;
 pub mod constants;
+pub mod traits;
 
 /// An index to a block.
 pub type BlockNumber = u32;
@@ -81,11 +84,40 @@ pub struct ExchangeIntention<AccountId, Balance, IntentionID> {
 	pub amount_in: Balance,
 	pub amount_out: Balance,
 	pub trade_limit: Balance,
+	/// Maximum deviation of the execution price of AMM trade from the spot price of the pool.
+	pub max_price_impact: Option<Permill>,
+	/// Amount of `asset_in` reserved until the intention is resolved.
+	pub reserved: Balance,
 	pub discount: bool,
 	pub sell_or_buy: IntentionType,
 	pub intention_id: IntentionID,
 }
 
+/// Direct trade between two intentions of opposite sides.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct DirectTradeInfo<Balance, IntentionID> {
+	pub intention_a: IntentionID,
+	pub intention_b: IntentionID,
+	/// Amount paid by account of `intention_a`.
+	pub amount_from_a: Balance,
+	/// Amount paid by account of `intention_b`.
+	pub amount_from_b: Balance,
+}
+
+/// Expected resolution of intentions and crossed limit orders of a pair at the end of block.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct IntentionsResolution<Balance, IntentionID> {
+	/// Direction of the net AMM trade - `asset_in` is sold for `asset_out`.
+	pub assets: asset::AssetPair,
+	pub direct_trades: Vec<DirectTradeInfo<Balance, IntentionID>>,
+	/// Intentions and limit orders left to be traded via AMM, completely or partially.
+	pub amm_intentions: Vec<IntentionID>,
+	/// Net amount of `asset_in` sold via AMM, both sides netted at the spot price of the pool.
+	pub amm_amount_in: Balance,
+}
+
 #[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, PartialOrd, Ord, MaxEncodedLen, RuntimeDebug, TypeInfo)]
 #[repr(u8)]
 pub enum ReserveIdentifier {
@@ -96,6 +128,120 @@ pub enum ReserveIdentifier {
 	Count,
 }
 
+/// Limits of trades and liquidity in a pool.
+#[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub struct TradingLimits {
+	/// Minimum amount of a trade.
+	pub min_trading_limit: Balance,
+	/// Minimum liquidity of a pool.
+	pub min_pool_liquidity: Balance,
+	/// Max fraction of pool reserve to sell in single transaction.
+	pub max_in_ratio: u128,
+	/// Max fraction of pool reserve to buy in single transaction.
+	pub max_out_ratio: u128,
+}
+
+/// Operations allowed in a pool.
+#[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub enum PoolState {
+	/// All operations are allowed.
+	Active,
+	/// Trades are not allowed, liquidity can be added and removed.
+	TradingPaused,
+	/// Only removing of liquidity is allowed so that liquidity providers can leave the pool.
+	LiquidityOnly,
+	/// No operations are allowed.
+	Frozen,
+}
+
+impl Default for PoolState {
+	fn default() -> Self {
+		PoolState::Active
+	}
+}
+
+impl PoolState {
+	pub fn is_trading_allowed(&self) -> bool {
+		*self == PoolState::Active
+	}
+
+	pub fn is_adding_liquidity_allowed(&self) -> bool {
+		matches!(self, PoolState::Active | PoolState::TradingPaused)
+	}
+
+	pub fn is_removing_liquidity_allowed(&self) -> bool {
+		*self != PoolState::Frozen
+	}
+}
+
+/// Statistics of trades in a pool. Volumes and fees are in the order of the pool assets.
+#[derive(Encode, Decode, Default, Eq, PartialEq, Copy, Clone, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub struct TradeStats {
+	/// Amounts of the pool assets paid (fee included) and received (fee deducted) by traders.
+	pub volume: (Balance, Balance),
+	/// Trade fees charged in the pool assets.
+	pub fees: (Balance, Balance),
+	pub trade_count: u64,
+}
+
+impl TradeStats {
+	pub fn add_trade(&mut self, volume: (Balance, Balance), fees: (Balance, Balance)) {
+		self.volume.0 = self.volume.0.saturating_add(volume.0);
+		self.volume.1 = self.volume.1.saturating_add(volume.1);
+		self.fees.0 = self.fees.0.saturating_add(fees.0);
+		self.fees.1 = self.fees.1.saturating_add(fees.1);
+		self.trade_count = self.trade_count.saturating_add(1);
+	}
+}
+
+/// Statistics of trades in a pool since its creation and in windows of fixed number of blocks.
+///
+/// Windows are aligned to multiples of the window length, so `window_start` of a window starting
+/// at block `n * length` is the same for all pools.
+#[derive(Encode, Decode, Default, Eq, PartialEq, Copy, Clone, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub struct PoolStats<BlockNumber> {
+	pub total: TradeStats,
+	/// First block of the current window.
+	pub window_start: BlockNumber,
+	pub window: TradeStats,
+	/// Statistics of the window right before the current one.
+	pub previous_window: TradeStats,
+}
+
+impl<BlockNumber: AtLeast32BitUnsigned + Copy> PoolStats<BlockNumber> {
+	/// Move to the window containing block `now` if the current window has ended.
+	///
+	/// Previous window is cleared if no trade happened in the window right before the new one.
+	pub fn roll_window(&mut self, now: BlockNumber, window_length: BlockNumber) {
+		if window_length.is_zero() || now < self.window_start.saturating_add(window_length) {
+			return;
+		}
+
+		let elapsed_windows = (now - self.window_start) / window_length;
+
+		self.previous_window = if elapsed_windows == One::one() {
+			self.window
+		} else {
+			TradeStats::default()
+		};
+		self.window = TradeStats::default();
+		self.window_start = now - (now % window_length);
+	}
+
+	/// Add a trade executed in block `now`.
+	pub fn add_trade(
+		&mut self,
+		now: BlockNumber,
+		window_length: BlockNumber,
+		volume: (Balance, Balance),
+		fees: (Balance, Balance),
+	) {
+		self.roll_window(now, window_length);
+		self.total.add_trade(volume, fees);
+		self.window.add_trade(volume, fees);
+	}
+}
+
 pub mod nft {
 	use super::*;
 
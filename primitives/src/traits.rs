@@ -0,0 +1,234 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{Balance, PoolState, TradingLimits};
+use frame_support::{traits::Get, weights::Weight};
+use sp_runtime::{DispatchError, DispatchResult};
+use sp_std::marker::PhantomData;
+use sp_std::vec::Vec;
+
+/// Calculation of trade amounts based on current pool state only.
+///
+/// Unlike `AMM::validate_sell` and `AMM::validate_buy`, the calculation does not depend on balances
+/// of the trading account, so it can be used to price trades which are executed later (e.g. hops of a route).
+pub trait AMMTradeCalculation<AssetPair, Balance> {
+	/// Return amount of `assets.asset_out` (fee deducted) received for selling `amount` of `assets.asset_in`.
+	fn calculate_sell(assets: AssetPair, amount: Balance) -> Result<Balance, DispatchError>;
+
+	/// Return amount of `assets.asset_in` (fee included) required to buy `amount` of `assets.asset_out`.
+	fn calculate_buy(assets: AssetPair, amount: Balance) -> Result<Balance, DispatchError>;
+}
+
+/// Provider of time-weighted average prices.
+pub trait TwapProvider<AssetId, BlockNumber> {
+	type Price;
+
+	/// Return average price of `asset_a` denominated in `asset_b` between blocks `from` and `to`.
+	///
+	/// Both blocks have to be blocks of stored price observations, `None` is returned otherwise.
+	fn twap(asset_a: AssetId, asset_b: AssetId, from: BlockNumber, to: BlockNumber) -> Option<Self::Price>;
+}
+
+/// Handler of flash swaps, implemented by pallets which use borrowed pool reserves.
+pub trait FlashSwapHandler<AccountId, AssetId, Balance> {
+	/// Called after `amount_out` of `asset_out` was transferred from `pool_account` to `who`.
+	///
+	/// The handler has to transfer `asset_out` and/or `asset_in` back to `pool_account` so that the pool
+	/// invariant increased by trade fee holds, otherwise the whole flash swap is reverted.
+	fn on_flash_swap(
+		who: &AccountId,
+		pool_account: &AccountId,
+		asset_out: AssetId,
+		amount_out: Balance,
+		asset_in: AssetId,
+		data: &[u8],
+	) -> DispatchResult;
+
+	fn on_flash_swap_weight() -> Weight;
+}
+
+impl<AccountId, AssetId, Balance> FlashSwapHandler<AccountId, AssetId, Balance> for () {
+	fn on_flash_swap(_: &AccountId, _: &AccountId, _: AssetId, _: Balance, _: AssetId, _: &[u8]) -> DispatchResult {
+		Ok(())
+	}
+
+	fn on_flash_swap_weight() -> Weight {
+		0
+	}
+}
+
+/// Provider of trading limits of pools.
+pub trait TradingLimitsProvider<AccountId> {
+	/// Return limits which apply to pools without their own limits.
+	fn default_limits() -> TradingLimits;
+
+	/// Return limits of the pool identified by `pool_account`.
+	fn pool_limits(pool_account: &AccountId) -> TradingLimits;
+}
+
+/// Trading limits which are the same for all pools and change only with a runtime upgrade.
+pub struct ConstantTradingLimits<MinTradingLimit, MinPoolLiquidity, MaxInRatio, MaxOutRatio>(
+	PhantomData<(MinTradingLimit, MinPoolLiquidity, MaxInRatio, MaxOutRatio)>,
+);
+
+impl<AccountId, MinTradingLimit, MinPoolLiquidity, MaxInRatio, MaxOutRatio> TradingLimitsProvider<AccountId>
+	for ConstantTradingLimits<MinTradingLimit, MinPoolLiquidity, MaxInRatio, MaxOutRatio>
+where
+	MinTradingLimit: Get<Balance>,
+	MinPoolLiquidity: Get<Balance>,
+	MaxInRatio: Get<u128>,
+	MaxOutRatio: Get<u128>,
+{
+	fn default_limits() -> TradingLimits {
+		TradingLimits {
+			min_trading_limit: MinTradingLimit::get(),
+			min_pool_liquidity: MinPoolLiquidity::get(),
+			max_in_ratio: MaxInRatio::get(),
+			max_out_ratio: MaxOutRatio::get(),
+		}
+	}
+
+	fn pool_limits(_pool_account: &AccountId) -> TradingLimits {
+		Self::default_limits()
+	}
+}
+
+/// Circuit breaker which can halt operations in a pool.
+pub trait CircuitBreaker<AccountId> {
+	/// Return operations allowed in the pool identified by `pool_account`.
+	fn pool_state(pool_account: &AccountId) -> PoolState;
+
+	/// Called after reserves of the pool have changed by a trade.
+	///
+	/// Reserves of the pool have to be passed in the same order of its assets in every call.
+	fn on_reserves_change(
+		pool_account: &AccountId,
+		reserves_before: (Balance, Balance),
+		reserves_after: (Balance, Balance),
+	);
+
+	/// Called after reserves of the pool have changed by adding or removing liquidity.
+	///
+	/// Reserves of the pool have to be passed in the same order of its assets in every call.
+	fn on_liquidity_change(
+		pool_account: &AccountId,
+		reserves_before: (Balance, Balance),
+		reserves_after: (Balance, Balance),
+	);
+}
+
+impl<AccountId> CircuitBreaker<AccountId> for () {
+	fn pool_state(_pool_account: &AccountId) -> PoolState {
+		PoolState::Active
+	}
+
+	fn on_reserves_change(_: &AccountId, _: (Balance, Balance), _: (Balance, Balance)) {}
+
+	fn on_liquidity_change(_: &AccountId, _: (Balance, Balance), _: (Balance, Balance)) {}
+}
+
+/// Access to names and metadata of assets kept in an asset registry.
+pub trait AssetMetadataRegistry<AssetId> {
+	/// Return registered name of an asset.
+	fn asset_name(asset_id: AssetId) -> Option<Vec<u8>>;
+
+	/// Return underlying assets of a pool share token, or `None` if the asset is not a pool share token.
+	fn pool_share_assets(asset_id: AssetId) -> Option<(AssetId, AssetId)>;
+
+	/// Return max length of asset names and symbols.
+	fn max_name_length() -> u32;
+
+	/// Change registered name of an asset.
+	fn set_asset_name(asset_id: AssetId, name: Vec<u8>) -> DispatchResult;
+
+	/// Set symbol and decimals of an asset.
+	fn set_asset_metadata(asset_id: AssetId, symbol: Vec<u8>, decimals: u8) -> DispatchResult;
+}
+
+impl<AssetId> AssetMetadataRegistry<AssetId> for () {
+	fn asset_name(_asset_id: AssetId) -> Option<Vec<u8>> {
+		None
+	}
+
+	fn pool_share_assets(_asset_id: AssetId) -> Option<(AssetId, AssetId)> {
+		None
+	}
+
+	fn max_name_length() -> u32 {
+		u32::MAX
+	}
+
+	fn set_asset_name(_asset_id: AssetId, _name: Vec<u8>) -> DispatchResult {
+		Ok(())
+	}
+
+	fn set_asset_metadata(_asset_id: AssetId, _symbol: Vec<u8>, _decimals: u8) -> DispatchResult {
+		Ok(())
+	}
+}
+
+/// Handler of changes of liquidity in AMM pools.
+pub trait OnLiquidityChangedHandler<AssetId, Balance> {
+	/// Called when `amount_a` of `asset_a` and `amount_b` of `asset_b` were added to or removed from a pool.
+	///
+	/// `liquidity` is total liquidity of the pool after the change.
+	fn on_liquidity_changed(
+		asset_a: AssetId,
+		asset_b: AssetId,
+		amount_a: Balance,
+		amount_b: Balance,
+		liquidity: Balance,
+	) -> Weight;
+
+	/// Return max weight of `on_liquidity_changed`.
+	fn on_liquidity_changed_weight() -> Weight;
+}
+
+impl<AssetId, Balance> OnLiquidityChangedHandler<AssetId, Balance> for () {
+	fn on_liquidity_changed(_: AssetId, _: AssetId, _: Balance, _: Balance, _: Balance) -> Weight {
+		0
+	}
+
+	fn on_liquidity_changed_weight() -> Weight {
+		0
+	}
+}
+
+/// Handler of destruction of AMM pools.
+pub trait OnPoolDestroyedHandler<AccountId, AssetId> {
+	/// Called when the pool of `asset_a` and `asset_b` identified by `pool_account` was destroyed.
+	fn on_pool_destroyed(pool_account: &AccountId, asset_a: AssetId, asset_b: AssetId) -> Weight;
+
+	/// Return max weight of `on_pool_destroyed`.
+	fn on_pool_destroyed_weight() -> Weight;
+}
+
+impl<AccountId, AssetId> OnPoolDestroyedHandler<AccountId, AssetId> for () {
+	fn on_pool_destroyed(_: &AccountId, _: AssetId, _: AssetId) -> Weight {
+		0
+	}
+
+	fn on_pool_destroyed_weight() -> Weight {
+		0
+	}
+}
+
+/// Resolver of exchange intentions which were not traded directly with other intentions.
+pub trait Resolver<Intention> {
+	/// Resolve intention via AMM pool.
+	fn resolve_single_intention(intention: &Intention);
+}
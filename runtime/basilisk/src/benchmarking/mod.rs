@@ -3,13 +3,17 @@
 pub mod currencies;
 pub mod duster;
 pub mod multi_payment;
+pub mod route_executor;
 pub mod tokens;
 pub mod vesting;
 
 use crate::AssetRegistry;
 use crate::XYK;
-use frame_support::assert_ok;
+use crate::{Currencies, NativeAssetId, PoolCreationDeposit};
+use frame_support::{assert_ok, traits::Get};
 use frame_system::RawOrigin;
+use orml_traits::MultiCurrencyExtended;
+use sp_runtime::traits::SaturatedConversion;
 
 use common_runtime::AccountId;
 use primitives::{AssetId, Balance, Price};
@@ -38,11 +42,19 @@ pub fn update_asset(asset_id: AssetId, name: Vec<u8>, deposit: Balance) -> Resul
 }
 
 pub fn create_pool(who: AccountId, asset_a: AssetId, asset_b: AssetId, amount: Balance, price: Price) {
+	// pool creation deposit is reserved in native currency
+	assert_ok!(<Currencies as MultiCurrencyExtended<_>>::update_balance(
+		NativeAssetId::get(),
+		&who,
+		PoolCreationDeposit::get().saturated_into()
+	));
+
 	assert_ok!(XYK::create_pool(
 		RawOrigin::Signed(who).into(),
 		asset_a,
 		asset_b,
 		amount,
-		price
+		price,
+		None
 	));
 }
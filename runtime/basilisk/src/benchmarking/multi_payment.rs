@@ -89,7 +89,7 @@ runtime_benchmarks! {
 	}: { <Runtime as pallet_transaction_multi_payment::Config>::SpotPriceProvider::spot_price(asset_id, asset_out) }
 	verify{
 		assert_eq!(<Runtime as pallet_transaction_multi_payment::Config>::SpotPriceProvider::spot_price(asset_id, asset_out),
-			Some(Price::from((2,1))));
+			Some(Price::from_inner(2_004_000_000_000_000_000)));
 
 	}
 }
@@ -0,0 +1,115 @@
+// This file is part of Basilisk-node
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{AccountId, AssetId, Balance, Currencies, MaxNumberOfTrades, Runtime};
+use primitives::Price;
+
+use super::*;
+
+use frame_benchmarking::account;
+use frame_benchmarking::BenchmarkError;
+use frame_support::assert_ok;
+use frame_system::RawOrigin;
+use orml_benchmarking::runtime_benchmarks;
+use sp_runtime::traits::SaturatedConversion;
+use sp_std::vec;
+
+use orml_traits::{MultiCurrency, MultiCurrencyExtended};
+
+type RouteExecutor<T> = pallet_route_executor::Pallet<T>;
+
+const SEED: u32 = 1;
+
+pub fn update_balance(currency_id: AssetId, who: &AccountId, balance: Balance) {
+	assert_ok!(<Currencies as MultiCurrencyExtended<_>>::update_balance(
+		currency_id,
+		who,
+		balance.saturated_into()
+	));
+}
+
+// Registers `n + 1` assets and creates a pool for each consecutive pair of them.
+fn create_route(n: u32) -> Result<Vec<AssetId>, BenchmarkError> {
+	let maker: AccountId = account("maker", 0, SEED);
+
+	let mut route = Vec::new();
+	for i in 0..=n {
+		let asset_id = register_asset(vec![b'R', b'T', i as u8], 1u128)
+			.map_err(|_| BenchmarkError::Stop("Failed to register asset"))?;
+		update_balance(asset_id, &maker, 2_000_000_000_000_000);
+		route.push(asset_id);
+	}
+
+	for assets in route.windows(2) {
+		create_pool(
+			maker.clone(),
+			assets[0],
+			assets[1],
+			1_000_000_000_000_000,
+			Price::from(1),
+		);
+	}
+
+	Ok(route)
+}
+
+runtime_benchmarks! {
+	{ Runtime, pallet_route_executor }
+
+	sell {
+		let n in 1 .. MaxNumberOfTrades::get();
+
+		let caller: AccountId = account("caller", 0, SEED);
+		let route = create_route(n)?;
+		let asset_out = route[route.len() - 1];
+
+		update_balance(route[0], &caller, 1_000_000_000_000);
+
+	}: { RouteExecutor::<Runtime>::sell(RawOrigin::Signed(caller.clone()).into(), route, 1_000_000_000_000, 0)? }
+	verify {
+		assert!(<Currencies as MultiCurrency<_>>::free_balance(asset_out, &caller) > 0);
+	}
+
+	buy {
+		let n in 1 .. MaxNumberOfTrades::get();
+
+		let caller: AccountId = account("caller", 0, SEED);
+		let route = create_route(n)?;
+		let asset_out = route[route.len() - 1];
+
+		update_balance(route[0], &caller, 2_000_000_000_000);
+
+	}: { RouteExecutor::<Runtime>::buy(RawOrigin::Signed(caller.clone()).into(), route, 1_000_000_000_000, 2_000_000_000_000)? }
+	verify {
+		assert_eq!(<Currencies as MultiCurrency<_>>::free_balance(asset_out, &caller), 1_000_000_000_000);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use orml_benchmarking::impl_benchmark_test_suite;
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		frame_system::GenesisConfig::default()
+			.build_storage::<crate::Runtime>()
+			.unwrap()
+			.into()
+	}
+
+	impl_benchmark_test_suite!(new_test_ext(),);
+}
@@ -0,0 +1,38 @@
+use super::*;
+use frame_support::sp_runtime::DispatchResult;
+use frame_support::traits::Get;
+use frame_system::RawOrigin;
+
+use pallet_asset_registry::AssetType;
+use primitives::traits::AssetMetadataRegistry;
+use sp_std::vec::Vec;
+
+pub struct AssetRegistryMetadata<T>(sp_std::marker::PhantomData<T>);
+
+impl<T: pallet_asset_registry::Config<AssetId = AssetId>> AssetMetadataRegistry<AssetId> for AssetRegistryMetadata<T> {
+	fn asset_name(asset_id: AssetId) -> Option<Vec<u8>> {
+		pallet_asset_registry::Pallet::<T>::assets(asset_id).map(|details| details.name.into_inner())
+	}
+
+	fn pool_share_assets(asset_id: AssetId) -> Option<(AssetId, AssetId)> {
+		match pallet_asset_registry::Pallet::<T>::assets(asset_id)?.asset_type {
+			AssetType::PoolShare(asset_a, asset_b) => Some((asset_a, asset_b)),
+			_ => None,
+		}
+	}
+
+	fn max_name_length() -> u32 {
+		T::StringLimit::get()
+	}
+
+	fn set_asset_name(asset_id: AssetId, name: Vec<u8>) -> DispatchResult {
+		let details = pallet_asset_registry::Pallet::<T>::assets(asset_id)
+			.ok_or(pallet_asset_registry::Error::<T>::AssetNotFound)?;
+
+		pallet_asset_registry::Pallet::<T>::update(RawOrigin::Root.into(), asset_id, name, details.asset_type, None)
+	}
+
+	fn set_asset_metadata(asset_id: AssetId, symbol: Vec<u8>, decimals: u8) -> DispatchResult {
+		pallet_asset_registry::Pallet::<T>::set_metadata(RawOrigin::Root.into(), asset_id, symbol, decimals)
+	}
+}
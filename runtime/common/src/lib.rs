@@ -18,7 +18,9 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 pub mod adapter;
+pub mod asset_metadata;
 pub mod locked_balance;
+pub mod price_oracle;
 pub mod weights;
 
 use codec::{Decode, Encode, MaxEncodedLen};
@@ -151,9 +153,46 @@ parameter_types! {
 	pub const MinPoolLiquidity: Balance = MIN_POOL_LIQUIDITY;
 	pub const MaxInRatio: u128 = MAX_IN_RATIO;
 	pub const MaxOutRatio: u128 = MAX_OUT_RATIO;
+	pub const MaxFeeTiers: u32 = 10;
+	pub const MaxPriceObservations: u32 = 24;
+	pub const PoolStatsWindow: BlockNumber = DAYS;
+	pub const MaxBatchTradeSize: u32 = 16;
+	pub const PoolCreationDeposit: Balance = 1_000 * DOLLARS;
 	pub const RegistryStrLimit: u32 = 32;
 }
 
+// pallet exchange
+parameter_types! {
+	pub const MaxLimitOrdersPerPair: u32 = 100;
+	pub const MaxLimitOrderLifetime: BlockNumber = 7 * DAYS;
+}
+
+// pallet route executor
+parameter_types! {
+	pub const MaxNumberOfTrades: u32 = 5;
+}
+
+// pallet stableswap
+parameter_types! {
+	pub const StableswapPalletId: PalletId = PalletId(*b"stblswap");
+	pub const MaxAssetsInStableswapPool: u32 = 5;
+	pub const MaxStableswapAmplification: u32 = 10_000;
+	pub const MaxStableswapAmplificationChange: u32 = 10;
+	pub const MinStableswapRampDuration: BlockNumber = DAYS;
+}
+
+// pallet weighted pool
+parameter_types! {
+	pub const WeightedPoolPalletId: PalletId = PalletId(*b"wghtpool");
+	pub const MaxAssetsInWeightedPool: u32 = 8;
+}
+
+// pallet circuit breaker
+parameter_types! {
+	pub const MaxPoolReserveChange: Permill = Permill::from_percent(50);
+	pub const MaxPoolPriceChange: Permill = Permill::from_percent(20);
+}
+
 // pallet duster
 parameter_types! {
 	pub const DustingReward: u128 = 0;
@@ -162,6 +201,8 @@ parameter_types! {
 // pallet lbp
 parameter_types! {
 	pub LBPExchangeFee: (u32, u32) = (2, 1_000);
+	// LBP pools use relay chain block numbers, relay chain produces blocks twice as often.
+	pub const LBPPoolStatsWindow: BlockNumber = 2 * DAYS;
 }
 
 // pallet democracy
@@ -259,4 +300,5 @@ parameter_types! {
 	pub const MinPlannedYieldingPeriods: BlockNumber = 100;
 	pub const MinTotalFarmRewards: Balance = NATIVE_EXISTENTIAL_DEPOSIT * 1_000;
 	pub const NftClass: primitives::ClassId = 1;
+	pub const MaxFarmsPerAmmPool: u32 = 10;
 }
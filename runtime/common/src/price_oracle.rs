@@ -0,0 +1,36 @@
+use super::*;
+use frame_support::{traits::Get, weights::Weight};
+
+use pallet_price_oracle::PriceDataAccumulator;
+use primitives::traits::OnLiquidityChangedHandler;
+
+/// Keeps liquidity data of the price oracle up to date with liquidity changes of AMM pools.
+///
+/// Only liquidity of the pair accumulated in the current block is updated, added and removed amounts are not
+/// trades and don't count to the traded volume. Liquidity of pairs not traded in the block is reported by the
+/// next trade in the pair.
+pub struct PriceOracleLiquidityHandler<T>(sp_std::marker::PhantomData<T>);
+
+impl<T: pallet_price_oracle::Config> OnLiquidityChangedHandler<AssetId, Balance> for PriceOracleLiquidityHandler<T> {
+	fn on_liquidity_changed(
+		asset_a: AssetId,
+		asset_b: AssetId,
+		_amount_a: Balance,
+		_amount_b: Balance,
+		liquidity: Balance,
+	) -> Weight {
+		let pair_name = pallet_price_oracle::Pallet::<T>::get_name(asset_a, asset_b);
+
+		PriceDataAccumulator::<T>::mutate_exists(pair_name, |maybe_entry| {
+			if let Some(entry) = maybe_entry {
+				entry.liquidity_amount = liquidity;
+			}
+		});
+
+		Self::on_liquidity_changed_weight()
+	}
+
+	fn on_liquidity_changed_weight() -> Weight {
+		T::DbWeight::get().reads_writes(1, 1)
+	}
+}
@@ -54,13 +54,13 @@ impl<T: frame_system::Config> WeightInfo for BasiliskWeight<T> {
 	}
 	fn sell_intention() -> Weight {
 		(93_292_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(6 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
 	}
 	fn buy_intention() -> Weight {
 		(95_420_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(6 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
 	}
 	fn on_finalize(t: u32) -> Weight {
 		(0 as Weight) // Standard Error: 59_000
@@ -106,4 +106,35 @@ impl<T: frame_system::Config> WeightInfo for BasiliskWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(15 as Weight))
 			.saturating_add(T::DbWeight::get().writes(7 as Weight))
 	}
+	fn place_limit_order() -> Weight {
+		(61_027_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn cancel_limit_order() -> Weight {
+		(47_356_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn process_limit_orders(o: u32) -> Weight {
+		(18_914_000 as Weight) // Standard Error: 54_000
+			.saturating_add((132_807_000 as Weight).saturating_mul(o as Weight))
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(o as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((4 as Weight).saturating_mul(o as Weight)))
+	}
+	fn prune_expired_limit_orders(o: u32) -> Weight {
+		(8_611_000 as Weight) // Standard Error: 12_000
+			.saturating_add((28_930_000 as Weight).saturating_mul(o as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(o as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(o as Weight)))
+	}
+	fn cancel_intention() -> Weight {
+		(38_645_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
 }
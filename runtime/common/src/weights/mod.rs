@@ -1,5 +1,6 @@
 pub mod asset_registry;
 pub mod balances;
+pub mod circuit_breaker;
 pub mod collator_selection;
 pub mod currencies;
 pub mod democracy;
@@ -10,11 +11,15 @@ pub mod marketplace;
 pub mod nft;
 pub mod payment;
 pub mod price_oracle;
+pub mod route_executor;
 pub mod scheduler;
+pub mod stableswap;
 pub mod system;
 pub mod timestamp;
 pub mod tokens;
+pub mod trading_limits;
 pub mod treasury;
 pub mod utility;
 pub mod vesting;
+pub mod weighted_pool;
 pub mod xyk;
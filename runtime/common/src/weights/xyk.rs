@@ -51,27 +51,67 @@ pub struct BasiliskWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for BasiliskWeight<T> {
 	fn create_pool() -> Weight {
 		(204_077_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(17 as Weight))
-			.saturating_add(T::DbWeight::get().writes(15 as Weight))
+			.saturating_add(T::DbWeight::get().reads(28 as Weight))
+			.saturating_add(T::DbWeight::get().writes(21 as Weight))
 	}
 	fn add_liquidity() -> Weight {
 		(151_436_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(12 as Weight))
-			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+			.saturating_add(T::DbWeight::get().reads(17 as Weight))
+			.saturating_add(T::DbWeight::get().writes(11 as Weight))
 	}
 	fn remove_liquidity() -> Weight {
 		(146_728_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(12 as Weight))
-			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+			.saturating_add(T::DbWeight::get().reads(19 as Weight))
+			.saturating_add(T::DbWeight::get().writes(14 as Weight))
+	}
+	fn add_liquidity_single_asset() -> Weight {
+		(279_748_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(31 as Weight))
+			.saturating_add(T::DbWeight::get().writes(18 as Weight))
+	}
+	fn remove_liquidity_single_asset() -> Weight {
+		(275_040_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(33 as Weight))
+			.saturating_add(T::DbWeight::get().writes(19 as Weight))
 	}
 	fn sell() -> Weight {
 		(128_312_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(11 as Weight))
-			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+			.saturating_add(T::DbWeight::get().reads(19 as Weight))
+			.saturating_add(T::DbWeight::get().writes(10 as Weight))
 	}
 	fn buy() -> Weight {
 		(128_093_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(11 as Weight))
-			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+			.saturating_add(T::DbWeight::get().reads(19 as Weight))
+			.saturating_add(T::DbWeight::get().writes(10 as Weight))
+	}
+	fn set_fee_tiers() -> Weight {
+		(21_483_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_pool_fee() -> Weight {
+		(28_760_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_protocol_fee() -> Weight {
+		(20_815_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn flash_swap() -> Weight {
+		(119_713_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(12 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	fn set_asset_pool_permission() -> Weight {
+		(19_968_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_require_allowed_assets() -> Weight {
+		(18_647_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn batch_trade(n: u32, l: u32) -> Weight {
+		(14_283_000 as Weight) // Standard Error: 1_702_000
+			.saturating_add((561_218_000 as Weight).saturating_mul(n as Weight)) // Standard Error: 9_000
+			.saturating_add((5_342_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(T::DbWeight::get().reads((74 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(l as Weight)))
+			.saturating_add(T::DbWeight::get().writes((45 as Weight).saturating_mul(n as Weight)))
 	}
 }
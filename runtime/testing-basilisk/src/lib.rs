@@ -42,7 +42,7 @@ use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
 	traits::{AccountIdConversion, BlakeTwo256, Block as BlockT, IdentityLookup},
 	transaction_validity::{TransactionSource, TransactionValidity},
-	ApplyExtrinsicResult, Perbill,
+	ApplyExtrinsicResult, FixedU128, Perbill,
 };
 use sp_std::convert::From;
 use sp_std::marker::PhantomData;
@@ -56,7 +56,7 @@ use scale_info::TypeInfo;
 // A few exports that help ease life for downstream crates.
 use frame_support::{
 	construct_runtime, parameter_types,
-	traits::{EnsureOneOf, EnsureOrigin, EqualPrivilegeOnly, Everything, Get, InstanceFilter, U128CurrencyToVote},
+	traits::{Contains, EnsureOneOf, EnsureOrigin, EqualPrivilegeOnly, Get, InstanceFilter, U128CurrencyToVote},
 	weights::{
 		constants::{BlockExecutionWeight, RocksDbWeight},
 		DispatchClass, Weight, WeightToFeeCoefficient, WeightToFeeCoefficients, WeightToFeePolynomial,
@@ -98,8 +98,15 @@ pub mod opaque {
 }
 
 mod testing {
-	use super::{parameter_types, BlockNumber, Everything, MINUTES};
-	pub type BaseFilter = Everything;
+	use super::{parameter_types, BlockNumber, Call, Contains, MINUTES};
+
+	pub struct BaseFilter;
+	impl Contains<Call> for BaseFilter {
+		fn contains(call: &Call) -> bool {
+			// Flash swaps cannot be repaid without a flash swap handler.
+			!matches!(call, Call::XYK(pallet_xyk::Call::flash_swap { .. }))
+		}
+	}
 
 	parameter_types! {
 		pub const LaunchPeriod: BlockNumber = MINUTES;
@@ -113,10 +120,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("testing-basilisk"),
 	impl_name: create_runtime_str!("testing-basilisk"),
 	authoring_version: 1,
-	spec_version: 47,
+	spec_version: 91,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 	state_version: 0,
 };
 
@@ -133,7 +140,8 @@ use common_runtime::adapter::OrmlTokensAdapter;
 use common_runtime::locked_balance::MultiCurrencyLockedBalance;
 use primitives::{
 	nft::{ClassType, NftPermissions},
-	ClassId, InstanceId,
+	traits::TwapProvider,
+	ClassId, InstanceId, PoolStats,
 };
 use smallvec::smallvec;
 
@@ -299,7 +307,7 @@ impl pallet_transaction_multi_payment::Config for Runtime {
 	type Event = Event;
 	type AcceptedCurrencyOrigin = EnsureSuperMajorityTechCommitteeOrRoot;
 	type Currencies = Currencies;
-	type SpotPriceProvider = pallet_xyk::XYKSpotPrice<Runtime>;
+	type SpotPriceProvider = pallet_xyk::XYKFeeAdjustedSpotPrice<Runtime>;
 	type WeightInfo = common_runtime::weights::payment::BasiliskWeight<Runtime>;
 	type WithdrawFeeForSetCurrency = MultiPaymentCurrencySetFee;
 	type WeightToFee = WeightToFee;
@@ -324,7 +332,14 @@ impl InstanceFilter<Call> for ProxyType {
 					| Call::Treasury(..) | Call::Tips(..)
 					| Call::Utility(..)
 			),
-			ProxyType::Exchange => matches!(c, Call::XYK(..) | Call::Exchange(..) | Call::LBP(..) | Call::NFT(..)),
+			ProxyType::Exchange => matches!(
+				c,
+				Call::XYK(..)
+					| Call::Exchange(..) | Call::LBP(..)
+					| Call::NFT(..) | Call::RouteExecutor(..)
+					| Call::Stableswap(..)
+					| Call::WeightedPool(..)
+			),
 			// Transfer group doesn't include cross-chain transfers
 			ProxyType::Transfer => matches!(c, Call::Balances(..) | Call::Currencies(..) | Call::Tokens(..)),
 		}
@@ -409,20 +424,66 @@ impl pallet_asset_registry::Config for Runtime {
 	type WeightInfo = common_runtime::weights::asset_registry::BasiliskWeight<Runtime>;
 }
 
+parameter_types! {
+	pub TreasuryAccount: AccountId = TreasuryPalletId::get().into_account();
+}
+
 impl pallet_xyk::Config for Runtime {
 	type Event = Event;
 	type AssetRegistry = AssetRegistry;
+	type AssetMetadata = common_runtime::asset_metadata::AssetRegistryMetadata<Runtime>;
 	type AssetPairAccountId = AssetPairAccountId<Self>;
 	type Currency = Currencies;
 	type NativeAssetId = NativeAssetId;
 	type WeightInfo = common_runtime::weights::xyk::BasiliskWeight<Runtime>;
 	type GetExchangeFee = ExchangeFee;
-	type MinTradingLimit = MinTradingLimit;
-	type MinPoolLiquidity = MinPoolLiquidity;
-	type MaxInRatio = MaxInRatio;
-	type MaxOutRatio = MaxOutRatio;
-	type CanCreatePool = pallet_lbp::DisallowWhenLBPPoolRunning<Runtime>;
+	type MaxFeeTiers = MaxFeeTiers;
+	type MaxPriceObservations = MaxPriceObservations;
+	type StatsWindow = PoolStatsWindow;
+	type AuthorityOrigin = EnsureSuperMajorityTechCommitteeOrRoot;
+	type ProtocolFeeReceiver = TreasuryAccount;
+	type TradingLimits = TradingLimits;
+	type CircuitBreaker = CircuitBreaker;
+	type CanCreatePool =
+		pallet_xyk::DisallowNotPermittedAssets<Runtime, pallet_lbp::DisallowWhenLBPPoolRunning<Runtime>>;
+	type PoolDeposit = PoolCreationDeposit;
 	type AMMHandler = pallet_price_oracle::PriceOracleHandler<Runtime>;
+	type LiquidityChangedHandler = common_runtime::price_oracle::PriceOracleLiquidityHandler<Runtime>;
+	type PoolDestroyedHandler = LiquidityMining;
+	type FlashSwapHandler = ();
+	type MaxBatchSize = MaxBatchTradeSize;
+}
+
+impl pallet_route_executor::Config for Runtime {
+	type Event = Event;
+	type AMM = XYK;
+	type MaxNumberOfTrades = MaxNumberOfTrades;
+	type WeightInfo = common_runtime::weights::route_executor::BasiliskWeight<Runtime>;
+}
+
+impl pallet_stableswap::Config for Runtime {
+	type Event = Event;
+	type AssetRegistry = AssetRegistry;
+	type Currency = Currencies;
+	type PalletId = StableswapPalletId;
+	type AuthorityOrigin = EnsureSuperMajorityTechCommitteeOrRoot;
+	type MaxAssetsInPool = MaxAssetsInStableswapPool;
+	type MaxAmplification = MaxStableswapAmplification;
+	type MaxAmplificationChange = MaxStableswapAmplificationChange;
+	type MinRampDuration = MinStableswapRampDuration;
+	type TradingLimits = TradingLimits;
+	type WeightInfo = common_runtime::weights::stableswap::BasiliskWeight<Runtime>;
+}
+
+impl pallet_weighted_pool::Config for Runtime {
+	type Event = Event;
+	type AssetRegistry = AssetRegistry;
+	type Currency = Currencies;
+	type PalletId = WeightedPoolPalletId;
+	type AuthorityOrigin = EnsureSuperMajorityTechCommitteeOrRoot;
+	type MaxAssetsInPool = MaxAssetsInWeightedPool;
+	type TradingLimits = TradingLimits;
+	type WeightInfo = common_runtime::weights::weighted_pool::BasiliskWeight<Runtime>;
 }
 
 impl pallet_exchange::Config for Runtime {
@@ -431,6 +492,8 @@ impl pallet_exchange::Config for Runtime {
 	type Resolver = Exchange;
 	type Currency = Currencies;
 	type WeightInfo = common_runtime::weights::exchange::BasiliskWeight<Runtime>;
+	type MaxLimitOrdersPerPair = MaxLimitOrdersPerPair;
+	type MaxLimitOrderLifetime = MaxLimitOrderLifetime;
 }
 
 impl pallet_lbp::Config for Runtime {
@@ -440,12 +503,29 @@ impl pallet_lbp::Config for Runtime {
 	type CreatePoolOrigin = EnsureSuperMajorityTechCommitteeOrRoot;
 	type LBPWeightFunction = pallet_lbp::LBPWeightFunction;
 	type AssetPairAccountId = AssetPairAccountId<Self>;
-	type MinTradingLimit = MinTradingLimit;
-	type MinPoolLiquidity = MinPoolLiquidity;
-	type MaxInRatio = MaxInRatio;
-	type MaxOutRatio = MaxOutRatio;
+	type TradingLimits = TradingLimits;
+	type CircuitBreaker = CircuitBreaker;
 	type WeightInfo = common_runtime::weights::lbp::BasiliskWeight<Runtime>;
 	type BlockNumberProvider = cumulus_pallet_parachain_system::RelaychainBlockNumberProvider<Runtime>;
+	type StatsWindow = LBPPoolStatsWindow;
+}
+
+impl pallet_trading_limits::Config for Runtime {
+	type Event = Event;
+	type AuthorityOrigin = EnsureSuperMajorityTechCommitteeOrRoot;
+	type DefaultMinTradingLimit = MinTradingLimit;
+	type DefaultMinPoolLiquidity = MinPoolLiquidity;
+	type DefaultMaxInRatio = MaxInRatio;
+	type DefaultMaxOutRatio = MaxOutRatio;
+	type WeightInfo = common_runtime::weights::trading_limits::BasiliskWeight<Runtime>;
+}
+
+impl pallet_circuit_breaker::Config for Runtime {
+	type Event = Event;
+	type AuthorityOrigin = EnsureSuperMajorityTechCommitteeOrRoot;
+	type MaxReserveChange = MaxPoolReserveChange;
+	type MaxPriceChange = MaxPoolPriceChange;
+	type WeightInfo = common_runtime::weights::circuit_breaker::BasiliskWeight<Runtime>;
 }
 
 impl pallet_price_oracle::Config for Runtime {
@@ -790,6 +870,7 @@ impl pallet_liquidity_mining::Config for Runtime {
 	type MinTotalFarmRewards = MinTotalFarmRewards;
 	type BlockNumberProvider = cumulus_pallet_parachain_system::RelaychainBlockNumberProvider<Runtime>;
 	type NftClass = NftClass;
+	type MaxFarmsPerAmmPool = MaxFarmsPerAmmPool;
 	type AMM = XYK;
 	type WeightInfo = ();
 }
@@ -863,6 +944,11 @@ construct_runtime!(
 		PriceOracle: pallet_price_oracle::{Pallet, Call, Storage, Event<T>} = 107,
 		RelayChainInfo: pallet_relaychain_info::{Pallet, Event<T>} = 108,
 		Marketplace: pallet_marketplace::{Pallet, Call, Event<T>, Storage} = 109,
+		RouteExecutor: pallet_route_executor::{Pallet, Call, Event<T>} = 110,
+		Stableswap: pallet_stableswap::{Pallet, Call, Storage, Event<T>} = 111,
+		WeightedPool: pallet_weighted_pool::{Pallet, Call, Storage, Event<T>} = 112,
+		TradingLimits: pallet_trading_limits::{Pallet, Call, Storage, Event<T>} = 113,
+		CircuitBreaker: pallet_circuit_breaker::{Pallet, Call, Storage, Event<T>} = 114,
 
 		// ORML related modules - starts at 150
 		Currencies: orml_currencies::{Pallet, Call, Event<T>} = 150,
@@ -1063,6 +1149,95 @@ impl_runtime_apis! {
 			XYK::pair_account_from_assets(asset_a, asset_b)
 		}
 
+		fn quote_sell(
+			asset_in: AssetId,
+			asset_out: AssetId,
+			amount: Balance,
+		) -> Result<xyk_rpc::TradeQuote<Balance>, sp_runtime::DispatchError> {
+			XYK::quote_sell(asset_in, asset_out, amount).map(|quote| xyk_rpc::TradeQuote {
+				amount: quote.amount,
+				fee: quote.fee,
+				protocol_fee: quote.protocol_fee,
+				spot_price_before: quote.spot_price_before,
+				spot_price_after: quote.spot_price_after,
+				price_impact: quote.price_impact,
+			})
+		}
+
+		fn quote_buy(
+			asset_out: AssetId,
+			asset_in: AssetId,
+			amount: Balance,
+		) -> Result<xyk_rpc::TradeQuote<Balance>, sp_runtime::DispatchError> {
+			XYK::quote_buy(asset_out, asset_in, amount).map(|quote| xyk_rpc::TradeQuote {
+				amount: quote.amount,
+				fee: quote.fee,
+				protocol_fee: quote.protocol_fee,
+				spot_price_before: quote.spot_price_before,
+				spot_price_after: quote.spot_price_after,
+				price_impact: quote.price_impact,
+			})
+		}
+
+		fn get_pools(
+			start_after: Option<AccountId>,
+			limit: u32,
+		) -> Vec<xyk_rpc::PoolInfo<AccountId, AssetId, Balance>> {
+			XYK::get_pools_info(start_after, limit)
+				.into_iter()
+				.map(|pool| xyk_rpc::PoolInfo {
+					pool_account: pool.pool_account,
+					asset_a: pool.assets.0,
+					asset_b: pool.assets.1,
+					reserve_a: pool.reserves.0,
+					reserve_b: pool.reserves.1,
+					share_token: pool.share_token,
+					total_shares: pool.total_liquidity,
+					fee: pool.fee,
+				})
+				.collect()
+		}
+
+		fn get_pool(
+			asset_a: AssetId,
+			asset_b: AssetId,
+		) -> Option<xyk_rpc::PoolInfo<AccountId, AssetId, Balance>> {
+			XYK::get_pool_info(XYK::pair_account_from_assets(asset_a, asset_b)).map(|pool| xyk_rpc::PoolInfo {
+				pool_account: pool.pool_account,
+				asset_a: pool.assets.0,
+				asset_b: pool.assets.1,
+				reserve_a: pool.reserves.0,
+				reserve_b: pool.reserves.1,
+				share_token: pool.share_token,
+				total_shares: pool.total_liquidity,
+				fee: pool.fee,
+			})
+		}
+
+		fn get_pool_by_share_token(share_token: AssetId) -> Option<xyk_rpc::PoolInfo<AccountId, AssetId, Balance>> {
+			XYK::get_pool_by_share_token(share_token).map(|pool| xyk_rpc::PoolInfo {
+				pool_account: pool.pool_account,
+				asset_a: pool.assets.0,
+				asset_b: pool.assets.1,
+				reserve_a: pool.reserves.0,
+				reserve_b: pool.reserves.1,
+				share_token: pool.share_token,
+				total_shares: pool.total_liquidity,
+				fee: pool.fee,
+			})
+		}
+
+		fn get_price_observation_blocks(asset_a: AssetId, asset_b: AssetId) -> Vec<BlockNumber> {
+			XYK::get_price_observation_blocks(asset_a, asset_b)
+		}
+
+		fn get_twap(asset_a: AssetId, asset_b: AssetId, from: BlockNumber, to: BlockNumber) -> Option<FixedU128> {
+			<XYK as TwapProvider<AssetId, BlockNumber>>::twap(asset_a, asset_b, from, to)
+		}
+
+		fn get_pool_stats(asset_a: AssetId, asset_b: AssetId) -> Option<PoolStats<BlockNumber>> {
+			XYK::get_pool_stats(XYK::pair_account_from_assets(asset_a, asset_b))
+		}
 	}
 
 	impl pallet_lbp_rpc_runtime_api::LBPApi<
@@ -1073,6 +1248,32 @@ impl_runtime_apis! {
 		fn get_pool_id(asset_a: AssetId, asset_b: AssetId) -> AccountId{
 			LBP::pair_account_from_assets(asset_a, asset_b)
 		}
+
+		fn get_pool_stats(asset_a: AssetId, asset_b: AssetId) -> Option<PoolStats<BlockNumber>> {
+			LBP::get_pool_stats(LBP::pair_account_from_assets(asset_a, asset_b))
+		}
+	}
+
+	impl pallet_exchange_rpc_runtime_api::ExchangeApi<
+		Block,
+		AccountId,
+		AssetId,
+		Balance,
+		Hash,
+	> for Runtime {
+		fn pending_intentions(
+			asset_a: AssetId,
+			asset_b: AssetId,
+		) -> pallet_exchange_rpc_runtime_api::PendingIntentions<AccountId, Balance, Hash> {
+			pallet_exchange_rpc_runtime_api::PendingIntentions {
+				asset_a_intentions: Exchange::get_intentions((asset_a, asset_b)),
+				asset_b_intentions: Exchange::get_intentions((asset_b, asset_a)),
+			}
+		}
+
+		fn simulate_resolution() -> Vec<pallet_exchange_rpc_runtime_api::IntentionsResolution<Balance, Hash>> {
+			Exchange::simulate_resolution()
+		}
 	}
 
 
@@ -1092,6 +1293,10 @@ impl_runtime_apis! {
 			let mut list = Vec::<BenchmarkList>::new();
 
 			list_benchmark!(list, extra, pallet_xyk, XYK);
+			list_benchmark!(list, extra, pallet_stableswap, Stableswap);
+			list_benchmark!(list, extra, pallet_weighted_pool, WeightedPool);
+			list_benchmark!(list, extra, pallet_trading_limits, TradingLimits);
+			list_benchmark!(list, extra, pallet_circuit_breaker, CircuitBreaker);
 			list_benchmark!(list, extra, pallet_lbp, LBP);
 			list_benchmark!(list, extra, pallet_price_oracle, PriceOracle);
 			list_benchmark!(list, extra, pallet_exchange, ExchangeBench::<Runtime>);
@@ -1146,6 +1351,10 @@ impl_runtime_apis! {
 
 			// Basilisk pallets
 			add_benchmark!(params, batches, pallet_xyk, XYK);
+			add_benchmark!(params, batches, pallet_stableswap, Stableswap);
+			add_benchmark!(params, batches, pallet_weighted_pool, WeightedPool);
+			add_benchmark!(params, batches, pallet_trading_limits, TradingLimits);
+			add_benchmark!(params, batches, pallet_circuit_breaker, CircuitBreaker);
 			add_benchmark!(params, batches, pallet_lbp, LBP);
 			add_benchmark!(params, batches, pallet_price_oracle, PriceOracle);
 			add_benchmark!(params, batches, pallet_exchange, ExchangeBench::<Runtime>);